@@ -30,6 +30,7 @@ mod settings;
 use crate::settings::GLOBAL_SETTINGS;
 
 use fly::http_server::serve_http;
+use fly::runtime_lifecycle::{self, heap_exceeded, DEFAULT_HEAP_RECYCLE_THRESHOLD};
 
 mod runtime_selector;
 use crate::runtime_selector::DistributedRuntimeSelector;
@@ -48,6 +49,7 @@ mod libs;
 mod logging;
 mod metrics;
 mod proxy;
+mod sentry_sink;
 use crate::conn::*;
 use crate::metrics::*;
 use fly::metrics::*;
@@ -291,67 +293,94 @@ fn main() {
 
 use std::time;
 
-static MAX_RUNTIME_IDLE_SECONDS: usize = 5 * 60;
+static DEFAULT_RUNTIME_IDLE_SECONDS: u64 = 5 * 60;
 
 fn runtime_monitoring() -> impl Future<Item = (), Error = ()> + Send + 'static {
     Interval::new_interval(Duration::from_secs(15))
         .map_err(|e| error!("timer error: {}", e))
         .take_while(|_| Ok(unsafe { SELECTOR.is_some() }))
         .for_each(|_| {
+            let (recycle_threshold, idle_ttl) = {
+                let settings = GLOBAL_SETTINGS.read().unwrap();
+                (
+                    settings
+                        .heap_recycle_threshold
+                        .unwrap_or(DEFAULT_HEAP_RECYCLE_THRESHOLD),
+                    settings
+                        .runtime_idle_ttl_secs
+                        .unwrap_or(DEFAULT_RUNTIME_IDLE_SECONDS) as usize,
+                )
+            };
             match unsafe { SELECTOR.as_ref().unwrap() }.runtimes.read() {
                 Err(e) => error!("error getting read lock on runtime selector: {}", e),
                 Ok(guard) => {
-                    guard.iter().for_each(|(k, rt)| {
-                        let stats = rt.heap_statistics();
-                        RUNTIME_USED_HEAP_GAUGE
-                            .with_label_values(&[rt.name.as_str(), &rt.version.as_str()])
-                            .set(stats.used_heap_size as i64);
-                        RUNTIME_TOTAL_HEAP_GAUGE
-                            .with_label_values(&[rt.name.as_str(), &rt.version.as_str()])
-                            .set(stats.total_heap_size as i64);
-                        RUNTIME_EXTERNAL_ALLOCATIONS_GAUGE
-                            .with_label_values(&[rt.name.as_str(), &rt.version.as_str()])
-                            .set(stats.externally_allocated as i64);
-                        RUNTIME_MALLOCED_MEMORY_GAUGE
-                            .with_label_values(&[rt.name.as_str(), &rt.version.as_str()])
-                            .set(stats.malloced_memory as i64);
-                        RUNTIME_PEAK_MALLOCED_MEMORY_GAUGE
-                            .with_label_values(&[rt.name.as_str(), &rt.version.as_str()])
-                            .set(stats.peak_malloced_memory as i64);
-                        info!(
-                            "{}:v{} runtime heap at: {:.2} MB",
-                            rt.name,
-                            rt.version,
-                            stats.used_heap_size as f64 / 1024.0 / 1024.0
-                        );
+                    guard.iter().for_each(|(k, pool)| {
+                        let recycle = {
+                            let runtimes = pool.read();
+                            let rt = match runtimes.first() {
+                                Some(rt) => rt,
+                                None => return,
+                            };
+                            let (mut used, mut total, mut external, mut malloced, mut peak) =
+                                (0, 0, 0, 0, 0);
+                            let mut recycle = false;
+                            for rt in runtimes.iter() {
+                                let stats = rt.heap_statistics();
+                                used += stats.used_heap_size;
+                                total += stats.total_heap_size;
+                                external += stats.externally_allocated;
+                                malloced += stats.malloced_memory;
+                                peak += stats.peak_malloced_memory;
+                                recycle = recycle || heap_exceeded(&stats, recycle_threshold);
+                            }
+                            RUNTIME_USED_HEAP_GAUGE
+                                .with_label_values(&[rt.name.as_str(), &rt.version.as_str()])
+                                .set(used as i64);
+                            RUNTIME_TOTAL_HEAP_GAUGE
+                                .with_label_values(&[rt.name.as_str(), &rt.version.as_str()])
+                                .set(total as i64);
+                            RUNTIME_EXTERNAL_ALLOCATIONS_GAUGE
+                                .with_label_values(&[rt.name.as_str(), &rt.version.as_str()])
+                                .set(external as i64);
+                            RUNTIME_MALLOCED_MEMORY_GAUGE
+                                .with_label_values(&[rt.name.as_str(), &rt.version.as_str()])
+                                .set(malloced as i64);
+                            RUNTIME_PEAK_MALLOCED_MEMORY_GAUGE
+                                .with_label_values(&[rt.name.as_str(), &rt.version.as_str()])
+                                .set(peak as i64);
+                            info!(
+                                "{}:v{} runtime heap at: {:.2} MB across {} isolates",
+                                rt.name,
+                                rt.version,
+                                used as f64 / 1024.0 / 1024.0,
+                                runtimes.len()
+                            );
+                            recycle
+                        };
+
+                        // swap out runtimes about to run out of heap.
+                        if recycle {
+                            let key = k.clone();
+                            tokio::spawn(future::lazy(move || {
+                                runtime_lifecycle::recycle(
+                                    unsafe { SELECTOR.as_ref().unwrap() },
+                                    &key,
+                                );
+                                Ok(())
+                            }));
+                            return;
+                        }
+
+                        pool.autoscale();
 
                         // teardown idle runtimes.
                         if let Ok(epoch) = time::SystemTime::now().duration_since(time::UNIX_EPOCH)
                         {
-                            if epoch.as_secs() as usize - rt.last_event_at.load(Ordering::SeqCst)
-                                > MAX_RUNTIME_IDLE_SECONDS
+                            if epoch.as_secs() as usize - pool.last_event_at() > idle_ttl
                             {
                                 let key = k.clone();
                                 tokio::spawn(future::lazy(move || {
-                                    let mut w = match unsafe { SELECTOR.as_ref().unwrap() }
-                                        .runtimes
-                                        .write()
-                                    {
-                                        Ok(w) => w,
-                                        Err(poisoned) => {
-                                            error!(
-                                                "error getting write lock on runtime selector: {}",
-                                                poisoned
-                                            );
-                                            poisoned.into_inner()
-                                        }
-                                    };
-                                    match w.remove(&key) {
-                                        None => {}
-                                        Some(mut rt) => {
-                                            rt.dispose();
-                                        }
-                                    };
+                                    unsafe { SELECTOR.as_ref().unwrap() }.evict(&key, "idle");
                                     Ok(())
                                 }));
                             }
@@ -1,4 +1,4 @@
-use prometheus::Histogram;
+use prometheus::{Histogram, IntCounterVec};
 
 lazy_static! {
     pub static ref TLS_HANDSHAKE_TIME_HISTOGRAM: Histogram = register_histogram!(
@@ -7,4 +7,16 @@ lazy_static! {
         vec![0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 1.0, 5.0, 10.0, 60.0, 120.0]
     )
     .unwrap();
+    pub static ref RUNTIME_EVICTIONS_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_runtime_evictions_total",
+        "Runtimes evicted from this node, by reason.",
+        &["runtime", "version", "reason"]
+    )
+    .unwrap();
+    pub static ref RUNTIME_ADMISSION_REFUSALS_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_runtime_admission_refusals_total",
+        "Runtimes refused because the node memory budget was exhausted.",
+        &["runtime", "version"]
+    )
+    .unwrap();
 }
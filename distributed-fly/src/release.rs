@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::sync::RwLock;
 
 use rmp_serde::Deserializer;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 
 use crate::is_interrupting;
@@ -12,6 +13,9 @@ use rmpv::Value;
 use crate::kms::decrypt;
 
 use crate::settings::GLOBAL_SETTINGS;
+use fly::http_client::ClientIdentity;
+use fly::runtime_permissions::RuntimePermissions;
+use fly::settings::{CompressionMode, HttpCacheConfig, HttpLimits};
 
 use super::REDIS_POOL;
 use std::thread;
@@ -92,6 +96,10 @@ impl Release {
     match self.config {
       Value::Map(ref map) => {
         for tup in map {
+          // private keys stay out of JS
+          if tup.0.as_str() == Some(CLIENT_IDENTITIES_KEY) {
+            continue;
+          }
           conf.push(parse_config_entry(&tup, &self.secrets));
         }
       }
@@ -100,8 +108,84 @@ impl Release {
 
     serde_json::to_string(&Value::Map(conf))
   }
+
+  /// Client certificates for fetch from the release config's
+  /// `client_identities` entry, a map of names to `cert` and `key` PEMs which
+  /// are usually `fromSecret` references.
+  pub fn client_identities(&self) -> HashMap<String, ClientIdentity> {
+    let mut identities = HashMap::new();
+    let entry = match self.config {
+      Value::Map(ref map) => map
+        .iter()
+        .find(|(k, _)| k.as_str() == Some(CLIENT_IDENTITIES_KEY)),
+      _ => None,
+    };
+    if let Some((_, Value::Map(map))) = entry {
+      for (name, value) in map {
+        let name = match name.as_str() {
+          Some(name) => name,
+          None => continue,
+        };
+        let field = |key: &str| match value {
+          Value::Map(fields) => fields
+            .iter()
+            .find(|(k, _)| k.as_str() == Some(key))
+            .map(|f| parse_config_entry(f, &self.secrets).1)
+            .and_then(|v| v.as_str().map(|v| v.to_string())),
+          _ => None,
+        };
+        match (field("cert"), field("key")) {
+          (Some(cert), Some(key)) => {
+            identities.insert(name.to_string(), ClientIdentity::new(cert, key));
+          }
+          _ => warn!("client identity {} needs a cert and a key", name),
+        }
+      }
+    }
+    identities
+  }
+
+  /// Permissions from the release config's `permissions` entry. OS access is
+  /// never granted this way.
+  pub fn permissions(&self) -> Option<RuntimePermissions> {
+    let mut perms: RuntimePermissions = self.config_entry("permissions")?;
+    perms.allow_os = false;
+    Some(perms)
+  }
+
+  /// Size limits from the release config's `http_limits` entry.
+  pub fn http_limits(&self) -> Option<HttpLimits> {
+    self.config_entry("http_limits")
+  }
+
+  /// The release config's `compression` entry: `off`, `gzip` or `auto`.
+  pub fn compression(&self) -> Option<CompressionMode> {
+    self.config_entry("compression")
+  }
+
+  /// The release config's `http_cache` entry, the edge cache stays off without it.
+  pub fn http_cache(&self) -> Option<HttpCacheConfig> {
+    self.config_entry("http_cache")
+  }
+
+  // Deserializes an entry of the release config, skipping invalid ones.
+  fn config_entry<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+    let entry = match self.config {
+      Value::Map(ref map) => map.iter().find(|(k, _)| k.as_str() == Some(key)),
+      _ => None,
+    }?;
+    match rmpv::ext::from_value::<T>(entry.1.clone()) {
+      Ok(value) => Some(value),
+      Err(e) => {
+        warn!("invalid {} in release config: {}", key, e);
+        None
+      }
+    }
+  }
 }
 
+const CLIENT_IDENTITIES_KEY: &str = "client_identities";
+
 fn get_by_app_key(conn: &redis::Connection, app_key: &str) -> Result<Option<Release>, String> {
   let buf: Vec<u8> = match conn.get(format!("{}:release:latest", app_key)) {
     Ok(v) => v,
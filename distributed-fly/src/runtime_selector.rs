@@ -2,18 +2,27 @@ use futures::Future;
 
 use fly::{
     runtime::{Runtime, RuntimeConfig},
-    RuntimeSelector, SelectorError,
+    runtime_lifecycle::{self, RecyclableSelector},
+    runtime_pool::{PooledRuntime, RuntimePool},
+    settings::RuntimePoolConfig,
+    RuntimeLease, RuntimeSelector, SelectorError,
 };
 
 use std::collections::HashMap;
 use std::sync::RwLock;
+use std::time;
 
 use crate::libs::fetch_libs;
+use crate::metrics::{RUNTIME_ADMISSION_REFUSALS_TOTAL, RUNTIME_EVICTIONS_TOTAL};
 use crate::release::Release;
-use crate::settings::GLOBAL_SETTINGS;
+use crate::sentry_sink::ERROR_SINK;
+use crate::settings::{GlobalSettings, GLOBAL_SETTINGS};
+
+// Runtimes which served an event this recently are never evicted to make room.
+static MIN_RESIDENCY_SECONDS: usize = 30;
 
 pub struct DistributedRuntimeSelector {
-    pub runtimes: RwLock<HashMap<String, Box<Runtime>>>,
+    pub runtimes: RwLock<HashMap<String, RuntimePool>>,
 }
 
 impl DistributedRuntimeSelector {
@@ -22,6 +31,97 @@ impl DistributedRuntimeSelector {
             runtimes: RwLock::new(HashMap::new()),
         }
     }
+
+    /// Removes an app's runtimes from the selector, disposing of them once
+    /// their in-flight events are done.
+    pub fn evict(&self, key: &str, reason: &str) {
+        let mut writer = match self.runtimes.write() {
+            Ok(w) => w,
+            Err(poisoned) => {
+                error!("runtimes writer is poisoned! {}", poisoned);
+                poisoned.into_inner() // recover...
+            }
+        };
+        if let Some(pool) = writer.remove(key) {
+            retire(key, pool, reason);
+        }
+    }
+
+    // Evicts least recently used runtimes until one more app of average
+    // size fits in the node memory budget.
+    fn make_room(&self, runtimes: &mut HashMap<String, RuntimePool>) -> Result<(), SelectorError> {
+        let budget = match GLOBAL_SETTINGS.read().unwrap().runtime_memory_budget_mb {
+            Some(mb) => mb as usize * 1024 * 1024,
+            None => return Ok(()),
+        };
+        if runtimes.is_empty() {
+            return Ok(());
+        }
+
+        let apps: Vec<AppUsage> = runtimes
+            .iter()
+            .map(|(k, pool)| AppUsage {
+                key: k.clone(),
+                footprint: memory_footprint(pool),
+                last_event_at: pool.last_event_at(),
+            })
+            .collect();
+
+        let victims = match pick_evictions(&apps, budget, epoch_secs()) {
+            Ok(victims) => victims,
+            Err(usage) => {
+                warn!(
+                    "runtime memory budget exhausted: {} MB used of {} MB",
+                    usage / 1024 / 1024,
+                    budget / 1024 / 1024
+                );
+                return Err(SelectorError::AtCapacity);
+            }
+        };
+        for key in victims {
+            if let Some(pool) = runtimes.remove(&key) {
+                retire(&key, pool, "memory");
+            }
+        }
+        Ok(())
+    }
+}
+
+struct AppUsage {
+    key: String,
+    footprint: usize,
+    last_event_at: usize,
+}
+
+// Apps to evict, least recently used first, so one more app of average size
+// fits in `budget`. Apps which served an event in the last
+// MIN_RESIDENCY_SECONDS are kept, when evicting all the others isn't enough
+// nothing is evicted and the usage left over is returned.
+fn pick_evictions(apps: &[AppUsage], budget: usize, now: usize) -> Result<Vec<String>, usize> {
+    if apps.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut usage: usize = apps.iter().map(|app| app.footprint).sum();
+    let estimate = usage / apps.len();
+
+    let mut candidates: Vec<&AppUsage> = apps
+        .iter()
+        .filter(|app| now.saturating_sub(app.last_event_at) >= MIN_RESIDENCY_SECONDS)
+        .collect();
+    candidates.sort_by_key(|app| app.last_event_at);
+
+    let mut victims = vec![];
+    let mut candidates = candidates.into_iter();
+    while usage + estimate > budget {
+        match candidates.next() {
+            Some(app) => {
+                usage = usage.saturating_sub(app.footprint);
+                victims.push(app.key.clone());
+            }
+            None => return Err(usage),
+        }
+    }
+    Ok(victims)
 }
 
 impl Drop for DistributedRuntimeSelector {
@@ -30,15 +130,15 @@ impl Drop for DistributedRuntimeSelector {
             Ok(w) => w,
             Err(poisoned) => poisoned.into_inner(),
         };
-        writer.iter_mut().for_each(|(k, rt)| {
+        writer.iter_mut().for_each(|(k, pool)| {
             debug!("Disposing of runtime: {}", k);
-            rt.dispose();
+            pool.dispose();
         });
     }
 }
 
 impl RuntimeSelector for DistributedRuntimeSelector {
-    fn get_by_hostname(&self, hostname: &str) -> Result<Option<&mut Runtime>, SelectorError> {
+    fn get_by_hostname(&self, hostname: &str) -> Result<Option<RuntimeLease>, SelectorError> {
         let rel = match Release::get(hostname) {
             Err(e) => return Err(SelectorError::Failure(e)),
             Ok(maybe_rel) => match maybe_rel {
@@ -66,119 +166,251 @@ impl RuntimeSelector for DistributedRuntimeSelector {
                     poisoned.into_inner() // recover...
                 }
             };
-            let settings = {
-                use fly::settings::*;
-                let global_settings = &*GLOBAL_SETTINGS.read().unwrap();
-                Settings {
-                    data_store: Some(DataStore::Postgres(PostgresStoreConfig {
-                        url: global_settings.cockroach_host.clone(),
-                        database: Some(format!("objectstore_{}", rel.app_id)),
-                        tls_ca_crt: if let Some(ref certs_path) =
-                            global_settings.cockroach_certs_path
-                        {
-                            Some(format!("{}/ca.crt", certs_path))
-                        } else {
-                            None
-                        },
-                        tls_client_crt: if let Some(ref certs_path) =
-                            global_settings.cockroach_certs_path
-                        {
-                            Some(format!("{}/client.root.crt", certs_path))
-                        } else {
-                            None
-                        },
-                        tls_client_key: if let Some(ref certs_path) =
-                            global_settings.cockroach_certs_path
-                        {
-                            Some(format!("{}/client.root.key", certs_path))
-                        } else {
-                            None
-                        },
-                    })), // TODO: use postgres store
-                    cache_store: Some(CacheStore::Redis(RedisStoreConfig {
-                        url: global_settings.redis_cache_url.clone(),
-                        namespace: Some(rel.app_id.to_string()),
-                    })), // TODO: use redis store
-                    cache_store_notifier: match global_settings.redis_cache_notifier_url {
-                        Some(ref url) => {
-                            Some(CacheStoreNotifier::Redis(RedisCacheNotifierConfig {
-                                reader_url: url.clone(),
-                                writer_url: global_settings
-                                    .redis_cache_notifier_writer_url
-                                    .as_ref()
-                                    .unwrap_or(url)
-                                    .clone(),
-                            }))
-                        }
-                        None => None,
-                    },
-                    fs_store: Some(FsStore::Redis(RedisStoreConfig {
-                        namespace: Some(format!("app:{}:release:latest:file:", rel.app_id)),
-                        url: global_settings.redis_url.clone(),
-                    })),
-                    acme_store: Some(AcmeStoreConfig::Redis(RedisStoreConfig {
-                        url: global_settings.redis_url.clone(),
-                        namespace: None,
-                    })),
+            // another request could have built it while we waited for the lock
+            if !writer.contains_key(&key) {
+                if let Err(e) = self.make_room(&mut writer) {
+                    RUNTIME_ADMISSION_REFUSALS_TOTAL
+                        .with_label_values(&[&rel.app_id.to_string(), &rel.version.to_string()])
+                        .inc();
+                    return Err(e);
                 }
-            };
+                let pool_config = runtime_pool_config(&GLOBAL_SETTINGS.read().unwrap());
+                writer.insert(
+                    key.clone(),
+                    RuntimePool::new(Box::new(move || build_runtime(&rel)), &pool_config),
+                );
+            }
+        }
+
+        let runtimes = runtimes.read().unwrap(); // TODO: no unwrap
+        match runtimes.get(&key) {
+            Some(pool) => Ok(pool.lease()),
+            None => Ok(None),
+        }
+    }
+}
+
+impl RecyclableSelector for DistributedRuntimeSelector {
+    fn replace_runtime(&self, key: &str) -> Result<Option<PooledRuntime>, SelectorError> {
+        match self.runtimes.read() {
+            // torn down in the meantime, it'll get rebuilt on the next request.
+            Ok(guard) => Ok(guard.get(key).and_then(|pool| pool.replace_heaviest())),
+            Err(e) => Err(SelectorError::Failure(format!("{}", e))),
+        }
+    }
+}
+
+fn retire(key: &str, pool: RuntimePool, reason: &str) {
+    info!("evicting runtime {} ({})", key, reason);
+    for rt in pool.into_runtimes() {
+        RUNTIME_EVICTIONS_TOTAL
+            .with_label_values(&[rt.name.as_str(), rt.version.as_str(), reason])
+            .inc();
+        tokio::spawn(runtime_lifecycle::retire(rt));
+    }
+}
 
-            let mut rt = Runtime::new(RuntimeConfig {
-                name: Some(rel.app_id.to_string()),
-                version: Some(rel.version.to_string()),
-                settings: &settings,
-                module_resolvers: Some(vec![]),
-                app_logger: &slog_scope::logger(),
-                msg_handler: None,
-                permissions: None,
-                dev_tools: false,
-            });
-            let merged_conf = rel.clone().parsed_config().unwrap();
-            rt.eval(
-                "<app config>",
-                &format!(
-                    "window.fly.app = {{ config: {}, version: {} }};",
-                    merged_conf, rel.version
-                ),
-            );
-
-            // load external libraries if requested
-            if let Some(libs) = rel.libs {
-                match fetch_libs(&libs[..]) {
-                    Ok(lib_sources) => {
-                        for (key, source) in lib_sources.iter() {
-                            if let Some(source) = source {
-                                rt.eval(&format!("<lib:{}>", key), source);
-                            } else {
-                                warn!("app {} requested missing lib: {}", &rel.app_id, &key);
-                            }
-                        }
+fn runtime_pool_config(global_settings: &GlobalSettings) -> RuntimePoolConfig {
+    let mut config = RuntimePoolConfig::default();
+    if let Some(min_size) = global_settings.runtime_pool_min_size {
+        config.min_size = min_size;
+    }
+    if let Some(max_size) = global_settings.runtime_pool_max_size {
+        config.max_size = max_size;
+    }
+    config
+}
+
+fn build_runtime(rel: &Release) -> Box<Runtime> {
+    let settings = {
+        use fly::settings::*;
+        let global_settings = &*GLOBAL_SETTINGS.read().unwrap();
+        Settings {
+            data_store: Some(DataStore::Postgres(PostgresStoreConfig {
+                url: global_settings.cockroach_host.clone(),
+                database: Some(format!("objectstore_{}", rel.app_id)),
+                tls_ca_crt: if let Some(ref certs_path) = global_settings.cockroach_certs_path {
+                    Some(format!("{}/ca.crt", certs_path))
+                } else {
+                    None
+                },
+                tls_client_crt: if let Some(ref certs_path) = global_settings.cockroach_certs_path {
+                    Some(format!("{}/client.root.crt", certs_path))
+                } else {
+                    None
+                },
+                tls_client_key: if let Some(ref certs_path) = global_settings.cockroach_certs_path {
+                    Some(format!("{}/client.root.key", certs_path))
+                } else {
+                    None
+                },
+            })), // TODO: use postgres store
+            cache_store: Some(CacheStore::Redis(RedisStoreConfig {
+                url: global_settings.redis_cache_url.clone(),
+                namespace: Some(rel.app_id.to_string()),
+            })), // TODO: use redis store
+            cache_store_notifier: match global_settings.redis_cache_notifier_url {
+                Some(ref url) => Some(CacheStoreNotifier::Redis(RedisCacheNotifierConfig {
+                    reader_url: url.clone(),
+                    writer_url: global_settings
+                        .redis_cache_notifier_writer_url
+                        .as_ref()
+                        .unwrap_or(url)
+                        .clone(),
+                })),
+                None => None,
+            },
+            fs_store: Some(FsStore::Redis(RedisStoreConfig {
+                namespace: Some(format!("app:{}:release:latest:file:", rel.app_id)),
+                url: global_settings.redis_url.clone(),
+            })),
+            fs_root: None,
+            acme_store: Some(AcmeStoreConfig::Redis(RedisStoreConfig {
+                url: global_settings.redis_url.clone(),
+                namespace: None,
+            })),
+            event_time_limit_ms: global_settings.event_time_limit_ms,
+            heap_recycle_threshold: global_settings.heap_recycle_threshold,
+            runtime_pool: Some(runtime_pool_config(global_settings)),
+            error_reporting: None,
+            permissions: None,
+            ip_filter: Some(IpFilterConfig {
+                deny_private: true,
+                deny: vec![],
+                blacklist_ip_path: global_settings.blacklist_ip_path.clone(),
+            }),
+            fetch: None,
+            client_identities: None,
+            sse_heartbeat_secs: None,
+            http_limits: rel.http_limits(),
+            compression: rel.compression(),
+            http_cache: rel.http_cache(),
+        }
+    };
+
+    let mut rt = Runtime::new(RuntimeConfig {
+        name: Some(rel.app_id.to_string()),
+        version: Some(rel.version.to_string()),
+        settings: &settings,
+        module_resolvers: Some(vec![]),
+        app_logger: &slog_scope::logger(),
+        msg_handler: None,
+        permissions: rel.permissions(),
+        dev_tools: false,
+        error_hook: None,
+        error_sink: ERROR_SINK.clone(),
+        client_identities: Some(rel.client_identities()),
+    });
+    let merged_conf = rel.clone().parsed_config().unwrap();
+    rt.eval(
+        "<app config>",
+        &format!(
+            "window.fly.app = {{ config: {}, version: {} }};",
+            merged_conf, rel.version
+        ),
+    );
+
+    // load external libraries if requested
+    if let Some(ref libs) = rel.libs {
+        match fetch_libs(&libs[..]) {
+            Ok(lib_sources) => {
+                for (key, source) in lib_sources.iter() {
+                    if let Some(source) = source {
+                        rt.eval(&format!("<lib:{}>", key), source);
+                    } else {
+                        warn!("app {} requested missing lib: {}", &rel.app_id, &key);
                     }
-                    Err(e) => warn!("error loading libs for app {}: {}", &rel.app, e),
                 }
             }
+            Err(e) => warn!("error loading libs for app {}: {}", &rel.app, e),
+        }
+    }
 
-            rt.eval("app.js", &rel.source);
-            let app = rel.app;
-            let app_id = rel.app_id;
-            let version = rel.version;
+    rt.eval("app.js", &rel.source);
+    let app = rel.app.clone();
+    let app_id = rel.app_id;
+    let version = rel.version;
 
-            // TODO: ughh, refactor!
-            // let _key2 = key.clone();
-            tokio::spawn(rt.run().then(move |res: Result<(), _>| {
-                if let Err(_) = res {
-                    error!("app: {} ({}) v{} ended abruptly", app, app_id, version);
-                }
-                // runtimes.write().unwrap().remove(&key2);
-                Ok(())
-            }));
-            writer.insert(key.clone(), rt);
+    // TODO: ughh, refactor!
+    // let _key2 = key.clone();
+    tokio::spawn(rt.run().then(move |res: Result<(), _>| {
+        if let Err(_) = res {
+            error!("app: {} ({}) v{} ended abruptly", app, app_id, version);
         }
+        // runtimes.write().unwrap().remove(&key2);
+        Ok(())
+    }));
+    rt
+}
 
-        let runtimes = runtimes.read().unwrap(); // TODO: no unwrap
-        match runtimes.get(&key) {
-            Some(rt) => Ok(Some(rt.ptr.to_runtime())),
-            None => Ok(None),
+fn memory_footprint(pool: &RuntimePool) -> usize {
+    pool.read()
+        .iter()
+        .map(|rt| {
+            let stats = rt.heap_statistics();
+            stats.total_heap_size + stats.externally_allocated
+        })
+        .sum()
+}
+
+fn epoch_secs() -> usize {
+    match time::SystemTime::now().duration_since(time::UNIX_EPOCH) {
+        Ok(epoch) => epoch.as_secs() as usize,
+        Err(_) => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(key: &str, mb: usize, last_event_at: usize) -> AppUsage {
+        AppUsage {
+            key: key.to_string(),
+            footprint: mb * 1024 * 1024,
+            last_event_at,
         }
     }
+
+    #[test]
+    fn test_evicts_least_recently_used_first() {
+        let now = 1000;
+        let apps = vec![
+            app("a:1", 10, now - 100),
+            app("b:1", 10, now - 500),
+            app("c:1", 10, now - 300),
+        ];
+        // 30MB used and 10MB more expected, 20MB has to go
+        let victims = pick_evictions(&apps, 20 * 1024 * 1024, now).unwrap();
+        assert_eq!(victims, vec!["b:1".to_string(), "c:1".to_string()]);
+    }
+
+    #[test]
+    fn test_nothing_evicted_within_budget() {
+        let now = 1000;
+        let apps = vec![app("a:1", 10, now - 100), app("b:1", 10, now - 500)];
+        assert!(pick_evictions(&apps, 30 * 1024 * 1024, now)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_recently_used_apps_stay_resident() {
+        let now = 1000;
+        let apps = vec![
+            app("a:1", 10, now - 5),
+            app("b:1", 10, now - 500),
+            app("c:1", 10, now),
+        ];
+        // only b:1 may go, which isn't enough
+        assert_eq!(
+            pick_evictions(&apps, 20 * 1024 * 1024, now),
+            Err(20 * 1024 * 1024)
+        );
+        // but it is when the budget allows for it
+        assert_eq!(
+            pick_evictions(&apps, 30 * 1024 * 1024, now),
+            Ok(vec!["b:1".to_string()])
+        );
+    }
 }
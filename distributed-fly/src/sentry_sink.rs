@@ -0,0 +1,73 @@
+use fly::error_reporting::{ErrorReport, ErrorSink, RateLimitedSink, DEFAULT_REPORTS_PER_MINUTE};
+use sentry::protocol::{Event, Exception, Frame, Level, Stacktrace};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::settings::GLOBAL_SETTINGS;
+
+lazy_static! {
+    /// Shared by every runtime, so the rate limit applies per app across
+    /// versions and isolates. None without a sentry DSN.
+    pub static ref ERROR_SINK: Option<Arc<ErrorSink>> = {
+        let settings = GLOBAL_SETTINGS.read().unwrap();
+        if settings.sentry_dsn.is_none() {
+            return None;
+        }
+        Some(Arc::new(RateLimitedSink::new(
+            SentrySink,
+            settings
+                .error_reports_per_minute
+                .unwrap_or(DEFAULT_REPORTS_PER_MINUTE),
+            Duration::from_secs(60),
+        )))
+    };
+}
+
+/// Sends app errors to the sentry client set up in main.
+pub struct SentrySink;
+
+impl ErrorSink for SentrySink {
+    fn report(&self, report: ErrorReport) {
+        sentry::capture_event(to_event(&report));
+    }
+}
+
+fn to_event(report: &ErrorReport) -> Event<'static> {
+    // sentry wants the outermost frame first, v8 gives the innermost first
+    let frames = report
+        .frames
+        .iter()
+        .rev()
+        .map(|f| Frame {
+            function: Some(f.function_name.clone()),
+            filename: Some(f.filename.clone()),
+            lineno: Some(u64::from(f.line)),
+            colno: Some(u64::from(f.col)),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+
+    let mut event = Event {
+        level: Level::Error,
+        message: Some(report.message.clone()),
+        exception: vec![Exception {
+            ty: report.kind.as_str().to_string(),
+            value: Some(report.message.clone()),
+            stacktrace: if frames.is_empty() {
+                None
+            } else {
+                Some(Stacktrace {
+                    frames,
+                    ..Default::default()
+                })
+            },
+            ..Default::default()
+        }]
+        .into(),
+        ..Default::default()
+    };
+    for (k, v) in report.tags() {
+        event.tags.insert(k.to_string(), v);
+    }
+    event
+}
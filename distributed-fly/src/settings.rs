@@ -44,6 +44,13 @@ pub struct GlobalSettings {
     // pub logger_host: String,
     // pub logger_port: String,
     pub sentry_dsn: Option<String>,
+    pub error_reports_per_minute: Option<u32>,
+    pub event_time_limit_ms: Option<u64>,
+    pub heap_recycle_threshold: Option<f64>,
+    pub runtime_memory_budget_mb: Option<u64>,
+    pub runtime_idle_ttl_secs: Option<u64>,
+    pub runtime_pool_min_size: Option<usize>,
+    pub runtime_pool_max_size: Option<usize>,
 }
 
 #[derive(Debug, Deserialize)]
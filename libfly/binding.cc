@@ -90,6 +90,69 @@ js_runtime *FromIsolate(v8::Isolate *isolate)
   return static_cast<js_runtime *>(isolate->GetData(0));
 }
 
+static std::string ToStdString(v8::Isolate *isolate, v8::Local<v8::Value> value)
+{
+  if (value.IsEmpty())
+    return std::string();
+  v8::String::Utf8Value str(isolate, value);
+  return *str ? std::string(*str) : std::string();
+}
+
+// Hands an exception and the frames it was thrown from over to rust.
+void ReportException(js_runtime *rt,
+                     v8::Local<v8::Context> context,
+                     v8::Local<v8::Value> exception,
+                     int64_t request_id)
+{
+  if (rt == nullptr || rt->exception_cb == nullptr)
+    return;
+
+  auto *isolate = context->GetIsolate();
+  v8::HandleScope handle_scope(isolate);
+  v8::Context::Scope context_scope(context);
+
+  // prefer the stack captured when the error was created over the one it's
+  // being reported from
+  auto stack_trace = v8::Exception::GetStackTrace(exception);
+  auto message = v8::Exception::CreateMessage(isolate, exception);
+  if (stack_trace.IsEmpty())
+    stack_trace = message->GetStackTrace();
+
+  std::vector<std::string> names;
+  std::vector<js_stack_frame> frames;
+  if (!stack_trace.IsEmpty())
+  {
+    int count = stack_trace->GetFrameCount();
+    // reserved upfront so c_str() pointers stay valid while filling it
+    names.reserve(count * 2);
+    for (int i = 0; i < count; ++i)
+    {
+      auto frame = stack_trace->GetFrame(isolate, i);
+      names.push_back(ToStdString(isolate, frame->GetFunctionName()));
+      names.push_back(ToStdString(isolate, frame->GetScriptName()));
+      frames.push_back(js_stack_frame{
+          names[names.size() - 2].c_str(),
+          names[names.size() - 1].c_str(),
+          frame->GetLineNumber(),
+          frame->GetColumn(),
+      });
+    }
+  }
+  else
+  {
+    names.push_back(ToStdString(isolate, message->GetScriptResourceName()));
+    frames.push_back(js_stack_frame{
+        "",
+        names[0].c_str(),
+        message->GetLineNumber(context).FromMaybe(0),
+        message->GetStartColumn(context).FromMaybe(0) + 1,
+    });
+  }
+
+  std::string exception_str = ToStdString(isolate, exception);
+  rt->exception_cb(rt, exception_str.c_str(), frames.data(), static_cast<int>(frames.size()), request_id);
+}
+
 void HandleExceptionStr(v8::Local<v8::Context> context,
                         v8::Local<v8::Value> exception,
                         std::string *exception_str)
@@ -174,6 +237,7 @@ void HandleException(v8::Local<v8::Context> context,
 {
   v8::Isolate *isolate = context->GetIsolate();
   js_runtime *rt = FromIsolate(isolate);
+  ReportException(rt, context, exception, -1);
   std::string exception_str;
   HandleExceptionStr(context, exception, &exception_str);
   if (rt != nullptr)
@@ -335,6 +399,12 @@ v8::MaybeLocal<v8::Module> ModuleImportCallback(
 
   js_compiled_module module_data = rt->resolve_cb(rt, *specifier_utf_val, referrer->GetIdentityHash());
 
+  if (module_data.ptr == nullptr)
+  {
+    // the resolver threw an exception describing the failure
+    return v8::MaybeLocal<v8::Module>();
+  }
+
   v8::Persistent<v8::Module> *module_persistent = static_cast<v8::Persistent<v8::Module> *>(module_data.ptr);
 
   v8::Local<v8::Module> module_local = module_persistent->Get(isolate);
@@ -358,7 +428,7 @@ bool ExecuteV8Module(v8::Local<v8::Context> context, js_compiled_module module_d
 
   auto module_instantiated = module_local->InstantiateModule(context, &ModuleImportCallback);
 
-  if (module_instantiated.FromMaybe(false))
+  if (!module_instantiated.FromMaybe(false))
   {
     // DCHECK(try_catch.HasCaught());
     HandleException(context, try_catch.Exception());
@@ -377,6 +447,21 @@ bool ExecuteV8Module(v8::Local<v8::Context> context, js_compiled_module module_d
   return true;
 }
 
+// The exception a module failed to compile with, and where.
+std::string CompileErrorStr(v8::Local<v8::Context> context,
+                            v8::Local<v8::Value> exception)
+{
+  auto *isolate = context->GetIsolate();
+  auto message = v8::Exception::CreateMessage(isolate, exception);
+  v8::String::Utf8Value exception_str(isolate, exception);
+  v8::String::Utf8Value script_name(isolate, message->GetScriptResourceName());
+  char buf[1024];
+  snprintf(buf, sizeof(buf), "%s (%s:%d:%d)", *exception_str, *script_name,
+           message->GetLineNumber(context).FromMaybe(0),
+           message->GetStartColumn(context).FromMaybe(0) + 1);
+  return buf;
+}
+
 js_compile_module_result CompileV8Module(v8::Local<v8::Context> context, js_module_data module_data)
 {
   auto *isolate = context->GetIsolate();
@@ -403,27 +488,30 @@ js_compile_module_result CompileV8Module(v8::Local<v8::Context> context, js_modu
 
   if (module.IsEmpty())
   {
-    // DCHECK(try_catch.HasCaught());
-    HandleException(context, try_catch.Exception());
+    // the importing script gets this thrown at it by the resolve callback
+    static thread_local std::string compile_error;
+    compile_error = CompileErrorStr(context, try_catch.Exception());
     return js_compile_module_result{
-        NULL,
+        js_compiled_module{0, module_data, nullptr},
         false,
+        compile_error.c_str(),
     };
   }
 
   auto module_local = module.ToLocalChecked();
 
-  v8::Persistent<v8::Module> module_persistent(isolate, module_local);
+  auto *module_persistent = new v8::Persistent<v8::Module>(isolate, module_local);
 
   auto compiled_module = js_compiled_module{
       module_local->GetIdentityHash(),
       module_data,
-      &module_persistent,
+      module_persistent,
   };
 
   return js_compile_module_result{
-      &compiled_module,
+      compiled_module,
       true,
+      nullptr,
   };
 }
 
@@ -510,12 +598,30 @@ void SetGlobalErrorHandler(const v8::FunctionCallbackInfo<v8::Value> &args)
   rt->global_error_handler.Reset(isolate, func);
 }
 
+void ReportError(const v8::FunctionCallbackInfo<v8::Value> &args)
+{
+  v8::Isolate *isolate = args.GetIsolate();
+  js_runtime *rt = FromIsolate(isolate);
+  if (rt == nullptr)
+    return;
+
+  v8::HandleScope handle_scope(isolate);
+  auto context = isolate->GetCurrentContext();
+
+  int64_t request_id = -1;
+  if (args[1]->IsUint32())
+    request_id = args[1]->Uint32Value(context).FromJust();
+
+  ReportException(rt, context, args[0], request_id);
+}
+
 intptr_t ext_refs[] = {
     reinterpret_cast<intptr_t>(Print),
     reinterpret_cast<intptr_t>(Send),
     reinterpret_cast<intptr_t>(Recv),
     reinterpret_cast<intptr_t>(SetGlobalErrorHandler),
     reinterpret_cast<intptr_t>(GetNextStreamId),
+    reinterpret_cast<intptr_t>(ReportError),
     0};
 
 void InitContext(v8::Isolate *isolate, v8::Local<v8::Context> context)
@@ -545,6 +651,10 @@ void InitContext(v8::Isolate *isolate, v8::Local<v8::Context> context)
   auto gnsi_tmpl = v8::FunctionTemplate::New(isolate, GetNextStreamId);
   auto gnsi_val = gnsi_tmpl->GetFunction(context).ToLocalChecked();
   fly->Set(context, v8_str(isolate, "getNextStreamId"), gnsi_val).FromJust();
+
+  auto re_tmpl = v8::FunctionTemplate::New(isolate, ReportError);
+  auto re_val = re_tmpl->GetFunction(context).ToLocalChecked();
+  fly->Set(context, v8_str(isolate, "reportError"), re_val).FromJust();
 }
 
 extern "C"
@@ -595,6 +705,7 @@ extern "C"
       printf("Unhandled promise rejection:\n");
       printf("is native error? %s\n", error->IsNativeError() ? "true" : "false");
       printf("%s\n", *v8::String::Utf8Value(v8::Isolate::GetCurrent(), error));
+      ReportException(rt, context, error, -1);
       break;
     case v8::kPromiseHandlerAddedAfterReject:
       printf("promise handler added after reject\n");
@@ -614,6 +725,7 @@ extern "C"
 
     rt->recv_cb = options.recv_cb;
     rt->print_cb = options.print_cb;
+    rt->exception_cb = options.exception_cb;
     rt->allocator = new LimitedAllocator(options.soft_memory_limit * 1024 * 1024, options.hard_memory_limit * 1024 * 1024);
     v8::Isolate::CreateParams params;
 
@@ -641,6 +753,7 @@ extern "C"
     isolate->SetEventLogger(log_event_cb);
     isolate->AddMicrotasksCompletedCallback(microtasks_completed_cb);
     isolate->SetPromiseRejectCallback(promise_rejected_cb);
+    isolate->SetCaptureStackTraceForUncaughtExceptions(true, 32);
 
     v8::Locker locker(isolate);
     v8::Isolate::Scope isolate_scope(isolate);
@@ -679,6 +792,10 @@ extern "C"
     auto context = rt->context.Get(rt->isolate);
     v8::Context::Scope context_scope(context);
 
+    // a termination requested after the previous script already returned
+    // must not take down the next one.
+    rt->isolate->CancelTerminateExecution();
+
     v8::TryCatch try_catch(rt->isolate);
     try_catch.SetVerbose(true);
 
@@ -705,6 +822,13 @@ extern "C"
 
     delete[] args;
 
+    if (try_catch.HasTerminated())
+    {
+      // the watchdog stopped a runaway script, allow the isolate to run again.
+      rt->isolate->CancelTerminateExecution();
+      return 0;
+    }
+
     if (try_catch.HasCaught())
     {
       HandleException(context, try_catch.Exception());
@@ -748,6 +872,23 @@ extern "C"
     rt->isolate->RunMicrotasks();
   }
 
+  bool js_runtime_terminate_execution(const runtime *rt)
+  {
+    if (!rt->isolate)
+    {
+      printf("isolate has been disposed\n");
+      return false;
+    }
+    // only interrupt code that is actually running, an idle isolate would
+    // otherwise kill whatever script it runs next.
+    if (!rt->isolate->IsInUse())
+      return false;
+    // safe to call from any thread, V8 will throw an uncatchable exception
+    // in the running script.
+    rt->isolate->TerminateExecution();
+    return true;
+  }
+
   bool js_eval(const js_runtime *rt, const char *filename, const char *code)
   {
     if (!rt->isolate)
@@ -846,6 +987,11 @@ extern "C"
       printf("isolate has been disposed\n");
       return;
     }
+    for (auto *module_persistent : rt->modules)
+    {
+      module_persistent->Reset();
+      delete module_persistent;
+    }
     rt->isolate->Dispose();
     delete rt;
   }
@@ -878,6 +1024,25 @@ extern "C"
   js_compile_module_result js_compile_module(const runtime *rt, js_module_data module_data)
   {
     VALUE_SCOPE(rt->isolate, rt->context);
-    return CompileV8Module(ctx, module_data);
+    auto result = CompileV8Module(ctx, module_data);
+    if (result.success)
+    {
+      auto *module_persistent = static_cast<v8::Persistent<v8::Module> *>(result.compiled_module.ptr);
+      const_cast<runtime *>(rt)->modules.push_back(module_persistent);
+    }
+    return result;
+  }
+
+  void js_throw_module_error(const runtime *rt, bool syntax_error, const char *message, const char *specifier, const char *referrer)
+  {
+    auto *isolate = rt->isolate;
+    v8::HandleScope handle_scope(isolate);
+    auto context = rt->context.Get(isolate);
+    auto msg = v8_str(isolate, message);
+    auto exception = syntax_error ? v8::Exception::SyntaxError(msg) : v8::Exception::Error(msg);
+    auto err = exception.As<v8::Object>();
+    err->Set(context, v8_str(isolate, "specifier"), v8_str(isolate, specifier)).FromJust();
+    err->Set(context, v8_str(isolate, "referrer"), v8_str(isolate, referrer)).FromJust();
+    isolate->ThrowException(exception);
   }
 }
@@ -49,8 +49,16 @@ struct js_compiled_module {
 };
 
 struct js_compile_module_result {
-  const js_compiled_module *compiled_module;
+  js_compiled_module compiled_module;
   bool success;
+  const char *error; // V8's message when compiling failed, valid until the next failure
+};
+
+struct js_stack_frame {
+  const char *function_name;
+  const char *script_name;
+  int line;
+  int column;
 };
 
 struct js_runtime;
@@ -62,6 +70,9 @@ typedef void (*fly_print_cb)(runtime *rt, int8_t lvl, const char *msg);
 
 typedef js_compiled_module (*fly_resolve_cb)(runtime *rt, const char *specifier, int referer_identity_hash);
 
+// request_id is -1 when the exception can't be tied to an event.
+typedef void (*fly_exception_cb)(runtime *rt, const char *message, const js_stack_frame *frames, int frame_count, int64_t request_id);
+
 struct js_runtime_options
 {
   fly_simple_buf snapshot;
@@ -69,6 +80,7 @@ struct js_runtime_options
   fly_recv_cb recv_cb;
   fly_print_cb print_cb;
   fly_resolve_cb resolve_cb;
+  fly_exception_cb exception_cb;
   size_t soft_memory_limit;
   size_t hard_memory_limit;
 };
@@ -98,11 +110,13 @@ extern "C"
   extern void js_set_response(const runtime *rt, fly_buf buf);
 
   extern void js_runtime_dispose(const runtime *rt);
+  extern bool js_runtime_terminate_execution(const runtime *rt);
   extern void js_runtime_run_micro_tasks(const runtime *rt);
 
   extern const char *js_version();
 
   extern js_compile_module_result js_compile_module(const runtime *rt, js_module_data module_data);
+  extern void js_throw_module_error(const runtime *rt, bool syntax_error, const char *message, const char *specifier, const char *referrer);
 
 } // extern "C"
 
@@ -4,6 +4,7 @@
 #include "allocator.h"
 #include <v8.h>
 #include <string>
+#include <vector>
 #include <sstream>
 #include <iostream>
 
@@ -22,6 +23,9 @@ extern "C"
     fly_print_cb print_cb;
     std::string last_exception;
     fly_resolve_cb resolve_cb;
+    fly_exception_cb exception_cb;
+    // every module compiled, released when the runtime is disposed
+    std::vector<v8::Persistent<v8::Module> *> modules;
   };
 }
 
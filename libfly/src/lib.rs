@@ -30,10 +30,19 @@ pub struct js_runtime_options {
     pub recv_cb: RecvCb,
     pub print_cb: PrintCb,
     pub resolve_cb: ResolveCb,
+    pub exception_cb: ExceptionCb,
     pub soft_memory_limit: size_t,
     pub hard_memory_limit: size_t,
 }
 
+#[repr(C)]
+pub struct js_stack_frame {
+    pub function_name: *const c_char,
+    pub script_name: *const c_char,
+    pub line: c_int,
+    pub column: c_int,
+}
+
 #[repr(C)]
 pub struct js_runtime {
     _unused: [u8; 0],
@@ -58,6 +67,7 @@ pub struct js_compiled_module {
 pub struct js_compile_module_result {
     pub compiled_module: js_compiled_module,
     pub success: bool,
+    pub error: *const c_char,
 }
 
 #[repr(C)]
@@ -71,7 +81,7 @@ pub struct js_callback_info {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct js_heap_stats {
     pub total_heap_size: size_t,
     pub total_heap_size_executable: size_t,
@@ -94,12 +104,20 @@ pub fn version() -> String {
 type RecvCb = unsafe extern "C" fn(rt: *const js_runtime, buf: fly_buf, data_buf: fly_buf);
 type PrintCb = unsafe extern "C" fn(rt: *const js_runtime, lvl: i8, msg: *const c_char);
 type ResolveCb = unsafe extern "C" fn(rt: *const js_runtime, specifier: *const c_char, referer_identity_hash: i32) -> js_compiled_module;
+type ExceptionCb = unsafe extern "C" fn(
+    rt: *const js_runtime,
+    message: *const c_char,
+    frames: *const js_stack_frame,
+    frame_count: c_int,
+    request_id: i64,
+);
 
 extern "C" {
     pub fn js_init();
     pub fn js_version() -> *const c_char;
     pub fn js_runtime_new(options: js_runtime_options) -> *const js_runtime;
     pub fn js_runtime_dispose(rt: *const js_runtime) -> *const c_void;
+    pub fn js_runtime_terminate_execution(rt: *const js_runtime) -> bool;
     pub fn js_runtime_run_micro_tasks(rt: *const js_runtime) -> *const c_void;
     pub fn js_get_data(rt: *const js_runtime) -> *const c_void;
     pub fn js_set_response(rt: *const js_runtime, buf: fly_buf);
@@ -111,6 +129,13 @@ extern "C" {
     pub fn js_eval(rt: *const js_runtime, filename: *const c_char, code: *const c_char) -> bool;
     pub fn js_run_module(rt: *const js_runtime, module_data: js_compiled_module) -> bool;
     pub fn js_compile_module(rt: *const js_runtime, module_data: js_module_data) -> js_compile_module_result;
+    pub fn js_throw_module_error(
+        rt: *const js_runtime,
+        syntax_error: bool,
+        message: *const c_char,
+        specifier: *const c_char,
+        referrer: *const c_char,
+    );
 }
 
 #[no_mangle]
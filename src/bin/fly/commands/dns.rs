@@ -100,6 +100,9 @@ pub fn exec(args: &ArgMatches<'_>) -> FlyCliResult<()> {
         msg_handler: None,
         permissions: None,
         dev_tools: true,
+        error_hook: None,
+        error_sink: None,
+        client_identities: None,
     });
 
     runtime.eval_file_with_dev_tools(entry_file);
@@ -24,6 +24,9 @@ pub fn exec(args: &ArgMatches<'_>) -> FlyCliResult<()> {
         msg_handler: None,
         permissions: None,
         dev_tools: true,
+        error_hook: None,
+        error_sink: None,
+        client_identities: None,
     });
 
     let entry_file = args.value_of("input").unwrap();
@@ -9,11 +9,17 @@ use hyper::Server;
 
 use tokio::prelude::*;
 
+use fly::error_reporting;
 use fly::fixed_runtime_selector::FixedRuntimeSelector;
 use fly::http_server::serve_http;
 use fly::runtime::*;
+use fly::runtime_lifecycle::{self, heap_exceeded, DEFAULT_HEAP_RECYCLE_THRESHOLD};
+use fly::runtime_pool::RuntimePool;
 use fly::settings::SETTINGS;
 
+use std::time::Duration;
+use tokio::timer::Interval;
+
 static mut SELECTOR: Option<FixedRuntimeSelector> = None;
 
 pub fn cli() -> App {
@@ -50,29 +56,50 @@ pub fn cli() -> App {
 pub fn exec(args: &ArgMatches<'_>) -> FlyCliResult<()> {
     info!("V8 version: {}", libfly::version());
 
-    let mut runtime = Runtime::new(RuntimeConfig {
-        name: None,
-        version: None,
-        settings: &SETTINGS.read().unwrap(),
-        module_resolvers: None,
-        app_logger: &slog_scope::logger(),
-        msg_handler: None,
-        permissions: None,
-        dev_tools: true,
-    });
-
-    if args.is_present("lib") {
-        for lib_path in glob(args.values_of("lib").unwrap().collect(), None)? {
-            runtime.eval_file(&lib_path);
-        }
-    }
-
-    if let Some(path) = glob(vec![args.value_of("path").unwrap()], Some(1))?.first() {
-        println!("Running app {}", path);
-        runtime.eval_file_with_dev_tools(path);
+    let lib_paths = if args.is_present("lib") {
+        glob(args.values_of("lib").unwrap().collect(), None)?
     } else {
-        return Err(FlyCliError::from("No source code found"));
-    }
+        vec![]
+    };
+
+    let app_path = match glob(vec![args.value_of("path").unwrap()], Some(1))?.first() {
+        Some(path) => path.clone(),
+        None => return Err(FlyCliError::from("No source code found")),
+    };
+    println!("Running app {}", app_path);
+
+    let error_sink = SETTINGS
+        .read()
+        .unwrap()
+        .error_reporting
+        .as_ref()
+        .map(error_reporting::sink_from_config);
+
+    let build_runtime = move || {
+        let mut runtime = Runtime::new(RuntimeConfig {
+            name: None,
+            version: None,
+            settings: &SETTINGS.read().unwrap(),
+            module_resolvers: None,
+            app_logger: &slog_scope::logger(),
+            msg_handler: None,
+            permissions: None,
+            dev_tools: true,
+            error_hook: None,
+            error_sink: error_sink.clone(),
+            client_identities: None,
+        });
+        for lib_path in lib_paths.iter() {
+            runtime.eval_file(lib_path);
+        }
+        runtime.eval_file_with_dev_tools(&app_path);
+        tokio::spawn(
+            runtime
+                .run()
+                .map_err(|e| error!("error running runtime event loop: {}", e)),
+        );
+        runtime
+    };
 
     let bind = match args.value_of("bind") {
         Some(b) => b,
@@ -108,14 +135,12 @@ pub fn exec(args: &ArgMatches<'_>) -> FlyCliResult<()> {
         });
 
     tokio::run(future::lazy(move || {
-        tokio::spawn(
-            runtime
-                .run()
-                .map_err(|e| error!("error running runtime event loop: {}", e)),
-        );
-        unsafe { SELECTOR = Some(FixedRuntimeSelector::new(runtime)) }
+        let pool_config = SETTINGS.read().unwrap().runtime_pool.clone();
+        let pool = RuntimePool::new(Box::new(build_runtime), &pool_config.unwrap_or_default());
+        unsafe { SELECTOR = Some(FixedRuntimeSelector::with_pool(pool)) }
 
         tokio::spawn(server);
+        tokio::spawn(runtime_monitoring());
 
         println!("Listening on http://{}", addr);
 
@@ -124,3 +149,27 @@ pub fn exec(args: &ArgMatches<'_>) -> FlyCliResult<()> {
 
     Ok(())
 }
+
+fn runtime_monitoring() -> impl Future<Item = (), Error = ()> + Send + 'static {
+    let threshold = SETTINGS
+        .read()
+        .unwrap()
+        .heap_recycle_threshold
+        .unwrap_or(DEFAULT_HEAP_RECYCLE_THRESHOLD);
+    Interval::new_interval(Duration::from_secs(15))
+        .map_err(|e| error!("timer error: {}", e))
+        .take_while(|_| Ok(unsafe { SELECTOR.is_some() }))
+        .for_each(move |_| {
+            let selector = unsafe { SELECTOR.as_ref().unwrap() };
+            let exceeded = selector
+                .pool()
+                .read()
+                .iter()
+                .any(|rt| heap_exceeded(&rt.heap_statistics(), threshold));
+            if exceeded {
+                runtime_lifecycle::recycle(selector, "");
+            }
+            selector.pool().autoscale();
+            Ok(())
+        })
+}
@@ -4,7 +4,11 @@ use clap::{Arg, ArgMatches};
 use fly::runtime::{Runtime, RuntimeConfig};
 use fly::runtime_permissions::RuntimePermissions;
 use fly::settings::SETTINGS;
-use futures::Future;
+use futures::{Future, Sink, Stream};
+use std::net::SocketAddr;
+use std::thread;
+use tokio::net::TcpListener;
+use tokio::reactor::Handle;
 
 const PATTERN_DEFAULT: &str = "**/*.{test,spec}.{js,ts}";
 
@@ -29,15 +33,26 @@ pub fn cli() -> App {
 }
 
 pub fn exec(args: &ArgMatches<'_>) -> FlyCliResult<()> {
+    // specs write files into a directory removed once they're done
+    let fs_root = tempfile::tempdir()?;
+    let mut settings = SETTINGS.read().unwrap().clone();
+    settings.fs_root = Some(fs_root.path().to_string_lossy().into_owned());
+
     let mut rt = Runtime::new(RuntimeConfig {
         name: None,
         version: None,
-        settings: &SETTINGS.read().unwrap(),
+        settings: &settings,
         module_resolvers: None,
         app_logger: &slog_scope::logger(),
         msg_handler: None,
-        permissions: Some(RuntimePermissions::new(true)),
+        permissions: Some(RuntimePermissions {
+            allow_fs_write: true,
+            ..RuntimePermissions::new(true)
+        }),
         dev_tools: true,
+        error_hook: None,
+        error_sink: None,
+        client_identities: None,
     });
 
     if args.is_present("lib") {
@@ -48,6 +63,15 @@ pub fn exec(args: &ArgMatches<'_>) -> FlyCliResult<()> {
 
     let test_files = glob(args.values_of("paths").unwrap().collect(), None)?;
 
+    let echo_addr = spawn_echo_server()?;
+    rt.eval(
+        "<testEnv>",
+        &format!(
+            "window.WEBSOCKET_ECHO_URL = {};",
+            serde_json::to_string(&format!("ws://{}/", echo_addr)).unwrap()
+        ),
+    );
+
     rt.eval(
         "<runTests>",
         &format!(
@@ -63,3 +87,37 @@ pub fn exec(args: &ArgMatches<'_>) -> FlyCliResult<()> {
 
     Ok(())
 }
+
+// A websocket server echoing every message back, so specs don't need to reach
+// out to the internet.
+fn spawn_echo_server() -> FlyCliResult<SocketAddr> {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    thread::spawn(move || {
+        let listener = match TcpListener::from_std(listener, &Handle::default()) {
+            Ok(listener) => listener,
+            Err(e) => return error!("error starting websocket echo server: {}", e),
+        };
+        tokio::run(
+            listener
+                .incoming()
+                .map_err(|e| error!("websocket echo server error: {}", e))
+                .for_each(|tcp| {
+                    tokio::spawn(
+                        tokio_tungstenite::accept_async(tcp)
+                            .map_err(|_| ())
+                            .and_then(|ws| {
+                                let (sink, stream) = ws.split();
+                                stream
+                                    .take_while(|m| Ok(m.is_text() || m.is_binary()))
+                                    .forward(sink)
+                                    .map(|_| ())
+                                    .map_err(|_| ())
+                            }),
+                    );
+                    Ok(())
+                }),
+        );
+    });
+    Ok(addr)
+}
@@ -14,6 +14,12 @@ fn main() -> FlyCliResult<()> {
   let _guard = slog_scope::set_global_logger(logger);
   slog_stdlog::init().unwrap();
 
+  if let Some(ref conf) = fly::settings::SETTINGS.read().unwrap().fetch {
+    if let Err(e) = fly::http_client::check_config(conf) {
+      return Err(format!("invalid fetch settings: {}", e).as_str().into());
+    }
+  }
+
   let args = cli().get_matches();
   let (cmd, subcommand_args) = args.subcommand();
   let exec_fn = commands::command_exec(cmd).expect("Unknown command");
@@ -0,0 +1,442 @@
+use std::io::{self, Write};
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use brotli::CompressorWriter;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use futures::{future, Async, Future, Poll, Stream};
+use hyper::{header, HeaderMap, StatusCode};
+
+use crate::settings::CompressionMode;
+
+/// Static bodies smaller than this go out as is, compressing them wouldn't
+/// save enough to be worth it.
+pub const MIN_COMPRESS_BYTES: usize = 1024;
+
+// Favors speed, bodies are compressed on every response.
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_WINDOW: u32 = 22;
+const BROTLI_BUFFER: usize = 4096;
+
+static OTHER_ALLOWED_MIME_TYPES: [&str; 9] = [
+    "application/x-javascript",
+    "application/vnd.ms-fontobject",
+    "application/x-font-opentype",
+    "application/x-font-truetype",
+    "application/x-font-ttf",
+    "font/eot",
+    "font/opentype",
+    "font/otf",
+    "image/vnd.microsoft.icon",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Gzip,
+    Brotli,
+}
+
+impl Encoding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Brotli => "br",
+        }
+    }
+}
+
+/// Picks the encoding for a client's `Accept-Encoding`, if any is allowed.
+pub fn negotiate(mode: CompressionMode, accept: Option<&header::HeaderValue>) -> Option<Encoding> {
+    if mode == CompressionMode::Off {
+        return None;
+    }
+    let accept = accept?.to_str().ok()?;
+    // explicit codings win over `*`, whatever their order
+    let (mut gzip, mut brotli, mut any) = (None, None, None);
+    for item in accept.split(',') {
+        let mut params = item.split(';');
+        let coding = params.next().unwrap_or("").trim();
+        let q = params
+            .filter_map(|p| {
+                let p = p.trim();
+                if p.starts_with("q=") {
+                    p[2..].parse::<f32>().ok()
+                } else {
+                    None
+                }
+            })
+            .next()
+            .unwrap_or(1.0);
+        if coding.eq_ignore_ascii_case("gzip") {
+            gzip = Some(q > 0.0);
+        } else if coding.eq_ignore_ascii_case("br") {
+            brotli = Some(q > 0.0);
+        } else if coding == "*" {
+            any = Some(q > 0.0);
+        }
+    }
+    if brotli == Some(true) && mode == CompressionMode::Auto {
+        Some(Encoding::Brotli)
+    } else if gzip.or(any) == Some(true) {
+        Some(Encoding::Gzip)
+    } else {
+        None
+    }
+}
+
+/// Whether a response may be compressed on its way out. Responses already
+/// encoded, partial content and event streams are left alone.
+pub fn compressible(status: StatusCode, headers: &HeaderMap) -> bool {
+    if status == StatusCode::NO_CONTENT
+        || status == StatusCode::NOT_MODIFIED
+        || status == StatusCode::PARTIAL_CONTENT
+    {
+        return false;
+    }
+    if headers.contains_key(header::CONTENT_ENCODING) || headers.contains_key(header::CONTENT_RANGE)
+    {
+        return false;
+    }
+    let no_transform = headers.get_all(header::CACHE_CONTROL).iter().any(|v| {
+        v.to_str()
+            .map(|v| {
+                v.split(',')
+                    .any(|d| d.trim().eq_ignore_ascii_case("no-transform"))
+            })
+            .unwrap_or(false)
+    });
+    if no_transform {
+        return false;
+    }
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map_or(false, compressible_type)
+}
+
+fn compressible_type(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    let mut parts = essence.splitn(2, '/');
+    let (kind, subtype) = match (parts.next(), parts.next()) {
+        (Some(kind), Some(subtype)) => (kind, subtype),
+        _ => return false,
+    };
+    match kind {
+        // streamed events have to reach the client as soon as they're sent
+        "text" => subtype != "event-stream",
+        "application" => {
+            subtype == "javascript"
+                || subtype == "json"
+                || subtype == "xml"
+                || subtype.ends_with("+json")
+                || subtype.ends_with("+xml")
+        }
+        "image" if subtype == "svg+xml" => true,
+        _ => OTHER_ALLOWED_MIME_TYPES.contains(&essence.as_str()),
+    }
+}
+
+/// Marks a response as compressed. Its length changes, so it's dropped, and
+/// a strong ETag is weakened as the bytes no longer match it.
+pub fn set_encoding_headers(headers: &mut HeaderMap, encoding: Encoding) {
+    headers.remove(header::CONTENT_LENGTH);
+    let weakened = headers
+        .get(header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .filter(|etag| !etag.starts_with("W/"))
+        .and_then(|etag| header::HeaderValue::from_str(&format!("W/{}", etag)).ok());
+    if let Some(etag) = weakened {
+        headers.insert(header::ETAG, etag);
+    }
+    headers.insert(
+        header::CONTENT_ENCODING,
+        header::HeaderValue::from_static(encoding.as_str()),
+    );
+    let varies = headers.get_all(header::VARY).iter().any(|v| {
+        v.to_str()
+            .map(|v| {
+                v.split(',').any(|f| {
+                    let f = f.trim();
+                    f == "*" || f.eq_ignore_ascii_case("accept-encoding")
+                })
+            })
+            .unwrap_or(false)
+    });
+    if !varies {
+        headers.append(
+            header::VARY,
+            header::HeaderValue::from_static("Accept-Encoding"),
+        );
+    }
+}
+
+/// Compresses a whole body at once.
+pub fn encode(encoding: Encoding, bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let output = Output::default();
+    let mut encoder = Encoder::new(encoding, output.clone());
+    encoder.write(bytes)?;
+    encoder.finish()?;
+    Ok(output.take())
+}
+
+/// Compresses a whole body once it's polled for, in a blocking section when
+/// on a thread pool so other futures aren't held up meanwhile.
+pub fn encode_later(
+    encoding: Encoding,
+    bytes: Vec<u8>,
+) -> impl Future<Item = Vec<u8>, Error = io::Error> + Send {
+    future::poll_fn(
+        move || match tokio_threadpool::blocking(|| encode(encoding, &bytes)) {
+            Ok(Async::Ready(res)) => res.map(Async::Ready),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            // not on a thread pool
+            Err(_) => encode(encoding, &bytes).map(Async::Ready),
+        },
+    )
+}
+
+// Collects what an encoder wrote so far.
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl Output {
+    fn take(&self) -> Vec<u8> {
+        mem::replace(&mut *self.0.lock().unwrap(), vec![])
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+enum Encoder {
+    Gzip(GzEncoder<Output>),
+    Brotli(CompressorWriter<Output>),
+}
+
+impl Encoder {
+    fn new(encoding: Encoding, output: Output) -> Self {
+        match encoding {
+            Encoding::Gzip => Encoder::Gzip(GzEncoder::new(output, Compression::default())),
+            Encoding::Brotli => Encoder::Brotli(CompressorWriter::new(
+                output,
+                BROTLI_BUFFER,
+                BROTLI_QUALITY,
+                BROTLI_WINDOW,
+            )),
+        }
+    }
+
+    fn write(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            Encoder::Gzip(e) => e.write_all(buf),
+            Encoder::Brotli(e) => e.write_all(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Encoder::Gzip(e) => e.flush(),
+            Encoder::Brotli(e) => e.flush(),
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Encoder::Gzip(e) => e.finish().map(|_| ()),
+            Encoder::Brotli(e) => {
+                e.into_inner();
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Compresses a streamed body as it goes. Every chunk is flushed through
+/// the encoder so the client gets it without waiting for the next one.
+pub struct EncodedStream<S> {
+    inner: S,
+    encoder: Option<Encoder>,
+    output: Output,
+}
+
+impl<S> EncodedStream<S> {
+    pub fn new(inner: S, encoding: Encoding) -> Self {
+        let output = Output::default();
+        EncodedStream {
+            inner,
+            encoder: Some(Encoder::new(encoding, output.clone())),
+            output,
+        }
+    }
+}
+
+impl<S> Stream for EncodedStream<S>
+where
+    S: Stream<Item = Vec<u8>, Error = io::Error>,
+{
+    type Item = Vec<u8>;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, io::Error> {
+        loop {
+            let encoder = match self.encoder {
+                Some(ref mut encoder) => encoder,
+                None => return Ok(Async::Ready(None)),
+            };
+            match self.inner.poll()? {
+                Async::Ready(Some(chunk)) => {
+                    encoder.write(&chunk)?;
+                    encoder.flush()?;
+                }
+                Async::Ready(None) => self.encoder.take().unwrap().finish()?,
+                Async::NotReady => return Ok(Async::NotReady),
+            }
+            let out = self.output.take();
+            if !out.is_empty() {
+                return Ok(Async::Ready(Some(out)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use futures::stream;
+    use std::io::Read;
+
+    #[test]
+    fn test_negotiate() {
+        let accept = |v: &'static str| header::HeaderValue::from_static(v);
+        assert_eq!(
+            negotiate(CompressionMode::Auto, Some(&accept("gzip, deflate, br"))),
+            Some(Encoding::Brotli)
+        );
+        assert_eq!(
+            negotiate(CompressionMode::Gzip, Some(&accept("gzip, br"))),
+            Some(Encoding::Gzip)
+        );
+        assert_eq!(
+            negotiate(CompressionMode::Auto, Some(&accept("br;q=0, gzip;q=0.5"))),
+            Some(Encoding::Gzip)
+        );
+        assert_eq!(
+            negotiate(CompressionMode::Auto, Some(&accept("identity"))),
+            None
+        );
+        assert_eq!(
+            negotiate(CompressionMode::Auto, Some(&accept("*"))),
+            Some(Encoding::Gzip)
+        );
+        assert_eq!(
+            negotiate(CompressionMode::Auto, Some(&accept("gzip;q=0, *"))),
+            None
+        );
+        assert_eq!(
+            negotiate(CompressionMode::Auto, Some(&accept("*, br;q=0"))),
+            Some(Encoding::Gzip)
+        );
+        assert_eq!(negotiate(CompressionMode::Off, Some(&accept("gzip"))), None);
+        assert_eq!(negotiate(CompressionMode::Auto, None), None);
+    }
+
+    #[test]
+    fn test_compressible() {
+        let mut headers = HeaderMap::new();
+        assert!(!compressible(StatusCode::OK, &headers));
+
+        headers.insert(
+            header::CONTENT_TYPE,
+            "text/html; charset=utf-8".parse().unwrap(),
+        );
+        assert!(compressible(StatusCode::OK, &headers));
+        assert!(!compressible(StatusCode::PARTIAL_CONTENT, &headers));
+
+        headers.insert(
+            header::CACHE_CONTROL,
+            "public, no-transform".parse().unwrap(),
+        );
+        assert!(!compressible(StatusCode::OK, &headers));
+        headers.remove(header::CACHE_CONTROL);
+
+        headers.insert(header::CONTENT_ENCODING, "gzip".parse().unwrap());
+        assert!(!compressible(StatusCode::OK, &headers));
+
+        assert!(compressible_type("application/ld+json"));
+        assert!(compressible_type("image/svg+xml"));
+        assert!(compressible_type("font/otf"));
+        assert!(!compressible_type("text/event-stream"));
+        assert!(!compressible_type("image/png"));
+    }
+
+    #[test]
+    fn test_set_encoding_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_LENGTH, "2048".parse().unwrap());
+        headers.insert(header::VARY, "Origin".parse().unwrap());
+        headers.insert(header::ETAG, "\"v1\"".parse().unwrap());
+        set_encoding_headers(&mut headers, Encoding::Brotli);
+        assert!(!headers.contains_key(header::CONTENT_LENGTH));
+        assert_eq!(headers[header::ETAG], "W/\"v1\"");
+        assert_eq!(headers[header::CONTENT_ENCODING], "br");
+        assert_eq!(headers.get_all(header::VARY).iter().count(), 2);
+
+        set_encoding_headers(&mut headers, Encoding::Brotli);
+        assert_eq!(headers.get_all(header::VARY).iter().count(), 2);
+        assert_eq!(headers[header::ETAG], "W/\"v1\"");
+    }
+
+    #[test]
+    fn test_encoded_stream() {
+        let chunks = vec![b"hello ".to_vec(), b"world".to_vec()];
+        let encoded: Vec<u8> =
+            EncodedStream::new(stream::iter_ok::<_, io::Error>(chunks), Encoding::Gzip)
+                .concat2()
+                .wait()
+                .unwrap();
+        let mut decoded = String::new();
+        GzDecoder::new(&encoded[..])
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "hello world");
+    }
+
+    #[test]
+    fn test_encode_brotli() {
+        let body = "compress me ".repeat(200);
+        let encoded = encode(Encoding::Brotli, body.as_bytes()).unwrap();
+        assert!(encoded.len() < body.len());
+        let mut decoded = vec![];
+        brotli::Decompressor::new(&encoded[..], 4096)
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, body.as_bytes());
+    }
+
+    #[test]
+    fn test_encode_later() {
+        let body = "compress me ".repeat(200).into_bytes();
+        let encoded = encode(Encoding::Gzip, &body).unwrap();
+        let pool = tokio_threadpool::ThreadPool::new();
+        let later = pool.spawn_handle(encode_later(Encoding::Gzip, body.clone()));
+        assert_eq!(later.wait().unwrap(), encoded);
+
+        // inline outside a thread pool
+        assert_eq!(encode_later(Encoding::Gzip, body).wait().unwrap(), encoded);
+    }
+}
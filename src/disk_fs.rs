@@ -1,86 +1,369 @@
 use crate::fs_store::*;
-use futures::{future, Future, Stream};
+use futures::{future, Async, Future, Stream};
 
 use crate::runtime::EVENT_LOOP;
 
-use std::io;
+use std::fs;
+use std::io::{self, Read, SeekFrom};
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
 use tokio::codec::{BytesCodec, FramedRead};
 
 use futures::sync::{mpsc, oneshot};
 
-pub struct DiskFsStore {}
+/// Where files go when no root is configured.
+pub const DEFAULT_ROOT: &str = "fly_fs";
+
+// Suffix of files still being written.
+const PARTIAL_SUFFIX: &str = ".partial";
+
+pub struct DiskFsStore {
+    root: PathBuf,
+}
 
 impl DiskFsStore {
+    /// Confines files to `DEFAULT_ROOT`, under the current directory.
     pub fn new() -> Self {
-        DiskFsStore {}
+        DiskFsStore::with_root(PathBuf::from(DEFAULT_ROOT))
+    }
+
+    /// Confines files to `root`, created if missing.
+    pub fn with_root(root: PathBuf) -> Self {
+        if let Err(e) = fs::create_dir_all(&root) {
+            warn!("error creating fs root {}: {}", root.display(), e);
+        }
+        DiskFsStore {
+            root: root.canonicalize().unwrap_or(root),
+        }
+    }
+
+    // Joins a path to the root, absolute paths being taken as relative to it.
+    // Paths climbing out of the root are refused, and so are paths going
+    // through a symlink leading outside of it.
+    fn resolve(&self, path: &str) -> FsResult<PathBuf> {
+        let denied =
+            || FsError::PermissionDenied(format!("path outside of the app root: {}", path));
+
+        let mut resolved = self.root.clone();
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::RootDir | Component::CurDir => {}
+                Component::ParentDir | Component::Prefix(_) => return Err(denied()),
+            }
+        }
+
+        // what doesn't exist yet can't be a symlink, the rest has to really
+        // be under the root
+        let mut existing = resolved.as_path();
+        loop {
+            match existing.canonicalize() {
+                Ok(real) => {
+                    if !real.starts_with(&self.root) {
+                        return Err(denied());
+                    }
+                    break;
+                }
+                // a dangling symlink could still be written through
+                Err(_) if existing.symlink_metadata().is_ok() => return Err(denied()),
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => match existing.parent() {
+                    Some(parent) => existing = parent,
+                    None => break,
+                },
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(resolved)
     }
 }
 
 impl FsStore for DiskFsStore {
     fn read(&self, path: String) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send> {
-        let (tx, rx) = oneshot::channel::<Result<Option<FsStream>, FsError>>();
+        match self.resolve(&path) {
+            Ok(path) => DiskFsStore::read_file(path, None, Some(self.root.clone())),
+            Err(e) => Box::new(future::err(e)),
+        }
+    }
 
-        EVENT_LOOP.0.spawn(future::lazy(move || {
-            tokio::fs::File::open(path).then(move |fileres| {
-                if let Err(e) = fileres {
-                    if e.kind() == io::ErrorKind::NotFound {
-                        if let Err(_) = tx.send(Ok(None)) {
-                            error!("unknown error sending into channel");
-                        }
-                        return Ok(());
+    fn read_range(
+        &self,
+        path: String,
+        range: Range<u64>,
+    ) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send> {
+        match self.resolve(&path) {
+            Ok(path) => {
+                DiskFsStore::read_file(path, Some(range), Some(self.root.clone()))
+            }
+            Err(e) => Box::new(future::err(e)),
+        }
+    }
+
+    fn stat(&self, path: String) -> Box<Future<Item = Option<FsMeta>, Error = FsError> + Send> {
+        let resolved = match self.resolve(&path) {
+            Ok(resolved) => resolved,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let root = self.root.clone();
+        on_event_loop(
+            blocking(move || {
+                let meta = fs::metadata(&resolved)?;
+                check_opened(&root, &resolved, &meta)?;
+                Ok(meta)
+            })
+            .then(move |res| match res {
+                Ok(ref meta) if !meta.is_file() => Ok(None),
+                Ok(meta) => Ok(Some(FsMeta {
+                    size: meta.len(),
+                    modified: meta.modified().ok(),
+                    content_type: content_type(&path),
+                })),
+                Err(FsError::IoErr(ref e)) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e),
+            }),
+        )
+    }
+
+    fn write(
+        &self,
+        path: String,
+        data_stream: Box<Stream<Item = Vec<u8>, Error = ()> + Send>,
+    ) -> EmptyFsFuture {
+        let path = match self.resolve(&path) {
+            Ok(path) => path,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let dir = path.parent().unwrap_or(&self.root).to_path_buf();
+        // written next to the file and renamed over it, so readers never see
+        // half a file
+        let tmp = partial_path(&path);
+        let cleanup = tmp.clone();
+        let root = self.root.clone();
+        on_event_loop(
+            tokio::fs::create_dir_all(dir)
+                .and_then({
+                    let tmp = tmp.clone();
+                    // never through a symlink
+                    move |_| {
+                        tokio::fs::OpenOptions::new()
+                            .write(true)
+                            .create_new(true)
+                            .open(tmp)
                     }
-                    if let Err(_) = tx.send(Err(e.into())) {
-                        error!("unknown error sending into channel");
+                })
+                .and_then(|file| file.metadata())
+                .map_err(FsError::from)
+                .and_then({
+                    let tmp = tmp.clone();
+                    move |(file, meta)| {
+                        blocking(move || check_opened(&root, &tmp, &meta)).map(|_| file)
                     }
+                })
+                .and_then(move |file| {
+                    data_stream
+                        .map_err(|_| FsError::Failure("error receiving file data".to_string()))
+                        .fold(file, |file, chunk| {
+                            tokio::io::write_all(file, chunk)
+                                .map(|(file, _)| file)
+                                .map_err(FsError::from)
+                        })
+                })
+                .and_then(|mut file| future::poll_fn(move || file.poll_sync_all()).from_err())
+                .and_then(move |_| tokio::fs::rename(tmp, path).from_err())
+                .or_else(move |e| tokio::fs::remove_file(cleanup).then(|_| Err(e))),
+        )
+    }
 
-                    return Err(());
-                }
+    fn delete(&self, path: String) -> EmptyFsFuture {
+        let path = match self.resolve(&path) {
+            Ok(path) => path,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        on_event_loop(tokio::fs::remove_file(path).then(|res| match res {
+            Ok(_) => Ok(()),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }))
+    }
 
-                let file = fileres.unwrap();
-                match file.metadata().wait() {
-                    Err(e) => {
-                        if let Err(_) = tx.send(Err(e.into())) {
-                            error!("unknown error sending into channel");
-                        }
-                        return Err(());
+    fn list(&self, prefix: String) -> Box<Future<Item = Vec<String>, Error = FsError> + Send> {
+        // only the directory the prefix points into needs walking
+        let dir = match prefix.rfind('/') {
+            Some(i) => &prefix[..i],
+            None => "",
+        };
+        let start = match self.resolve(dir) {
+            Ok(start) => start,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let base = dir.trim_start_matches('/').to_string();
+        let prefix = prefix.trim_start_matches('/').to_string();
+        on_event_loop(blocking(move || {
+            let mut paths = vec![];
+            walk(&start, &base, &mut paths)?;
+            paths.retain(|p| p.starts_with(&prefix));
+            paths.sort();
+            Ok(paths)
+        }))
+    }
+}
+
+// Where a file is written before it's renamed into place.
+fn partial_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+    path.with_file_name(format!(
+        ".{}.{:016x}{}",
+        name,
+        rand::random::<u64>(),
+        PARTIAL_SUFFIX
+    ))
+}
+
+fn is_partial(name: &str) -> bool {
+    name.starts_with('.') && name.ends_with(PARTIAL_SUFFIX)
+}
+
+// `resolve` checks a path before it's used, but a symlink could be swapped in
+// before the file is opened. The file opened has to be the one the path
+// really leads to under the root.
+fn check_opened(root: &Path, path: &Path, opened: &fs::Metadata) -> FsResult<()> {
+    let real = path.canonicalize()?;
+    if real.starts_with(root) && same_file(&fs::metadata(&real)?, opened) {
+        Ok(())
+    } else {
+        Err(FsError::PermissionDenied(format!(
+            "path outside of the app root: {}",
+            path.display()
+        )))
+    }
+}
+
+#[cfg(unix)]
+fn same_file(a: &fs::Metadata, b: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+#[cfg(not(unix))]
+fn same_file(_: &fs::Metadata, _: &fs::Metadata) -> bool {
+    true
+}
+
+// Collects the paths of files under `dir`, `rel` being its own path.
+fn walk(dir: &Path, rel: &str, out: &mut Vec<String>) -> FsResult<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if is_partial(&name) {
+            continue;
+        }
+        let path = if rel.is_empty() {
+            name
+        } else {
+            format!("{}/{}", rel.trim_end_matches('/'), name)
+        };
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            walk(&entry.path(), &path, out)?;
+        } else if file_type.is_file() {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+// Runs std::fs, or other blocking, calls on the threadpool's blocking
+// capacity, so they don't hold up the event loop's other tasks.
+pub(crate) fn blocking<T, E, F>(f: F) -> impl Future<Item = T, Error = E> + Send
+where
+    F: FnOnce() -> Result<T, E> + Send,
+    T: Send,
+    E: Send,
+{
+    let mut f = Some(f);
+    future::poll_fn(move || {
+        let res = match tokio_threadpool::blocking(|| f.take().unwrap()()) {
+            Ok(Async::Ready(res)) => res,
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            // not on a thread pool
+            Err(_) => f.take().unwrap()(),
+        };
+        res.map(Async::Ready)
+    })
+}
+
+// tokio's fs futures have to run on the event loop's threadpool.
+pub(crate) fn on_event_loop<T, F>(fut: F) -> Box<Future<Item = T, Error = FsError> + Send>
+where
+    F: Future<Item = T, Error = FsError> + Send + 'static,
+    T: Send + 'static,
+{
+    let (tx, rx) = oneshot::channel::<FsResult<T>>();
+    EVENT_LOOP.0.spawn(fut.then(move |res| {
+        if let Err(_) = tx.send(res) {
+            error!("unknown error sending into channel");
+        }
+        Ok(())
+    }));
+    Box::new(
+        rx.map_err(|_| FsError::Failure("error receiving fs response".to_string()))
+            .and_then(|res| res),
+    )
+}
+
+impl DiskFsStore {
+    // Files read from a root are checked to still be under it once opened.
+    pub(crate) fn read_file(
+        path: PathBuf,
+        range: Option<Range<u64>>,
+        root: Option<PathBuf>,
+    ) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send> {
+        let (tx, rx) = oneshot::channel::<Result<Option<FsStream>, FsError>>();
+
+        EVENT_LOOP.0.spawn(future::lazy(move || {
+            tokio::fs::File::open(path.clone())
+                .and_then(|file| file.metadata())
+                .map_err(FsError::from)
+                .and_then(move |(file, meta)| {
+                    blocking(move || match root {
+                        Some(ref root) => check_opened(root, &path, &meta).map(|_| meta),
+                        None => Ok(meta),
+                    })
+                    .map(|meta| (file, meta))
+                })
+                .and_then(move |(file, meta)| {
+                    if !meta.is_file() {
+                        return future::Either::A(future::ok(None));
                     }
-                    Ok((file, meta)) => {
-                        if !meta.is_file() {
-                            if let Err(_) = tx.send(Ok(None)) {
-                                error!("unknown error sending into channel");
-                            }
-                            return Ok(());
-                        }
-                        let (btx, brx) = mpsc::unbounded::<Result<Vec<u8>, FsError>>();
-                        let btxerr = btx.clone();
-                        EVENT_LOOP.0.spawn(
-                            FramedRead::new(file, BytesCodec::new())
-                                .map_err(move |e| {
-                                    if let Err(e) = btxerr.clone().unbounded_send(Err(e.into())) {
-                                        error!("error sending into channel: {}", e);
-                                    }
-                                }).for_each(move |chunk| {
-                                    if let Err(e) = btx.clone().unbounded_send(Ok(chunk.to_vec())) {
-                                        error!("error sending into channel: {}", e);
-                                    }
-                                    Ok(())
-                                }).and_then(|_| Ok(())),
-                        );
-
-                        if let Err(_) = tx.send(Ok(Some(Box::new(
-                            brx.map_err(|_| {
-                                FsError::Failure("error receiving fs chunk".to_string())
-                            }).and_then(|chunk_res| match chunk_res {
-                                Ok(c) => Ok(c),
-                                Err(e) => Err(e),
-                            }),
-                        )))) {
-                            error!("unknown error sending stream info channel");
+                    let range = range.unwrap_or(0..u64::max_value());
+                    future::Either::B(
+                        file.seek(SeekFrom::Start(range.start))
+                            .map(move |(file, _)| {
+                                Some((file, range.end.saturating_sub(range.start)))
+                            })
+                            .from_err(),
+                    )
+                })
+                .then(move |res| {
+                    let res = match res {
+                        Ok(Some((file, len))) => Ok(Some(DiskFsStore::stream_file(file, len))),
+                        Ok(None) => Ok(None),
+                        Err(FsError::IoErr(ref e)) if e.kind() == io::ErrorKind::NotFound => {
+                            Ok(None)
                         }
-                        Ok(())
+                        Err(e) => Err(e),
+                    };
+                    if let Err(_) = tx.send(res) {
+                        error!("unknown error sending into channel");
                     }
-                }
-            })
+                    Ok(())
+                })
         }));
 
         Box::new(
@@ -94,6 +377,30 @@ impl FsStore for DiskFsStore {
                 }),
         )
     }
+
+    // reads the file on the event loop, forwarding chunks as they come in
+    fn stream_file(file: tokio::fs::File, len: u64) -> FsStream {
+        let (btx, brx) = mpsc::unbounded::<Result<Vec<u8>, FsError>>();
+        let btxerr = btx.clone();
+        EVENT_LOOP.0.spawn(
+            FramedRead::new(file.take(len), BytesCodec::new())
+                .map_err(move |e| {
+                    if let Err(e) = btxerr.clone().unbounded_send(Err(e.into())) {
+                        error!("error sending into channel: {}", e);
+                    }
+                }).for_each(move |chunk| {
+                    if let Err(e) = btx.clone().unbounded_send(Ok(chunk.to_vec())) {
+                        error!("error sending into channel: {}", e);
+                    }
+                    Ok(())
+                }).and_then(|_| Ok(())),
+        );
+
+        Box::new(
+            brx.map_err(|_| FsError::Failure("error receiving fs chunk".to_string()))
+                .and_then(|chunk_res| chunk_res),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -103,7 +410,7 @@ mod tests {
 
     #[test]
     fn test_disk_fs_read() {
-        let store = DiskFsStore::new();
+        let store = DiskFsStore::with_root(PathBuf::from("."));
         let path = "README.md";
 
         assert_eq!(
@@ -121,4 +428,195 @@ mod tests {
 
         assert!(store.read("notfound".to_string()).wait().unwrap().is_none());
     }
+
+    #[test]
+    fn test_disk_fs_read_range() {
+        let store = DiskFsStore::with_root(PathBuf::from("."));
+        let path = "README.md";
+        let contents = fs::read(path).unwrap();
+
+        assert_eq!(
+            store
+                .read_range(path.to_string(), 10..20)
+                .wait()
+                .unwrap()
+                .unwrap()
+                .stream
+                .concat2()
+                .wait()
+                .unwrap(),
+            contents[10..20].to_vec()
+        );
+
+        let len = contents.len() as u64;
+        assert_eq!(
+            store
+                .read_range(path.to_string(), len - 5..len + 100)
+                .wait()
+                .unwrap()
+                .unwrap()
+                .stream
+                .concat2()
+                .wait()
+                .unwrap(),
+            contents[contents.len() - 5..].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_disk_fs_stat() {
+        let store = DiskFsStore::with_root(PathBuf::from("."));
+        let meta = store.stat("README.md".to_string()).wait().unwrap().unwrap();
+        assert_eq!(meta.size, fs::metadata("README.md").unwrap().len());
+        assert!(meta.modified.is_some());
+
+        assert!(store.stat("notfound".to_string()).wait().unwrap().is_none());
+        assert!(store.stat("src".to_string()).wait().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_disk_fs_write_list_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFsStore::with_root(dir.path().to_path_buf());
+
+        let write = |path: &str, data: &[u8]| {
+            store
+                .write(
+                    path.to_string(),
+                    Box::new(futures::stream::once(Ok(data.to_vec()))),
+                )
+                .wait()
+                .unwrap()
+        };
+        write("assets/app.js", b"console.log(1)");
+        write("assets/img/logo.svg", b"<svg></svg>");
+        write("index.html", b"<html></html>");
+
+        assert_eq!(
+            fs::read(dir.path().join("assets/app.js")).unwrap(),
+            b"console.log(1)".to_vec()
+        );
+        let meta = store
+            .stat("/assets/app.js".to_string())
+            .wait()
+            .unwrap()
+            .unwrap();
+        assert_eq!(meta.size, 14);
+        assert_eq!(meta.content_type, "application/javascript");
+
+        assert_eq!(
+            store.list("".to_string()).wait().unwrap(),
+            vec!["assets/app.js", "assets/img/logo.svg", "index.html"]
+        );
+        assert_eq!(
+            store.list("assets/im".to_string()).wait().unwrap(),
+            vec!["assets/img/logo.svg"]
+        );
+        assert!(store
+            .list("missing/".to_string())
+            .wait()
+            .unwrap()
+            .is_empty());
+
+        // a failed write leaves the old file alone, and nothing half written
+        assert!(store
+            .write(
+                "index.html".to_string(),
+                Box::new(futures::stream::iter_result(vec![
+                    Ok(b"<ht".to_vec()),
+                    Err(())
+                ])),
+            )
+            .wait()
+            .is_err());
+        assert_eq!(
+            fs::read(dir.path().join("index.html")).unwrap(),
+            b"<html></html>".to_vec()
+        );
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+
+        store.delete("assets/app.js".to_string()).wait().unwrap();
+        store.delete("assets/app.js".to_string()).wait().unwrap();
+        assert!(store
+            .stat("assets/app.js".to_string())
+            .wait()
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_disk_fs_outside_root() {
+        let store = DiskFsStore::with_root(PathBuf::from("."));
+        assert!(store.read("../Cargo.toml".to_string()).wait().is_err());
+        assert!(store
+            .stat("src/../../Cargo.toml".to_string())
+            .wait()
+            .is_err());
+        assert!(store.list("../".to_string()).wait().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_disk_fs_sandbox() {
+        use std::os::unix::fs::symlink;
+
+        let outside = tempfile::tempdir().unwrap();
+        fs::write(outside.path().join("secret.txt"), b"secret").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("app");
+        let store = DiskFsStore::with_root(root.clone());
+        fs::create_dir(root.join("sub")).unwrap();
+        fs::write(root.join("sub/file.txt"), b"file").unwrap();
+        symlink(outside.path(), root.join("escape")).unwrap();
+        symlink(outside.path().join("missing"), root.join("dangling")).unwrap();
+        symlink(root.join("sub"), root.join("inside")).unwrap();
+
+        fn denied<T>(res: FsResult<T>) -> bool {
+            match res {
+                Err(FsError::PermissionDenied(_)) => true,
+                _ => false,
+            }
+        }
+        let stat = |path: &str| store.stat(path.to_string()).wait();
+        let write = |path: &str| {
+            store
+                .write(
+                    path.to_string(),
+                    Box::new(futures::stream::once(Ok(b"x".to_vec()))),
+                )
+                .wait()
+        };
+
+        assert!(denied(stat("../secret.txt")));
+        assert!(denied(stat("sub/../../secret.txt")));
+        assert!(denied(stat("escape/secret.txt")));
+        assert!(denied(
+            store
+                .read("escape/secret.txt".to_string())
+                .wait()
+                .map(|_| ())
+        ));
+        assert!(denied(write("escape/new.txt")));
+        assert!(denied(write("dangling")));
+        assert!(denied(store.list("escape/".to_string()).wait()));
+        assert!(!outside.path().join("new.txt").exists());
+        assert!(!outside.path().join("missing").exists());
+
+        // absolute paths stay under the root
+        assert!(stat("/etc/passwd").unwrap().is_none());
+        assert_eq!(stat("inside/file.txt").unwrap().unwrap().size, 4);
+        assert_eq!(stat("/sub/./file.txt").unwrap().unwrap().size, 4);
+
+        // a symlink swapped in between resolving and opening
+        fs::create_dir(root.join("swap")).unwrap();
+        fs::write(root.join("swap/secret.txt"), b"fine").unwrap();
+        let resolved = store.resolve("swap/secret.txt").unwrap();
+        fs::remove_dir_all(root.join("swap")).unwrap();
+        symlink(outside.path(), root.join("swap")).unwrap();
+        assert!(denied(
+            DiskFsStore::read_file(resolved, None, Some(store.root.clone()))
+                .wait()
+                .map(|_| ())
+        ));
+    }
 }
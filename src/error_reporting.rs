@@ -0,0 +1,342 @@
+use futures::Future;
+use hyper::client::HttpConnector;
+use hyper::{Body, Client, Request};
+use hyper_tls::HttpsConnector;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::errors::{ErrorKind, FlyError};
+use crate::js::{JsException, JsFrame};
+use crate::metrics::{ERROR_REPORTS_DROPPED_TOTAL, ERROR_REPORTS_TOTAL};
+use crate::runtime::EVENT_LOOP;
+use crate::settings::ErrorReportingConfig;
+
+/// Reports an app may send per minute before the rest are dropped.
+pub static DEFAULT_REPORTS_PER_MINUTE: u32 = 60;
+
+lazy_static! {
+    static ref HTTP_CLIENT: Client<HttpsConnector<HttpConnector>, Body> =
+        Client::builder().build(HttpsConnector::new(1).unwrap());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorReportKind {
+    Exception,
+    OpFailure,
+}
+
+impl ErrorReportKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorReportKind::Exception => "exception",
+            ErrorReportKind::OpFailure => "op_failure",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorReport {
+    pub kind: ErrorReportKind,
+    pub app: String,
+    pub version: String,
+    pub request_id: Option<u32>,
+    pub message: String,
+    pub frames: Vec<JsFrame>,
+}
+
+impl ErrorReport {
+    pub fn exception(app: &str, version: &str, exception: &JsException) -> Self {
+        ErrorReport {
+            kind: ErrorReportKind::Exception,
+            app: app.to_string(),
+            version: version.to_string(),
+            request_id: exception.request_id,
+            message: exception.message.clone(),
+            frames: exception.frames.clone(),
+        }
+    }
+
+    /// `request_id` is the event whose code started the op, if any.
+    pub fn op_failure(
+        app: &str,
+        version: &str,
+        request_id: Option<u32>,
+        op: &str,
+        err: &FlyError,
+    ) -> Self {
+        ErrorReport {
+            kind: ErrorReportKind::OpFailure,
+            app: app.to_string(),
+            version: version.to_string(),
+            request_id,
+            message: format!("{}: {}", op, err),
+            frames: vec![],
+        }
+    }
+
+    pub fn tags(&self) -> Vec<(&'static str, String)> {
+        let mut tags = vec![
+            ("app", self.app.clone()),
+            ("version", self.version.clone()),
+            ("kind", self.kind.as_str().to_string()),
+        ];
+        if let Some(id) = self.request_id {
+            tags.push(("request_id", id.to_string()));
+        }
+        tags
+    }
+}
+
+/// Whether an op failure is worth reporting. Apps run into missing files,
+/// denied permissions or blocked addresses in normal operation, those are only
+/// answered to the app.
+pub fn is_unexpected(err: &FlyError) -> bool {
+    match err.kind() {
+        ErrorKind::NotFound
+        | ErrorKind::PermissionDenied
+        | ErrorKind::AlreadyExists
+        | ErrorKind::InvalidInput
+        | ErrorKind::AddressBlocked
+        | ErrorKind::TooManyRedirects
+        | ErrorKind::RedirectNotAllowed
+        | ErrorKind::Aborted => false,
+        _ => true,
+    }
+}
+
+/// Destination for app errors, e.g. an error tracker. Reporting must not
+/// block: it's called from the runtime's thread.
+pub trait ErrorSink: Send + Sync {
+    fn report(&self, report: ErrorReport);
+}
+
+/// Drops reports past `max` per app within each `window`, so a crash loop in
+/// one app can't flood the tracker.
+pub struct RateLimitedSink<S: ErrorSink> {
+    inner: S,
+    max: u32,
+    window: Duration,
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl<S: ErrorSink> RateLimitedSink<S> {
+    pub fn new(inner: S, max: u32, window: Duration) -> Self {
+        RateLimitedSink {
+            inner,
+            max,
+            window,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn admit(&self, app: &str) -> bool {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        let entry = windows.entry(app.to_string()).or_insert((now, 0));
+        if now.duration_since(entry.0) >= self.window {
+            *entry = (now, 0);
+        }
+        if entry.1 >= self.max {
+            return false;
+        }
+        entry.1 += 1;
+        true
+    }
+}
+
+impl<S: ErrorSink> ErrorSink for RateLimitedSink<S> {
+    fn report(&self, report: ErrorReport) {
+        if self.admit(&report.app) {
+            ERROR_REPORTS_TOTAL
+                .with_label_values(&[report.app.as_str(), report.kind.as_str()])
+                .inc();
+            self.inner.report(report);
+        } else {
+            ERROR_REPORTS_DROPPED_TOTAL
+                .with_label_values(&[report.app.as_str()])
+                .inc();
+        }
+    }
+}
+
+/// POSTs each report as JSON to a collector.
+pub struct HttpErrorSink {
+    url: String,
+}
+
+impl HttpErrorSink {
+    pub fn new(url: &str) -> Self {
+        HttpErrorSink {
+            url: url.to_string(),
+        }
+    }
+}
+
+impl ErrorSink for HttpErrorSink {
+    fn report(&self, report: ErrorReport) {
+        let body = match serde_json::to_vec(&report) {
+            Ok(b) => b,
+            Err(e) => {
+                error!("error serializing error report: {}", e);
+                return;
+            }
+        };
+        let req = match Request::post(self.url.as_str())
+            .header(hyper::header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+        {
+            Ok(req) => req,
+            Err(e) => {
+                error!("error building error report request: {}", e);
+                return;
+            }
+        };
+        let url = self.url.clone();
+        EVENT_LOOP.0.spawn(
+            HTTP_CLIENT
+                .request(req)
+                .map(|_| ())
+                .map_err(move |e| warn!("error sending error report to {}: {}", url, e)),
+        );
+    }
+}
+
+pub fn sink_from_config(config: &ErrorReportingConfig) -> Arc<ErrorSink> {
+    Arc::new(RateLimitedSink::new(
+        HttpErrorSink::new(&config.collector_url),
+        config
+            .reports_per_minute
+            .unwrap_or(DEFAULT_REPORTS_PER_MINUTE),
+        Duration::from_secs(60),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors;
+    use futures::Stream;
+    use hyper::service::service_fn;
+    use hyper::{Response, Server};
+    use std::io;
+    use std::sync::mpsc;
+
+    fn report(app: &str) -> ErrorReport {
+        ErrorReport::exception(
+            app,
+            "3",
+            &JsException {
+                message: "Error: boom".to_string(),
+                frames: vec![JsFrame {
+                    function_name: "handler".to_string(),
+                    filename: "app.ts".to_string(),
+                    line: 3,
+                    col: 7,
+                }],
+                request_id: Some(42),
+            },
+        )
+    }
+
+    #[derive(Clone, Default)]
+    struct MemorySink(Arc<Mutex<Vec<ErrorReport>>>);
+
+    impl ErrorSink for MemorySink {
+        fn report(&self, report: ErrorReport) {
+            self.0.lock().unwrap().push(report);
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_per_app() {
+        let reports = MemorySink::default();
+        let sink = RateLimitedSink::new(reports.clone(), 2, Duration::from_secs(60));
+        for _ in 0..5 {
+            sink.report(report("noisy"));
+        }
+        sink.report(report("quiet"));
+
+        let apps: Vec<_> = reports
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.app.clone())
+            .collect();
+        assert_eq!(apps, vec!["noisy", "noisy", "quiet"]);
+    }
+
+    #[test]
+    fn test_rate_limit_window_resets() {
+        let reports = MemorySink::default();
+        let sink = RateLimitedSink::new(reports.clone(), 1, Duration::from_millis(0));
+        sink.report(report("app"));
+        sink.report(report("app"));
+        assert_eq!(reports.0.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_tags() {
+        let tags = report("app").tags();
+        assert!(tags.contains(&("app", "app".to_string())));
+        assert!(tags.contains(&("version", "3".to_string())));
+        assert!(tags.contains(&("request_id", "42".to_string())));
+        assert!(tags.contains(&("kind", "exception".to_string())));
+    }
+
+    #[test]
+    fn test_op_failure_request_id() {
+        let err = FlyError::from("connection reset".to_string());
+        let report = ErrorReport::op_failure("app", "3", Some(7), "HttpRequest", &err);
+        assert_eq!(report.message, "HttpRequest: connection reset");
+        assert!(report.tags().contains(&("request_id", "7".to_string())));
+        assert!(report.tags().contains(&("kind", "op_failure".to_string())));
+    }
+
+    #[test]
+    fn test_expected_failures_not_unexpected() {
+        assert!(is_unexpected(&FlyError::from("boom".to_string())));
+        assert!(!is_unexpected(&FlyError::from(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no such file"
+        ))));
+        assert!(!is_unexpected(&errors::new(
+            ErrorKind::PermissionDenied,
+            "fs write denied".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_http_sink_posts_to_collector() {
+        let (tx, rx) = mpsc::channel::<Vec<u8>>();
+        let tx = Arc::new(Mutex::new(tx));
+
+        let collector = Server::bind(&([127, 0, 0, 1], 0).into()).serve(move || {
+            let tx = tx.clone();
+            service_fn(move |req: Request<Body>| {
+                let tx = tx.clone();
+                req.into_body().concat2().map(move |body| {
+                    tx.lock().unwrap().send(body.to_vec()).unwrap();
+                    Response::new(Body::empty())
+                })
+            })
+        });
+        let addr = collector.local_addr();
+        let mut el = tokio::runtime::Runtime::new().unwrap();
+        el.spawn(collector.map_err(|e| panic!("collector error: {}", e)));
+
+        HttpErrorSink::new(&format!("http://{}/errors", addr)).report(report("app"));
+
+        let body = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        let posted: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(posted["kind"], "exception");
+        assert_eq!(posted["app"], "app");
+        assert_eq!(posted["version"], "3");
+        assert_eq!(posted["request_id"], 42);
+        assert_eq!(posted["message"], "Error: boom");
+        assert_eq!(posted["frames"][0]["filename"], "app.ts");
+        assert_eq!(posted["frames"][0]["line"], 3);
+    }
+}
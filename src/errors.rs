@@ -5,6 +5,9 @@ use std::fmt;
 use std::io;
 use url;
 
+use crate::fs_store::FsError;
+use crate::ip_filter;
+
 pub type FlyResult<T> = Result<T, FlyError>;
 
 #[derive(Debug)]
@@ -139,12 +142,25 @@ impl From<url::ParseError> for FlyError {
 impl From<hyper::Error> for FlyError {
   #[inline]
   fn from(err: hyper::Error) -> FlyError {
+    if let Some(blocked) = ip_filter::blocked_cause(&err) {
+      return new(ErrorKind::AddressBlocked, blocked.to_string());
+    }
     FlyError {
       repr: Repr::HyperErr(err),
     }
   }
 }
 
+impl From<FsError> for FlyError {
+  #[inline]
+  fn from(err: FsError) -> FlyError {
+    match err {
+      FsError::PermissionDenied(msg) => new(ErrorKind::PermissionDenied, msg),
+      err => format!("fs error: {:?}", err).into(),
+    }
+  }
+}
+
 impl From<String> for FlyError {
   #[inline]
   fn from(err: String) -> FlyError {
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Time the isolate spent running each watched event's code: the event
+/// handler itself and the callbacks of the ops it started. Time spent waiting
+/// on those ops, like a slow fetch or a timer, doesn't count.
+///
+/// Finished slices count the CPU time of the runtime's thread where the
+/// platform provides it, the slice still running is counted by the wall clock.
+#[derive(Clone, Default)]
+pub struct EventClock(Arc<Mutex<Clocks>>);
+
+#[derive(Default)]
+struct Clocks {
+    used: HashMap<u32, Duration>,
+    running: Option<Slice>,
+}
+
+struct Slice {
+    event: Option<u32>,
+    started: Instant,
+    cpu_started: Option<Duration>,
+    // sends into JS made while JS is already running belong to the outer one
+    depth: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    /// Time the event has left.
+    Within(Duration),
+    /// Whether the isolate is running the event's code right now.
+    Exceeded { running: bool },
+}
+
+impl EventClock {
+    pub fn new() -> Self {
+        EventClock::default()
+    }
+
+    /// Starts counting time for `event`.
+    pub fn watch(&self, event: u32) {
+        self.lock().used.insert(event, Duration::from_millis(0));
+    }
+
+    pub fn forget(&self, event: u32) {
+        self.lock().used.remove(&event);
+    }
+
+    /// The event whose code the isolate is running, if any.
+    pub fn current(&self) -> Option<u32> {
+        self.lock().running_event()
+    }
+
+    /// Called on the runtime's thread right before running JS for `event`.
+    pub fn enter(&self, event: Option<u32>) {
+        let mut clocks = self.lock();
+        if let Some(ref mut slice) = clocks.running {
+            slice.depth += 1;
+            return;
+        }
+        clocks.running = Some(Slice {
+            event,
+            started: Instant::now(),
+            cpu_started: thread_cpu_time(),
+            depth: 0,
+        });
+    }
+
+    /// Called on the runtime's thread once JS returned.
+    pub fn leave(&self) {
+        let mut clocks = self.lock();
+        let slice = match clocks.running.take() {
+            Some(mut slice) => {
+                if slice.depth > 0 {
+                    slice.depth -= 1;
+                    clocks.running = Some(slice);
+                    return;
+                }
+                slice
+            }
+            None => return,
+        };
+        let spent = match (slice.cpu_started, thread_cpu_time()) {
+            (Some(start), Some(end)) if end >= start => end - start,
+            _ => slice.started.elapsed(),
+        };
+        if let Some(used) = slice.event.and_then(|event| clocks.used.get_mut(&event)) {
+            *used += spent;
+        }
+    }
+
+    pub fn verdict(&self, event: u32, limit: Duration) -> Verdict {
+        let clocks = self.lock();
+        let running = clocks.running_event() == Some(event);
+        let mut used = clocks.used.get(&event).cloned().unwrap_or_default();
+        if running {
+            if let Some(ref slice) = clocks.running {
+                used += slice.started.elapsed();
+            }
+        }
+        if used >= limit {
+            Verdict::Exceeded { running }
+        } else {
+            Verdict::Within(limit - used)
+        }
+    }
+
+    /// Calls `terminate` if the isolate is running `event`'s code, keeping it
+    /// from moving on to another event in the meantime.
+    pub fn terminate_if_running<F>(&self, event: u32, terminate: F) -> bool
+    where
+        F: FnOnce() -> bool,
+    {
+        let clocks = self.lock();
+        clocks.running_event() == Some(event) && terminate()
+    }
+
+    fn lock(&self) -> MutexGuard<Clocks> {
+        match self.0.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl Clocks {
+    fn running_event(&self) -> Option<u32> {
+        self.running.as_ref().and_then(|slice| slice.event)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn thread_cpu_time() -> Option<Duration> {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    if unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut ts) } != 0 {
+        return None;
+    }
+    Some(Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn busy(duration: Duration) {
+        let start = Instant::now();
+        while start.elapsed() < duration {}
+    }
+
+    #[test]
+    fn test_busy_event_exceeds() {
+        let clock = EventClock::new();
+        let limit = Duration::from_millis(50);
+        clock.watch(1);
+
+        clock.enter(Some(1));
+        busy(Duration::from_millis(60));
+        assert_eq!(clock.verdict(1, limit), Verdict::Exceeded { running: true });
+        assert!(clock.terminate_if_running(1, || true));
+        clock.leave();
+
+        assert_eq!(
+            clock.verdict(1, limit),
+            Verdict::Exceeded { running: false }
+        );
+        assert!(!clock.terminate_if_running(1, || true));
+    }
+
+    #[test]
+    fn test_awaiting_event_within() {
+        let clock = EventClock::new();
+        let limit = Duration::from_millis(50);
+        clock.watch(1);
+
+        // handler runs, awaits a timer, then its callback runs
+        clock.enter(Some(1));
+        busy(Duration::from_millis(5));
+        clock.leave();
+        thread::sleep(Duration::from_millis(80));
+        clock.enter(Some(1));
+        busy(Duration::from_millis(5));
+        clock.leave();
+
+        match clock.verdict(1, limit) {
+            // the time spent waiting didn't count
+            Verdict::Within(left) => assert!(left > Duration::from_millis(20)),
+            v => panic!("unexpected verdict {:?}", v),
+        }
+    }
+
+    #[test]
+    fn test_other_event_running() {
+        let clock = EventClock::new();
+        let limit = Duration::from_millis(10);
+        clock.watch(1);
+        clock.watch(2);
+
+        clock.enter(Some(1));
+        busy(Duration::from_millis(20));
+        clock.leave();
+
+        // event 1 is over its limit, but event 2's code must not be killed
+        clock.enter(Some(2));
+        assert_eq!(clock.current(), Some(2));
+        assert_eq!(
+            clock.verdict(1, limit),
+            Verdict::Exceeded { running: false }
+        );
+        assert!(!clock.terminate_if_running(1, || true));
+
+        // nested sends stay with the outer event
+        clock.enter(Some(1));
+        assert_eq!(clock.current(), Some(2));
+        clock.leave();
+        clock.leave();
+        assert_eq!(clock.current(), None);
+    }
+}
@@ -1,25 +1,43 @@
 use crate::runtime::Runtime;
-use crate::{RuntimeSelector, SelectorError};
+use crate::runtime_lifecycle::RecyclableSelector;
+use crate::runtime_pool::{PooledRuntime, RuntimePool};
+use crate::{RuntimeLease, RuntimeSelector, SelectorError};
 
 pub struct FixedRuntimeSelector {
-    runtime: Box<Runtime>,
+    pool: RuntimePool,
 }
 
 impl FixedRuntimeSelector {
     pub fn new(runtime: Box<Runtime>) -> Self {
-        FixedRuntimeSelector { runtime }
+        FixedRuntimeSelector {
+            pool: RuntimePool::from_runtime(runtime),
+        }
+    }
+
+    pub fn with_pool(pool: RuntimePool) -> Self {
+        FixedRuntimeSelector { pool }
+    }
+
+    pub fn pool(&self) -> &RuntimePool {
+        &self.pool
     }
 }
 
 impl RuntimeSelector for FixedRuntimeSelector {
-    fn get_by_hostname(&self, _: &str) -> Result<Option<&mut Runtime>, SelectorError> {
-        Ok(Some(self.runtime.ptr.to_runtime()))
+    fn get_by_hostname(&self, _: &str) -> Result<Option<RuntimeLease>, SelectorError> {
+        Ok(self.pool.lease())
+    }
+}
+
+impl RecyclableSelector for FixedRuntimeSelector {
+    fn replace_runtime(&self, _: &str) -> Result<Option<PooledRuntime>, SelectorError> {
+        Ok(self.pool.replace_heaviest())
     }
 }
 
 impl Drop for FixedRuntimeSelector {
     fn drop(&mut self) {
         debug!("Dropping FixedRuntime, will dispose of runtime.");
-        self.runtime.dispose();
+        self.pool.dispose();
     }
 }
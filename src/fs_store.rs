@@ -1,10 +1,44 @@
 use futures::{Future, Stream};
 use std::io;
+use std::ops::Range;
+use std::time::SystemTime;
 
 pub type FsStream = Box<Stream<Item = Vec<u8>, Error = FsError> + Send>;
+pub type EmptyFsFuture = Box<Future<Item = (), Error = FsError> + Send>;
 
 pub trait FsStore {
     fn read(&self, path: String) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send>;
+    /// Reads part of a file, `range` end exclusive. A range running past the
+    /// end of the file stops there.
+    fn read_range(
+        &self,
+        path: String,
+        range: Range<u64>,
+    ) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send>;
+    fn stat(&self, path: String) -> Box<Future<Item = Option<FsMeta>, Error = FsError> + Send>;
+    /// Replaces a file, or creates it.
+    fn write(
+        &self,
+        path: String,
+        data_stream: Box<Stream<Item = Vec<u8>, Error = ()> + Send>,
+    ) -> EmptyFsFuture;
+    /// Deleting a file that doesn't exist isn't an error.
+    fn delete(&self, path: String) -> EmptyFsFuture;
+    /// Paths of the files starting with `prefix`, sorted.
+    fn list(&self, prefix: String) -> Box<Future<Item = Vec<String>, Error = FsError> + Send>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FsMeta {
+    pub size: u64,
+    /// Not every store keeps track of it.
+    pub modified: Option<SystemTime>,
+    pub content_type: String,
+}
+
+/// The content type of a file, from its extension.
+pub fn content_type(path: &str) -> String {
+    mime_guess::guess_mime_type(path).to_string()
 }
 
 pub struct FsEntry {
@@ -15,6 +49,8 @@ pub struct FsEntry {
 pub enum FsError {
     Unknown,
     NotFound,
+    /// The path leads outside of where the store may go.
+    PermissionDenied(String),
     Failure(String),
     IoErr(io::Error),
 }
@@ -0,0 +1,500 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures::{stream, Async, Future, Poll, Stream};
+use hyper::{header, HeaderMap, Method, StatusCode};
+
+use crate::cache_store::{CacheError, CacheSetOptions, CacheStore, CacheStream, EmptyCacheFuture};
+use crate::runtime::RuntimeHandle;
+
+/// Keeps edge cache entries apart from the ones the app sets through
+/// `fly.cache`, in the same store.
+const KEY_PREFIX: &str = "fly-edge:";
+
+static SURROGATE_KEY: &str = "surrogate-key";
+
+// Statuses cacheable without the origin opting in, per RFC 7231 section 6.1.
+static CACHEABLE_STATUSES: [u16; 7] = [200, 203, 204, 300, 301, 404, 410];
+
+/// Status and headers of a cached response, kept as its entry's meta.
+#[derive(Debug, Serialize, Deserialize)]
+struct EntryMeta {
+    status: u16,
+    headers: Vec<(String, String)>,
+    /// Request headers named by `Vary`, as they were for the cached response.
+    vary: Vec<(String, Option<String>)>,
+    stored_at: u64,
+    /// Length of the body, entries stored before it was recorded lack it.
+    #[serde(default)]
+    size: Option<u64>,
+}
+
+/// A response served from the cache, without entering the isolate.
+pub struct CachedResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    /// Length of the body, if known.
+    pub size: Option<u64>,
+    pub body: CacheStream,
+}
+
+/// The key a request's response is cached under, if the cache may answer
+/// it. Requests carrying credentials or asking to skip caches bypass it.
+pub fn cache_key(method: &Method, url: &str, headers: &HeaderMap) -> Option<String> {
+    if method != Method::GET && method != Method::HEAD {
+        return None;
+    }
+    if headers.contains_key(header::AUTHORIZATION) {
+        return None;
+    }
+    let skip = directives(headers, header::CACHE_CONTROL)
+        .iter()
+        .any(|(name, value)| match name.as_str() {
+            "no-cache" | "no-store" => true,
+            "max-age" => value.as_ref().map_or(false, |v| v == "0"),
+            _ => false,
+        });
+    if skip || has_token(headers, header::PRAGMA, "no-cache") {
+        return None;
+    }
+    Some(format!("{}{}", KEY_PREFIX, url))
+}
+
+/// Whether `key` belongs to the edge cache rather than the app.
+pub fn is_edge_key(key: &str) -> bool {
+    key.starts_with(KEY_PREFIX)
+}
+
+/// Looks a response up, treating entries cached for a different variant of
+/// the request as misses.
+pub fn lookup(
+    store: &CacheStore,
+    key: String,
+    req_headers: HeaderMap,
+) -> Box<Future<Item = Option<CachedResponse>, Error = CacheError> + Send> {
+    Box::new(store.get(key).map(move |entry| {
+        let entry = entry?;
+        let meta: EntryMeta = serde_json::from_str(entry.meta.as_ref()?).ok()?;
+        let matches = meta.vary.iter().all(|(name, value)| {
+            req_headers.get(name.as_str()).and_then(|v| v.to_str().ok())
+                == value.as_ref().map(|v| v.as_str())
+        });
+        if !matches {
+            return None;
+        }
+        let mut headers = HeaderMap::new();
+        for (name, value) in meta.headers {
+            let name = header::HeaderName::from_bytes(name.as_bytes()).ok()?;
+            headers.append(name, header::HeaderValue::from_str(&value).ok()?);
+        }
+        let age = now_secs().saturating_sub(meta.stored_at);
+        headers.insert(header::AGE, header::HeaderValue::from(age));
+        Some(CachedResponse {
+            status: StatusCode::from_u16(meta.status).ok()?,
+            headers,
+            size: meta.size,
+            body: entry.stream,
+        })
+    }))
+}
+
+/// Removes the headers meant for the cache rather than the client.
+pub fn strip_surrogate_headers(headers: &mut HeaderMap) {
+    headers.remove(SURROGATE_KEY);
+}
+
+/// A response about to be cached, once its whole body went through.
+pub struct PendingEntry {
+    key: String,
+    meta: EntryMeta,
+    opts: CacheSetOptions,
+    max_bytes: usize,
+}
+
+impl PendingEntry {
+    /// Prepares caching a response to a GET, if its headers allow it.
+    /// `Cache-Control` sets how long it's kept, `s-maxage` taking precedence,
+    /// and `Surrogate-Key` lists the tags it can be purged by.
+    pub fn new(
+        key: String,
+        max_bytes: usize,
+        method: &Method,
+        req_headers: &HeaderMap,
+        status: StatusCode,
+        headers: &HeaderMap,
+    ) -> Option<Self> {
+        if method != Method::GET || !CACHEABLE_STATUSES.contains(&status.as_u16()) {
+            return None;
+        }
+        if headers.contains_key(header::SET_COOKIE) {
+            return None;
+        }
+        let ttl = ttl(headers)?;
+
+        let mut vary = vec![];
+        for value in headers.get_all(header::VARY) {
+            for name in value.to_str().ok()?.split(',') {
+                let name = name.trim().to_ascii_lowercase();
+                if name == "*" {
+                    return None;
+                }
+                if name.is_empty() {
+                    continue;
+                }
+                let value = req_headers
+                    .get(name.as_str())
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| v.to_string());
+                vary.push((name, value));
+            }
+        }
+
+        let mut stored = vec![];
+        for (name, value) in headers {
+            if name.as_str() == SURROGATE_KEY {
+                continue;
+            }
+            stored.push((name.as_str().to_string(), value.to_str().ok()?.to_string()));
+        }
+
+        let tags: Vec<String> = headers
+            .get_all(SURROGATE_KEY)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split_whitespace())
+            .map(|tag| tag.to_string())
+            .collect();
+
+        let meta = EntryMeta {
+            status: status.as_u16(),
+            headers: stored,
+            vary,
+            stored_at: now_secs(),
+            size: None,
+        };
+        Some(PendingEntry {
+            key,
+            meta,
+            opts: CacheSetOptions {
+                ttl: Some(ttl),
+                tags: if tags.is_empty() { None } else { Some(tags) },
+                meta: None,
+            },
+            max_bytes,
+        })
+    }
+
+    /// Caches `body` in the runtime's store, unless the runtime is gone.
+    pub fn store(self, rt: &RuntimeHandle, body: Vec<u8>) {
+        rt.with(move |rt| {
+            if let Some(fut) = self.store_in(&*rt.cache_store, body) {
+                rt.spawn(fut.map_err(|e| error!("error storing response in http cache: {:?}", e)));
+            }
+        });
+    }
+
+    fn store_in(mut self, store: &CacheStore, body: Vec<u8>) -> Option<EmptyCacheFuture> {
+        if body.len() > self.max_bytes {
+            return None;
+        }
+        self.meta.size = Some(body.len() as u64);
+        self.opts.meta = match serde_json::to_string(&self.meta) {
+            Ok(meta) => Some(meta),
+            Err(e) => {
+                error!("error serializing http cache entry meta: {}", e);
+                return None;
+            }
+        };
+        Some(store.set(self.key, Box::new(stream::once(Ok(body))), self.opts))
+    }
+}
+
+/// Passes a response body through, handing it to `store` once it ended.
+/// Bodies cut short or over the size limit aren't cached.
+pub struct CachingStream<S, F> {
+    inner: S,
+    pending: Option<(PendingEntry, Vec<u8>, F)>,
+}
+
+impl<S, F> CachingStream<S, F>
+where
+    F: FnOnce(PendingEntry, Vec<u8>),
+{
+    pub fn new(inner: S, pending: Option<PendingEntry>, store: F) -> Self {
+        CachingStream {
+            inner,
+            pending: pending.map(|p| (p, vec![], store)),
+        }
+    }
+}
+
+impl<S, F> Stream for CachingStream<S, F>
+where
+    S: Stream<Item = Vec<u8>, Error = ()>,
+    F: FnOnce(PendingEntry, Vec<u8>),
+{
+    type Item = Vec<u8>;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, ()> {
+        let polled = self.inner.poll();
+        match polled {
+            Ok(Async::Ready(Some(ref chunk))) => {
+                let over = match self.pending {
+                    Some((ref pending, ref mut body, _)) => {
+                        body.extend_from_slice(chunk);
+                        body.len() > pending.max_bytes
+                    }
+                    None => false,
+                };
+                if over {
+                    self.pending = None;
+                }
+            }
+            Ok(Async::Ready(None)) => {
+                if let Some((pending, body, store)) = self.pending.take() {
+                    store(pending, body);
+                }
+            }
+            Ok(Async::NotReady) => {}
+            Err(_) => self.pending = None,
+        }
+        polled
+    }
+}
+
+// How long a response may be cached, it has to opt in explicitly.
+fn ttl(headers: &HeaderMap) -> Option<u32> {
+    let mut max_age = None;
+    let mut s_maxage = None;
+    for (name, value) in directives(headers, header::CACHE_CONTROL) {
+        match name.as_str() {
+            "private" | "no-store" | "no-cache" => return None,
+            "max-age" => max_age = value.and_then(|v| v.parse::<u32>().ok()),
+            "s-maxage" => s_maxage = value.and_then(|v| v.parse::<u32>().ok()),
+            _ => {}
+        }
+    }
+    s_maxage.or(max_age).filter(|ttl| *ttl > 0)
+}
+
+// The comma separated directives of a header, names lowercased.
+fn directives(headers: &HeaderMap, name: header::HeaderName) -> Vec<(String, Option<String>)> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .filter_map(|d| {
+            let mut parts = d.splitn(2, '=');
+            let name = parts.next()?.trim().to_ascii_lowercase();
+            if name.is_empty() {
+                return None;
+            }
+            let value = parts.next().map(|v| v.trim().trim_matches('"').to_string());
+            Some((name, value))
+        })
+        .collect()
+}
+
+fn has_token(headers: &HeaderMap, name: header::HeaderName, token: &str) -> bool {
+    directives(headers, name)
+        .iter()
+        .any(|(name, _)| name == token)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sqlite_cache::SqliteCacheStore;
+    use std::sync::{Arc, Mutex};
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(*name, header::HeaderValue::from_static(*value));
+        }
+        map
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let url = "https://example.com/a?b=c";
+        assert_eq!(
+            cache_key(&Method::GET, url, &HeaderMap::new()).unwrap(),
+            "fly-edge:https://example.com/a?b=c"
+        );
+        assert!(cache_key(&Method::HEAD, url, &HeaderMap::new()).is_some());
+        assert!(cache_key(&Method::POST, url, &HeaderMap::new()).is_none());
+        assert!(cache_key(
+            &Method::GET,
+            url,
+            &headers(&[("authorization", "Bearer x")])
+        )
+        .is_none());
+        assert!(cache_key(
+            &Method::GET,
+            url,
+            &headers(&[("cache-control", "max-age=0")])
+        )
+        .is_none());
+        assert!(cache_key(&Method::GET, url, &headers(&[("pragma", "no-cache")])).is_none());
+    }
+
+    #[test]
+    fn test_ttl() {
+        assert_eq!(
+            ttl(&headers(&[("cache-control", "public, max-age=60")])),
+            Some(60)
+        );
+        assert_eq!(
+            ttl(&headers(&[("cache-control", "max-age=60, s-maxage=3600")])),
+            Some(3600)
+        );
+        assert_eq!(
+            ttl(&headers(&[("cache-control", "private, max-age=60")])),
+            None
+        );
+        assert_eq!(ttl(&headers(&[("cache-control", "max-age=0")])), None);
+        assert_eq!(ttl(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_directives() {
+        let map = headers(&[
+            ("cache-control", "Public, max-age=\"10\""),
+            ("cache-control", "no-transform"),
+        ]);
+        assert_eq!(
+            directives(&map, header::CACHE_CONTROL),
+            vec![
+                ("public".to_string(), None),
+                ("max-age".to_string(), Some("10".to_string())),
+                ("no-transform".to_string(), None),
+            ]
+        );
+    }
+
+    const KEY: &str = "fly-edge:https://example.com/";
+
+    fn pending(max_bytes: usize) -> PendingEntry {
+        PendingEntry::new(
+            KEY.to_string(),
+            max_bytes,
+            &Method::GET,
+            &headers(&[("accept-encoding", "gzip")]),
+            StatusCode::OK,
+            &headers(&[
+                ("cache-control", "max-age=60"),
+                ("vary", "Accept-Encoding"),
+                ("surrogate-key", "a b"),
+            ]),
+        )
+        .unwrap()
+    }
+
+    // Stores everything handed to it, and whether it was called at all.
+    fn recorder() -> (
+        Arc<Mutex<Option<Vec<u8>>>>,
+        impl FnOnce(PendingEntry, Vec<u8>),
+    ) {
+        let stored = Arc::new(Mutex::new(None));
+        let s = stored.clone();
+        (stored, move |_, body| *s.lock().unwrap() = Some(body))
+    }
+
+    #[test]
+    fn test_lookup_vary() {
+        let dir = tempfile::tempdir().unwrap();
+        let store =
+            SqliteCacheStore::new(dir.path().join("cache.db").to_str().unwrap().to_string());
+        pending(1024)
+            .store_in(&store, b"hello".to_vec())
+            .unwrap()
+            .wait()
+            .unwrap();
+
+        let hit = lookup(
+            &store,
+            KEY.to_string(),
+            headers(&[("accept-encoding", "gzip")]),
+        )
+        .wait()
+        .unwrap()
+        .unwrap();
+        assert_eq!(hit.status, StatusCode::OK);
+        assert_eq!(hit.size, Some(5));
+        assert!(hit.headers.contains_key(header::AGE));
+        assert!(!hit.headers.contains_key(SURROGATE_KEY));
+        assert_eq!(hit.body.concat2().wait().unwrap(), b"hello".to_vec());
+
+        // other variants of the request miss
+        assert!(lookup(
+            &store,
+            KEY.to_string(),
+            headers(&[("accept-encoding", "br")])
+        )
+        .wait()
+        .unwrap()
+        .is_none());
+        assert!(lookup(&store, KEY.to_string(), HeaderMap::new())
+            .wait()
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_caching_stream_stores_on_completion() {
+        let (stored, store) = recorder();
+        let chunks = CachingStream::new(
+            stream::iter_ok::<_, ()>(vec![b"he".to_vec(), b"llo".to_vec()]),
+            Some(pending(1024)),
+            store,
+        )
+        .collect()
+        .wait()
+        .unwrap();
+        assert_eq!(chunks.concat(), b"hello".to_vec());
+        assert_eq!(*stored.lock().unwrap(), Some(b"hello".to_vec()));
+    }
+
+    #[test]
+    fn test_caching_stream_skips_incomplete_bodies() {
+        // cut short
+        let (stored, store) = recorder();
+        let res = CachingStream::new(
+            stream::iter_result(vec![Ok(b"he".to_vec()), Err(())]),
+            Some(pending(1024)),
+            store,
+        )
+        .collect()
+        .wait();
+        assert!(res.is_err());
+        assert_eq!(*stored.lock().unwrap(), None);
+
+        // over the size limit, still passed through
+        let (stored, store) = recorder();
+        let chunks = CachingStream::new(
+            stream::iter_ok::<_, ()>(vec![b"he".to_vec(), b"llo".to_vec()]),
+            Some(pending(3)),
+            store,
+        )
+        .collect()
+        .wait()
+        .unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(*stored.lock().unwrap(), None);
+    }
+
+    #[test]
+    fn test_is_edge_key() {
+        assert!(is_edge_key(KEY));
+        assert!(!is_edge_key("my-key"));
+    }
+}
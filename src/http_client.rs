@@ -0,0 +1,594 @@
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::Future;
+use hyper::client::connect::{Connect, Connected, Destination};
+use hyper::client::{HttpConnector, ResponseFuture};
+use hyper::{Body, Client, Request};
+use hyper_tls::HttpsConnector;
+use native_tls::{Certificate, Identity, TlsConnector};
+use openssl::error::ErrorStack;
+use openssl::pkcs12::Pkcs12;
+use openssl::pkey::PKey;
+use openssl::stack::Stack;
+use openssl::x509::X509;
+use tokio::timer::Timeout;
+
+use crate::errors::{self, ErrorKind, FlyError, FlyResult};
+use crate::ip_filter::{FilteringResolver, IpFilter};
+use crate::metrics::FETCH_CONNECTIONS_TOTAL;
+use crate::runtime::EVENT_LOOP;
+use crate::settings::{FetchConfig, Settings};
+
+pub type FetchConnector =
+    CountingConnector<TimeoutConnector<HttpsConnector<HttpConnector<FilteringResolver>>>>;
+type HyperClient = Client<FetchConnector, Body>;
+
+/// Sends outbound fetches, over HTTP/2 to the hosts configured for it and
+/// HTTP/1.1 to the rest.
+#[derive(Clone)]
+pub struct FetchClient {
+    http1: HyperClient,
+    http2: HyperClient,
+    http2_hosts: Arc<Vec<String>>,
+}
+
+impl FetchClient {
+    pub fn request(&self, req: Request<Body>) -> ResponseFuture {
+        let http2 = match req.uri().host() {
+            Some(host) => self
+                .http2_hosts
+                .iter()
+                .any(|h| h.eq_ignore_ascii_case(host)),
+            None => false,
+        };
+        if http2 {
+            self.http2.request(req)
+        } else {
+            self.http1.request(req)
+        }
+    }
+}
+
+/// Builds a client resolving names with `resolver` and connecting with `tls`.
+pub fn build_client(
+    conf: &FetchConfig,
+    resolver: FilteringResolver,
+    tls: TlsConnector,
+) -> FetchClient {
+    let mut http = HttpConnector::new_with_resolver(resolver);
+    http.enforce_http(false);
+    let connector = CountingConnector::new(TimeoutConnector::new(
+        HttpsConnector::from((http, tls)),
+        Duration::from_secs(conf.connect_timeout_secs),
+    ));
+
+    let mut builder = Client::builder();
+    builder
+        .executor(EVENT_LOOP.0.clone())
+        .max_idle_per_host(conf.max_idle_per_host)
+        .keep_alive_timeout(Duration::from_secs(conf.idle_timeout_secs));
+    let http1 = builder.build(connector.clone());
+    let http2 = builder.http2_only(true).build(connector);
+
+    FetchClient {
+        http1,
+        http2,
+        http2_hosts: Arc::new(conf.http2_hosts.clone()),
+    }
+}
+
+/// Trusts the configured CA bundle on top of the system roots, presenting
+/// `identity` to servers that ask for a client certificate.
+pub fn tls_connector(
+    conf: &FetchConfig,
+    identity: Option<&ClientIdentity>,
+) -> FlyResult<TlsConnector> {
+    let mut tls = TlsConnector::builder();
+    if let Some(ref path) = conf.ca_bundle_path {
+        for cert in load_ca_bundle(path)? {
+            tls.add_root_certificate(cert);
+        }
+    }
+    if let Some(identity) = identity {
+        tls.identity(identity.to_native()?);
+    }
+    tls.build()
+        .map_err(|e| format!("error building tls connector: {}", e).into())
+}
+
+/// Fails if fetches can't be set up with `conf`, for checking it on startup.
+pub fn check_config(conf: &FetchConfig) -> FlyResult<()> {
+    tls_connector(conf, None).map(|_| ())
+}
+
+/// A client certificate and its private key, both PEM encoded. The key stays
+/// in Rust: JS only ever refers to an identity by name.
+#[derive(Clone)]
+pub struct ClientIdentity {
+    cert_pem: String,
+    key_pem: String,
+}
+
+impl ClientIdentity {
+    /// `cert_pem` may hold intermediate certificates after the leaf.
+    pub fn new(cert_pem: String, key_pem: String) -> Self {
+        ClientIdentity { cert_pem, key_pem }
+    }
+
+    pub fn from_files(cert_path: &str, key_path: &str) -> FlyResult<Self> {
+        let read = |path: &str| {
+            fs::read_to_string(path).map_err(|e| format!("error reading {}: {}", path, e))
+        };
+        Ok(ClientIdentity::new(read(cert_path)?, read(key_path)?))
+    }
+
+    // native-tls only takes PKCS#12 identities.
+    fn to_native(&self) -> FlyResult<Identity> {
+        let invalid = |e: ErrorStack| format!("invalid client identity: {}", e);
+        let mut certs = X509::stack_from_pem(self.cert_pem.as_bytes()).map_err(invalid)?;
+        if certs.is_empty() {
+            return Err("invalid client identity: no certificate".to_string().into());
+        }
+        let leaf = certs.remove(0);
+        let key = PKey::private_key_from_pem(self.key_pem.as_bytes()).map_err(invalid)?;
+        let mut chain = Stack::new().map_err(invalid)?;
+        for cert in certs {
+            chain.push(cert).map_err(invalid)?;
+        }
+        let mut builder = Pkcs12::builder();
+        builder.ca(chain);
+        let der = builder
+            .build("", "client", &key, &leaf)
+            .and_then(|p| p.to_der())
+            .map_err(invalid)?;
+        Identity::from_pkcs12(&der, "")
+            .map_err(|e| format!("invalid client identity: {}", e).into())
+    }
+}
+
+impl fmt::Debug for ClientIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClientIdentity")
+            .field("cert_pem", &self.cert_pem)
+            .field("key_pem", &"<redacted>")
+            .finish()
+    }
+}
+
+/// The clients a runtime fetches with: a default one, and one per identity
+/// the app can present, built on first use.
+pub struct FetchClients {
+    conf: FetchConfig,
+    resolver: FilteringResolver,
+    // set unless the configured CA bundle couldn't be loaded
+    tls: Result<TlsConnector, String>,
+    default: Option<FetchClient>,
+    identities: HashMap<String, ClientIdentity>,
+    clients: Mutex<HashMap<String, FetchClient>>,
+}
+
+impl FetchClients {
+    /// A broken `conf` is logged, and every fetch then fails with its error.
+    pub fn new(
+        conf: FetchConfig,
+        filter: IpFilter,
+        identities: HashMap<String, ClientIdentity>,
+    ) -> Self {
+        let resolver = FilteringResolver::new(conf.dns_threads, Arc::new(filter));
+        let tls = tls_connector(&conf, None).map_err(|e| {
+            error!("could not set up fetch: {}", e);
+            e.to_string()
+        });
+        let default = match tls {
+            Ok(ref tls) => Some(build_client(&conf, resolver.clone(), tls.clone())),
+            Err(_) => None,
+        };
+        FetchClients {
+            conf,
+            resolver,
+            tls,
+            default,
+            identities,
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The client presenting no identity.
+    pub fn default_client(&self) -> FlyResult<FetchClient> {
+        match self.default {
+            Some(ref client) => Ok(client.clone()),
+            None => Err(self.unavailable()),
+        }
+    }
+
+    /// The client presenting the named identity.
+    pub fn get(&self, name: &str) -> FlyResult<FetchClient> {
+        let mut clients = self.clients.lock().unwrap();
+        if let Some(client) = clients.get(name) {
+            return Ok(client.clone());
+        }
+        let identity = self.identities.get(name).ok_or_else(|| {
+            errors::new(
+                ErrorKind::NotFound,
+                format!("unknown client identity '{}'", name),
+            )
+        })?;
+        let tls = tls_connector(&self.conf, Some(identity))?;
+        let client = build_client(&self.conf, self.resolver.clone(), tls);
+        clients.insert(name.to_string(), client.clone());
+        Ok(client)
+    }
+
+    /// What WebSockets connect with, the same resolver and TLS settings as
+    /// the default client.
+    pub fn connector(&self) -> FlyResult<(FilteringResolver, TlsConnector)> {
+        match self.tls {
+            Ok(ref tls) => Ok((self.resolver.clone(), tls.clone())),
+            Err(_) => Err(self.unavailable()),
+        }
+    }
+
+    fn unavailable(&self) -> FlyError {
+        match self.tls {
+            Err(ref e) => format!("fetch is unavailable: {}", e).into(),
+            Ok(_) => "fetch is unavailable".to_string().into(),
+        }
+    }
+
+    /// Applies to IP literals, which skip the resolver.
+    pub fn ip_filter(&self) -> Arc<IpFilter> {
+        self.resolver.filter().clone()
+    }
+
+    pub fn clear(&self) {
+        self.clients.lock().unwrap().clear();
+    }
+}
+
+/// Loads the identities configured in `Settings`, skipping broken ones.
+pub fn load_identities(settings: &Settings) -> HashMap<String, ClientIdentity> {
+    let mut identities = HashMap::new();
+    if let Some(ref configs) = settings.client_identities {
+        for (name, conf) in configs {
+            match ClientIdentity::from_files(&conf.cert_path, &conf.key_path) {
+                Ok(identity) => {
+                    identities.insert(name.clone(), identity);
+                }
+                Err(e) => error!("could not load client identity {}: {}", name, e),
+            }
+        }
+    }
+    identities
+}
+
+/// Reads every certificate in a PEM bundle.
+pub fn load_ca_bundle(path: &str) -> FlyResult<Vec<Certificate>> {
+    let pem =
+        fs::read_to_string(path).map_err(|e| format!("error reading ca bundle {}: {}", path, e))?;
+    split_pem(&pem)
+        .iter()
+        .map(|block| {
+            Certificate::from_pem(block.as_bytes())
+                .map_err(|e| format!("invalid certificate in {}: {}", path, e).into())
+        })
+        .collect()
+}
+
+const PEM_END: &str = "-----END CERTIFICATE-----";
+
+fn split_pem(pem: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut rest = pem;
+    while let Some(start) = rest.find("-----BEGIN CERTIFICATE-----") {
+        let end = match rest[start..].find(PEM_END) {
+            Some(end) => start + end + PEM_END.len(),
+            None => break,
+        };
+        blocks.push(rest[start..end].to_string());
+        rest = &rest[end..];
+    }
+    blocks
+}
+
+/// Counts new connections per host. Compared to `FETCH_HTTP_REQUESTS_TOTAL`,
+/// this tells how often requests reuse a pooled connection.
+#[derive(Clone)]
+pub struct CountingConnector<C> {
+    inner: C,
+}
+
+impl<C> CountingConnector<C> {
+    pub fn new(inner: C) -> Self {
+        CountingConnector { inner }
+    }
+}
+
+impl<C> Connect for CountingConnector<C>
+where
+    C: Connect,
+    C::Future: 'static,
+{
+    type Transport = C::Transport;
+    type Error = C::Error;
+    type Future = Box<Future<Item = (C::Transport, Connected), Error = C::Error> + Send>;
+
+    fn connect(&self, dst: Destination) -> Self::Future {
+        let default_port = if dst.scheme() == "https" { 443 } else { 80 };
+        let host = format!("{}:{}", dst.host(), dst.port().unwrap_or(default_port));
+        Box::new(self.inner.connect(dst).map(move |conn| {
+            FETCH_CONNECTIONS_TOTAL
+                .with_label_values(&[host.as_str()])
+                .inc();
+            conn
+        }))
+    }
+}
+
+/// Gives up on connections, TLS handshake included, taking longer than
+/// `timeout`.
+#[derive(Clone)]
+pub struct TimeoutConnector<C> {
+    inner: C,
+    timeout: Duration,
+}
+
+impl<C> TimeoutConnector<C> {
+    pub fn new(inner: C, timeout: Duration) -> Self {
+        TimeoutConnector { inner, timeout }
+    }
+}
+
+impl<C> Connect for TimeoutConnector<C>
+where
+    C: Connect,
+    C::Future: 'static,
+{
+    type Transport = C::Transport;
+    type Error = Box<StdError + Send + Sync>;
+    type Future = Box<Future<Item = (C::Transport, Connected), Error = Self::Error> + Send>;
+
+    fn connect(&self, dst: Destination) -> Self::Future {
+        let timeout = self.timeout;
+        Box::new(
+            Timeout::new(self.inner.connect(dst), timeout).map_err(move |e| {
+                if e.is_elapsed() {
+                    return io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        format!("connect timed out after {:?}", timeout),
+                    )
+                    .into();
+                }
+                match e.into_inner() {
+                    Some(e) => e.into(),
+                    None => io::Error::new(io::ErrorKind::Other, "connect timer error").into(),
+                }
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_pem() {
+        let pem = "junk\n-----BEGIN CERTIFICATE-----\nAAA\n-----END CERTIFICATE-----\n\
+                   # comment\n-----BEGIN CERTIFICATE-----\nBBB\n-----END CERTIFICATE-----\n";
+        let blocks = split_pem(pem);
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[0].contains("AAA"));
+        assert!(blocks[1].contains("BBB"));
+        assert!(blocks[1].ends_with(PEM_END));
+    }
+
+    #[test]
+    fn test_client_identity() {
+        let (key, cert) = certificate("client", None, None);
+        let cert_pem = String::from_utf8(cert.to_pem().unwrap()).unwrap();
+        let key_pem = String::from_utf8(key.private_key_to_pem_pkcs8().unwrap()).unwrap();
+
+        let identity = ClientIdentity::new(cert_pem.clone(), key_pem.clone());
+        assert!(identity.to_native().is_ok());
+        assert!(!format!("{:?}", identity).contains(&key_pem));
+
+        let broken = ClientIdentity::new(cert_pem, "not a key".to_string());
+        assert!(broken.to_native().is_err());
+    }
+
+    type KeyPair = (PKey<openssl::pkey::Private>, X509);
+
+    // A P-256 certificate for `cn`, signed by `issuer` or self-signed.
+    fn certificate(cn: &str, issuer: Option<&KeyPair>, ip: Option<&str>) -> KeyPair {
+        use openssl::asn1::Asn1Time;
+        use openssl::bn::BigNum;
+        use openssl::ec::{EcGroup, EcKey};
+        use openssl::hash::MessageDigest;
+        use openssl::nid::Nid;
+        use openssl::x509::extension::{BasicConstraints, SubjectAlternativeName};
+        use openssl::x509::X509NameBuilder;
+
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", cn).unwrap();
+        let name = name.build();
+
+        let mut cert = X509::builder().unwrap();
+        cert.set_version(2).unwrap();
+        let serial = BigNum::from_u32(rand::random::<u32>() >> 1).unwrap();
+        cert.set_serial_number(&serial.to_asn1_integer().unwrap())
+            .unwrap();
+        cert.set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        cert.set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        cert.set_subject_name(&name).unwrap();
+        cert.set_pubkey(&key).unwrap();
+        match issuer {
+            Some((issuer_key, issuer_cert)) => {
+                cert.set_issuer_name(issuer_cert.subject_name()).unwrap();
+                if let Some(ip) = ip {
+                    let san = SubjectAlternativeName::new()
+                        .ip(ip)
+                        .build(&cert.x509v3_context(Some(issuer_cert), None))
+                        .unwrap();
+                    cert.append_extension(san).unwrap();
+                }
+                cert.sign(issuer_key, MessageDigest::sha256()).unwrap();
+            }
+            None => {
+                cert.set_issuer_name(&name).unwrap();
+                let ca = BasicConstraints::new().critical().ca().build().unwrap();
+                cert.append_extension(ca).unwrap();
+                cert.sign(&key, MessageDigest::sha256()).unwrap();
+            }
+        }
+        (key, cert.build())
+    }
+
+    // Serves HTTPS on a local port to clients presenting a certificate signed
+    // by `ca`, answering with the client certificate's common name.
+    fn serve_mtls(ca: &X509, server: &KeyPair) -> u16 {
+        use openssl::nid::Nid;
+        use openssl::ssl::{SslAcceptor, SslMethod, SslVerifyMode};
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(&server.0).unwrap();
+        acceptor.set_certificate(&server.1).unwrap();
+        acceptor.cert_store_mut().add_cert(ca.clone()).unwrap();
+        acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        let acceptor = acceptor.build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut tls = match stream.map(|s| acceptor.accept(s)) {
+                    Ok(Ok(tls)) => tls,
+                    _ => continue,
+                };
+                let cn = tls
+                    .ssl()
+                    .peer_certificate()
+                    .and_then(|cert| {
+                        cert.subject_name()
+                            .entries_by_nid(Nid::COMMONNAME)
+                            .next()
+                            .and_then(|e| e.data().as_utf8().ok())
+                            .map(|cn| cn.to_string())
+                    })
+                    .unwrap_or_default();
+                let mut req = vec![];
+                let mut buf = [0; 1024];
+                while !req.ends_with(b"\r\n\r\n") {
+                    match tls.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => req.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let _ = write!(
+                    tls,
+                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    cn.len(),
+                    cn
+                );
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_mutual_tls() {
+        use futures::Stream;
+        use std::io::Write;
+
+        let ca = certificate("test ca", None, None);
+        let server = certificate("server", Some(&ca), Some("127.0.0.1"));
+        let client = certificate("client", Some(&ca), None);
+        let port = serve_mtls(&ca.1, &server);
+
+        let mut bundle = tempfile::NamedTempFile::new().unwrap();
+        bundle.write_all(&ca.1.to_pem().unwrap()).unwrap();
+        let conf = FetchConfig {
+            ca_bundle_path: Some(bundle.path().to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+        let identity = ClientIdentity::new(
+            String::from_utf8(client.1.to_pem().unwrap()).unwrap(),
+            String::from_utf8(client.0.private_key_to_pem_pkcs8().unwrap()).unwrap(),
+        );
+
+        let mut el = tokio::runtime::Runtime::new().unwrap();
+        let uri: hyper::Uri = format!("https://127.0.0.1:{}/", port).parse().unwrap();
+        let get = |client: FetchClient| {
+            client
+                .request(Request::get(uri.clone()).body(Body::empty()).unwrap())
+                .and_then(|res| res.into_body().concat2())
+                .map(|body| body.to_vec())
+        };
+
+        let mut identities = HashMap::new();
+        identities.insert("test".to_string(), identity);
+        let clients = FetchClients::new(conf, IpFilter::default(), identities);
+
+        let with_identity = clients.get("test").unwrap();
+        assert_eq!(el.block_on(get(with_identity)).unwrap(), b"client".to_vec());
+
+        let without = clients.default_client().unwrap();
+        assert!(el.block_on(get(without)).is_err());
+    }
+
+    #[test]
+    fn test_broken_ca_bundle() {
+        let conf = FetchConfig {
+            ca_bundle_path: Some("/nonexistent/ca.pem".to_string()),
+            ..Default::default()
+        };
+        assert!(check_config(&conf).is_err());
+
+        let clients = FetchClients::new(conf, IpFilter::default(), HashMap::new());
+        assert!(clients.default_client().is_err());
+        assert!(clients.connector().is_err());
+    }
+
+    #[test]
+    fn test_split_pem_truncated() {
+        let pem = "-----BEGIN CERTIFICATE-----\nAAA\n";
+        assert!(split_pem(pem).is_empty());
+    }
+
+    #[test]
+    fn test_connect_timeout() {
+        use tokio::net::TcpStream;
+
+        // never connects
+        struct Stalled;
+        impl Connect for Stalled {
+            type Transport = TcpStream;
+            type Error = io::Error;
+            type Future = futures::future::Empty<(TcpStream, Connected), io::Error>;
+
+            fn connect(&self, _: Destination) -> Self::Future {
+                futures::future::empty()
+            }
+        }
+
+        let mut el = tokio::runtime::Runtime::new().unwrap();
+        let client: Client<_, Body> =
+            Client::builder().build(TimeoutConnector::new(Stalled, Duration::from_millis(50)));
+        let err = el
+            .block_on(client.get("http://example.com/".parse().unwrap()))
+            .err()
+            .unwrap();
+        assert!(err.is_connect());
+    }
+}
@@ -0,0 +1,390 @@
+use std::ops::Range;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+use futures::{Async, Poll, Stream};
+use hyper::{header, HeaderMap, Method, StatusCode};
+
+/// A single byte range from a `Range` header, before the length of the
+/// content it applies to is known.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ByteRange {
+    /// From an offset through an inclusive end, or through the end of the content.
+    From(u64, Option<u64>),
+    /// The last n bytes.
+    Suffix(u64),
+}
+
+impl ByteRange {
+    /// Requests for several ranges aren't supported, they get everything.
+    pub fn parse(value: &str) -> Option<ByteRange> {
+        let value = value.trim();
+        if !value.starts_with("bytes=") {
+            return None;
+        }
+        let spec = &value["bytes=".len()..];
+        if spec.contains(',') {
+            return None;
+        }
+        let mut parts = spec.splitn(2, '-');
+        let start = parts.next()?.trim();
+        let end = parts.next()?.trim();
+        if start.is_empty() {
+            return end.parse().ok().map(ByteRange::Suffix);
+        }
+        let start = start.parse().ok()?;
+        let end = if end.is_empty() {
+            None
+        } else {
+            Some(end.parse().ok()?)
+        };
+        if end.map_or(false, |end| end < start) {
+            return None;
+        }
+        Some(ByteRange::From(start, end))
+    }
+
+    /// The offsets it covers in content of `len` bytes, end exclusive. `None`
+    /// when it can't be satisfied.
+    pub fn resolve(self, len: u64) -> Option<Range<u64>> {
+        match self {
+            ByteRange::From(start, _) if start >= len => None,
+            ByteRange::From(start, end) => {
+                Some(start..end.map_or(len, |end| end.saturating_add(1).min(len)))
+            }
+            ByteRange::Suffix(n) if n == 0 || len == 0 => None,
+            ByteRange::Suffix(n) => Some(len.saturating_sub(n)..len),
+        }
+    }
+}
+
+/// How a request is answered given the full response to it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Full,
+    NotModified,
+    /// The range to send, and the full length.
+    Partial(Range<u64>, u64),
+    /// The full length.
+    Unsatisfiable(u64),
+}
+
+impl Outcome {
+    /// Sets the status and headers answering the request.
+    pub fn apply(&self, status: &mut StatusCode, headers: &mut HeaderMap) {
+        match self {
+            Outcome::Full => {}
+            Outcome::NotModified => {
+                *status = StatusCode::NOT_MODIFIED;
+                headers.remove(header::CONTENT_LENGTH);
+            }
+            Outcome::Partial(range, len) => {
+                *status = StatusCode::PARTIAL_CONTENT;
+                headers.insert(
+                    header::CONTENT_RANGE,
+                    header::HeaderValue::from_str(&format!(
+                        "bytes {}-{}/{}",
+                        range.start,
+                        range.end - 1,
+                        len
+                    ))
+                    .unwrap(),
+                );
+                headers.insert(
+                    header::CONTENT_LENGTH,
+                    header::HeaderValue::from(range.end - range.start),
+                );
+            }
+            Outcome::Unsatisfiable(len) => {
+                *status = StatusCode::RANGE_NOT_SATISFIABLE;
+                headers.insert(
+                    header::CONTENT_RANGE,
+                    header::HeaderValue::from_str(&format!("bytes */{}", len)).unwrap(),
+                );
+                headers.remove(header::CONTENT_LENGTH);
+            }
+        }
+    }
+
+    /// Whether any of the body goes out.
+    pub fn has_body(&self) -> bool {
+        match self {
+            Outcome::Full | Outcome::Partial(..) => true,
+            _ => false,
+        }
+    }
+
+    /// The part of the body that goes out, all of it when `None`.
+    pub fn range(&self) -> Option<Range<u64>> {
+        match self {
+            Outcome::Partial(range, _) => Some(range.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Checks a request's conditional and range headers against the full, 200
+/// response to it. Ranges need the length of the response body.
+pub fn evaluate(
+    method: &Method,
+    req: &HeaderMap,
+    status: StatusCode,
+    res: &HeaderMap,
+    len: Option<u64>,
+) -> Outcome {
+    if status != StatusCode::OK || (method != Method::GET && method != Method::HEAD) {
+        return Outcome::Full;
+    }
+    let etag = header_str(res, header::ETAG);
+    let last_modified = header_str(res, header::LAST_MODIFIED).and_then(parse_http_date);
+
+    // If-Modified-Since only counts without If-None-Match, RFC 7232 section 6
+    if let Some(tags) = header_str(req, header::IF_NONE_MATCH) {
+        if etag.map_or(false, |etag| etag_matches(tags, etag)) {
+            return Outcome::NotModified;
+        }
+    } else if let Some(since) = header_str(req, header::IF_MODIFIED_SINCE).and_then(parse_http_date)
+    {
+        if last_modified.map_or(false, |modified| modified <= since) {
+            return Outcome::NotModified;
+        }
+    }
+
+    if method != Method::GET {
+        return Outcome::Full;
+    }
+    let range = match header_str(req, header::RANGE).and_then(ByteRange::parse) {
+        Some(range) => range,
+        None => return Outcome::Full,
+    };
+    // a range of a representation that changed since makes no sense
+    if let Some(validator) = header_str(req, header::IF_RANGE) {
+        let current = if validator.starts_with('"') {
+            etag == Some(validator)
+        } else {
+            parse_http_date(validator).map_or(false, |date| last_modified == Some(date))
+        };
+        if !current {
+            return Outcome::Full;
+        }
+    }
+    let len = match len {
+        Some(len) => len,
+        None => return Outcome::Full,
+    };
+    match range.resolve(len) {
+        Some(range) => Outcome::Partial(range, len),
+        None => Outcome::Unsatisfiable(len),
+    }
+}
+
+fn header_str(headers: &HeaderMap, name: header::HeaderName) -> Option<&str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+// Weak comparison, If-None-Match ignores the W/ prefix.
+fn etag_matches(tags: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| {
+        let tag = tag.trim();
+        if tag.starts_with("W/") {
+            tag[2..].to_string()
+        } else {
+            tag.to_string()
+        }
+    };
+    let etag = opaque(etag);
+    tags.trim() == "*" || tags.split(',').any(|tag| opaque(tag) == etag)
+}
+
+/// A weak ETag for a file. Size and mtime can't prove two files byte for
+/// byte equal, so it's never used to match `If-Range`.
+pub fn file_etag(size: u64, modified: SystemTime) -> String {
+    let secs = modified
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format!("W/\"{:x}-{:x}\"", secs, size)
+}
+
+pub fn http_date(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time)
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+pub fn parse_http_date(value: &str) -> Option<SystemTime> {
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|date| SystemTime::from(date.with_timezone(&Utc)))
+}
+
+/// Passes on the bytes of a stream within a range, end exclusive, and all of
+/// them without one. It ends as soon as the range went through.
+pub struct SliceStream<S> {
+    inner: S,
+    range: Option<Range<u64>>,
+    pos: u64,
+}
+
+impl<S> SliceStream<S> {
+    pub fn new(inner: S, range: Option<Range<u64>>) -> Self {
+        SliceStream {
+            inner,
+            range,
+            pos: 0,
+        }
+    }
+}
+
+impl<S> Stream for SliceStream<S>
+where
+    S: Stream<Item = Vec<u8>>,
+{
+    type Item = Vec<u8>;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, S::Error> {
+        let range = match self.range {
+            Some(ref range) => range.clone(),
+            None => return self.inner.poll(),
+        };
+        loop {
+            if self.pos >= range.end {
+                return Ok(Async::Ready(None));
+            }
+            let chunk = match self.inner.poll()? {
+                Async::Ready(Some(chunk)) => chunk,
+                Async::Ready(None) => return Ok(Async::Ready(None)),
+                Async::NotReady => return Ok(Async::NotReady),
+            };
+            let start = self.pos;
+            let end = start + chunk.len() as u64;
+            self.pos = end;
+            if end <= range.start {
+                continue;
+            }
+            let from = range.start.saturating_sub(start) as usize;
+            let to = (range.end.min(end) - start) as usize;
+            if from == 0 && to == chunk.len() {
+                return Ok(Async::Ready(Some(chunk)));
+            }
+            return Ok(Async::Ready(Some(chunk[from..to].to_vec())));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{stream, Future};
+
+    #[test]
+    fn test_byte_range() {
+        assert_eq!(
+            ByteRange::parse("bytes=0-499"),
+            Some(ByteRange::From(0, Some(499)))
+        );
+        assert_eq!(
+            ByteRange::parse("bytes=500-"),
+            Some(ByteRange::From(500, None))
+        );
+        assert_eq!(ByteRange::parse("bytes=-500"), Some(ByteRange::Suffix(500)));
+        assert_eq!(ByteRange::parse("bytes=0-1,5-6"), None);
+        assert_eq!(ByteRange::parse("bytes=5-1"), None);
+        assert_eq!(ByteRange::parse("items=0-1"), None);
+
+        assert_eq!(ByteRange::From(0, Some(499)).resolve(1000), Some(0..500));
+        assert_eq!(
+            ByteRange::From(900, Some(2000)).resolve(1000),
+            Some(900..1000)
+        );
+        assert_eq!(ByteRange::From(1000, None).resolve(1000), None);
+        assert_eq!(ByteRange::Suffix(100).resolve(1000), Some(900..1000));
+        assert_eq!(ByteRange::Suffix(2000).resolve(1000), Some(0..1000));
+        assert_eq!(ByteRange::Suffix(10).resolve(0), None);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let mut res = HeaderMap::new();
+        res.insert(header::ETAG, "\"abc\"".parse().unwrap());
+        res.insert(
+            header::LAST_MODIFIED,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        let eval = |req: &HeaderMap| evaluate(&Method::GET, req, StatusCode::OK, &res, Some(100));
+
+        let mut req = HeaderMap::new();
+        assert_eq!(eval(&req), Outcome::Full);
+
+        req.insert(header::IF_NONE_MATCH, "\"xyz\", W/\"abc\"".parse().unwrap());
+        assert_eq!(eval(&req), Outcome::NotModified);
+
+        let mut req = HeaderMap::new();
+        req.insert(
+            header::IF_MODIFIED_SINCE,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(eval(&req), Outcome::NotModified);
+
+        let mut req = HeaderMap::new();
+        req.insert(header::RANGE, "bytes=10-19".parse().unwrap());
+        assert_eq!(eval(&req), Outcome::Partial(10..20, 100));
+        req.insert(header::IF_RANGE, "\"old\"".parse().unwrap());
+        assert_eq!(eval(&req), Outcome::Full);
+
+        let mut req = HeaderMap::new();
+        req.insert(header::RANGE, "bytes=200-".parse().unwrap());
+        assert_eq!(eval(&req), Outcome::Unsatisfiable(100));
+        assert_eq!(
+            evaluate(&Method::GET, &req, StatusCode::NOT_FOUND, &res, Some(100)),
+            Outcome::Full
+        );
+
+        // weak validators never satisfy If-Range
+        let mut weak = HeaderMap::new();
+        weak.insert(header::ETAG, "W/\"abc\"".parse().unwrap());
+        let mut req = HeaderMap::new();
+        req.insert(header::RANGE, "bytes=10-19".parse().unwrap());
+        req.insert(header::IF_RANGE, "W/\"abc\"".parse().unwrap());
+        assert_eq!(
+            evaluate(&Method::GET, &req, StatusCode::OK, &weak, Some(100)),
+            Outcome::Full
+        );
+        req.insert(header::IF_NONE_MATCH, "\"abc\"".parse().unwrap());
+        assert_eq!(
+            evaluate(&Method::GET, &req, StatusCode::OK, &weak, Some(100)),
+            Outcome::NotModified
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut status = StatusCode::OK;
+        let mut headers = HeaderMap::new();
+        Outcome::Partial(10..20, 100).apply(&mut status, &mut headers);
+        assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+        assert_eq!(headers[header::CONTENT_RANGE], "bytes 10-19/100");
+        assert_eq!(headers[header::CONTENT_LENGTH], "10");
+    }
+
+    #[test]
+    fn test_http_date() {
+        let date = "Wed, 21 Oct 2015 07:28:00 GMT";
+        assert_eq!(http_date(parse_http_date(date).unwrap()), date);
+    }
+
+    #[test]
+    fn test_slice_stream() {
+        let chunks = vec![b"hello".to_vec(), b" ".to_vec(), b"world".to_vec()];
+        let slice = |range| {
+            SliceStream::new(stream::iter_ok::<_, ()>(chunks.clone()), range)
+                .concat2()
+                .wait()
+                .unwrap()
+        };
+        assert_eq!(slice(Some(3..8)), b"lo wo".to_vec());
+        assert_eq!(slice(Some(6..11)), b"world".to_vec());
+        assert_eq!(slice(None), b"hello world".to_vec());
+    }
+}
@@ -1,22 +1,40 @@
-use futures::{future, Future, Stream};
+use futures::{future, Async, Future, Poll, Stream};
 use std::net::{IpAddr, SocketAddr};
 
+use crate::compression::{self, EncodedStream, Encoding, MIN_COMPRESS_BYTES};
+use crate::http_cache::{self, CachedResponse, CachingStream, PendingEntry};
+use crate::http_range::{self, Outcome, SliceStream};
 use crate::js::*;
 use crate::metrics::*;
+use crate::ops::websocket::accept_upgrade;
+use crate::runtime::RuntimeHandle;
+use crate::settings::HttpLimits;
 use crate::utils::*;
 use crate::{get_next_stream_id, RuntimeSelector};
 
 use hyper::body::Payload;
-use hyper::{header, Body, Request, Response, StatusCode};
+use hyper::upgrade::Upgraded;
+use hyper::{header, Body, HeaderMap, Request, Response, StatusCode};
+use prometheus::IntCounter;
+use sha1::{Digest, Sha1};
 
 use floating_duration::TimeAsFloat;
 use std::io;
-use std::time;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{self, Duration, Instant};
+use tokio::timer::Delay;
 
-use slog::{o, slog_debug, slog_error, slog_info};
+use slog::{o, slog_debug, slog_error, slog_info, slog_warn};
 
 type BoxedResponseFuture = Box<Future<Item = Response<Body>, Error = futures::Canceled> + Send>;
 
+// an SSE comment line, ignored by clients
+static SSE_HEARTBEAT: &[u8] = b":\n\n";
+
+// Appended to the client's key to compute the handshake's accept header.
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
 lazy_static! {
     // static ref SERVER_HEADER: &'static str =
     static ref SERVER_HEADER_VALUE: header::HeaderValue = {
@@ -142,74 +160,349 @@ pub fn serve_http(
     let outbound_data =
         DATA_OUT_TOTAL.with_label_values(&[rt_name.as_str(), rt_version.as_str(), "http_response"]);
 
-    let body = if body.is_end_stream() {
-        None
-    } else {
-        Some(JsBody::BoxedStream(Box::new({
-            body.map_err(|e| format!("{}", e).into())
-                .map(move |chunk| chunk.into_bytes().to_vec())
-                .inspect(move |bytes| inbound_data.inc_by(bytes.len() as i64))
-        })))
+    let limits = rt.http_limits.clone();
+    let report = LimitReport {
+        logger: logger.clone(),
+        name: rt_name.clone(),
+        version: rt_version.clone(),
     };
-
-    match rt.dispatch_event(
-        stream_id,
-        JsEvent::Fetch(JsHttpRequest {
-            id: stream_id,
-            method: parts.method,
-            remote_addr: remote_addr,
-            url: url,
-            headers: parts.headers.clone(),
-            body,
-        }),
-    ) {
-        None => future_response(
-            simple_response(StatusCode::SERVICE_UNAVAILABLE, None),
+    if let Some((kind, max, status)) = request_over_limit(&limits, &parts.headers) {
+        report.exceeded(kind, max);
+        return future_response(
+            simple_response(status, None),
             request_info,
             logger,
-            Some((rt.name.clone(), rt.version.clone())),
-        ),
-        Some(Err(e)) => {
-            slog_error!(logger, "error sending js http request: {:?}", e);
-            future_response(
-                simple_response(StatusCode::INTERNAL_SERVER_ERROR, None),
-                request_info,
-                logger,
-                Some((rt.name.clone(), rt.version.clone())),
-            )
+            Some((rt_name, rt_version)),
+        );
+    }
+
+    let websocket_key = if parts.version == hyper::Version::HTTP_11 {
+        websocket_key(&parts.headers)
+    } else {
+        None
+    };
+
+    let compression = rt.compression;
+    let accept_encoding = parts.headers.get(header::ACCEPT_ENCODING).cloned();
+
+    let cache_conf = rt.http_cache.clone();
+    let sse_heartbeat = rt.sse_heartbeat;
+    let cache_key = if cache_conf.enabled && websocket_key.is_none() {
+        let key = http_cache::cache_key(&parts.method, &url, &parts.headers);
+        if key.is_none() {
+            HTTP_CACHE_BYPASSES_TOTAL
+                .with_label_values(&[rt_name.as_str(), rt_version.as_str()])
+                .inc();
         }
-        Some(Ok(EventResponseChannel::Http(rx))) => wrap_future(
-            rx.and_then(move |res: JsHttpResponse| {
-                let (mut parts, mut body) = Response::<Body>::default().into_parts();
-                parts.headers = res.headers;
-                parts.status = res.status;
-
-                if let Some(js_body) = res.body {
-                    body = match js_body {
-                        JsBody::Stream(s) => Body::wrap_stream(
-                            s.map_err(|_| {
-                                io::Error::new(io::ErrorKind::Interrupted, "interrupted stream")
-                            })
-                            .inspect(move |v| {
-                                outbound_data.inc_by(v.len() as i64);
-                            }),
-                        ),
-                        JsBody::Static(b) => {
-                            outbound_data.inc_by(b.len() as i64);
-                            Body::from(b)
+        key
+    } else {
+        None
+    };
+    let req_method = parts.method.clone();
+    let req_headers = parts.headers.clone();
+
+    let rt_handle = rt.handle();
+    // set once a chunked body runs over the limit, the request is aborted in JS
+    let body_exceeded = Arc::new(AtomicBool::new(false));
+
+    // an upgrade request keeps its body, the upgraded connection comes out of it
+    let (body, on_upgrade) = if websocket_key.is_some() {
+        (None, Some(body.on_upgrade()))
+    } else if body.is_end_stream() {
+        (None, None)
+    } else {
+        let max_body = limits.max_request_body_bytes;
+        let exceeded = body_exceeded.clone();
+        let report = report.clone();
+        let abort_handle = rt_handle.clone();
+        let mut received = 0;
+        (
+            Some(JsBody::BoxedStream(Box::new({
+                body.map_err(|e| format!("{}", e).into())
+                    .map(move |chunk| chunk.into_bytes().to_vec())
+                    .inspect(move |bytes| inbound_data.inc_by(bytes.len() as i64))
+                    .and_then(move |bytes| {
+                        received += bytes.len() as u64;
+                        match max_body {
+                            Some(max) if received > max => {
+                                exceeded.store(true, Ordering::SeqCst);
+                                report.exceeded("request_body", max);
+                                spawn_abort(&abort_handle, stream_id);
+                                Err("request body too large".to_string().into())
+                            }
+                            _ => Ok(bytes),
                         }
-                        _ => unimplemented!(),
-                    };
-                }
+                    })
+            }))),
+            None,
+        )
+    };
+
+    let lookup = match cache_key {
+        Some(ref key) => Some(http_cache::lookup(
+            &*rt.cache_store,
+            key.clone(),
+            req_headers.clone(),
+        )),
+        None => None,
+    };
+    let hit_outbound_data = outbound_data.clone();
+    let hit_accept_encoding = accept_encoding.clone();
+    let hit_method = req_method.clone();
+    let hit_req_headers = req_headers.clone();
+    let request_logger = logger.clone();
+    let namever = Some((rt_name.clone(), rt_version.clone()));
 
-                Ok(Response::from_parts(parts, body))
+    let dispatch = move || -> BoxedResponseFuture {
+        match rt.dispatch_event(
+            stream_id,
+            JsEvent::Fetch(JsHttpRequest {
+                id: stream_id,
+                method: parts.method,
+                remote_addr: remote_addr,
+                url: url,
+                headers: parts.headers,
+                body,
             }),
-            request_info,
-            logger,
-            Some((rt.name.clone(), rt.version.clone())),
-        ),
-        _ => unimplemented!(),
+        ) {
+            None => Box::new(future::ok(simple_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                None,
+            ))),
+            Some(Err(e)) => {
+                slog_error!(logger, "error sending js http request: {:?}", e);
+                Box::new(future::ok(simple_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    None,
+                )))
+            }
+            Some(Ok(EventResponseChannel::Http(rx))) => {
+                let rx_logger = logger.clone();
+                let mut abort = AbortOnDrop::new(rt_handle.clone(), stream_id);
+                Box::new(
+                    rx.then(move |res| -> Result<Response<Body>, futures::Canceled> {
+                        abort.disarm();
+                        if body_exceeded.load(Ordering::SeqCst) {
+                            return Ok(simple_response(StatusCode::PAYLOAD_TOO_LARGE, None));
+                        }
+                        let res = match res {
+                            Ok(res) => res,
+                            Err(_) => {
+                                // the runtime gave up on this request, e.g. it ran over its time limit
+                                slog_error!(rx_logger, "runtime dropped the response");
+                                return Ok(simple_response(
+                                    StatusCode::INTERNAL_SERVER_ERROR,
+                                    None,
+                                ));
+                            }
+                        };
+                        if let Some(queue) = res.websocket {
+                            return Ok(match (websocket_key, on_upgrade) {
+                                (Some(key), Some(upgrade))
+                                    if res.status == StatusCode::SWITCHING_PROTOCOLS =>
+                                {
+                                    accept_upgrade(rt_handle, stream_id, upgrade, queue);
+                                    switching_protocols(res.headers, &key)
+                                }
+                                _ => {
+                                    let refused = future::err::<Upgraded, _>(
+                                        "not a websocket upgrade request",
+                                    );
+                                    accept_upgrade(rt_handle, stream_id, refused, queue);
+                                    simple_response(
+                                        StatusCode::BAD_REQUEST,
+                                        Some("expected a websocket upgrade request"),
+                                    )
+                                }
+                            });
+                        }
+
+                        if let Some((kind, max)) = response_over_limit(&limits, &res) {
+                            report.exceeded(kind, max);
+                            if let Some(JsBody::Stream(_)) = res.body {
+                                cancel_response_stream(&rt_handle, stream_id);
+                            }
+                            return Ok(simple_response(StatusCode::BAD_GATEWAY, None));
+                        }
+
+                        let mut pending = cache_key.and_then(|key| {
+                            PendingEntry::new(
+                                key,
+                                cache_conf.max_body_bytes,
+                                &req_method,
+                                &req_headers,
+                                res.status,
+                                &res.headers,
+                            )
+                        });
+
+                        let (mut parts, mut body) = Response::<Body>::default().into_parts();
+                        parts.headers = res.headers;
+                        parts.status = res.status;
+                        if cache_conf.enabled {
+                            http_cache::strip_surrogate_headers(&mut parts.headers);
+                        }
+
+                        let encoding = if compression::compressible(parts.status, &parts.headers) {
+                            compression::negotiate(compression, accept_encoding.as_ref())
+                        } else {
+                            None
+                        };
+
+                        if let Some(js_body) = res.body {
+                            body = match js_body {
+                                JsBody::Stream(s) => {
+                                    let heartbeat = if is_event_stream(&parts.headers) {
+                                        parts.headers.remove(header::CONTENT_LENGTH);
+                                        if !parts.headers.contains_key(header::CACHE_CONTROL) {
+                                            parts.headers.insert(
+                                                header::CACHE_CONTROL,
+                                                header::HeaderValue::from_static("no-cache"),
+                                            );
+                                        }
+                                        Some(sse_heartbeat)
+                                    } else {
+                                        None
+                                    };
+                                    let stream = ResponseStream::new(
+                                        CachingStream::new(
+                                            s.inspect(move |v| {
+                                                outbound_data.inc_by(v.len() as i64);
+                                            }),
+                                            pending.take(),
+                                            {
+                                                let rt_handle = rt_handle.clone();
+                                                move |pending, body| pending.store(&rt_handle, body)
+                                            },
+                                        ),
+                                        rt_handle.clone(),
+                                        stream_id,
+                                        heartbeat,
+                                        limits
+                                            .max_response_body_bytes
+                                            .map(|max| (max, report.clone())),
+                                    );
+                                    encode_stream(&mut parts.headers, encoding, stream)
+                                }
+                                JsBody::Static(b) => {
+                                    outbound_data.inc_by(b.len() as i64);
+                                    if let Some(pending) = pending.take() {
+                                        pending.store(&rt_handle, b.clone());
+                                    }
+                                    match encoding.filter(|_| b.len() >= MIN_COMPRESS_BYTES) {
+                                        // compressed as hyper polls the body,
+                                        // not on the response future
+                                        Some(encoding) => {
+                                            compression::set_encoding_headers(
+                                                &mut parts.headers,
+                                                encoding,
+                                            );
+                                            Body::wrap_stream(
+                                                compression::encode_later(encoding, b)
+                                                    .into_stream(),
+                                            )
+                                        }
+                                        None => Body::from(b),
+                                    }
+                                }
+                                _ => unimplemented!(),
+                            };
+                        }
+                        if let Some(pending) = pending {
+                            pending.store(&rt_handle, vec![]);
+                        }
+
+                        Ok(Response::from_parts(parts, body))
+                    }),
+                )
+            }
+            _ => unimplemented!(),
+        }
+    };
+
+    let res: BoxedResponseFuture = match lookup {
+        Some(lookup) => {
+            let cache_logger = request_logger.clone();
+            let hits =
+                HTTP_CACHE_HITS_TOTAL.with_label_values(&[rt_name.as_str(), rt_version.as_str()]);
+            let misses =
+                HTTP_CACHE_MISSES_TOTAL.with_label_values(&[rt_name.as_str(), rt_version.as_str()]);
+            Box::new(lookup.then(move |hit| -> BoxedResponseFuture {
+                match hit {
+                    Ok(Some(mut cached)) => {
+                        hits.inc();
+                        let outcome = http_range::evaluate(
+                            &hit_method,
+                            &hit_req_headers,
+                            cached.status,
+                            &cached.headers,
+                            cached.size,
+                        );
+                        outcome.apply(&mut cached.status, &mut cached.headers);
+                        let encoding = if compression::compressible(cached.status, &cached.headers)
+                        {
+                            compression::negotiate(compression, hit_accept_encoding.as_ref())
+                        } else {
+                            None
+                        };
+                        Box::new(future::ok(cached_response(
+                            cached,
+                            &outcome,
+                            encoding,
+                            hit_outbound_data,
+                        )))
+                    }
+                    Ok(None) => {
+                        misses.inc();
+                        dispatch()
+                    }
+                    Err(e) => {
+                        slog_error!(cache_logger, "error looking up http cache: {:?}", e);
+                        misses.inc();
+                        dispatch()
+                    }
+                }
+            }))
+        }
+        None => dispatch(),
+    };
+    wrap_future(res, request_info, request_logger, namever)
+}
+
+/// Compresses a streamed body on its way out, if the client accepts it.
+fn encode_stream<S>(headers: &mut HeaderMap, encoding: Option<Encoding>, stream: S) -> Body
+where
+    S: Stream<Item = Vec<u8>, Error = io::Error> + Send + 'static,
+{
+    match encoding {
+        Some(encoding) => {
+            compression::set_encoding_headers(headers, encoding);
+            Body::wrap_stream(EncodedStream::new(stream, encoding))
+        }
+        None => Body::wrap_stream(stream),
+    }
+}
+
+fn cached_response(
+    cached: CachedResponse,
+    outcome: &Outcome,
+    encoding: Option<Encoding>,
+    outbound_data: IntCounter,
+) -> Response<Body> {
+    let mut headers = cached.headers;
+    if !outcome.has_body() {
+        let mut res = Response::new(Body::empty());
+        *res.status_mut() = cached.status;
+        *res.headers_mut() = headers;
+        return res;
     }
+    let stream = SliceStream::new(cached.body, outcome.range())
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("{:?}", e)))
+        .inspect(move |v| outbound_data.inc_by(v.len() as i64));
+    let mut res = Response::new(encode_stream(&mut headers, encoding, stream));
+    *res.status_mut() = cached.status;
+    *res.headers_mut() = headers;
+    res
 }
 
 fn future_response(
@@ -228,6 +521,325 @@ fn simple_response(status: StatusCode, body: Option<&str>) -> Response<Body> {
         .unwrap()
 }
 
+/// Aborts the request in JS if hyper drops its response future, meaning the
+/// client went away before JS responded.
+struct AbortOnDrop {
+    rt: RuntimeHandle,
+    id: u32,
+    armed: bool,
+}
+
+impl AbortOnDrop {
+    fn new(rt: RuntimeHandle, id: u32) -> Self {
+        AbortOnDrop {
+            rt,
+            id,
+            armed: true,
+        }
+    }
+
+    fn disarm(&mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        if self.armed {
+            spawn_abort(&self.rt, self.id);
+        }
+    }
+}
+
+// Aborts request `id` from the runtime's thread. Runs from drop handlers, so
+// it doesn't panic.
+fn spawn_abort(rt: &RuntimeHandle, id: u32) {
+    rt.with(|rt| {
+        let handle = rt.handle();
+        let spawned = rt.try_spawn(future::lazy(move || {
+            handle.with(|rt| rt.abort_request(id));
+            Ok(())
+        }));
+        if !spawned {
+            debug!("could not abort request {}", id);
+        }
+    });
+}
+
+/// Counts and logs a request or response going over one of the app's limits.
+#[derive(Clone)]
+struct LimitReport {
+    logger: slog::Logger,
+    name: String,
+    version: String,
+}
+
+impl LimitReport {
+    fn exceeded(&self, kind: &str, max: u64) {
+        HTTP_LIMIT_EXCEEDED_TOTAL
+            .with_label_values(&[self.name.as_str(), self.version.as_str(), kind])
+            .inc();
+        slog_warn!(self.logger, "{} over the limit of {} bytes", kind, max);
+    }
+}
+
+// Roughly what the headers take on the wire: name, value, ": " and CRLF.
+fn headers_size(headers: &HeaderMap) -> u64 {
+    headers
+        .iter()
+        .map(|(name, value)| (name.as_str().len() + value.len() + 4) as u64)
+        .sum()
+}
+
+fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+/// The limit a request breaks before any of its body is read. Chunked bodies
+/// are counted as they stream in.
+fn request_over_limit(
+    limits: &HttpLimits,
+    headers: &HeaderMap,
+) -> Option<(&'static str, u64, StatusCode)> {
+    if let Some(max) = limits.max_header_bytes {
+        if headers_size(headers) > max {
+            return Some((
+                "request_header",
+                max,
+                StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+            ));
+        }
+    }
+    if let Some(max) = limits.max_request_body_bytes {
+        if content_length(headers).map_or(false, |len| len > max) {
+            return Some(("request_body", max, StatusCode::PAYLOAD_TOO_LARGE));
+        }
+    }
+    None
+}
+
+/// The limit a response from JS breaks before it's sent. Streamed bodies
+/// without a content length are cut off once they go over.
+fn response_over_limit(limits: &HttpLimits, res: &JsHttpResponse) -> Option<(&'static str, u64)> {
+    if let Some(max) = limits.max_header_bytes {
+        if headers_size(&res.headers) > max {
+            return Some(("response_header", max));
+        }
+    }
+    if let Some(max) = limits.max_response_body_bytes {
+        let len = match res.body {
+            Some(JsBody::Static(ref b)) => Some(b.len() as u64),
+            _ => content_length(&res.headers),
+        };
+        if len.map_or(false, |len| len > max) {
+            return Some(("response_body", max));
+        }
+    }
+    None
+}
+
+// Tells JS to stop producing a response body nobody will read. Runs from
+// drop handlers, so it doesn't panic.
+fn cancel_response_stream(rt: &RuntimeHandle, id: u32) {
+    rt.with(|rt| {
+        if let Ok(mut streams) = rt.streams.lock() {
+            streams.remove(&id);
+        }
+        let handle = rt.handle();
+        let spawned = rt.try_spawn(future::lazy(move || {
+            handle.with(|rt| send_stream_cancel(rt.ptr, id));
+            Ok(())
+        }));
+        if !spawned {
+            debug!("could not cancel response stream {}", id);
+        }
+    });
+}
+
+fn is_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map_or(false, |v| v.trim_start().starts_with("text/event-stream"))
+}
+
+/// A response body streamed from JS. Idle event streams get a heartbeat so
+/// intermediaries keep the connection open, and JS is told to stop producing
+/// if the client goes away or the body runs over its limit before the end.
+struct ResponseStream<S> {
+    inner: S,
+    rt: RuntimeHandle,
+    id: u32,
+    heartbeat: Option<(Delay, Duration)>,
+    limit: Option<(u64, LimitReport)>,
+    sent: u64,
+    // last bytes sent, a heartbeat may only go out between two events
+    tail: Vec<u8>,
+    done: bool,
+}
+
+impl<S> ResponseStream<S> {
+    fn new(
+        inner: S,
+        rt: RuntimeHandle,
+        id: u32,
+        heartbeat: Option<Duration>,
+        limit: Option<(u64, LimitReport)>,
+    ) -> Self {
+        ResponseStream {
+            inner,
+            rt,
+            id,
+            heartbeat: heartbeat.map(|period| (Delay::new(Instant::now() + period), period)),
+            limit,
+            sent: 0,
+            tail: vec![],
+            done: false,
+        }
+    }
+
+    fn reset_heartbeat(&mut self) {
+        if let Some((ref mut delay, period)) = self.heartbeat {
+            delay.reset(Instant::now() + period);
+        }
+    }
+
+    fn track_tail(&mut self, chunk: &[u8]) {
+        self.tail
+            .extend_from_slice(&chunk[chunk.len().saturating_sub(4)..]);
+        let excess = self.tail.len().saturating_sub(4);
+        self.tail.drain(..excess);
+    }
+
+    // Whether everything sent so far ends with a blank line, closing an event.
+    fn between_events(&self) -> bool {
+        self.tail.is_empty()
+            || self.tail.ends_with(b"\n\n")
+            || self.tail.ends_with(b"\r\r")
+            || self.tail.ends_with(b"\r\n\r\n")
+    }
+}
+
+impl<S> Stream for ResponseStream<S>
+where
+    S: Stream<Item = Vec<u8>, Error = ()>,
+{
+    type Item = Vec<u8>;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, io::Error> {
+        match self.inner.poll() {
+            Ok(Async::Ready(Some(chunk))) => {
+                self.sent += chunk.len() as u64;
+                if let Some((max, ref report)) = self.limit {
+                    if self.sent > max {
+                        report.exceeded("response_body", max);
+                        return Err(io::Error::new(
+                            io::ErrorKind::Other,
+                            "response body too large",
+                        ));
+                    }
+                }
+                if self.heartbeat.is_some() {
+                    self.track_tail(&chunk);
+                }
+                self.reset_heartbeat();
+                return Ok(Async::Ready(Some(chunk)));
+            }
+            Ok(Async::Ready(None)) => {
+                self.done = true;
+                return Ok(Async::Ready(None));
+            }
+            Ok(Async::NotReady) => {}
+            Err(_) => {
+                self.done = true;
+                return Err(io::Error::new(
+                    io::ErrorKind::Interrupted,
+                    "interrupted stream",
+                ));
+            }
+        }
+        let beat = match self.heartbeat {
+            Some((ref mut delay, _)) => match delay.poll() {
+                Ok(Async::Ready(())) => true,
+                Ok(Async::NotReady) => false,
+                Err(e) => {
+                    error!("sse heartbeat timer error: {}", e);
+                    self.heartbeat = None;
+                    false
+                }
+            },
+            None => false,
+        };
+        if beat {
+            self.reset_heartbeat();
+            if self.between_events() {
+                return Ok(Async::Ready(Some(SSE_HEARTBEAT.to_vec())));
+            }
+            // mid-event, the next beat is due a period later
+            if let Some((ref mut delay, _)) = self.heartbeat {
+                let _ = delay.poll();
+            }
+        }
+        Ok(Async::NotReady)
+    }
+}
+
+impl<S> Drop for ResponseStream<S> {
+    fn drop(&mut self) {
+        if !self.done {
+            cancel_response_stream(&self.rt, self.id);
+        }
+    }
+}
+
+/// The `Sec-WebSocket-Key` of a request asking for a websocket upgrade.
+fn websocket_key(headers: &HeaderMap) -> Option<header::HeaderValue> {
+    let has_token = |name: header::HeaderName, token: &str| {
+        headers.get_all(name).iter().any(|v| {
+            v.to_str()
+                .map(|v| v.split(',').any(|t| t.trim().eq_ignore_ascii_case(token)))
+                .unwrap_or(false)
+        })
+    };
+    if has_token(header::CONNECTION, "upgrade") && has_token(header::UPGRADE, "websocket") {
+        headers.get(header::SEC_WEBSOCKET_KEY).cloned()
+    } else {
+        None
+    }
+}
+
+fn websocket_accept(key: &[u8]) -> String {
+    let mut sha = Sha1::default();
+    sha.input(key);
+    sha.input(WEBSOCKET_GUID.as_bytes());
+    base64::encode(&sha.result())
+}
+
+fn switching_protocols(headers: HeaderMap, key: &header::HeaderValue) -> Response<Body> {
+    let mut res = Response::new(Body::empty());
+    *res.status_mut() = StatusCode::SWITCHING_PROTOCOLS;
+    *res.headers_mut() = headers;
+    let accept = websocket_accept(key.as_bytes());
+    let res_headers = res.headers_mut();
+    res_headers.insert(
+        header::UPGRADE,
+        header::HeaderValue::from_static("websocket"),
+    );
+    res_headers.insert(
+        header::CONNECTION,
+        header::HeaderValue::from_static("Upgrade"),
+    );
+    res_headers.insert(
+        header::SEC_WEBSOCKET_ACCEPT,
+        header::HeaderValue::from_str(&accept).unwrap(),
+    );
+    res
+}
+
 fn set_server_header(mut res: Response<Body>) -> Response<Body> {
     res.headers_mut()
         .insert(header::SERVER, SERVER_HEADER_VALUE.clone());
@@ -289,89 +901,198 @@ where
     }))
 }
 
-// static APPLICATION_X_JAVASCRIPT: &str = "application/x-javascript";
-// static APPLICATION_VND_MS_FONTOBJECT: &str = "application/vnd.ms-fontobject";
-// static APPLICATION_X_FONT_OPENTYPE: &str = "application/x-font-opentype";
-// static APPLICATION_X_FONT_TRUETYPE: &str = "application/x-font-truetype";
-// static APPLICATION_X_FONT_TTF: &str = "application/x-font-ttf";
-// static FONT_EOT: &str = "font/eot";
-// static FONT_OPENTYPE: &str = "font/opentype";
-// static FONT_OTF: &str = "font/otf";
-// static IMAGE_VND_MICROSOFT_ICON: &str = "image/vnd.microsoft.icon";
-
-// static OTHER_ALLOWED_MIME_TYPES: [&str; 9] = [
-//     APPLICATION_X_JAVASCRIPT,
-//     APPLICATION_VND_MS_FONTOBJECT,
-//     APPLICATION_X_FONT_OPENTYPE,
-//     APPLICATION_X_FONT_TRUETYPE,
-//     APPLICATION_X_FONT_TTF,
-//     FONT_EOT,
-//     FONT_OPENTYPE,
-//     FONT_OTF,
-//     IMAGE_VND_MICROSOFT_ICON,
-// ];
-
-// fn gunzip(chunk: hyper::Chunk) -> Result<Vec<u8>, FlyError> {
-//     let bytes = chunk.into_bytes();
-//     let mut v = vec![];
-//     let mut gz = GzDecoder::new(&bytes[..]);
-//     match gz.read_to_end(&mut v) {
-//         Ok(_) => Ok(v),
-//         Err(e) => Err(format!("gzip decode error: {}", e).into()),
-//     }
-// }
-
-// fn gzip<B>(bytes: B) -> Result<Vec<u8>, io::Error>
-// where
-//     B: AsRef<[u8]>,
-// {
-//     let mut v = vec![];
-//     let mut gz = GzEncoder::new(bytes.as_ref(), Compression::default());
-//     gz.read_to_end(&mut v)?;
-//     Ok(v)
-// }
-
-// fn contains_gzip(header_value: Option<&header::HeaderValue>) -> bool {
-//     if let Some(enc) = header_value {
-//         if let Ok(encstr) = enc.to_str() {
-//             if encstr.contains("gzip") {
-//                 true
-//             } else {
-//                 false
-//             }
-//         } else {
-//             false
-//         }
-//     } else {
-//         false
-//     }
-// }
-
-// fn gzippable_content_type(header_value: Option<&header::HeaderValue>) -> bool {
-//     if let Some(maybe_content_type) = header_value {
-//         if let Ok(content_type) = maybe_content_type.to_str() {
-//             if let Ok(m) = mime::Mime::from_str(content_type) {
-//                 if m.type_() == mime::TEXT {
-//                     true
-//                 } else if m.type_() == mime::APPLICATION {
-//                     match m.subtype() {
-//                         mime::JAVASCRIPT | mime::JSON | mime::XML => true,
-//                         _ => false,
-//                     }
-//                 } else if m.type_() == mime::IMAGE && m.subtype() == mime::SVG {
-//                     true
-//                 } else {
-//                     false
-//                 }
-//             } else if OTHER_ALLOWED_MIME_TYPES.contains(&content_type) {
-//                 true
-//             } else {
-//                 false
-//             }
-//         } else {
-//             false
-//         }
-//     } else {
-//         false
-//     }
-// }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_runtime;
+    use futures::future::Either;
+    use futures::stream;
+    use futures::sync::mpsc;
+    use std::fmt;
+    use tokio::runtime::current_thread;
+
+    #[test]
+    fn test_websocket_accept() {
+        // from RFC 6455, section 1.3
+        assert_eq!(
+            websocket_accept(b"dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn test_websocket_key() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONNECTION, "keep-alive, Upgrade".parse().unwrap());
+        headers.insert(header::UPGRADE, "WebSocket".parse().unwrap());
+        assert_eq!(websocket_key(&headers), None);
+
+        headers.insert(header::SEC_WEBSOCKET_KEY, "abc".parse().unwrap());
+        assert_eq!(websocket_key(&headers).unwrap(), "abc");
+
+        headers.insert(header::UPGRADE, "h2c".parse().unwrap());
+        assert_eq!(websocket_key(&headers), None);
+    }
+
+    #[test]
+    fn test_request_over_limit() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_LENGTH, "2048".parse().unwrap());
+        assert_eq!(headers_size(&headers), 22);
+        assert_eq!(request_over_limit(&HttpLimits::default(), &headers), None);
+
+        let limits = HttpLimits {
+            max_request_body_bytes: Some(1024),
+            ..Default::default()
+        };
+        assert_eq!(
+            request_over_limit(&limits, &headers),
+            Some(("request_body", 1024, StatusCode::PAYLOAD_TOO_LARGE))
+        );
+
+        let limits = HttpLimits {
+            max_header_bytes: Some(16),
+            ..Default::default()
+        };
+        assert_eq!(
+            request_over_limit(&limits, &headers).map(|(_, _, status)| status),
+            Some(StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE)
+        );
+    }
+
+    #[test]
+    fn test_response_over_limit() {
+        let limits = HttpLimits {
+            max_response_body_bytes: Some(4),
+            ..Default::default()
+        };
+        let res = |body: &[u8]| JsHttpResponse {
+            headers: HeaderMap::new(),
+            status: StatusCode::OK,
+            body: Some(JsBody::Static(body.to_vec())),
+            websocket: None,
+        };
+        assert_eq!(response_over_limit(&limits, &res(b"fine")), None);
+        assert_eq!(
+            response_over_limit(&limits, &res(b"too big")),
+            Some(("response_body", 4))
+        );
+    }
+
+    fn next<S: Stream>(el: &mut current_thread::Runtime, stream: S) -> (Option<S::Item>, S)
+    where
+        S::Error: fmt::Debug,
+    {
+        el.block_on(stream.into_future())
+            .map_err(|(e, _)| e)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_heartbeat_between_events() {
+        let mut el = current_thread::Runtime::new().unwrap();
+        let (mut tx, rx) = mpsc::channel::<Vec<u8>>(4);
+        let period = Duration::from_millis(20);
+        let stream = ResponseStream::new(rx, RuntimeHandle::default(), 1, Some(period), None);
+
+        tx.try_send(b"data: a\n\n".to_vec()).unwrap();
+        let (item, stream) = next(&mut el, stream);
+        assert_eq!(item.unwrap(), b"data: a\n\n".to_vec());
+        let (item, stream) = next(&mut el, stream);
+        assert_eq!(item.unwrap(), SSE_HEARTBEAT.to_vec());
+
+        // no heartbeat in the middle of an event
+        tx.try_send(b"data: b\n".to_vec()).unwrap();
+        let (item, stream) = next(&mut el, stream);
+        assert_eq!(item.unwrap(), b"data: b\n".to_vec());
+        let idle = el
+            .block_on(
+                stream
+                    .into_future()
+                    .select2(Delay::new(Instant::now() + period * 4)),
+            )
+            .map_err(|_| ())
+            .unwrap();
+        let stream = match idle {
+            Either::B((_, pending)) => pending.into_inner().unwrap(),
+            Either::A(((item, _), _)) => panic!("unexpected chunk {:?}", item),
+        };
+
+        tx.try_send(b"\n".to_vec()).unwrap();
+        let (item, stream) = next(&mut el, stream);
+        assert_eq!(item.unwrap(), b"\n".to_vec());
+        let (item, _) = next(&mut el, stream);
+        assert_eq!(item.unwrap(), SSE_HEARTBEAT.to_vec());
+    }
+
+    #[test]
+    fn test_response_stream_cancels_on_drop() {
+        let mut rt = test_runtime();
+        let open = |id: u32| {
+            let (tx, _) = mpsc::channel::<Vec<u8>>(1);
+            rt.streams.lock().unwrap().insert(id, tx);
+        };
+
+        // dropped before the end: JS stops producing
+        open(1);
+        drop(ResponseStream::new(
+            stream::empty::<Vec<u8>, ()>(),
+            rt.handle(),
+            1,
+            None,
+            None,
+        ));
+        assert!(!rt.streams.lock().unwrap().contains_key(&1));
+
+        // read to the end: nothing to cancel
+        open(2);
+        let mut done =
+            ResponseStream::new(stream::empty::<Vec<u8>, ()>(), rt.handle(), 2, None, None);
+        assert_eq!(done.poll().unwrap(), Async::Ready(None));
+        drop(done);
+        assert!(rt.streams.lock().unwrap().contains_key(&2));
+
+        // outliving its runtime is fine
+        let orphan =
+            ResponseStream::new(stream::empty::<Vec<u8>, ()>(), rt.handle(), 3, None, None);
+        rt.dispose();
+        drop(orphan);
+    }
+
+    #[test]
+    fn test_abort_on_drop() {
+        let mut rt = test_runtime();
+        let pending = |id: u32| {
+            let (tx, _) = futures::sync::oneshot::channel();
+            rt.responses.lock().unwrap().insert(id, tx);
+        };
+        let waiting = |id: u32| {
+            let deadline = Instant::now() + Duration::from_secs(2);
+            while rt.responses.lock().unwrap().contains_key(&id) {
+                if Instant::now() > deadline {
+                    return true;
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            false
+        };
+
+        // the client went away before JS responded
+        pending(1);
+        drop(AbortOnDrop::new(rt.handle(), 1));
+        assert!(!waiting(1));
+
+        // JS responded
+        pending(2);
+        let mut abort = AbortOnDrop::new(rt.handle(), 2);
+        abort.disarm();
+        drop(abort);
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(rt.responses.lock().unwrap().contains_key(&2));
+
+        // outliving its runtime is fine
+        let orphan = AbortOnDrop::new(rt.handle(), 3);
+        rt.dispose();
+        drop(orphan);
+    }
+}
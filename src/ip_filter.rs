@@ -0,0 +1,322 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use futures::Future;
+use hyper::client::connect::dns::{GaiResolver, Name, Resolve};
+
+use crate::settings::IpFilterConfig;
+
+lazy_static! {
+    // every resolver shares one getaddrinfo thread pool
+    static ref GAI_RESOLVER: Mutex<Option<GaiResolver>> = Mutex::new(None);
+}
+
+/// Loopback, link-local, private and otherwise non-routable ranges.
+pub const PRIVATE_CIDRS: &[&str] = &[
+    "0.0.0.0/8",
+    "10.0.0.0/8",
+    "100.64.0.0/10",
+    "127.0.0.0/8",
+    "169.254.0.0/16",
+    "172.16.0.0/12",
+    "192.0.0.0/24",
+    "192.168.0.0/16",
+    "198.18.0.0/15",
+    "224.0.0.0/4",
+    "240.0.0.0/4",
+    "::/128",
+    "::1/128",
+    "fc00::/7",
+    "fe80::/10",
+    "ff00::/8",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.addr, canonical(ip)) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => mask_eq(&net.octets(), &ip.octets(), self.prefix),
+            (IpAddr::V6(net), IpAddr::V6(ip)) => mask_eq(&net.octets(), &ip.octets(), self.prefix),
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (addr, prefix) = match s.find('/') {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|e| format!("invalid address in '{}': {}", s, e))?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(p) => p
+                .parse::<u8>()
+                .ok()
+                .filter(|p| *p <= max)
+                .ok_or_else(|| format!("invalid prefix length in '{}'", s))?,
+            None => max,
+        };
+        Ok(Cidr { addr, prefix })
+    }
+}
+
+// IPv4-mapped IPv6 addresses are checked against the IPv4 ranges.
+fn canonical(ip: &IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) if v6.segments()[..6] == [0, 0, 0, 0, 0, 0xffff] => {
+            let o = v6.octets();
+            IpAddr::from([o[12], o[13], o[14], o[15]])
+        }
+        _ => *ip,
+    }
+}
+
+fn mask_eq(net: &[u8], ip: &[u8], prefix: u8) -> bool {
+    let full = (prefix / 8) as usize;
+    if net[..full] != ip[..full] {
+        return false;
+    }
+    let rem = prefix % 8;
+    if rem == 0 {
+        return true;
+    }
+    let mask = 0xffu8 << (8 - rem);
+    net[full] & mask == ip[full] & mask
+}
+
+/// A deny list of address ranges outbound connections may not reach.
+#[derive(Debug, Clone, Default)]
+pub struct IpFilter {
+    deny: Vec<Cidr>,
+}
+
+impl IpFilter {
+    pub fn new(deny: Vec<Cidr>) -> Self {
+        IpFilter { deny }
+    }
+
+    pub fn from_config(conf: &IpFilterConfig) -> Self {
+        let mut filter = IpFilter::default();
+        if conf.deny_private {
+            filter.extend(PRIVATE_CIDRS.iter().cloned());
+        }
+        filter.extend(conf.deny.iter().map(String::as_str));
+        if let Some(ref path) = conf.blacklist_ip_path {
+            if let Err(e) = filter.load_file(path) {
+                error!("could not load ip blacklist {}: {}", path, e);
+            }
+        }
+        filter
+    }
+
+    /// Adds every range that parses, logging the rest.
+    pub fn extend<'a, I: IntoIterator<Item = &'a str>>(&mut self, cidrs: I) {
+        for s in cidrs {
+            match s.parse() {
+                Ok(cidr) => self.deny.push(cidr),
+                Err(e) => warn!("ignoring ip blacklist entry: {}", e),
+            }
+        }
+    }
+
+    /// Loads one range per line. Blank lines and `#` comments are skipped.
+    pub fn load_file(&mut self, path: &str) -> io::Result<()> {
+        let contents = fs::read_to_string(path)?;
+        self.extend(
+            contents
+                .lines()
+                .map(|l| l.split('#').next().unwrap().trim())
+                .filter(|l| !l.is_empty()),
+        );
+        Ok(())
+    }
+
+    pub fn is_blocked(&self, ip: &IpAddr) -> bool {
+        self.deny.iter().any(|c| c.contains(ip))
+    }
+
+    pub fn check(&self, host: &str, ip: &IpAddr) -> Result<(), AddressBlocked> {
+        if self.is_blocked(ip) {
+            Err(AddressBlocked {
+                host: host.to_string(),
+                ip: *ip,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AddressBlocked {
+    pub host: String,
+    pub ip: IpAddr,
+}
+
+impl fmt::Display for AddressBlocked {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.host == self.ip.to_string() {
+            write!(f, "connecting to {} is not allowed", self.ip)
+        } else {
+            write!(
+                f,
+                "connecting to {} ({}) is not allowed",
+                self.host, self.ip
+            )
+        }
+    }
+}
+
+impl Error for AddressBlocked {
+    fn description(&self) -> &str {
+        "address blocked"
+    }
+}
+
+impl From<AddressBlocked> for io::Error {
+    fn from(err: AddressBlocked) -> io::Error {
+        io::Error::new(io::ErrorKind::PermissionDenied, err)
+    }
+}
+
+/// Digs an `AddressBlocked` out of a failed connection.
+pub fn blocked_cause(err: &hyper::Error) -> Option<&AddressBlocked> {
+    if !err.is_connect() {
+        return None;
+    }
+    blocked_io(err.cause2()?.downcast_ref::<io::Error>()?)
+}
+
+/// Digs an `AddressBlocked` out of an error returned by `FilteringResolver`.
+pub fn blocked_io(err: &io::Error) -> Option<&AddressBlocked> {
+    err.get_ref()?.downcast_ref::<AddressBlocked>()
+}
+
+/// Resolves names with getaddrinfo, dropping addresses the filter blocks.
+/// Fails if every address was blocked.
+#[derive(Clone)]
+pub struct FilteringResolver {
+    inner: GaiResolver,
+    filter: Arc<IpFilter>,
+}
+
+impl FilteringResolver {
+    /// The shared thread pool is sized by whichever resolver is built first.
+    pub fn new(threads: usize, filter: Arc<IpFilter>) -> Self {
+        let mut gai = match GAI_RESOLVER.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let inner = gai.get_or_insert_with(|| GaiResolver::new(threads)).clone();
+        FilteringResolver { inner, filter }
+    }
+
+    /// Also applies to IP literals, which skip the resolver.
+    pub fn filter(&self) -> &Arc<IpFilter> {
+        &self.filter
+    }
+}
+
+impl Resolve for FilteringResolver {
+    type Addrs = ::std::vec::IntoIter<IpAddr>;
+    type Future = Box<Future<Item = Self::Addrs, Error = io::Error> + Send>;
+
+    fn resolve(&self, name: Name) -> Self::Future {
+        let host = name.as_str().to_string();
+        let filter = self.filter.clone();
+        Box::new(self.inner.resolve(name).and_then(move |addrs| {
+            let mut allowed = vec![];
+            let mut blocked = None;
+            for ip in addrs {
+                match filter.check(&host, &ip) {
+                    Ok(()) => allowed.push(ip),
+                    Err(e) => blocked = Some(e),
+                }
+            }
+            match blocked {
+                Some(e) if allowed.is_empty() => Err(e.into()),
+                _ => Ok(allowed.into_iter()),
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_cidr_contains() {
+        let cidr: Cidr = "172.16.0.0/12".parse().unwrap();
+        assert!(cidr.contains(&ip("172.16.0.1")));
+        assert!(cidr.contains(&ip("172.31.255.255")));
+        assert!(!cidr.contains(&ip("172.32.0.0")));
+        assert!(!cidr.contains(&ip("::1")));
+
+        let single: Cidr = "1.2.3.4".parse().unwrap();
+        assert!(single.contains(&ip("1.2.3.4")));
+        assert!(!single.contains(&ip("1.2.3.5")));
+
+        assert!("10.0.0.0/33".parse::<Cidr>().is_err());
+        assert!("nope/8".parse::<Cidr>().is_err());
+    }
+
+    #[test]
+    fn test_private_ranges() {
+        let mut filter = IpFilter::default();
+        filter.extend(PRIVATE_CIDRS.iter().cloned());
+        for blocked in &[
+            "127.0.0.1",
+            "10.1.2.3",
+            "192.168.1.1",
+            "169.254.169.254",
+            "::1",
+            "fe80::1",
+            "fd00::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(filter.is_blocked(&ip(blocked)), "{} not blocked", blocked);
+        }
+        for allowed in &["1.1.1.1", "172.32.0.1", "2606:4700::1111"] {
+            assert!(!filter.is_blocked(&ip(allowed)), "{} blocked", allowed);
+        }
+    }
+
+    #[test]
+    fn test_load_file() {
+        use std::io::Write;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            "# metadata\n169.254.169.254/32\n\n203.0.113.0/24 # docs"
+        )
+        .unwrap();
+
+        let mut filter = IpFilter::default();
+        filter.load_file(file.path().to_str().unwrap()).unwrap();
+        assert!(filter.is_blocked(&ip("169.254.169.254")));
+        assert!(filter.is_blocked(&ip("203.0.113.9")));
+        assert!(!filter.is_blocked(&ip("127.0.0.1")));
+    }
+}
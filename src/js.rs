@@ -1,13 +1,15 @@
 use crate::errors::FlyError;
+use crate::ops::websocket::SocketQueue;
 use futures::{sync::mpsc, Stream};
 use hyper::HeaderMap;
 use hyper::StatusCode;
+use std::fmt;
 use std::net::SocketAddr;
 use trust_dns as dns;
 
 pub enum JsBody {
     BoxedStream(Box<Stream<Item = Vec<u8>, Error = FlyError> + Send>),
-    Stream(mpsc::UnboundedReceiver<Vec<u8>>),
+    Stream(mpsc::Receiver<Vec<u8>>),
     Static(Vec<u8>),
 }
 
@@ -15,6 +17,9 @@ pub struct JsHttpResponse {
     pub headers: HeaderMap,
     pub status: StatusCode,
     pub body: Option<JsBody>,
+    /// Set when JS accepted a websocket upgrade: the messages it sends on
+    /// the socket, queued until the connection is upgraded.
+    pub websocket: Option<SocketQueue>,
 }
 
 pub struct JsHttpRequest {
@@ -63,3 +68,34 @@ pub struct JsDnsQuery {
     pub rr_type: dns::rr::RecordType,
     pub dns_class: dns::rr::DNSClass,
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JsFrame {
+    pub function_name: String,
+    pub filename: String,
+    pub line: u32,
+    pub col: u32,
+}
+
+/// An exception the app didn't handle, with source-mapped frames.
+#[derive(Debug, Clone)]
+pub struct JsException {
+    pub message: String,
+    pub frames: Vec<JsFrame>,
+    /// The event being handled when it was thrown, if known.
+    pub request_id: Option<u32>,
+}
+
+impl fmt::Display for JsException {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        for frame in &self.frames {
+            write!(
+                f,
+                "\n    at {} ({}:{}:{})",
+                frame.function_name, frame.filename, frame.line, frame.col
+            )?;
+        }
+        Ok(())
+    }
+}
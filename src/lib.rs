@@ -31,11 +31,21 @@ pub static BUILD_VERSION: &str = env!("BUILD_VERSION");
 
 pub mod js;
 pub mod v8env;
+pub mod compression;
+pub mod error_reporting;
 pub mod errors;
+pub mod event_clock;
+pub mod http_cache;
+pub mod http_client;
+pub mod http_range;
+pub mod ip_filter;
 pub mod msg;
 pub mod ops;
 pub mod runtime;
+pub mod runtime_lifecycle;
 pub mod runtime_permissions;
+pub mod runtime_pool;
+pub mod source_maps;
 pub mod utils;
 
 pub mod acme_store;
@@ -47,7 +57,7 @@ pub mod fs_store;
 pub mod settings;
 
 pub mod runtime_selector;
-pub use crate::runtime_selector::{RuntimeSelector, SelectorError};
+pub use crate::runtime_selector::{RuntimeLease, RuntimeSelector, SelectorError};
 
 pub mod dns_server;
 pub mod fixed_runtime_selector;
@@ -66,6 +76,7 @@ mod redis_acme;
 mod redis_cache;
 mod redis_cache_notifier;
 mod redis_fs;
+mod s3_fs;
 mod sqlite_cache;
 mod sqlite_data;
 
@@ -71,6 +71,42 @@ lazy_static! {
         &["runtime", "version"]
     )
     .unwrap();
+    pub static ref RUNTIME_TERMINATIONS_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_runtime_terminations_total",
+        "Events which exceeded their time limit and had their script terminated.",
+        &["runtime", "version", "event"]
+    )
+    .unwrap();
+    pub static ref RUNTIME_POOL_SIZE_GAUGE: IntGaugeVec = register_int_gauge_vec!(
+        "fly_runtime_pool_size",
+        "Number of isolates running each app version.",
+        &["runtime", "version"]
+    )
+    .unwrap();
+    pub static ref RUNTIME_RECYCLES_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_runtime_recycles_total",
+        "Runtimes replaced after their heap crossed the recycling threshold.",
+        &["runtime", "version"]
+    )
+    .unwrap();
+    pub static ref ERROR_REPORTS_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_error_reports_total",
+        "App errors sent to the error tracker.",
+        &["runtime", "kind"]
+    )
+    .unwrap();
+    pub static ref ERROR_REPORTS_DROPPED_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_error_reports_dropped_total",
+        "App errors not sent to the error tracker because of rate limiting.",
+        &["runtime"]
+    )
+    .unwrap();
+    pub static ref PERMISSION_DENIALS_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_permission_denials_total",
+        "Operations refused because the app lacks the capability.",
+        &["runtime", "version", "capability"]
+    )
+    .unwrap();
     pub static ref CACHE_GET_DURATION: HistogramVec = register_histogram_vec!(
         "fly_cache_get_duration_seconds",
         "Cache get duration in seconds.",
@@ -142,6 +178,24 @@ lazy_static! {
         &["type", "ns"]
     )
     .unwrap();
+    pub static ref HTTP_CACHE_HITS_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_http_cache_hits_total",
+        "Requests answered by the edge cache.",
+        &["runtime", "version"]
+    )
+    .unwrap();
+    pub static ref HTTP_CACHE_MISSES_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_http_cache_misses_total",
+        "Requests the edge cache had no response for.",
+        &["runtime", "version"]
+    )
+    .unwrap();
+    pub static ref HTTP_CACHE_BYPASSES_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_http_cache_bypasses_total",
+        "Requests the edge cache may not answer, e.g. not GET or carrying credentials.",
+        &["runtime", "version"]
+    )
+    .unwrap();
     pub static ref DATA_OUT_TOTAL: IntCounterVec = register_int_counter_vec!(
         "fly_data_out_bytes",
         "Outgoing data in bytes.",
@@ -154,12 +208,24 @@ lazy_static! {
         &["runtime", "version", "type"]
     )
     .unwrap();
+    pub static ref HTTP_LIMIT_EXCEEDED_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_http_limit_exceeded_total",
+        "Requests refused or cut off for going over a size limit.",
+        &["runtime", "version", "type"]
+    )
+    .unwrap();
     pub static ref FETCH_HTTP_REQUESTS_TOTAL: IntCounterVec = register_int_counter_vec!(
         "fly_fetch_http_requests_total",
         "Total number of fetch requests.",
         &["runtime", "version", "hostname"]
     )
     .unwrap();
+    pub static ref FETCH_CONNECTIONS_TOTAL: IntCounterVec = register_int_counter_vec!(
+        "fly_fetch_connections_total",
+        "New connections opened by fetch. The rest of the requests reused pooled connections.",
+        &["hostname"]
+    )
+    .unwrap();
     pub static ref FETCH_HEADERS_DURATION: HistogramVec = register_histogram_vec!(
         "fly_fetch_read_headers_duration_histogram_seconds",
         "Time to get headers for a fetch, by runtime, in seconds.",
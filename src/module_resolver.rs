@@ -7,6 +7,7 @@ use std::marker::Send;
 use std::clone::Clone;
 
 use std::collections::HashMap;
+use std::fmt;
 
 use serde_json;
 
@@ -66,6 +67,126 @@ pub trait ModuleResolverManager: Send + Sync {
     ) -> FlyResult<LoadedModule>;
 }
 
+/**
+ * Why an imported module couldn't be loaded. These are surfaced to the importing script as exceptions.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum ModuleLoadError {
+    UnknownReferrer {
+        specifier: String,
+        referrer_hash: i32,
+    },
+    Resolve {
+        specifier: String,
+        referrer: String,
+        message: String,
+    },
+    Compile {
+        specifier: String,
+        referrer: String,
+        /// V8's syntax error, with its location.
+        message: String,
+    },
+}
+
+impl ModuleLoadError {
+    pub fn specifier(&self) -> &str {
+        match self {
+            ModuleLoadError::UnknownReferrer { specifier, .. }
+            | ModuleLoadError::Resolve { specifier, .. }
+            | ModuleLoadError::Compile { specifier, .. } => specifier,
+        }
+    }
+
+    pub fn referrer(&self) -> String {
+        match self {
+            ModuleLoadError::UnknownReferrer { referrer_hash, .. } => {
+                format!("<unknown module #{}>", referrer_hash)
+            }
+            ModuleLoadError::Resolve { referrer, .. }
+            | ModuleLoadError::Compile { referrer, .. } => referrer.clone(),
+        }
+    }
+
+    /// Compile failures are thrown as a `SyntaxError`, everything else as an `Error`.
+    pub fn is_syntax_error(&self) -> bool {
+        match self {
+            ModuleLoadError::Compile { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for ModuleLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ModuleLoadError::UnknownReferrer {
+                specifier,
+                referrer_hash,
+            } => write!(
+                f,
+                "Cannot import '{}': importing module #{} is not loaded",
+                specifier, referrer_hash
+            ),
+            ModuleLoadError::Resolve {
+                specifier,
+                referrer,
+                message,
+            } => write!(
+                f,
+                "Cannot import '{}' from '{}': {}",
+                specifier, referrer, message
+            ),
+            ModuleLoadError::Compile {
+                specifier,
+                referrer,
+                message,
+            } => write!(
+                f,
+                "Cannot import '{}' from '{}': {}",
+                specifier, referrer, message
+            ),
+        }
+    }
+}
+
+/**
+ * Resolves and loads a module imported by an already loaded module.
+ */
+pub fn resolve_import(
+    manager: &ModuleResolverManager,
+    specifier: &str,
+    referrer_hash: i32,
+    referrer: Option<Box<LoadedModule>>,
+) -> Result<LoadedModule, ModuleLoadError> {
+    let referrer = match referrer {
+        Some(v) => v,
+        None => {
+            return Err(ModuleLoadError::UnknownReferrer {
+                specifier: specifier.to_string(),
+                referrer_hash,
+            });
+        }
+    };
+    let referrer_url = referrer.origin_url.clone();
+
+    manager
+        .resolve_module(
+            specifier.to_string(),
+            Some(RefererInfo {
+                origin_url: referrer.origin_url,
+                is_wasm: Some(referrer.loaded_source.is_wasm),
+                source_code: Some(referrer.loaded_source.source),
+                indentifier_hash: Some(referrer_hash),
+            }),
+        )
+        .map_err(|e| ModuleLoadError::Resolve {
+            specifier: specifier.to_string(),
+            referrer: referrer_url,
+            message: format!("{}", e),
+        })
+}
+
 /**
  * Parse url or join it to the working url if it's relative. working_url_str << MUST BE AN ABSOLUTE PATH.
  */
@@ -402,3 +523,95 @@ impl ModuleResolverManager for StandardModuleResolverManager {
         )))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn referrer() -> Option<Box<LoadedModule>> {
+        Some(Box::new(LoadedModule {
+            loaded_source: LoadedSourceCode {
+                is_wasm: false,
+                source_map: None,
+                source: "import './dep.js';".to_string(),
+            },
+            origin_url: "function:///app/index.js".to_string(),
+        }))
+    }
+
+    fn manager() -> StandardModuleResolverManager {
+        let resolver = FunctionModuleResolver::new(Box::new(|specifier, _| {
+            if specifier.ends_with("dep.js") {
+                Ok(ModuleSourceData {
+                    origin_url: "function:///app/dep.js".to_string(),
+                    source_loader: Box::new(JsonSecretsLoader::new(&serde_json::Value::Null)),
+                })
+            } else {
+                Err(FlyError::from(format!("no such module {}", specifier)))
+            }
+        }));
+        StandardModuleResolverManager::new(
+            vec![Box::new(resolver) as Box<ModuleResolver>],
+            Some("function:///".to_string()),
+        )
+    }
+
+    #[test]
+    fn test_resolve_import() {
+        let module = resolve_import(&manager(), "function:///app/dep.js", 1, referrer()).unwrap();
+        assert_eq!(module.origin_url, "function:///app/dep.js");
+    }
+
+    #[test]
+    fn test_resolve_import_unknown_referrer() {
+        let err = resolve_import(&manager(), "./dep.js", 42, None).unwrap_err();
+        assert_eq!(
+            err,
+            ModuleLoadError::UnknownReferrer {
+                specifier: "./dep.js".to_string(),
+                referrer_hash: 42,
+            }
+        );
+        assert_eq!(err.specifier(), "./dep.js");
+        assert_eq!(err.referrer(), "<unknown module #42>");
+        assert!(!err.is_syntax_error());
+    }
+
+    #[test]
+    fn test_resolve_import_resolver_failure() {
+        let err =
+            resolve_import(&manager(), "function:///app/missing.js", 1, referrer()).unwrap_err();
+        match err {
+            ModuleLoadError::Resolve {
+                ref specifier,
+                ref referrer,
+                ref message,
+            } => {
+                assert_eq!(specifier, "function:///app/missing.js");
+                assert_eq!(referrer, "function:///app/index.js");
+                assert!(message.contains("exausted all resolvers"));
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+        assert!(!err.is_syntax_error());
+        assert!(format!("{}", err).starts_with(
+            "Cannot import 'function:///app/missing.js' from 'function:///app/index.js'"
+        ));
+    }
+
+    #[test]
+    fn test_compile_error() {
+        let err = ModuleLoadError::Compile {
+            specifier: "./broken.js".to_string(),
+            referrer: "file:///app/index.js".to_string(),
+            message: "SyntaxError: Unexpected token } (file:///app/broken.js:3:1)".to_string(),
+        };
+        assert!(err.is_syntax_error());
+        assert_eq!(err.referrer(), "file:///app/index.js");
+        assert_eq!(
+            format!("{}", err),
+            "Cannot import './broken.js' from 'file:///app/index.js': \
+             SyntaxError: Unexpected token } (file:///app/broken.js:3:1)"
+        );
+    }
+}
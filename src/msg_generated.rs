@@ -741,11 +741,33 @@ pub enum Any {
   AcmeGetChallenge = 40,
   AcmeGetChallengeReady = 41,
   OsExit = 42,
+  StreamPull = 43,
+  CryptoImportKey = 44,
+  CryptoImportKeyReady = 45,
+  CryptoSign = 46,
+  CryptoSignReady = 47,
+  CryptoVerify = 48,
+  CryptoVerifyReady = 49,
+  CryptoCipher = 50,
+  CryptoCipherReady = 51,
+  WebSocketConnect = 52,
+  WebSocketConnectReady = 53,
+  WebSocketSend = 54,
+  WebSocketClose = 55,
+  StreamCancel = 56,
+  HttpRequestAbort = 57,
+  FetchAbort = 58,
+  FsWrite = 59,
+  FsDelete = 60,
+  FsStat = 61,
+  FsStatReady = 62,
+  FsList = 63,
+  FsListReady = 64,
 
 }
 
 const ENUM_MIN_ANY: u8 = 0;
-const ENUM_MAX_ANY: u8 = 42;
+const ENUM_MAX_ANY: u8 = 64;
 
 impl<'a> flatbuffers::Follow<'a> for Any {
   type Inner = Self;
@@ -779,7 +801,7 @@ impl flatbuffers::Push for Any {
 }
 
 #[allow(non_camel_case_types)]
-const ENUM_VALUES_ANY:[Any; 43] = [
+const ENUM_VALUES_ANY:[Any; 65] = [
   Any::NONE,
   Any::TimerStart,
   Any::TimerReady,
@@ -822,11 +844,33 @@ const ENUM_VALUES_ANY:[Any; 43] = [
   Any::ImageReady,
   Any::AcmeGetChallenge,
   Any::AcmeGetChallengeReady,
-  Any::OsExit
+  Any::OsExit,
+  Any::StreamPull,
+  Any::CryptoImportKey,
+  Any::CryptoImportKeyReady,
+  Any::CryptoSign,
+  Any::CryptoSignReady,
+  Any::CryptoVerify,
+  Any::CryptoVerifyReady,
+  Any::CryptoCipher,
+  Any::CryptoCipherReady,
+  Any::WebSocketConnect,
+  Any::WebSocketConnectReady,
+  Any::WebSocketSend,
+  Any::WebSocketClose,
+  Any::StreamCancel,
+  Any::HttpRequestAbort,
+  Any::FetchAbort,
+  Any::FsWrite,
+  Any::FsDelete,
+  Any::FsStat,
+  Any::FsStatReady,
+  Any::FsList,
+  Any::FsListReady
 ];
 
 #[allow(non_camel_case_types)]
-const ENUM_NAMES_ANY:[&'static str; 43] = [
+const ENUM_NAMES_ANY:[&'static str; 65] = [
     "NONE",
     "TimerStart",
     "TimerReady",
@@ -869,7 +913,29 @@ const ENUM_NAMES_ANY:[&'static str; 43] = [
     "ImageReady",
     "AcmeGetChallenge",
     "AcmeGetChallengeReady",
-    "OsExit"
+    "OsExit",
+    "StreamPull",
+    "CryptoImportKey",
+    "CryptoImportKeyReady",
+    "CryptoSign",
+    "CryptoSignReady",
+    "CryptoVerify",
+    "CryptoVerifyReady",
+    "CryptoCipher",
+    "CryptoCipherReady",
+    "WebSocketConnect",
+    "WebSocketConnectReady",
+    "WebSocketSend",
+    "WebSocketClose",
+    "StreamCancel",
+    "HttpRequestAbort",
+    "FetchAbort",
+    "FsWrite",
+    "FsDelete",
+    "FsStat",
+    "FsStatReady",
+    "FsList",
+    "FsListReady"
 ];
 
 pub fn enum_name_any(e: Any) -> &'static str {
@@ -917,11 +983,19 @@ pub enum ErrorKind {
   HttpCanceled = 32,
   HttpParse = 33,
   HttpOther = 34,
+  NotSupportedError = 35,
+  DataError = 36,
+  OperationError = 37,
+  InvalidAccessError = 38,
+  AddressBlocked = 39,
+  TooManyRedirects = 40,
+  RedirectNotAllowed = 41,
+  Aborted = 42,
 
 }
 
 const ENUM_MIN_ERROR_KIND: i8 = 0;
-const ENUM_MAX_ERROR_KIND: i8 = 34;
+const ENUM_MAX_ERROR_KIND: i8 = 42;
 
 impl<'a> flatbuffers::Follow<'a> for ErrorKind {
   type Inner = Self;
@@ -955,7 +1029,7 @@ impl flatbuffers::Push for ErrorKind {
 }
 
 #[allow(non_camel_case_types)]
-const ENUM_VALUES_ERROR_KIND:[ErrorKind; 35] = [
+const ENUM_VALUES_ERROR_KIND:[ErrorKind; 43] = [
   ErrorKind::NoError,
   ErrorKind::String,
   ErrorKind::NotFound,
@@ -990,11 +1064,19 @@ const ENUM_VALUES_ERROR_KIND:[ErrorKind; 35] = [
   ErrorKind::HttpClosed,
   ErrorKind::HttpCanceled,
   ErrorKind::HttpParse,
-  ErrorKind::HttpOther
+  ErrorKind::HttpOther,
+  ErrorKind::NotSupportedError,
+  ErrorKind::DataError,
+  ErrorKind::OperationError,
+  ErrorKind::InvalidAccessError,
+  ErrorKind::AddressBlocked,
+  ErrorKind::TooManyRedirects,
+  ErrorKind::RedirectNotAllowed,
+  ErrorKind::Aborted
 ];
 
 #[allow(non_camel_case_types)]
-const ENUM_NAMES_ERROR_KIND:[&'static str; 35] = [
+const ENUM_NAMES_ERROR_KIND:[&'static str; 43] = [
     "NoError",
     "String",
     "NotFound",
@@ -1029,7 +1111,15 @@ const ENUM_NAMES_ERROR_KIND:[&'static str; 35] = [
     "HttpClosed",
     "HttpCanceled",
     "HttpParse",
-    "HttpOther"
+    "HttpOther",
+    "NotSupportedError",
+    "DataError",
+    "OperationError",
+    "InvalidAccessError",
+    "AddressBlocked",
+    "TooManyRedirects",
+    "RedirectNotAllowed",
+    "Aborted"
 ];
 
 pub fn enum_name_error_kind(e: ErrorKind) -> &'static str {
@@ -1118,6 +1208,69 @@ pub fn enum_name_http_method(e: HttpMethod) -> &'static str {
   ENUM_NAMES_HTTP_METHOD[index]
 }
 
+#[allow(non_camel_case_types)]
+#[repr(i8)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RedirectMode {
+  Follow = 0,
+  Manual = 1,
+  Error = 2,
+
+}
+
+const ENUM_MIN_REDIRECT_MODE: i8 = 0;
+const ENUM_MAX_REDIRECT_MODE: i8 = 2;
+
+impl<'a> flatbuffers::Follow<'a> for RedirectMode {
+  type Inner = Self;
+  #[inline]
+  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    flatbuffers::read_scalar_at::<Self>(buf, loc)
+  }
+}
+
+impl flatbuffers::EndianScalar for RedirectMode {
+  #[inline]
+  fn to_little_endian(self) -> Self {
+    let n = i8::to_le(self as i8);
+    let p = &n as *const i8 as *const RedirectMode;
+    unsafe { *p }
+  }
+  #[inline]
+  fn from_little_endian(self) -> Self {
+    let n = i8::from_le(self as i8);
+    let p = &n as *const i8 as *const RedirectMode;
+    unsafe { *p }
+  }
+}
+
+impl flatbuffers::Push for RedirectMode {
+    type Output = RedirectMode;
+    #[inline]
+    fn push(&self, dst: &mut [u8], _rest: &[u8]) {
+        flatbuffers::emplace_scalar::<RedirectMode>(dst, *self);
+    }
+}
+
+#[allow(non_camel_case_types)]
+const ENUM_VALUES_REDIRECT_MODE:[RedirectMode; 3] = [
+  RedirectMode::Follow,
+  RedirectMode::Manual,
+  RedirectMode::Error
+];
+
+#[allow(non_camel_case_types)]
+const ENUM_NAMES_REDIRECT_MODE:[&'static str; 3] = [
+    "Follow",
+    "Manual",
+    "Error"
+];
+
+pub fn enum_name_redirect_mode(e: RedirectMode) -> &'static str {
+  let index: usize = e as usize;
+  ENUM_NAMES_REDIRECT_MODE[index]
+}
+
 #[allow(non_camel_case_types)]
 #[repr(i8)]
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -4964,15 +5117,15 @@ impl<'a: 'b, 'b> OsExitBuilder<'a, 'b> {
   }
 }
 
-pub enum BaseOffset {}
+pub enum CryptoImportKeyOffset {}
 #[derive(Copy, Clone, Debug, PartialEq)]
 
-pub struct Base<'a> {
+pub struct CryptoImportKey<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for Base<'a> {
-    type Inner = Base<'a>;
+impl<'a> flatbuffers::Follow<'a> for CryptoImportKey<'a> {
+    type Inner = CryptoImportKey<'a>;
     #[inline]
     fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
         Self {
@@ -4981,554 +5134,2734 @@ impl<'a> flatbuffers::Follow<'a> for Base<'a> {
     }
 }
 
-impl<'a> Base<'a> {
+impl<'a> CryptoImportKey<'a> {
     #[inline]
     pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        Base {
+        CryptoImportKey {
             _tab: table,
         }
     }
     #[allow(unused_mut)]
     pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
         _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args BaseArgs<'args>) -> flatbuffers::WIPOffset<Base<'bldr>> {
-      let mut builder = BaseBuilder::new(_fbb);
-      if let Some(x) = args.msg { builder.add_msg(x); }
-      if let Some(x) = args.error { builder.add_error(x); }
-      builder.add_cmd_id(args.cmd_id);
-      builder.add_msg_type(args.msg_type);
-      builder.add_error_kind(args.error_kind);
-      builder.add_sync(args.sync);
+        args: &'args CryptoImportKeyArgs<'args>) -> flatbuffers::WIPOffset<CryptoImportKey<'bldr>> {
+      let mut builder = CryptoImportKeyBuilder::new(_fbb);
+      if let Some(x) = args.named_curve { builder.add_named_curve(x); }
+      if let Some(x) = args.hash { builder.add_hash(x); }
+      if let Some(x) = args.algo { builder.add_algo(x); }
+      if let Some(x) = args.format { builder.add_format(x); }
       builder.finish()
     }
 
-    pub const VT_CMD_ID: flatbuffers::VOffsetT = 4;
-    pub const VT_SYNC: flatbuffers::VOffsetT = 6;
-    pub const VT_ERROR_KIND: flatbuffers::VOffsetT = 8;
-    pub const VT_ERROR: flatbuffers::VOffsetT = 10;
-    pub const VT_MSG_TYPE: flatbuffers::VOffsetT = 12;
-    pub const VT_MSG: flatbuffers::VOffsetT = 14;
+    pub const VT_FORMAT: flatbuffers::VOffsetT = 4;
+    pub const VT_ALGO: flatbuffers::VOffsetT = 6;
+    pub const VT_HASH: flatbuffers::VOffsetT = 8;
+    pub const VT_NAMED_CURVE: flatbuffers::VOffsetT = 10;
 
   #[inline]
-  pub fn cmd_id(&self) -> u32 {
-    self._tab.get::<u32>(Base::VT_CMD_ID, Some(0)).unwrap()
+  pub fn format(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CryptoImportKey::VT_FORMAT, None)
   }
   #[inline]
-  pub fn sync(&self) -> bool {
-    self._tab.get::<bool>(Base::VT_SYNC, Some(false)).unwrap()
+  pub fn algo(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CryptoImportKey::VT_ALGO, None)
   }
   #[inline]
-  pub fn error_kind(&self) -> ErrorKind {
-    self._tab.get::<ErrorKind>(Base::VT_ERROR_KIND, Some(ErrorKind::NoError)).unwrap()
+  pub fn hash(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CryptoImportKey::VT_HASH, None)
   }
   #[inline]
-  pub fn error(&self) -> Option<&'a str> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Base::VT_ERROR, None)
+  pub fn named_curve(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CryptoImportKey::VT_NAMED_CURVE, None)
   }
+}
+
+pub struct CryptoImportKeyArgs<'a> {
+    pub format: Option<flatbuffers::WIPOffset<&'a  str>>,
+    pub algo: Option<flatbuffers::WIPOffset<&'a  str>>,
+    pub hash: Option<flatbuffers::WIPOffset<&'a  str>>,
+    pub named_curve: Option<flatbuffers::WIPOffset<&'a  str>>,
+}
+impl<'a> Default for CryptoImportKeyArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        CryptoImportKeyArgs {
+            format: None,
+            algo: None,
+            hash: None,
+            named_curve: None,
+        }
+    }
+}
+pub struct CryptoImportKeyBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CryptoImportKeyBuilder<'a, 'b> {
   #[inline]
-  pub fn msg_type(&self) -> Any {
-    self._tab.get::<Any>(Base::VT_MSG_TYPE, Some(Any::NONE)).unwrap()
+  pub fn add_format(&mut self, format: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoImportKey::VT_FORMAT, format);
   }
   #[inline]
-  pub fn msg(&self) -> Option<flatbuffers::Table<'a>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(Base::VT_MSG, None)
+  pub fn add_algo(&mut self, algo: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoImportKey::VT_ALGO, algo);
   }
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_timer_start(&'a self) -> Option<TimerStart> {
-    if self.msg_type() == Any::TimerStart {
-      self.msg().map(|u| TimerStart::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn add_hash(&mut self, hash: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoImportKey::VT_HASH, hash);
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_timer_ready(&'a self) -> Option<TimerReady> {
-    if self.msg_type() == Any::TimerReady {
-      self.msg().map(|u| TimerReady::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn add_named_curve(&mut self, named_curve: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoImportKey::VT_NAMED_CURVE, named_curve);
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_timer_clear(&'a self) -> Option<TimerClear> {
-    if self.msg_type() == Any::TimerClear {
-      self.msg().map(|u| TimerClear::init_from_table(u))
-    } else {
-      None
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CryptoImportKeyBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CryptoImportKeyBuilder {
+      fbb_: _fbb,
+      start_: start,
     }
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_http_request(&'a self) -> Option<HttpRequest> {
-    if self.msg_type() == Any::HttpRequest {
-      self.msg().map(|u| HttpRequest::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn finish(self) -> flatbuffers::WIPOffset<CryptoImportKey<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
   }
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_http_response(&'a self) -> Option<HttpResponse> {
-    if self.msg_type() == Any::HttpResponse {
-      self.msg().map(|u| HttpResponse::init_from_table(u))
-    } else {
-      None
-    }
-  }
+pub enum CryptoImportKeyReadyOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_stream_chunk(&'a self) -> Option<StreamChunk> {
-    if self.msg_type() == Any::StreamChunk {
-      self.msg().map(|u| StreamChunk::init_from_table(u))
-    } else {
-      None
-    }
-  }
+pub struct CryptoImportKeyReady<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_http_request_start(&'a self) -> Option<HttpRequestStart> {
-    if self.msg_type() == Any::HttpRequestStart {
-      self.msg().map(|u| HttpRequestStart::init_from_table(u))
-    } else {
-      None
+impl<'a> flatbuffers::Follow<'a> for CryptoImportKeyReady<'a> {
+    type Inner = CryptoImportKeyReady<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
     }
-  }
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_fetch_http_response(&'a self) -> Option<FetchHttpResponse> {
-    if self.msg_type() == Any::FetchHttpResponse {
-      self.msg().map(|u| FetchHttpResponse::init_from_table(u))
-    } else {
-      None
+impl<'a> CryptoImportKeyReady<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        CryptoImportKeyReady {
+            _tab: table,
+        }
     }
-  }
-
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_cache_get(&'a self) -> Option<CacheGet> {
-    if self.msg_type() == Any::CacheGet {
-      self.msg().map(|u| CacheGet::init_from_table(u))
-    } else {
-      None
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args CryptoImportKeyReadyArgs<'args>) -> flatbuffers::WIPOffset<CryptoImportKeyReady<'bldr>> {
+      let mut builder = CryptoImportKeyReadyBuilder::new(_fbb);
+      if let Some(x) = args.key_type { builder.add_key_type(x); }
+      builder.add_id(args.id);
+      builder.finish()
     }
-  }
+
+    pub const VT_ID: flatbuffers::VOffsetT = 4;
+    pub const VT_KEY_TYPE: flatbuffers::VOffsetT = 6;
 
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_cache_get_ready(&'a self) -> Option<CacheGetReady> {
-    if self.msg_type() == Any::CacheGetReady {
-      self.msg().map(|u| CacheGetReady::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn id(&self) -> u32 {
+    self._tab.get::<u32>(CryptoImportKeyReady::VT_ID, Some(0)).unwrap()
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_cache_set(&'a self) -> Option<CacheSet> {
-    if self.msg_type() == Any::CacheSet {
-      self.msg().map(|u| CacheSet::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn key_type(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CryptoImportKeyReady::VT_KEY_TYPE, None)
   }
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_cache_set_ready(&'a self) -> Option<CacheSetReady> {
-    if self.msg_type() == Any::CacheSetReady {
-      self.msg().map(|u| CacheSetReady::init_from_table(u))
-    } else {
-      None
+pub struct CryptoImportKeyReadyArgs<'a> {
+    pub id: u32,
+    pub key_type: Option<flatbuffers::WIPOffset<&'a  str>>,
+}
+impl<'a> Default for CryptoImportKeyReadyArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        CryptoImportKeyReadyArgs {
+            id: 0,
+            key_type: None,
+        }
     }
+}
+pub struct CryptoImportKeyReadyBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CryptoImportKeyReadyBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_id(&mut self, id: u32) {
+    self.fbb_.push_slot::<u32>(CryptoImportKeyReady::VT_ID, id, 0);
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_cache_del(&'a self) -> Option<CacheDel> {
-    if self.msg_type() == Any::CacheDel {
-      self.msg().map(|u| CacheDel::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn add_key_type(&mut self, key_type: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoImportKeyReady::VT_KEY_TYPE, key_type);
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_cache_expire(&'a self) -> Option<CacheExpire> {
-    if self.msg_type() == Any::CacheExpire {
-      self.msg().map(|u| CacheExpire::init_from_table(u))
-    } else {
-      None
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CryptoImportKeyReadyBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CryptoImportKeyReadyBuilder {
+      fbb_: _fbb,
+      start_: start,
     }
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_cache_notify_del(&'a self) -> Option<CacheNotifyDel> {
-    if self.msg_type() == Any::CacheNotifyDel {
-      self.msg().map(|u| CacheNotifyDel::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn finish(self) -> flatbuffers::WIPOffset<CryptoImportKeyReady<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
   }
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_cache_notify_purge_tag(&'a self) -> Option<CacheNotifyPurgeTag> {
-    if self.msg_type() == Any::CacheNotifyPurgeTag {
-      self.msg().map(|u| CacheNotifyPurgeTag::init_from_table(u))
-    } else {
-      None
+pub enum CryptoSignOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct CryptoSign<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CryptoSign<'a> {
+    type Inner = CryptoSign<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
     }
-  }
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_cache_set_tags(&'a self) -> Option<CacheSetTags> {
-    if self.msg_type() == Any::CacheSetTags {
-      self.msg().map(|u| CacheSetTags::init_from_table(u))
-    } else {
-      None
+impl<'a> CryptoSign<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        CryptoSign {
+            _tab: table,
+        }
     }
-  }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args CryptoSignArgs<'args>) -> flatbuffers::WIPOffset<CryptoSign<'bldr>> {
+      let mut builder = CryptoSignBuilder::new(_fbb);
+      if let Some(x) = args.hash { builder.add_hash(x); }
+      if let Some(x) = args.algo { builder.add_algo(x); }
+      builder.add_key(args.key);
+      builder.finish()
+    }
+
+    pub const VT_KEY: flatbuffers::VOffsetT = 4;
+    pub const VT_ALGO: flatbuffers::VOffsetT = 6;
+    pub const VT_HASH: flatbuffers::VOffsetT = 8;
 
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_cache_purge_tag(&'a self) -> Option<CachePurgeTag> {
-    if self.msg_type() == Any::CachePurgeTag {
-      self.msg().map(|u| CachePurgeTag::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn key(&self) -> u32 {
+    self._tab.get::<u32>(CryptoSign::VT_KEY, Some(0)).unwrap()
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_cache_set_meta(&'a self) -> Option<CacheSetMeta> {
-    if self.msg_type() == Any::CacheSetMeta {
-      self.msg().map(|u| CacheSetMeta::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn algo(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CryptoSign::VT_ALGO, None)
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_crypto_digest(&'a self) -> Option<CryptoDigest> {
-    if self.msg_type() == Any::CryptoDigest {
-      self.msg().map(|u| CryptoDigest::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn hash(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CryptoSign::VT_HASH, None)
   }
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_crypto_digest_ready(&'a self) -> Option<CryptoDigestReady> {
-    if self.msg_type() == Any::CryptoDigestReady {
-      self.msg().map(|u| CryptoDigestReady::init_from_table(u))
-    } else {
-      None
+pub struct CryptoSignArgs<'a> {
+    pub key: u32,
+    pub algo: Option<flatbuffers::WIPOffset<&'a  str>>,
+    pub hash: Option<flatbuffers::WIPOffset<&'a  str>>,
+}
+impl<'a> Default for CryptoSignArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        CryptoSignArgs {
+            key: 0,
+            algo: None,
+            hash: None,
+        }
     }
+}
+pub struct CryptoSignBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CryptoSignBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_key(&mut self, key: u32) {
+    self.fbb_.push_slot::<u32>(CryptoSign::VT_KEY, key, 0);
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_crypto_random_values(&'a self) -> Option<CryptoRandomValues> {
-    if self.msg_type() == Any::CryptoRandomValues {
-      self.msg().map(|u| CryptoRandomValues::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn add_algo(&mut self, algo: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoSign::VT_ALGO, algo);
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_crypto_random_values_ready(&'a self) -> Option<CryptoRandomValuesReady> {
-    if self.msg_type() == Any::CryptoRandomValuesReady {
-      self.msg().map(|u| CryptoRandomValuesReady::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn add_hash(&mut self, hash: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoSign::VT_HASH, hash);
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_source_map(&'a self) -> Option<SourceMap> {
-    if self.msg_type() == Any::SourceMap {
-      self.msg().map(|u| SourceMap::init_from_table(u))
-    } else {
-      None
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CryptoSignBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CryptoSignBuilder {
+      fbb_: _fbb,
+      start_: start,
     }
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_source_map_ready(&'a self) -> Option<SourceMapReady> {
-    if self.msg_type() == Any::SourceMapReady {
-      self.msg().map(|u| SourceMapReady::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn finish(self) -> flatbuffers::WIPOffset<CryptoSign<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
   }
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_data_put(&'a self) -> Option<DataPut> {
-    if self.msg_type() == Any::DataPut {
-      self.msg().map(|u| DataPut::init_from_table(u))
-    } else {
-      None
+pub enum CryptoSignReadyOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct CryptoSignReady<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CryptoSignReady<'a> {
+    type Inner = CryptoSignReady<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
     }
-  }
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_data_get(&'a self) -> Option<DataGet> {
-    if self.msg_type() == Any::DataGet {
-      self.msg().map(|u| DataGet::init_from_table(u))
-    } else {
-      None
+impl<'a> CryptoSignReady<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        CryptoSignReady {
+            _tab: table,
+        }
     }
-  }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args CryptoSignReadyArgs<'args>) -> flatbuffers::WIPOffset<CryptoSignReady<'bldr>> {
+      let mut builder = CryptoSignReadyBuilder::new(_fbb);
+      if let Some(x) = args.buffer { builder.add_buffer(x); }
+      builder.finish()
+    }
+
+    pub const VT_BUFFER: flatbuffers::VOffsetT = 4;
 
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_data_get_ready(&'a self) -> Option<DataGetReady> {
-    if self.msg_type() == Any::DataGetReady {
-      self.msg().map(|u| DataGetReady::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn buffer(&self) -> Option<&'a [u8]> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(CryptoSignReady::VT_BUFFER, None).map(|v| v.safe_slice())
   }
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_data_del(&'a self) -> Option<DataDel> {
-    if self.msg_type() == Any::DataDel {
-      self.msg().map(|u| DataDel::init_from_table(u))
-    } else {
-      None
+pub struct CryptoSignReadyArgs<'a> {
+    pub buffer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a ,  u8>>>,
+}
+impl<'a> Default for CryptoSignReadyArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        CryptoSignReadyArgs {
+            buffer: None,
+        }
     }
+}
+pub struct CryptoSignReadyBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CryptoSignReadyBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_buffer(&mut self, buffer: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoSignReady::VT_BUFFER, buffer);
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_data_incr(&'a self) -> Option<DataIncr> {
-    if self.msg_type() == Any::DataIncr {
-      self.msg().map(|u| DataIncr::init_from_table(u))
-    } else {
-      None
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CryptoSignReadyBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CryptoSignReadyBuilder {
+      fbb_: _fbb,
+      start_: start,
     }
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_data_drop_collection(&'a self) -> Option<DataDropCollection> {
-    if self.msg_type() == Any::DataDropCollection {
-      self.msg().map(|u| DataDropCollection::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn finish(self) -> flatbuffers::WIPOffset<CryptoSignReady<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
   }
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_dns_query(&'a self) -> Option<DnsQuery> {
-    if self.msg_type() == Any::DnsQuery {
-      self.msg().map(|u| DnsQuery::init_from_table(u))
-    } else {
-      None
+pub enum CryptoVerifyOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct CryptoVerify<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CryptoVerify<'a> {
+    type Inner = CryptoVerify<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
     }
-  }
+}
 
-  #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_dns_request(&'a self) -> Option<DnsRequest> {
-    if self.msg_type() == Any::DnsRequest {
-      self.msg().map(|u| DnsRequest::init_from_table(u))
-    } else {
-      None
+impl<'a> CryptoVerify<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        CryptoVerify {
+            _tab: table,
+        }
     }
-  }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args CryptoVerifyArgs<'args>) -> flatbuffers::WIPOffset<CryptoVerify<'bldr>> {
+      let mut builder = CryptoVerifyBuilder::new(_fbb);
+      if let Some(x) = args.signature { builder.add_signature(x); }
+      if let Some(x) = args.hash { builder.add_hash(x); }
+      if let Some(x) = args.algo { builder.add_algo(x); }
+      builder.add_key(args.key);
+      builder.finish()
+    }
+
+    pub const VT_KEY: flatbuffers::VOffsetT = 4;
+    pub const VT_ALGO: flatbuffers::VOffsetT = 6;
+    pub const VT_HASH: flatbuffers::VOffsetT = 8;
+    pub const VT_SIGNATURE: flatbuffers::VOffsetT = 10;
 
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_dns_response(&'a self) -> Option<DnsResponse> {
-    if self.msg_type() == Any::DnsResponse {
-      self.msg().map(|u| DnsResponse::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn key(&self) -> u32 {
+    self._tab.get::<u32>(CryptoVerify::VT_KEY, Some(0)).unwrap()
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_add_event_listener(&'a self) -> Option<AddEventListener> {
-    if self.msg_type() == Any::AddEventListener {
-      self.msg().map(|u| AddEventListener::init_from_table(u))
-    } else {
-      None
-    }
+  pub fn algo(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CryptoVerify::VT_ALGO, None)
+  }
+  #[inline]
+  pub fn hash(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CryptoVerify::VT_HASH, None)
+  }
+  #[inline]
+  pub fn signature(&self) -> Option<&'a [u8]> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(CryptoVerify::VT_SIGNATURE, None).map(|v| v.safe_slice())
   }
+}
 
+pub struct CryptoVerifyArgs<'a> {
+    pub key: u32,
+    pub algo: Option<flatbuffers::WIPOffset<&'a  str>>,
+    pub hash: Option<flatbuffers::WIPOffset<&'a  str>>,
+    pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a ,  u8>>>,
+}
+impl<'a> Default for CryptoVerifyArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        CryptoVerifyArgs {
+            key: 0,
+            algo: None,
+            hash: None,
+            signature: None,
+        }
+    }
+}
+pub struct CryptoVerifyBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CryptoVerifyBuilder<'a, 'b> {
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_load_module(&'a self) -> Option<LoadModule> {
-    if self.msg_type() == Any::LoadModule {
-      self.msg().map(|u| LoadModule::init_from_table(u))
-    } else {
-      None
+  pub fn add_key(&mut self, key: u32) {
+    self.fbb_.push_slot::<u32>(CryptoVerify::VT_KEY, key, 0);
+  }
+  #[inline]
+  pub fn add_algo(&mut self, algo: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoVerify::VT_ALGO, algo);
+  }
+  #[inline]
+  pub fn add_hash(&mut self, hash: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoVerify::VT_HASH, hash);
+  }
+  #[inline]
+  pub fn add_signature(&mut self, signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoVerify::VT_SIGNATURE, signature);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CryptoVerifyBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CryptoVerifyBuilder {
+      fbb_: _fbb,
+      start_: start,
     }
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_load_module_resp(&'a self) -> Option<LoadModuleResp> {
-    if self.msg_type() == Any::LoadModuleResp {
-      self.msg().map(|u| LoadModuleResp::init_from_table(u))
-    } else {
-      None
+  pub fn finish(self) -> flatbuffers::WIPOffset<CryptoVerify<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum CryptoVerifyReadyOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct CryptoVerifyReady<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CryptoVerifyReady<'a> {
+    type Inner = CryptoVerifyReady<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> CryptoVerifyReady<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        CryptoVerifyReady {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args CryptoVerifyReadyArgs) -> flatbuffers::WIPOffset<CryptoVerifyReady<'bldr>> {
+      let mut builder = CryptoVerifyReadyBuilder::new(_fbb);
+      builder.add_valid(args.valid);
+      builder.finish()
     }
+
+    pub const VT_VALID: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub fn valid(&self) -> bool {
+    self._tab.get::<bool>(CryptoVerifyReady::VT_VALID, Some(false)).unwrap()
   }
+}
 
+pub struct CryptoVerifyReadyArgs {
+    pub valid: bool,
+}
+impl<'a> Default for CryptoVerifyReadyArgs {
+    #[inline]
+    fn default() -> Self {
+        CryptoVerifyReadyArgs {
+            valid: false,
+        }
+    }
+}
+pub struct CryptoVerifyReadyBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CryptoVerifyReadyBuilder<'a, 'b> {
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_image_apply_transforms(&'a self) -> Option<ImageApplyTransforms> {
-    if self.msg_type() == Any::ImageApplyTransforms {
-      self.msg().map(|u| ImageApplyTransforms::init_from_table(u))
-    } else {
-      None
+  pub fn add_valid(&mut self, valid: bool) {
+    self.fbb_.push_slot::<bool>(CryptoVerifyReady::VT_VALID, valid, false);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CryptoVerifyReadyBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CryptoVerifyReadyBuilder {
+      fbb_: _fbb,
+      start_: start,
     }
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_image_ready(&'a self) -> Option<ImageReady> {
-    if self.msg_type() == Any::ImageReady {
-      self.msg().map(|u| ImageReady::init_from_table(u))
-    } else {
-      None
+  pub fn finish(self) -> flatbuffers::WIPOffset<CryptoVerifyReady<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum CryptoCipherOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct CryptoCipher<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CryptoCipher<'a> {
+    type Inner = CryptoCipher<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> CryptoCipher<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        CryptoCipher {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args CryptoCipherArgs<'args>) -> flatbuffers::WIPOffset<CryptoCipher<'bldr>> {
+      let mut builder = CryptoCipherBuilder::new(_fbb);
+      builder.add_tag_length(args.tag_length);
+      if let Some(x) = args.additional_data { builder.add_additional_data(x); }
+      if let Some(x) = args.iv { builder.add_iv(x); }
+      if let Some(x) = args.algo { builder.add_algo(x); }
+      builder.add_key(args.key);
+      builder.add_decrypt(args.decrypt);
+      builder.finish()
     }
+
+    pub const VT_KEY: flatbuffers::VOffsetT = 4;
+    pub const VT_ALGO: flatbuffers::VOffsetT = 6;
+    pub const VT_DECRYPT: flatbuffers::VOffsetT = 8;
+    pub const VT_IV: flatbuffers::VOffsetT = 10;
+    pub const VT_ADDITIONAL_DATA: flatbuffers::VOffsetT = 12;
+    pub const VT_TAG_LENGTH: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub fn key(&self) -> u32 {
+    self._tab.get::<u32>(CryptoCipher::VT_KEY, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn algo(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CryptoCipher::VT_ALGO, None)
+  }
+  #[inline]
+  pub fn decrypt(&self) -> bool {
+    self._tab.get::<bool>(CryptoCipher::VT_DECRYPT, Some(false)).unwrap()
+  }
+  #[inline]
+  pub fn iv(&self) -> Option<&'a [u8]> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(CryptoCipher::VT_IV, None).map(|v| v.safe_slice())
+  }
+  #[inline]
+  pub fn additional_data(&self) -> Option<&'a [u8]> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(CryptoCipher::VT_ADDITIONAL_DATA, None).map(|v| v.safe_slice())
+  }
+  #[inline]
+  pub fn tag_length(&self) -> u32 {
+    self._tab.get::<u32>(CryptoCipher::VT_TAG_LENGTH, Some(128)).unwrap()
   }
+}
 
+pub struct CryptoCipherArgs<'a> {
+    pub key: u32,
+    pub algo: Option<flatbuffers::WIPOffset<&'a  str>>,
+    pub decrypt: bool,
+    pub iv: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a ,  u8>>>,
+    pub additional_data: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a ,  u8>>>,
+    pub tag_length: u32,
+}
+impl<'a> Default for CryptoCipherArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        CryptoCipherArgs {
+            key: 0,
+            algo: None,
+            decrypt: false,
+            iv: None,
+            additional_data: None,
+            tag_length: 128,
+        }
+    }
+}
+pub struct CryptoCipherBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CryptoCipherBuilder<'a, 'b> {
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_acme_get_challenge(&'a self) -> Option<AcmeGetChallenge> {
-    if self.msg_type() == Any::AcmeGetChallenge {
-      self.msg().map(|u| AcmeGetChallenge::init_from_table(u))
-    } else {
-      None
+  pub fn add_key(&mut self, key: u32) {
+    self.fbb_.push_slot::<u32>(CryptoCipher::VT_KEY, key, 0);
+  }
+  #[inline]
+  pub fn add_algo(&mut self, algo: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoCipher::VT_ALGO, algo);
+  }
+  #[inline]
+  pub fn add_decrypt(&mut self, decrypt: bool) {
+    self.fbb_.push_slot::<bool>(CryptoCipher::VT_DECRYPT, decrypt, false);
+  }
+  #[inline]
+  pub fn add_iv(&mut self, iv: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoCipher::VT_IV, iv);
+  }
+  #[inline]
+  pub fn add_additional_data(&mut self, additional_data: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoCipher::VT_ADDITIONAL_DATA, additional_data);
+  }
+  #[inline]
+  pub fn add_tag_length(&mut self, tag_length: u32) {
+    self.fbb_.push_slot::<u32>(CryptoCipher::VT_TAG_LENGTH, tag_length, 128);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CryptoCipherBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CryptoCipherBuilder {
+      fbb_: _fbb,
+      start_: start,
     }
   }
-
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_acme_get_challenge_ready(&'a self) -> Option<AcmeGetChallengeReady> {
-    if self.msg_type() == Any::AcmeGetChallengeReady {
-      self.msg().map(|u| AcmeGetChallengeReady::init_from_table(u))
-    } else {
-      None
+  pub fn finish(self) -> flatbuffers::WIPOffset<CryptoCipher<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum CryptoCipherReadyOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct CryptoCipherReady<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CryptoCipherReady<'a> {
+    type Inner = CryptoCipherReady<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> CryptoCipherReady<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        CryptoCipherReady {
+            _tab: table,
+        }
     }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args CryptoCipherReadyArgs<'args>) -> flatbuffers::WIPOffset<CryptoCipherReady<'bldr>> {
+      let mut builder = CryptoCipherReadyBuilder::new(_fbb);
+      if let Some(x) = args.buffer { builder.add_buffer(x); }
+      builder.finish()
+    }
+
+    pub const VT_BUFFER: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub fn buffer(&self) -> Option<&'a [u8]> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(CryptoCipherReady::VT_BUFFER, None).map(|v| v.safe_slice())
   }
+}
 
+pub struct CryptoCipherReadyArgs<'a> {
+    pub buffer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a ,  u8>>>,
+}
+impl<'a> Default for CryptoCipherReadyArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        CryptoCipherReadyArgs {
+            buffer: None,
+        }
+    }
+}
+pub struct CryptoCipherReadyBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CryptoCipherReadyBuilder<'a, 'b> {
   #[inline]
-  #[allow(non_snake_case)]
-  pub fn msg_as_os_exit(&'a self) -> Option<OsExit> {
-    if self.msg_type() == Any::OsExit {
-      self.msg().map(|u| OsExit::init_from_table(u))
-    } else {
-      None
+  pub fn add_buffer(&mut self, buffer: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CryptoCipherReady::VT_BUFFER, buffer);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CryptoCipherReadyBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CryptoCipherReadyBuilder {
+      fbb_: _fbb,
+      start_: start,
     }
   }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<CryptoCipherReady<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
 
+pub enum WebSocketConnectOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct WebSocketConnect<'a> {
+  pub _tab: flatbuffers::Table<'a>,
 }
 
-pub struct BaseArgs<'a> {
-    pub cmd_id: u32,
-    pub sync: bool,
-    pub error_kind: ErrorKind,
-    pub error: Option<flatbuffers::WIPOffset<&'a  str>>,
+impl<'a> flatbuffers::Follow<'a> for WebSocketConnect<'a> {
+    type Inner = WebSocketConnect<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> WebSocketConnect<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        WebSocketConnect {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args WebSocketConnectArgs<'args>) -> flatbuffers::WIPOffset<WebSocketConnect<'bldr>> {
+      let mut builder = WebSocketConnectBuilder::new(_fbb);
+      if let Some(x) = args.protocols { builder.add_protocols(x); }
+      if let Some(x) = args.url { builder.add_url(x); }
+      builder.add_id(args.id);
+      builder.finish()
+    }
+
+    pub const VT_ID: flatbuffers::VOffsetT = 4;
+    pub const VT_URL: flatbuffers::VOffsetT = 6;
+    pub const VT_PROTOCOLS: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub fn id(&self) -> u32 {
+    self._tab.get::<u32>(WebSocketConnect::VT_ID, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn url(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(WebSocketConnect::VT_URL, None)
+  }
+  #[inline]
+  pub fn protocols(&self) -> Option<flatbuffers::Vector<flatbuffers::ForwardsUOffset<&'a str>>> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<flatbuffers::ForwardsUOffset<&'a str>>>>(WebSocketConnect::VT_PROTOCOLS, None)
+  }
+}
+
+pub struct WebSocketConnectArgs<'a> {
+    pub id: u32,
+    pub url: Option<flatbuffers::WIPOffset<&'a  str>>,
+    pub protocols: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a , flatbuffers::ForwardsUOffset<&'a  str>>>>,
+}
+impl<'a> Default for WebSocketConnectArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        WebSocketConnectArgs {
+            id: 0,
+            url: None,
+            protocols: None,
+        }
+    }
+}
+pub struct WebSocketConnectBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> WebSocketConnectBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_id(&mut self, id: u32) {
+    self.fbb_.push_slot::<u32>(WebSocketConnect::VT_ID, id, 0);
+  }
+  #[inline]
+  pub fn add_url(&mut self, url: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(WebSocketConnect::VT_URL, url);
+  }
+  #[inline]
+  pub fn add_protocols(&mut self, protocols: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<&'b  str>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(WebSocketConnect::VT_PROTOCOLS, protocols);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> WebSocketConnectBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    WebSocketConnectBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<WebSocketConnect<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum WebSocketConnectReadyOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct WebSocketConnectReady<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for WebSocketConnectReady<'a> {
+    type Inner = WebSocketConnectReady<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> WebSocketConnectReady<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        WebSocketConnectReady {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args WebSocketConnectReadyArgs<'args>) -> flatbuffers::WIPOffset<WebSocketConnectReady<'bldr>> {
+      let mut builder = WebSocketConnectReadyBuilder::new(_fbb);
+      if let Some(x) = args.protocol { builder.add_protocol(x); }
+      builder.finish()
+    }
+
+    pub const VT_PROTOCOL: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub fn protocol(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(WebSocketConnectReady::VT_PROTOCOL, None)
+  }
+}
+
+pub struct WebSocketConnectReadyArgs<'a> {
+    pub protocol: Option<flatbuffers::WIPOffset<&'a  str>>,
+}
+impl<'a> Default for WebSocketConnectReadyArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        WebSocketConnectReadyArgs {
+            protocol: None,
+        }
+    }
+}
+pub struct WebSocketConnectReadyBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> WebSocketConnectReadyBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_protocol(&mut self, protocol: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(WebSocketConnectReady::VT_PROTOCOL, protocol);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> WebSocketConnectReadyBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    WebSocketConnectReadyBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<WebSocketConnectReady<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum WebSocketSendOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct WebSocketSend<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for WebSocketSend<'a> {
+    type Inner = WebSocketSend<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> WebSocketSend<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        WebSocketSend {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args WebSocketSendArgs) -> flatbuffers::WIPOffset<WebSocketSend<'bldr>> {
+      let mut builder = WebSocketSendBuilder::new(_fbb);
+      builder.add_id(args.id);
+      builder.add_text(args.text);
+      builder.finish()
+    }
+
+    pub const VT_ID: flatbuffers::VOffsetT = 4;
+    pub const VT_TEXT: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub fn id(&self) -> u32 {
+    self._tab.get::<u32>(WebSocketSend::VT_ID, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn text(&self) -> bool {
+    self._tab.get::<bool>(WebSocketSend::VT_TEXT, Some(false)).unwrap()
+  }
+}
+
+pub struct WebSocketSendArgs {
+    pub id: u32,
+    pub text: bool,
+}
+impl<'a> Default for WebSocketSendArgs {
+    #[inline]
+    fn default() -> Self {
+        WebSocketSendArgs {
+            id: 0,
+            text: false,
+        }
+    }
+}
+pub struct WebSocketSendBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> WebSocketSendBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_id(&mut self, id: u32) {
+    self.fbb_.push_slot::<u32>(WebSocketSend::VT_ID, id, 0);
+  }
+  #[inline]
+  pub fn add_text(&mut self, text: bool) {
+    self.fbb_.push_slot::<bool>(WebSocketSend::VT_TEXT, text, false);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> WebSocketSendBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    WebSocketSendBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<WebSocketSend<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum WebSocketCloseOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct WebSocketClose<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for WebSocketClose<'a> {
+    type Inner = WebSocketClose<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> WebSocketClose<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        WebSocketClose {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args WebSocketCloseArgs<'args>) -> flatbuffers::WIPOffset<WebSocketClose<'bldr>> {
+      let mut builder = WebSocketCloseBuilder::new(_fbb);
+      if let Some(x) = args.reason { builder.add_reason(x); }
+      builder.add_id(args.id);
+      builder.add_code(args.code);
+      builder.finish()
+    }
+
+    pub const VT_ID: flatbuffers::VOffsetT = 4;
+    pub const VT_CODE: flatbuffers::VOffsetT = 6;
+    pub const VT_REASON: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub fn id(&self) -> u32 {
+    self._tab.get::<u32>(WebSocketClose::VT_ID, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn code(&self) -> u16 {
+    self._tab.get::<u16>(WebSocketClose::VT_CODE, Some(1000)).unwrap()
+  }
+  #[inline]
+  pub fn reason(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(WebSocketClose::VT_REASON, None)
+  }
+}
+
+pub struct WebSocketCloseArgs<'a> {
+    pub id: u32,
+    pub code: u16,
+    pub reason: Option<flatbuffers::WIPOffset<&'a  str>>,
+}
+impl<'a> Default for WebSocketCloseArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        WebSocketCloseArgs {
+            id: 0,
+            code: 1000,
+            reason: None,
+        }
+    }
+}
+pub struct WebSocketCloseBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> WebSocketCloseBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_id(&mut self, id: u32) {
+    self.fbb_.push_slot::<u32>(WebSocketClose::VT_ID, id, 0);
+  }
+  #[inline]
+  pub fn add_code(&mut self, code: u16) {
+    self.fbb_.push_slot::<u16>(WebSocketClose::VT_CODE, code, 1000);
+  }
+  #[inline]
+  pub fn add_reason(&mut self, reason: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(WebSocketClose::VT_REASON, reason);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> WebSocketCloseBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    WebSocketCloseBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<WebSocketClose<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum FsWriteOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct FsWrite<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for FsWrite<'a> {
+    type Inner = FsWrite<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> FsWrite<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        FsWrite {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args FsWriteArgs<'args>) -> flatbuffers::WIPOffset<FsWrite<'bldr>> {
+      let mut builder = FsWriteBuilder::new(_fbb);
+      if let Some(x) = args.path { builder.add_path(x); }
+      builder.finish()
+    }
+
+    pub const VT_PATH: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub fn path(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(FsWrite::VT_PATH, None)
+  }
+}
+
+pub struct FsWriteArgs<'a> {
+    pub path: Option<flatbuffers::WIPOffset<&'a  str>>,
+}
+impl<'a> Default for FsWriteArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        FsWriteArgs {
+            path: None,
+        }
+    }
+}
+pub struct FsWriteBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> FsWriteBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_path(&mut self, path: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(FsWrite::VT_PATH, path);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FsWriteBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    FsWriteBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<FsWrite<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum FsDeleteOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct FsDelete<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for FsDelete<'a> {
+    type Inner = FsDelete<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> FsDelete<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        FsDelete {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args FsDeleteArgs<'args>) -> flatbuffers::WIPOffset<FsDelete<'bldr>> {
+      let mut builder = FsDeleteBuilder::new(_fbb);
+      if let Some(x) = args.path { builder.add_path(x); }
+      builder.finish()
+    }
+
+    pub const VT_PATH: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub fn path(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(FsDelete::VT_PATH, None)
+  }
+}
+
+pub struct FsDeleteArgs<'a> {
+    pub path: Option<flatbuffers::WIPOffset<&'a  str>>,
+}
+impl<'a> Default for FsDeleteArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        FsDeleteArgs {
+            path: None,
+        }
+    }
+}
+pub struct FsDeleteBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> FsDeleteBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_path(&mut self, path: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(FsDelete::VT_PATH, path);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FsDeleteBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    FsDeleteBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<FsDelete<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum FsStatOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct FsStat<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for FsStat<'a> {
+    type Inner = FsStat<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> FsStat<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        FsStat {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args FsStatArgs<'args>) -> flatbuffers::WIPOffset<FsStat<'bldr>> {
+      let mut builder = FsStatBuilder::new(_fbb);
+      if let Some(x) = args.path { builder.add_path(x); }
+      builder.finish()
+    }
+
+    pub const VT_PATH: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub fn path(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(FsStat::VT_PATH, None)
+  }
+}
+
+pub struct FsStatArgs<'a> {
+    pub path: Option<flatbuffers::WIPOffset<&'a  str>>,
+}
+impl<'a> Default for FsStatArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        FsStatArgs {
+            path: None,
+        }
+    }
+}
+pub struct FsStatBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> FsStatBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_path(&mut self, path: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(FsStat::VT_PATH, path);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FsStatBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    FsStatBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<FsStat<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum FsStatReadyOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct FsStatReady<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for FsStatReady<'a> {
+    type Inner = FsStatReady<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> FsStatReady<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        FsStatReady {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args FsStatReadyArgs<'args>) -> flatbuffers::WIPOffset<FsStatReady<'bldr>> {
+      let mut builder = FsStatReadyBuilder::new(_fbb);
+      builder.add_modified(args.modified);
+      builder.add_size(args.size);
+      if let Some(x) = args.content_type { builder.add_content_type(x); }
+      builder.finish()
+    }
+
+    pub const VT_SIZE: flatbuffers::VOffsetT = 4;
+    pub const VT_MODIFIED: flatbuffers::VOffsetT = 6;
+    pub const VT_CONTENT_TYPE: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub fn size(&self) -> u64 {
+    self._tab.get::<u64>(FsStatReady::VT_SIZE, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn modified(&self) -> u64 {
+    self._tab.get::<u64>(FsStatReady::VT_MODIFIED, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn content_type(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(FsStatReady::VT_CONTENT_TYPE, None)
+  }
+}
+
+pub struct FsStatReadyArgs<'a> {
+    pub size: u64,
+    pub modified: u64,
+    pub content_type: Option<flatbuffers::WIPOffset<&'a  str>>,
+}
+impl<'a> Default for FsStatReadyArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        FsStatReadyArgs {
+            size: 0,
+            modified: 0,
+            content_type: None,
+        }
+    }
+}
+pub struct FsStatReadyBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> FsStatReadyBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_size(&mut self, size: u64) {
+    self.fbb_.push_slot::<u64>(FsStatReady::VT_SIZE, size, 0);
+  }
+  #[inline]
+  pub fn add_modified(&mut self, modified: u64) {
+    self.fbb_.push_slot::<u64>(FsStatReady::VT_MODIFIED, modified, 0);
+  }
+  #[inline]
+  pub fn add_content_type(&mut self, content_type: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(FsStatReady::VT_CONTENT_TYPE, content_type);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FsStatReadyBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    FsStatReadyBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<FsStatReady<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum FsListOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct FsList<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for FsList<'a> {
+    type Inner = FsList<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> FsList<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        FsList {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args FsListArgs<'args>) -> flatbuffers::WIPOffset<FsList<'bldr>> {
+      let mut builder = FsListBuilder::new(_fbb);
+      if let Some(x) = args.prefix { builder.add_prefix(x); }
+      builder.finish()
+    }
+
+    pub const VT_PREFIX: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub fn prefix(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(FsList::VT_PREFIX, None)
+  }
+}
+
+pub struct FsListArgs<'a> {
+    pub prefix: Option<flatbuffers::WIPOffset<&'a  str>>,
+}
+impl<'a> Default for FsListArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        FsListArgs {
+            prefix: None,
+        }
+    }
+}
+pub struct FsListBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> FsListBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_prefix(&mut self, prefix: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(FsList::VT_PREFIX, prefix);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FsListBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    FsListBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<FsList<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum FsListReadyOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct FsListReady<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for FsListReady<'a> {
+    type Inner = FsListReady<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> FsListReady<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        FsListReady {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args FsListReadyArgs<'args>) -> flatbuffers::WIPOffset<FsListReady<'bldr>> {
+      let mut builder = FsListReadyBuilder::new(_fbb);
+      if let Some(x) = args.paths { builder.add_paths(x); }
+      builder.finish()
+    }
+
+    pub const VT_PATHS: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub fn paths(&self) -> Option<flatbuffers::Vector<flatbuffers::ForwardsUOffset<&'a str>>> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<flatbuffers::ForwardsUOffset<&'a str>>>>(FsListReady::VT_PATHS, None)
+  }
+}
+
+pub struct FsListReadyArgs<'a> {
+    pub paths: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a , flatbuffers::ForwardsUOffset<&'a  str>>>>,
+}
+impl<'a> Default for FsListReadyArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        FsListReadyArgs {
+            paths: None,
+        }
+    }
+}
+pub struct FsListReadyBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> FsListReadyBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_paths(&mut self, paths: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<&'b  str>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(FsListReady::VT_PATHS, paths);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FsListReadyBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    FsListReadyBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<FsListReady<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum BaseOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct Base<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Base<'a> {
+    type Inner = Base<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> Base<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        Base {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args BaseArgs<'args>) -> flatbuffers::WIPOffset<Base<'bldr>> {
+      let mut builder = BaseBuilder::new(_fbb);
+      if let Some(x) = args.msg { builder.add_msg(x); }
+      if let Some(x) = args.error { builder.add_error(x); }
+      builder.add_cmd_id(args.cmd_id);
+      builder.add_msg_type(args.msg_type);
+      builder.add_error_kind(args.error_kind);
+      builder.add_sync(args.sync);
+      builder.finish()
+    }
+
+    pub const VT_CMD_ID: flatbuffers::VOffsetT = 4;
+    pub const VT_SYNC: flatbuffers::VOffsetT = 6;
+    pub const VT_ERROR_KIND: flatbuffers::VOffsetT = 8;
+    pub const VT_ERROR: flatbuffers::VOffsetT = 10;
+    pub const VT_MSG_TYPE: flatbuffers::VOffsetT = 12;
+    pub const VT_MSG: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub fn cmd_id(&self) -> u32 {
+    self._tab.get::<u32>(Base::VT_CMD_ID, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn sync(&self) -> bool {
+    self._tab.get::<bool>(Base::VT_SYNC, Some(false)).unwrap()
+  }
+  #[inline]
+  pub fn error_kind(&self) -> ErrorKind {
+    self._tab.get::<ErrorKind>(Base::VT_ERROR_KIND, Some(ErrorKind::NoError)).unwrap()
+  }
+  #[inline]
+  pub fn error(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Base::VT_ERROR, None)
+  }
+  #[inline]
+  pub fn msg_type(&self) -> Any {
+    self._tab.get::<Any>(Base::VT_MSG_TYPE, Some(Any::NONE)).unwrap()
+  }
+  #[inline]
+  pub fn msg(&self) -> Option<flatbuffers::Table<'a>> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(Base::VT_MSG, None)
+  }
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_timer_start(&'a self) -> Option<TimerStart> {
+    if self.msg_type() == Any::TimerStart {
+      self.msg().map(|u| TimerStart::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_timer_ready(&'a self) -> Option<TimerReady> {
+    if self.msg_type() == Any::TimerReady {
+      self.msg().map(|u| TimerReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_timer_clear(&'a self) -> Option<TimerClear> {
+    if self.msg_type() == Any::TimerClear {
+      self.msg().map(|u| TimerClear::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_http_request(&'a self) -> Option<HttpRequest> {
+    if self.msg_type() == Any::HttpRequest {
+      self.msg().map(|u| HttpRequest::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_http_response(&'a self) -> Option<HttpResponse> {
+    if self.msg_type() == Any::HttpResponse {
+      self.msg().map(|u| HttpResponse::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_stream_chunk(&'a self) -> Option<StreamChunk> {
+    if self.msg_type() == Any::StreamChunk {
+      self.msg().map(|u| StreamChunk::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_http_request_start(&'a self) -> Option<HttpRequestStart> {
+    if self.msg_type() == Any::HttpRequestStart {
+      self.msg().map(|u| HttpRequestStart::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_fetch_http_response(&'a self) -> Option<FetchHttpResponse> {
+    if self.msg_type() == Any::FetchHttpResponse {
+      self.msg().map(|u| FetchHttpResponse::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_cache_get(&'a self) -> Option<CacheGet> {
+    if self.msg_type() == Any::CacheGet {
+      self.msg().map(|u| CacheGet::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_cache_get_ready(&'a self) -> Option<CacheGetReady> {
+    if self.msg_type() == Any::CacheGetReady {
+      self.msg().map(|u| CacheGetReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_cache_set(&'a self) -> Option<CacheSet> {
+    if self.msg_type() == Any::CacheSet {
+      self.msg().map(|u| CacheSet::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_cache_set_ready(&'a self) -> Option<CacheSetReady> {
+    if self.msg_type() == Any::CacheSetReady {
+      self.msg().map(|u| CacheSetReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_cache_del(&'a self) -> Option<CacheDel> {
+    if self.msg_type() == Any::CacheDel {
+      self.msg().map(|u| CacheDel::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_cache_expire(&'a self) -> Option<CacheExpire> {
+    if self.msg_type() == Any::CacheExpire {
+      self.msg().map(|u| CacheExpire::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_cache_notify_del(&'a self) -> Option<CacheNotifyDel> {
+    if self.msg_type() == Any::CacheNotifyDel {
+      self.msg().map(|u| CacheNotifyDel::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_cache_notify_purge_tag(&'a self) -> Option<CacheNotifyPurgeTag> {
+    if self.msg_type() == Any::CacheNotifyPurgeTag {
+      self.msg().map(|u| CacheNotifyPurgeTag::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_cache_set_tags(&'a self) -> Option<CacheSetTags> {
+    if self.msg_type() == Any::CacheSetTags {
+      self.msg().map(|u| CacheSetTags::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_cache_purge_tag(&'a self) -> Option<CachePurgeTag> {
+    if self.msg_type() == Any::CachePurgeTag {
+      self.msg().map(|u| CachePurgeTag::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_cache_set_meta(&'a self) -> Option<CacheSetMeta> {
+    if self.msg_type() == Any::CacheSetMeta {
+      self.msg().map(|u| CacheSetMeta::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_digest(&'a self) -> Option<CryptoDigest> {
+    if self.msg_type() == Any::CryptoDigest {
+      self.msg().map(|u| CryptoDigest::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_digest_ready(&'a self) -> Option<CryptoDigestReady> {
+    if self.msg_type() == Any::CryptoDigestReady {
+      self.msg().map(|u| CryptoDigestReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_random_values(&'a self) -> Option<CryptoRandomValues> {
+    if self.msg_type() == Any::CryptoRandomValues {
+      self.msg().map(|u| CryptoRandomValues::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_random_values_ready(&'a self) -> Option<CryptoRandomValuesReady> {
+    if self.msg_type() == Any::CryptoRandomValuesReady {
+      self.msg().map(|u| CryptoRandomValuesReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_source_map(&'a self) -> Option<SourceMap> {
+    if self.msg_type() == Any::SourceMap {
+      self.msg().map(|u| SourceMap::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_source_map_ready(&'a self) -> Option<SourceMapReady> {
+    if self.msg_type() == Any::SourceMapReady {
+      self.msg().map(|u| SourceMapReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_data_put(&'a self) -> Option<DataPut> {
+    if self.msg_type() == Any::DataPut {
+      self.msg().map(|u| DataPut::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_data_get(&'a self) -> Option<DataGet> {
+    if self.msg_type() == Any::DataGet {
+      self.msg().map(|u| DataGet::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_data_get_ready(&'a self) -> Option<DataGetReady> {
+    if self.msg_type() == Any::DataGetReady {
+      self.msg().map(|u| DataGetReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_data_del(&'a self) -> Option<DataDel> {
+    if self.msg_type() == Any::DataDel {
+      self.msg().map(|u| DataDel::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_data_incr(&'a self) -> Option<DataIncr> {
+    if self.msg_type() == Any::DataIncr {
+      self.msg().map(|u| DataIncr::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_data_drop_collection(&'a self) -> Option<DataDropCollection> {
+    if self.msg_type() == Any::DataDropCollection {
+      self.msg().map(|u| DataDropCollection::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_dns_query(&'a self) -> Option<DnsQuery> {
+    if self.msg_type() == Any::DnsQuery {
+      self.msg().map(|u| DnsQuery::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_dns_request(&'a self) -> Option<DnsRequest> {
+    if self.msg_type() == Any::DnsRequest {
+      self.msg().map(|u| DnsRequest::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_dns_response(&'a self) -> Option<DnsResponse> {
+    if self.msg_type() == Any::DnsResponse {
+      self.msg().map(|u| DnsResponse::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_add_event_listener(&'a self) -> Option<AddEventListener> {
+    if self.msg_type() == Any::AddEventListener {
+      self.msg().map(|u| AddEventListener::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_load_module(&'a self) -> Option<LoadModule> {
+    if self.msg_type() == Any::LoadModule {
+      self.msg().map(|u| LoadModule::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_load_module_resp(&'a self) -> Option<LoadModuleResp> {
+    if self.msg_type() == Any::LoadModuleResp {
+      self.msg().map(|u| LoadModuleResp::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_image_apply_transforms(&'a self) -> Option<ImageApplyTransforms> {
+    if self.msg_type() == Any::ImageApplyTransforms {
+      self.msg().map(|u| ImageApplyTransforms::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_image_ready(&'a self) -> Option<ImageReady> {
+    if self.msg_type() == Any::ImageReady {
+      self.msg().map(|u| ImageReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_acme_get_challenge(&'a self) -> Option<AcmeGetChallenge> {
+    if self.msg_type() == Any::AcmeGetChallenge {
+      self.msg().map(|u| AcmeGetChallenge::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_acme_get_challenge_ready(&'a self) -> Option<AcmeGetChallengeReady> {
+    if self.msg_type() == Any::AcmeGetChallengeReady {
+      self.msg().map(|u| AcmeGetChallengeReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_os_exit(&'a self) -> Option<OsExit> {
+    if self.msg_type() == Any::OsExit {
+      self.msg().map(|u| OsExit::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_stream_pull(&'a self) -> Option<StreamPull> {
+    if self.msg_type() == Any::StreamPull {
+      self.msg().map(|u| StreamPull::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_import_key(&'a self) -> Option<CryptoImportKey> {
+    if self.msg_type() == Any::CryptoImportKey {
+      self.msg().map(|u| CryptoImportKey::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_import_key_ready(&'a self) -> Option<CryptoImportKeyReady> {
+    if self.msg_type() == Any::CryptoImportKeyReady {
+      self.msg().map(|u| CryptoImportKeyReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_sign(&'a self) -> Option<CryptoSign> {
+    if self.msg_type() == Any::CryptoSign {
+      self.msg().map(|u| CryptoSign::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_sign_ready(&'a self) -> Option<CryptoSignReady> {
+    if self.msg_type() == Any::CryptoSignReady {
+      self.msg().map(|u| CryptoSignReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_verify(&'a self) -> Option<CryptoVerify> {
+    if self.msg_type() == Any::CryptoVerify {
+      self.msg().map(|u| CryptoVerify::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_verify_ready(&'a self) -> Option<CryptoVerifyReady> {
+    if self.msg_type() == Any::CryptoVerifyReady {
+      self.msg().map(|u| CryptoVerifyReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_cipher(&'a self) -> Option<CryptoCipher> {
+    if self.msg_type() == Any::CryptoCipher {
+      self.msg().map(|u| CryptoCipher::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_crypto_cipher_ready(&'a self) -> Option<CryptoCipherReady> {
+    if self.msg_type() == Any::CryptoCipherReady {
+      self.msg().map(|u| CryptoCipherReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_web_socket_connect(&'a self) -> Option<WebSocketConnect> {
+    if self.msg_type() == Any::WebSocketConnect {
+      self.msg().map(|u| WebSocketConnect::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_web_socket_connect_ready(&'a self) -> Option<WebSocketConnectReady> {
+    if self.msg_type() == Any::WebSocketConnectReady {
+      self.msg().map(|u| WebSocketConnectReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_web_socket_send(&'a self) -> Option<WebSocketSend> {
+    if self.msg_type() == Any::WebSocketSend {
+      self.msg().map(|u| WebSocketSend::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_web_socket_close(&'a self) -> Option<WebSocketClose> {
+    if self.msg_type() == Any::WebSocketClose {
+      self.msg().map(|u| WebSocketClose::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_stream_cancel(&'a self) -> Option<StreamCancel> {
+    if self.msg_type() == Any::StreamCancel {
+      self.msg().map(|u| StreamCancel::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_http_request_abort(&'a self) -> Option<HttpRequestAbort> {
+    if self.msg_type() == Any::HttpRequestAbort {
+      self.msg().map(|u| HttpRequestAbort::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_fetch_abort(&'a self) -> Option<FetchAbort> {
+    if self.msg_type() == Any::FetchAbort {
+      self.msg().map(|u| FetchAbort::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_fs_write(&'a self) -> Option<FsWrite> {
+    if self.msg_type() == Any::FsWrite {
+      self.msg().map(|u| FsWrite::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_fs_delete(&'a self) -> Option<FsDelete> {
+    if self.msg_type() == Any::FsDelete {
+      self.msg().map(|u| FsDelete::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_fs_stat(&'a self) -> Option<FsStat> {
+    if self.msg_type() == Any::FsStat {
+      self.msg().map(|u| FsStat::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_fs_stat_ready(&'a self) -> Option<FsStatReady> {
+    if self.msg_type() == Any::FsStatReady {
+      self.msg().map(|u| FsStatReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_fs_list(&'a self) -> Option<FsList> {
+    if self.msg_type() == Any::FsList {
+      self.msg().map(|u| FsList::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn msg_as_fs_list_ready(&'a self) -> Option<FsListReady> {
+    if self.msg_type() == Any::FsListReady {
+      self.msg().map(|u| FsListReady::init_from_table(u))
+    } else {
+      None
+    }
+  }
+
+}
+
+pub struct BaseArgs<'a> {
+    pub cmd_id: u32,
+    pub sync: bool,
+    pub error_kind: ErrorKind,
+    pub error: Option<flatbuffers::WIPOffset<&'a  str>>,
     pub msg_type: Any,
     pub msg: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
 }
-impl<'a> Default for BaseArgs<'a> {
+impl<'a> Default for BaseArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        BaseArgs {
+            cmd_id: 0,
+            sync: false,
+            error_kind: ErrorKind::NoError,
+            error: None,
+            msg_type: Any::NONE,
+            msg: None,
+        }
+    }
+}
+pub struct BaseBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> BaseBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_cmd_id(&mut self, cmd_id: u32) {
+    self.fbb_.push_slot::<u32>(Base::VT_CMD_ID, cmd_id, 0);
+  }
+  #[inline]
+  pub fn add_sync(&mut self, sync: bool) {
+    self.fbb_.push_slot::<bool>(Base::VT_SYNC, sync, false);
+  }
+  #[inline]
+  pub fn add_error_kind(&mut self, error_kind: ErrorKind) {
+    self.fbb_.push_slot::<ErrorKind>(Base::VT_ERROR_KIND, error_kind, ErrorKind::NoError);
+  }
+  #[inline]
+  pub fn add_error(&mut self, error: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Base::VT_ERROR, error);
+  }
+  #[inline]
+  pub fn add_msg_type(&mut self, msg_type: Any) {
+    self.fbb_.push_slot::<Any>(Base::VT_MSG_TYPE, msg_type, Any::NONE);
+  }
+  #[inline]
+  pub fn add_msg(&mut self, msg: flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Base::VT_MSG, msg);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> BaseBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    BaseBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Base<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum TimerStartOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct TimerStart<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for TimerStart<'a> {
+    type Inner = TimerStart<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> TimerStart<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        TimerStart {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args TimerStartArgs) -> flatbuffers::WIPOffset<TimerStart<'bldr>> {
+      let mut builder = TimerStartBuilder::new(_fbb);
+      builder.add_delay(args.delay);
+      builder.add_id(args.id);
+      builder.finish()
+    }
+
+    pub const VT_ID: flatbuffers::VOffsetT = 4;
+    pub const VT_DELAY: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub fn id(&self) -> u32 {
+    self._tab.get::<u32>(TimerStart::VT_ID, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn delay(&self) -> u32 {
+    self._tab.get::<u32>(TimerStart::VT_DELAY, Some(0)).unwrap()
+  }
+}
+
+pub struct TimerStartArgs {
+    pub id: u32,
+    pub delay: u32,
+}
+impl<'a> Default for TimerStartArgs {
+    #[inline]
+    fn default() -> Self {
+        TimerStartArgs {
+            id: 0,
+            delay: 0,
+        }
+    }
+}
+pub struct TimerStartBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> TimerStartBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_id(&mut self, id: u32) {
+    self.fbb_.push_slot::<u32>(TimerStart::VT_ID, id, 0);
+  }
+  #[inline]
+  pub fn add_delay(&mut self, delay: u32) {
+    self.fbb_.push_slot::<u32>(TimerStart::VT_DELAY, delay, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> TimerStartBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    TimerStartBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<TimerStart<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum TimerReadyOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct TimerReady<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for TimerReady<'a> {
+    type Inner = TimerReady<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> TimerReady<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        TimerReady {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args TimerReadyArgs) -> flatbuffers::WIPOffset<TimerReady<'bldr>> {
+      let mut builder = TimerReadyBuilder::new(_fbb);
+      builder.add_id(args.id);
+      builder.add_canceled(args.canceled);
+      builder.finish()
+    }
+
+    pub const VT_ID: flatbuffers::VOffsetT = 4;
+    pub const VT_CANCELED: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub fn id(&self) -> u32 {
+    self._tab.get::<u32>(TimerReady::VT_ID, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn canceled(&self) -> bool {
+    self._tab.get::<bool>(TimerReady::VT_CANCELED, Some(false)).unwrap()
+  }
+}
+
+pub struct TimerReadyArgs {
+    pub id: u32,
+    pub canceled: bool,
+}
+impl<'a> Default for TimerReadyArgs {
+    #[inline]
+    fn default() -> Self {
+        TimerReadyArgs {
+            id: 0,
+            canceled: false,
+        }
+    }
+}
+pub struct TimerReadyBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> TimerReadyBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_id(&mut self, id: u32) {
+    self.fbb_.push_slot::<u32>(TimerReady::VT_ID, id, 0);
+  }
+  #[inline]
+  pub fn add_canceled(&mut self, canceled: bool) {
+    self.fbb_.push_slot::<bool>(TimerReady::VT_CANCELED, canceled, false);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> TimerReadyBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    TimerReadyBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<TimerReady<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum TimerClearOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct TimerClear<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for TimerClear<'a> {
+    type Inner = TimerClear<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> TimerClear<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        TimerClear {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args TimerClearArgs) -> flatbuffers::WIPOffset<TimerClear<'bldr>> {
+      let mut builder = TimerClearBuilder::new(_fbb);
+      builder.add_id(args.id);
+      builder.finish()
+    }
+
+    pub const VT_ID: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub fn id(&self) -> u32 {
+    self._tab.get::<u32>(TimerClear::VT_ID, Some(0)).unwrap()
+  }
+}
+
+pub struct TimerClearArgs {
+    pub id: u32,
+}
+impl<'a> Default for TimerClearArgs {
+    #[inline]
+    fn default() -> Self {
+        TimerClearArgs {
+            id: 0,
+        }
+    }
+}
+pub struct TimerClearBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> TimerClearBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_id(&mut self, id: u32) {
+    self.fbb_.push_slot::<u32>(TimerClear::VT_ID, id, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> TimerClearBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    TimerClearBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<TimerClear<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+pub enum HttpHeaderOffset {}
+#[derive(Copy, Clone, Debug, PartialEq)]
+
+pub struct HttpHeader<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for HttpHeader<'a> {
+    type Inner = HttpHeader<'a>;
+    #[inline]
+    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self {
+            _tab: flatbuffers::Table { buf: buf, loc: loc },
+        }
+    }
+}
+
+impl<'a> HttpHeader<'a> {
+    #[inline]
+    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        HttpHeader {
+            _tab: table,
+        }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args HttpHeaderArgs<'args>) -> flatbuffers::WIPOffset<HttpHeader<'bldr>> {
+      let mut builder = HttpHeaderBuilder::new(_fbb);
+      if let Some(x) = args.value { builder.add_value(x); }
+      if let Some(x) = args.key { builder.add_key(x); }
+      builder.finish()
+    }
+
+    pub const VT_KEY: flatbuffers::VOffsetT = 4;
+    pub const VT_VALUE: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub fn key(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(HttpHeader::VT_KEY, None)
+  }
+  #[inline]
+  pub fn value(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(HttpHeader::VT_VALUE, None)
+  }
+}
+
+pub struct HttpHeaderArgs<'a> {
+    pub key: Option<flatbuffers::WIPOffset<&'a  str>>,
+    pub value: Option<flatbuffers::WIPOffset<&'a  str>>,
+}
+impl<'a> Default for HttpHeaderArgs<'a> {
     #[inline]
     fn default() -> Self {
-        BaseArgs {
-            cmd_id: 0,
-            sync: false,
-            error_kind: ErrorKind::NoError,
-            error: None,
-            msg_type: Any::NONE,
-            msg: None,
+        HttpHeaderArgs {
+            key: None,
+            value: None,
         }
     }
 }
-pub struct BaseBuilder<'a: 'b, 'b> {
+pub struct HttpHeaderBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b> BaseBuilder<'a, 'b> {
-  #[inline]
-  pub fn add_cmd_id(&mut self, cmd_id: u32) {
-    self.fbb_.push_slot::<u32>(Base::VT_CMD_ID, cmd_id, 0);
-  }
-  #[inline]
-  pub fn add_sync(&mut self, sync: bool) {
-    self.fbb_.push_slot::<bool>(Base::VT_SYNC, sync, false);
-  }
-  #[inline]
-  pub fn add_error_kind(&mut self, error_kind: ErrorKind) {
-    self.fbb_.push_slot::<ErrorKind>(Base::VT_ERROR_KIND, error_kind, ErrorKind::NoError);
-  }
-  #[inline]
-  pub fn add_error(&mut self, error: flatbuffers::WIPOffset<&'b  str>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Base::VT_ERROR, error);
-  }
+impl<'a: 'b, 'b> HttpHeaderBuilder<'a, 'b> {
   #[inline]
-  pub fn add_msg_type(&mut self, msg_type: Any) {
-    self.fbb_.push_slot::<Any>(Base::VT_MSG_TYPE, msg_type, Any::NONE);
+  pub fn add_key(&mut self, key: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(HttpHeader::VT_KEY, key);
   }
   #[inline]
-  pub fn add_msg(&mut self, msg: flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Base::VT_MSG, msg);
+  pub fn add_value(&mut self, value: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(HttpHeader::VT_VALUE, value);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> BaseBuilder<'a, 'b> {
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> HttpHeaderBuilder<'a, 'b> {
     let start = _fbb.start_table();
-    BaseBuilder {
+    HttpHeaderBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<Base<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<HttpHeader<'a>> {
     let o = self.fbb_.end_table(self.start_);
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-pub enum TimerStartOffset {}
+pub enum StreamChunkOffset {}
 #[derive(Copy, Clone, Debug, PartialEq)]
 
-pub struct TimerStart<'a> {
+pub struct StreamChunk<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for TimerStart<'a> {
-    type Inner = TimerStart<'a>;
+impl<'a> flatbuffers::Follow<'a> for StreamChunk<'a> {
+    type Inner = StreamChunk<'a>;
     #[inline]
     fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
         Self {
@@ -5537,86 +7870,98 @@ impl<'a> flatbuffers::Follow<'a> for TimerStart<'a> {
     }
 }
 
-impl<'a> TimerStart<'a> {
+impl<'a> StreamChunk<'a> {
     #[inline]
     pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        TimerStart {
+        StreamChunk {
             _tab: table,
         }
     }
     #[allow(unused_mut)]
     pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
         _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args TimerStartArgs) -> flatbuffers::WIPOffset<TimerStart<'bldr>> {
-      let mut builder = TimerStartBuilder::new(_fbb);
-      builder.add_delay(args.delay);
+        args: &'args StreamChunkArgs) -> flatbuffers::WIPOffset<StreamChunk<'bldr>> {
+      let mut builder = StreamChunkBuilder::new(_fbb);
       builder.add_id(args.id);
+      builder.add_text(args.text);
+      builder.add_done(args.done);
       builder.finish()
     }
 
     pub const VT_ID: flatbuffers::VOffsetT = 4;
-    pub const VT_DELAY: flatbuffers::VOffsetT = 6;
+    pub const VT_DONE: flatbuffers::VOffsetT = 6;
+    pub const VT_TEXT: flatbuffers::VOffsetT = 8;
 
   #[inline]
   pub fn id(&self) -> u32 {
-    self._tab.get::<u32>(TimerStart::VT_ID, Some(0)).unwrap()
+    self._tab.get::<u32>(StreamChunk::VT_ID, Some(0)).unwrap()
   }
   #[inline]
-  pub fn delay(&self) -> u32 {
-    self._tab.get::<u32>(TimerStart::VT_DELAY, Some(0)).unwrap()
+  pub fn done(&self) -> bool {
+    self._tab.get::<bool>(StreamChunk::VT_DONE, Some(false)).unwrap()
+  }
+  #[inline]
+  pub fn text(&self) -> bool {
+    self._tab.get::<bool>(StreamChunk::VT_TEXT, Some(false)).unwrap()
   }
 }
 
-pub struct TimerStartArgs {
+pub struct StreamChunkArgs {
     pub id: u32,
-    pub delay: u32,
+    pub done: bool,
+    pub text: bool,
 }
-impl<'a> Default for TimerStartArgs {
+impl<'a> Default for StreamChunkArgs {
     #[inline]
     fn default() -> Self {
-        TimerStartArgs {
+        StreamChunkArgs {
             id: 0,
-            delay: 0,
+            done: false,
+            text: false,
         }
     }
 }
-pub struct TimerStartBuilder<'a: 'b, 'b> {
+pub struct StreamChunkBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b> TimerStartBuilder<'a, 'b> {
+impl<'a: 'b, 'b> StreamChunkBuilder<'a, 'b> {
   #[inline]
   pub fn add_id(&mut self, id: u32) {
-    self.fbb_.push_slot::<u32>(TimerStart::VT_ID, id, 0);
+    self.fbb_.push_slot::<u32>(StreamChunk::VT_ID, id, 0);
   }
   #[inline]
-  pub fn add_delay(&mut self, delay: u32) {
-    self.fbb_.push_slot::<u32>(TimerStart::VT_DELAY, delay, 0);
+  pub fn add_done(&mut self, done: bool) {
+    self.fbb_.push_slot::<bool>(StreamChunk::VT_DONE, done, false);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> TimerStartBuilder<'a, 'b> {
+  pub fn add_text(&mut self, text: bool) {
+    self.fbb_.push_slot::<bool>(StreamChunk::VT_TEXT, text, false);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> StreamChunkBuilder<'a, 'b> {
     let start = _fbb.start_table();
-    TimerStartBuilder {
+    StreamChunkBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<TimerStart<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<StreamChunk<'a>> {
     let o = self.fbb_.end_table(self.start_);
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-pub enum TimerReadyOffset {}
+pub enum StreamPullOffset {}
 #[derive(Copy, Clone, Debug, PartialEq)]
 
-pub struct TimerReady<'a> {
+pub struct StreamPull<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for TimerReady<'a> {
-    type Inner = TimerReady<'a>;
+impl<'a> flatbuffers::Follow<'a> for StreamPull<'a> {
+    type Inner = StreamPull<'a>;
     #[inline]
     fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
         Self {
@@ -5625,86 +7970,86 @@ impl<'a> flatbuffers::Follow<'a> for TimerReady<'a> {
     }
 }
 
-impl<'a> TimerReady<'a> {
+impl<'a> StreamPull<'a> {
     #[inline]
     pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        TimerReady {
+        StreamPull {
             _tab: table,
         }
     }
     #[allow(unused_mut)]
     pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
         _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args TimerReadyArgs) -> flatbuffers::WIPOffset<TimerReady<'bldr>> {
-      let mut builder = TimerReadyBuilder::new(_fbb);
+        args: &'args StreamPullArgs) -> flatbuffers::WIPOffset<StreamPull<'bldr>> {
+      let mut builder = StreamPullBuilder::new(_fbb);
       builder.add_id(args.id);
-      builder.add_canceled(args.canceled);
+      builder.add_cancel(args.cancel);
       builder.finish()
     }
 
     pub const VT_ID: flatbuffers::VOffsetT = 4;
-    pub const VT_CANCELED: flatbuffers::VOffsetT = 6;
+    pub const VT_CANCEL: flatbuffers::VOffsetT = 6;
 
   #[inline]
   pub fn id(&self) -> u32 {
-    self._tab.get::<u32>(TimerReady::VT_ID, Some(0)).unwrap()
+    self._tab.get::<u32>(StreamPull::VT_ID, Some(0)).unwrap()
   }
   #[inline]
-  pub fn canceled(&self) -> bool {
-    self._tab.get::<bool>(TimerReady::VT_CANCELED, Some(false)).unwrap()
+  pub fn cancel(&self) -> bool {
+    self._tab.get::<bool>(StreamPull::VT_CANCEL, Some(false)).unwrap()
   }
 }
 
-pub struct TimerReadyArgs {
+pub struct StreamPullArgs {
     pub id: u32,
-    pub canceled: bool,
+    pub cancel: bool,
 }
-impl<'a> Default for TimerReadyArgs {
+impl<'a> Default for StreamPullArgs {
     #[inline]
     fn default() -> Self {
-        TimerReadyArgs {
+        StreamPullArgs {
             id: 0,
-            canceled: false,
+            cancel: false,
         }
     }
 }
-pub struct TimerReadyBuilder<'a: 'b, 'b> {
+pub struct StreamPullBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b> TimerReadyBuilder<'a, 'b> {
+impl<'a: 'b, 'b> StreamPullBuilder<'a, 'b> {
   #[inline]
   pub fn add_id(&mut self, id: u32) {
-    self.fbb_.push_slot::<u32>(TimerReady::VT_ID, id, 0);
+    self.fbb_.push_slot::<u32>(StreamPull::VT_ID, id, 0);
   }
   #[inline]
-  pub fn add_canceled(&mut self, canceled: bool) {
-    self.fbb_.push_slot::<bool>(TimerReady::VT_CANCELED, canceled, false);
+  pub fn add_cancel(&mut self, cancel: bool) {
+    self.fbb_.push_slot::<bool>(StreamPull::VT_CANCEL, cancel, false);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> TimerReadyBuilder<'a, 'b> {
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> StreamPullBuilder<'a, 'b> {
     let start = _fbb.start_table();
-    TimerReadyBuilder {
+    StreamPullBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<TimerReady<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<StreamPull<'a>> {
     let o = self.fbb_.end_table(self.start_);
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-pub enum TimerClearOffset {}
+pub enum StreamCancelOffset {}
 #[derive(Copy, Clone, Debug, PartialEq)]
 
-pub struct TimerClear<'a> {
+pub struct StreamCancel<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for TimerClear<'a> {
-    type Inner = TimerClear<'a>;
+impl<'a> flatbuffers::Follow<'a> for StreamCancel<'a> {
+    type Inner = StreamCancel<'a>;
     #[inline]
     fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
         Self {
@@ -5713,18 +8058,18 @@ impl<'a> flatbuffers::Follow<'a> for TimerClear<'a> {
     }
 }
 
-impl<'a> TimerClear<'a> {
+impl<'a> StreamCancel<'a> {
     #[inline]
     pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        TimerClear {
+        StreamCancel {
             _tab: table,
         }
     }
     #[allow(unused_mut)]
     pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
         _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args TimerClearArgs) -> flatbuffers::WIPOffset<TimerClear<'bldr>> {
-      let mut builder = TimerClearBuilder::new(_fbb);
+        args: &'args StreamCancelArgs) -> flatbuffers::WIPOffset<StreamCancel<'bldr>> {
+      let mut builder = StreamCancelBuilder::new(_fbb);
       builder.add_id(args.id);
       builder.finish()
     }
@@ -5733,54 +8078,54 @@ impl<'a> TimerClear<'a> {
 
   #[inline]
   pub fn id(&self) -> u32 {
-    self._tab.get::<u32>(TimerClear::VT_ID, Some(0)).unwrap()
+    self._tab.get::<u32>(StreamCancel::VT_ID, Some(0)).unwrap()
   }
 }
 
-pub struct TimerClearArgs {
+pub struct StreamCancelArgs {
     pub id: u32,
 }
-impl<'a> Default for TimerClearArgs {
+impl<'a> Default for StreamCancelArgs {
     #[inline]
     fn default() -> Self {
-        TimerClearArgs {
+        StreamCancelArgs {
             id: 0,
         }
     }
 }
-pub struct TimerClearBuilder<'a: 'b, 'b> {
+pub struct StreamCancelBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b> TimerClearBuilder<'a, 'b> {
+impl<'a: 'b, 'b> StreamCancelBuilder<'a, 'b> {
   #[inline]
   pub fn add_id(&mut self, id: u32) {
-    self.fbb_.push_slot::<u32>(TimerClear::VT_ID, id, 0);
+    self.fbb_.push_slot::<u32>(StreamCancel::VT_ID, id, 0);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> TimerClearBuilder<'a, 'b> {
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> StreamCancelBuilder<'a, 'b> {
     let start = _fbb.start_table();
-    TimerClearBuilder {
+    StreamCancelBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<TimerClear<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<StreamCancel<'a>> {
     let o = self.fbb_.end_table(self.start_);
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-pub enum HttpHeaderOffset {}
+pub enum HttpRequestAbortOffset {}
 #[derive(Copy, Clone, Debug, PartialEq)]
 
-pub struct HttpHeader<'a> {
+pub struct HttpRequestAbort<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for HttpHeader<'a> {
-    type Inner = HttpHeader<'a>;
+impl<'a> flatbuffers::Follow<'a> for HttpRequestAbort<'a> {
+    type Inner = HttpRequestAbort<'a>;
     #[inline]
     fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
         Self {
@@ -5789,86 +8134,74 @@ impl<'a> flatbuffers::Follow<'a> for HttpHeader<'a> {
     }
 }
 
-impl<'a> HttpHeader<'a> {
+impl<'a> HttpRequestAbort<'a> {
     #[inline]
     pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        HttpHeader {
+        HttpRequestAbort {
             _tab: table,
         }
     }
     #[allow(unused_mut)]
     pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
         _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args HttpHeaderArgs<'args>) -> flatbuffers::WIPOffset<HttpHeader<'bldr>> {
-      let mut builder = HttpHeaderBuilder::new(_fbb);
-      if let Some(x) = args.value { builder.add_value(x); }
-      if let Some(x) = args.key { builder.add_key(x); }
+        args: &'args HttpRequestAbortArgs) -> flatbuffers::WIPOffset<HttpRequestAbort<'bldr>> {
+      let mut builder = HttpRequestAbortBuilder::new(_fbb);
+      builder.add_id(args.id);
       builder.finish()
     }
 
-    pub const VT_KEY: flatbuffers::VOffsetT = 4;
-    pub const VT_VALUE: flatbuffers::VOffsetT = 6;
+    pub const VT_ID: flatbuffers::VOffsetT = 4;
 
   #[inline]
-  pub fn key(&self) -> Option<&'a str> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(HttpHeader::VT_KEY, None)
-  }
-  #[inline]
-  pub fn value(&self) -> Option<&'a str> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(HttpHeader::VT_VALUE, None)
+  pub fn id(&self) -> u32 {
+    self._tab.get::<u32>(HttpRequestAbort::VT_ID, Some(0)).unwrap()
   }
 }
 
-pub struct HttpHeaderArgs<'a> {
-    pub key: Option<flatbuffers::WIPOffset<&'a  str>>,
-    pub value: Option<flatbuffers::WIPOffset<&'a  str>>,
+pub struct HttpRequestAbortArgs {
+    pub id: u32,
 }
-impl<'a> Default for HttpHeaderArgs<'a> {
+impl<'a> Default for HttpRequestAbortArgs {
     #[inline]
     fn default() -> Self {
-        HttpHeaderArgs {
-            key: None,
-            value: None,
+        HttpRequestAbortArgs {
+            id: 0,
         }
     }
 }
-pub struct HttpHeaderBuilder<'a: 'b, 'b> {
+pub struct HttpRequestAbortBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b> HttpHeaderBuilder<'a, 'b> {
-  #[inline]
-  pub fn add_key(&mut self, key: flatbuffers::WIPOffset<&'b  str>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(HttpHeader::VT_KEY, key);
-  }
+impl<'a: 'b, 'b> HttpRequestAbortBuilder<'a, 'b> {
   #[inline]
-  pub fn add_value(&mut self, value: flatbuffers::WIPOffset<&'b  str>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(HttpHeader::VT_VALUE, value);
+  pub fn add_id(&mut self, id: u32) {
+    self.fbb_.push_slot::<u32>(HttpRequestAbort::VT_ID, id, 0);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> HttpHeaderBuilder<'a, 'b> {
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> HttpRequestAbortBuilder<'a, 'b> {
     let start = _fbb.start_table();
-    HttpHeaderBuilder {
+    HttpRequestAbortBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<HttpHeader<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<HttpRequestAbort<'a>> {
     let o = self.fbb_.end_table(self.start_);
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-pub enum StreamChunkOffset {}
+pub enum FetchAbortOffset {}
 #[derive(Copy, Clone, Debug, PartialEq)]
 
-pub struct StreamChunk<'a> {
+pub struct FetchAbort<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for StreamChunk<'a> {
-    type Inner = StreamChunk<'a>;
+impl<'a> flatbuffers::Follow<'a> for FetchAbort<'a> {
+    type Inner = FetchAbort<'a>;
     #[inline]
     fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
         Self {
@@ -5877,72 +8210,60 @@ impl<'a> flatbuffers::Follow<'a> for StreamChunk<'a> {
     }
 }
 
-impl<'a> StreamChunk<'a> {
+impl<'a> FetchAbort<'a> {
     #[inline]
     pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        StreamChunk {
+        FetchAbort {
             _tab: table,
         }
     }
     #[allow(unused_mut)]
     pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
         _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args StreamChunkArgs) -> flatbuffers::WIPOffset<StreamChunk<'bldr>> {
-      let mut builder = StreamChunkBuilder::new(_fbb);
+        args: &'args FetchAbortArgs) -> flatbuffers::WIPOffset<FetchAbort<'bldr>> {
+      let mut builder = FetchAbortBuilder::new(_fbb);
       builder.add_id(args.id);
-      builder.add_done(args.done);
       builder.finish()
     }
 
     pub const VT_ID: flatbuffers::VOffsetT = 4;
-    pub const VT_DONE: flatbuffers::VOffsetT = 6;
 
   #[inline]
   pub fn id(&self) -> u32 {
-    self._tab.get::<u32>(StreamChunk::VT_ID, Some(0)).unwrap()
-  }
-  #[inline]
-  pub fn done(&self) -> bool {
-    self._tab.get::<bool>(StreamChunk::VT_DONE, Some(false)).unwrap()
+    self._tab.get::<u32>(FetchAbort::VT_ID, Some(0)).unwrap()
   }
 }
 
-pub struct StreamChunkArgs {
+pub struct FetchAbortArgs {
     pub id: u32,
-    pub done: bool,
 }
-impl<'a> Default for StreamChunkArgs {
+impl<'a> Default for FetchAbortArgs {
     #[inline]
     fn default() -> Self {
-        StreamChunkArgs {
+        FetchAbortArgs {
             id: 0,
-            done: false,
         }
     }
 }
-pub struct StreamChunkBuilder<'a: 'b, 'b> {
+pub struct FetchAbortBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b> StreamChunkBuilder<'a, 'b> {
+impl<'a: 'b, 'b> FetchAbortBuilder<'a, 'b> {
   #[inline]
   pub fn add_id(&mut self, id: u32) {
-    self.fbb_.push_slot::<u32>(StreamChunk::VT_ID, id, 0);
-  }
-  #[inline]
-  pub fn add_done(&mut self, done: bool) {
-    self.fbb_.push_slot::<bool>(StreamChunk::VT_DONE, done, false);
+    self.fbb_.push_slot::<u32>(FetchAbort::VT_ID, id, 0);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> StreamChunkBuilder<'a, 'b> {
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FetchAbortBuilder<'a, 'b> {
     let start = _fbb.start_table();
-    StreamChunkBuilder {
+    FetchAbortBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<StreamChunk<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<FetchAbort<'a>> {
     let o = self.fbb_.end_table(self.start_);
     flatbuffers::WIPOffset::new(o.value())
   }
@@ -5977,10 +8298,15 @@ impl<'a> HttpRequest<'a> {
         _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
         args: &'args HttpRequestArgs<'args>) -> flatbuffers::WIPOffset<HttpRequest<'bldr>> {
       let mut builder = HttpRequestBuilder::new(_fbb);
+      if let Some(x) = args.client_identity { builder.add_client_identity(x); }
+      builder.add_retries(args.retries);
+      builder.add_max_redirects(args.max_redirects);
+      builder.add_timeout_ms(args.timeout_ms);
       if let Some(x) = args.remote_addr { builder.add_remote_addr(x); }
       if let Some(x) = args.headers { builder.add_headers(x); }
       if let Some(x) = args.url { builder.add_url(x); }
       builder.add_id(args.id);
+      builder.add_redirect(args.redirect);
       builder.add_has_body(args.has_body);
       builder.add_method(args.method);
       builder.finish()
@@ -5992,6 +8318,11 @@ impl<'a> HttpRequest<'a> {
     pub const VT_HEADERS: flatbuffers::VOffsetT = 10;
     pub const VT_REMOTE_ADDR: flatbuffers::VOffsetT = 12;
     pub const VT_HAS_BODY: flatbuffers::VOffsetT = 14;
+    pub const VT_TIMEOUT_MS: flatbuffers::VOffsetT = 16;
+    pub const VT_REDIRECT: flatbuffers::VOffsetT = 18;
+    pub const VT_MAX_REDIRECTS: flatbuffers::VOffsetT = 20;
+    pub const VT_RETRIES: flatbuffers::VOffsetT = 22;
+    pub const VT_CLIENT_IDENTITY: flatbuffers::VOffsetT = 24;
 
   #[inline]
   pub fn id(&self) -> u32 {
@@ -6017,6 +8348,26 @@ impl<'a> HttpRequest<'a> {
   pub fn has_body(&self) -> bool {
     self._tab.get::<bool>(HttpRequest::VT_HAS_BODY, Some(false)).unwrap()
   }
+  #[inline]
+  pub fn timeout_ms(&self) -> u32 {
+    self._tab.get::<u32>(HttpRequest::VT_TIMEOUT_MS, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn redirect(&self) -> RedirectMode {
+    self._tab.get::<RedirectMode>(HttpRequest::VT_REDIRECT, Some(RedirectMode::Follow)).unwrap()
+  }
+  #[inline]
+  pub fn max_redirects(&self) -> u32 {
+    self._tab.get::<u32>(HttpRequest::VT_MAX_REDIRECTS, Some(20)).unwrap()
+  }
+  #[inline]
+  pub fn retries(&self) -> u32 {
+    self._tab.get::<u32>(HttpRequest::VT_RETRIES, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn client_identity(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(HttpRequest::VT_CLIENT_IDENTITY, None)
+  }
 }
 
 pub struct HttpRequestArgs<'a> {
@@ -6026,6 +8377,11 @@ pub struct HttpRequestArgs<'a> {
     pub headers: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a , flatbuffers::ForwardsUOffset<HttpHeader<'a >>>>>,
     pub remote_addr: Option<flatbuffers::WIPOffset<&'a  str>>,
     pub has_body: bool,
+    pub timeout_ms: u32,
+    pub redirect: RedirectMode,
+    pub max_redirects: u32,
+    pub retries: u32,
+    pub client_identity: Option<flatbuffers::WIPOffset<&'a  str>>,
 }
 impl<'a> Default for HttpRequestArgs<'a> {
     #[inline]
@@ -6037,6 +8393,11 @@ impl<'a> Default for HttpRequestArgs<'a> {
             headers: None,
             remote_addr: None,
             has_body: false,
+            timeout_ms: 0,
+            redirect: RedirectMode::Follow,
+            max_redirects: 20,
+            retries: 0,
+            client_identity: None,
         }
     }
 }
@@ -6070,6 +8431,26 @@ impl<'a: 'b, 'b> HttpRequestBuilder<'a, 'b> {
     self.fbb_.push_slot::<bool>(HttpRequest::VT_HAS_BODY, has_body, false);
   }
   #[inline]
+  pub fn add_timeout_ms(&mut self, timeout_ms: u32) {
+    self.fbb_.push_slot::<u32>(HttpRequest::VT_TIMEOUT_MS, timeout_ms, 0);
+  }
+  #[inline]
+  pub fn add_redirect(&mut self, redirect: RedirectMode) {
+    self.fbb_.push_slot::<RedirectMode>(HttpRequest::VT_REDIRECT, redirect, RedirectMode::Follow);
+  }
+  #[inline]
+  pub fn add_max_redirects(&mut self, max_redirects: u32) {
+    self.fbb_.push_slot::<u32>(HttpRequest::VT_MAX_REDIRECTS, max_redirects, 20);
+  }
+  #[inline]
+  pub fn add_retries(&mut self, retries: u32) {
+    self.fbb_.push_slot::<u32>(HttpRequest::VT_RETRIES, retries, 0);
+  }
+  #[inline]
+  pub fn add_client_identity(&mut self, client_identity: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(HttpRequest::VT_CLIENT_IDENTITY, client_identity);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> HttpRequestBuilder<'a, 'b> {
     let start = _fbb.start_table();
     HttpRequestBuilder {
@@ -6116,6 +8497,7 @@ impl<'a> HttpResponse<'a> {
       if let Some(x) = args.headers { builder.add_headers(x); }
       builder.add_id(args.id);
       builder.add_status(args.status);
+      builder.add_websocket(args.websocket);
       builder.add_has_body(args.has_body);
       builder.finish()
     }
@@ -6124,6 +8506,7 @@ impl<'a> HttpResponse<'a> {
     pub const VT_HEADERS: flatbuffers::VOffsetT = 6;
     pub const VT_STATUS: flatbuffers::VOffsetT = 8;
     pub const VT_HAS_BODY: flatbuffers::VOffsetT = 10;
+    pub const VT_WEBSOCKET: flatbuffers::VOffsetT = 12;
 
   #[inline]
   pub fn id(&self) -> u32 {
@@ -6141,6 +8524,10 @@ impl<'a> HttpResponse<'a> {
   pub fn has_body(&self) -> bool {
     self._tab.get::<bool>(HttpResponse::VT_HAS_BODY, Some(false)).unwrap()
   }
+  #[inline]
+  pub fn websocket(&self) -> bool {
+    self._tab.get::<bool>(HttpResponse::VT_WEBSOCKET, Some(false)).unwrap()
+  }
 }
 
 pub struct HttpResponseArgs<'a> {
@@ -6148,6 +8535,7 @@ pub struct HttpResponseArgs<'a> {
     pub headers: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a , flatbuffers::ForwardsUOffset<HttpHeader<'a >>>>>,
     pub status: u16,
     pub has_body: bool,
+    pub websocket: bool,
 }
 impl<'a> Default for HttpResponseArgs<'a> {
     #[inline]
@@ -6157,6 +8545,7 @@ impl<'a> Default for HttpResponseArgs<'a> {
             headers: None,
             status: 0,
             has_body: false,
+            websocket: false,
         }
     }
 }
@@ -6182,6 +8571,10 @@ impl<'a: 'b, 'b> HttpResponseBuilder<'a, 'b> {
     self.fbb_.push_slot::<bool>(HttpResponse::VT_HAS_BODY, has_body, false);
   }
   #[inline]
+  pub fn add_websocket(&mut self, websocket: bool) {
+    self.fbb_.push_slot::<bool>(HttpResponse::VT_WEBSOCKET, websocket, false);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> HttpResponseBuilder<'a, 'b> {
     let start = _fbb.start_table();
     HttpResponseBuilder {
@@ -6301,9 +8694,11 @@ impl<'a> FetchHttpResponse<'a> {
         _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
         args: &'args FetchHttpResponseArgs<'args>) -> flatbuffers::WIPOffset<FetchHttpResponse<'bldr>> {
       let mut builder = FetchHttpResponseBuilder::new(_fbb);
+      if let Some(x) = args.url { builder.add_url(x); }
       if let Some(x) = args.headers { builder.add_headers(x); }
       builder.add_id(args.id);
       builder.add_status(args.status);
+      builder.add_redirected(args.redirected);
       builder.add_has_body(args.has_body);
       builder.finish()
     }
@@ -6312,6 +8707,8 @@ impl<'a> FetchHttpResponse<'a> {
     pub const VT_HEADERS: flatbuffers::VOffsetT = 6;
     pub const VT_STATUS: flatbuffers::VOffsetT = 8;
     pub const VT_HAS_BODY: flatbuffers::VOffsetT = 10;
+    pub const VT_URL: flatbuffers::VOffsetT = 12;
+    pub const VT_REDIRECTED: flatbuffers::VOffsetT = 14;
 
   #[inline]
   pub fn id(&self) -> u32 {
@@ -6329,6 +8726,14 @@ impl<'a> FetchHttpResponse<'a> {
   pub fn has_body(&self) -> bool {
     self._tab.get::<bool>(FetchHttpResponse::VT_HAS_BODY, Some(false)).unwrap()
   }
+  #[inline]
+  pub fn url(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(FetchHttpResponse::VT_URL, None)
+  }
+  #[inline]
+  pub fn redirected(&self) -> bool {
+    self._tab.get::<bool>(FetchHttpResponse::VT_REDIRECTED, Some(false)).unwrap()
+  }
 }
 
 pub struct FetchHttpResponseArgs<'a> {
@@ -6336,6 +8741,8 @@ pub struct FetchHttpResponseArgs<'a> {
     pub headers: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a , flatbuffers::ForwardsUOffset<HttpHeader<'a >>>>>,
     pub status: u16,
     pub has_body: bool,
+    pub url: Option<flatbuffers::WIPOffset<&'a  str>>,
+    pub redirected: bool,
 }
 impl<'a> Default for FetchHttpResponseArgs<'a> {
     #[inline]
@@ -6345,6 +8752,8 @@ impl<'a> Default for FetchHttpResponseArgs<'a> {
             headers: None,
             status: 0,
             has_body: false,
+            url: None,
+            redirected: false,
         }
     }
 }
@@ -6370,6 +8779,14 @@ impl<'a: 'b, 'b> FetchHttpResponseBuilder<'a, 'b> {
     self.fbb_.push_slot::<bool>(FetchHttpResponse::VT_HAS_BODY, has_body, false);
   }
   #[inline]
+  pub fn add_url(&mut self, url: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(FetchHttpResponse::VT_URL, url);
+  }
+  #[inline]
+  pub fn add_redirected(&mut self, redirected: bool) {
+    self.fbb_.push_slot::<bool>(FetchHttpResponse::VT_REDIRECTED, redirected, false);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FetchHttpResponseBuilder<'a, 'b> {
     let start = _fbb.start_table();
     FetchHttpResponseBuilder {
@@ -20,7 +20,13 @@ impl MessageHandler for DefaultMessageHandler {
             msg::Any::TimerClear => ops::timers::op_timer_clear,
             msg::Any::HttpRequest => ops::fetch::op_fetch,
             msg::Any::HttpResponse => ops::fetch::op_http_response,
+            msg::Any::FetchAbort => ops::fetch::op_fetch_abort,
+            msg::Any::FsWrite => ops::fs::op_fs_write,
+            msg::Any::FsDelete => ops::fs::op_fs_delete,
+            msg::Any::FsStat => ops::fs::op_fs_stat,
+            msg::Any::FsList => ops::fs::op_fs_list,
             msg::Any::StreamChunk => ops::streams::op_stream_chunk,
+            msg::Any::StreamPull => ops::streams::op_stream_pull,
             msg::Any::CacheGet => ops::cache::op_cache_get,
             msg::Any::CacheSet => ops::cache::op_cache_set,
             msg::Any::CacheDel => ops::cache::op_cache_del,
@@ -31,6 +37,10 @@ impl MessageHandler for DefaultMessageHandler {
             msg::Any::CachePurgeTag => ops::cache::op_cache_purge_tag,
             msg::Any::CryptoDigest => ops::crypto::op_crypto_digest,
             msg::Any::CryptoRandomValues => ops::crypto::op_crypto_random_values,
+            msg::Any::CryptoImportKey => ops::crypto::op_crypto_import_key,
+            msg::Any::CryptoSign => ops::crypto::op_crypto_sign,
+            msg::Any::CryptoVerify => ops::crypto::op_crypto_verify,
+            msg::Any::CryptoCipher => ops::crypto::op_crypto_cipher,
             msg::Any::SourceMap => ops::source_map::op_source_map,
             msg::Any::DataPut => ops::data::op_data_put,
             msg::Any::DataGet => ops::data::op_data_get,
@@ -44,6 +54,9 @@ impl MessageHandler for DefaultMessageHandler {
             msg::Any::ImageApplyTransforms => ops::image::op_image_transform,
             msg::Any::AcmeGetChallenge => ops::acme::op_get_challenge,
             msg::Any::OsExit => ops::os::op_exit,
+            msg::Any::WebSocketConnect => ops::websocket::op_websocket_connect,
+            msg::Any::WebSocketSend => ops::websocket::op_websocket_send,
+            msg::Any::WebSocketClose => ops::websocket::op_websocket_close,
             _ => unimplemented!(),
         };
 
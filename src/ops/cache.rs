@@ -1,10 +1,11 @@
-use futures::sync::mpsc;
-
 use crate::msg;
 use flatbuffers::FlatBufferBuilder;
 
+use crate::errors::{self, ErrorKind, FlyResult};
+use crate::http_cache;
 use crate::js::*;
 use crate::runtime::Runtime;
+use crate::runtime_permissions::Capability;
 use crate::utils::*;
 use libfly::*;
 
@@ -15,9 +16,24 @@ use futures::{Future, Stream};
 use crate::cache_store::*;
 use crate::cache_store_notifier::*;
 
+// The edge cache keeps its entries in the same store, they aren't the app's
+// to read or write.
+fn check_key(rt: &Runtime, key: &str) -> FlyResult<()> {
+  if http_cache::is_edge_key(key) {
+    return Err(errors::new(
+      ErrorKind::PermissionDenied,
+      format!("cache key {} is reserved", key),
+    ));
+  }
+  rt.check_permission(Capability::CacheNamespace(key))
+}
+
 pub fn op_cache_del(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
   let msg = base.msg_as_cache_del().unwrap();
   let key = msg.key().unwrap().to_string();
+  if let Err(e) = check_key(rt, &key) {
+    return odd_future(e);
+  }
 
   rt.spawn(
     rt.cache_store
@@ -31,6 +47,9 @@ pub fn op_cache_del(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op
 pub fn op_cache_expire(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
   let msg = base.msg_as_cache_expire().unwrap();
   let key = msg.key().unwrap().to_string();
+  if let Err(e) = check_key(rt, &key) {
+    return odd_future(e);
+  }
   let ttl = msg.ttl();
 
   rt.spawn(
@@ -46,10 +65,13 @@ pub fn op_cache_set(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op
   let cmd_id = base.cmd_id();
   let msg = base.msg_as_cache_set().unwrap();
   let key = msg.key().unwrap().to_string();
+  if let Err(e) = check_key(rt, &key) {
+    return odd_future(e);
+  }
 
   let stream_id = get_next_stream_id();
 
-  let (sender, recver) = mpsc::unbounded::<Vec<u8>>();
+  let (sender, recver) = stream_channel();
   {
     rt.streams.lock().unwrap().insert(stream_id, sender);
   }
@@ -112,6 +134,9 @@ pub fn op_cache_get(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op
   let stream_id = get_next_stream_id();
 
   let key = msg.key().unwrap().to_string();
+  if let Err(e) = check_key(rt, &key) {
+    return odd_future(e);
+  }
   let ptr = rt.ptr;
 
   Box::new(
@@ -163,6 +188,9 @@ pub fn op_cache_notify_del(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) ->
   let msg = base.msg_as_cache_notify_del().unwrap();
 
   let key = msg.key().unwrap().to_string();
+  if let Err(e) = check_key(rt, &key) {
+    return odd_future(e);
+  }
 
   Box::new(
     rt.cache_store
@@ -182,6 +210,9 @@ pub fn op_cache_notify_purge_tag(rt: &mut Runtime, base: &msg::Base, _raw: fly_b
   let msg = base.msg_as_cache_notify_purge_tag().unwrap();
 
   let tag = msg.tag().unwrap().to_string();
+  if let Err(e) = rt.check_permission(Capability::CacheNamespace(&tag)) {
+    return odd_future(e);
+  }
 
   Box::new(
     rt.cache_store
@@ -200,6 +231,9 @@ pub fn op_cache_notify_purge_tag(rt: &mut Runtime, base: &msg::Base, _raw: fly_b
 pub fn op_cache_set_meta(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
   let msg = base.msg_as_cache_set_meta().unwrap();
   let key = msg.key().unwrap().to_string();
+  if let Err(e) = check_key(rt, &key) {
+    return odd_future(e);
+  }
   let meta = msg.meta().unwrap().to_string();
 
   rt.spawn(
@@ -214,6 +248,9 @@ pub fn op_cache_set_meta(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> B
 pub fn op_cache_purge_tag(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
   let msg = base.msg_as_cache_purge_tag().unwrap();
   let tag = msg.tag().unwrap().to_string();
+  if let Err(e) = rt.check_permission(Capability::CacheNamespace(&tag)) {
+    return odd_future(e);
+  }
 
   rt.spawn(
     rt.cache_store
@@ -1,3 +1,4 @@
+use crate::errors::{self, ErrorKind, FlyError, FlyResult};
 use crate::msg;
 use flatbuffers::FlatBufferBuilder;
 
@@ -14,10 +15,23 @@ use sha1::Sha1;
 
 #[allow(unused_imports)]
 use sha2::Digest; // puts trait in scope
-use sha2::Sha256;
+use sha2::{Sha256, Sha384, Sha512};
+
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::ec::{EcGroup, EcKey, EcPoint};
+use openssl::ecdsa::EcdsaSig;
+use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
+use openssl::memcmp;
+use openssl::nid::Nid;
+use openssl::pkey::{HasPublic, PKey, PKeyRef, Private, Public};
+use openssl::sign::{Signer, Verifier};
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
 
 use futures::future;
+use std::collections::HashMap;
 use std::slice;
+use std::sync::{Arc, Mutex};
 
 pub fn op_crypto_random_values(_rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
     let cmd_id = base.cmd_id();
@@ -55,8 +69,12 @@ pub fn op_crypto_digest(_rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Bo
     let cmd_id = base.cmd_id();
     let msg = base.msg_as_crypto_digest().unwrap();
 
-    let algo = msg.algo().unwrap().to_uppercase();
-    let buffer = unsafe { slice::from_raw_parts(raw.data_ptr, raw.data_len) }.to_vec();
+    let algo = msg.algo().unwrap_or("").to_uppercase();
+    match algo.as_str() {
+        "SHA-256" | "SHA-384" | "SHA-512" | "SHA-1" => {}
+        _ => return odd_future(unsupported("digest", &algo)),
+    }
+    let buffer = raw_bytes(&raw);
 
     Box::new(future::lazy(move || {
         let builder = &mut FlatBufferBuilder::new();
@@ -67,13 +85,25 @@ pub fn op_crypto_digest(_rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Bo
                 let res = h.result();
                 builder.create_vector(res.as_slice())
             }
+            "SHA-384" => {
+                let mut h = Sha384::default();
+                h.input(buffer.as_slice());
+                let res = h.result();
+                builder.create_vector(res.as_slice())
+            }
+            "SHA-512" => {
+                let mut h = Sha512::default();
+                h.input(buffer.as_slice());
+                let res = h.result();
+                builder.create_vector(res.as_slice())
+            }
             "SHA-1" => {
                 let mut h = Sha1::default();
                 h.input(buffer.as_slice());
                 let res = h.result();
                 builder.create_vector(res.as_slice())
             }
-            _ => unimplemented!(),
+            _ => return Err(unsupported("digest", &algo)),
         };
 
         let crypto_ready = msg::CryptoDigestReady::create(
@@ -94,3 +124,617 @@ pub fn op_crypto_digest(_rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Bo
         ))
     }))
 }
+
+/// Key material imported by an app, referred to from JS by id.
+pub enum CryptoKey {
+    Hmac {
+        hash: MessageDigest,
+        key: Vec<u8>,
+    },
+    AesGcm {
+        key: Vec<u8>,
+    },
+    EcdsaPublic {
+        hash: MessageDigest,
+        key: PKey<Public>,
+    },
+    EcdsaPrivate {
+        hash: MessageDigest,
+        key: PKey<Private>,
+    },
+}
+
+impl CryptoKey {
+    pub fn key_type(&self) -> &'static str {
+        match self {
+            CryptoKey::Hmac { .. } | CryptoKey::AesGcm { .. } => "secret",
+            CryptoKey::EcdsaPublic { .. } => "public",
+            CryptoKey::EcdsaPrivate { .. } => "private",
+        }
+    }
+}
+
+/// Keys imported in a runtime. Importing the same key twice returns the same
+/// id, so apps importing a key on each request don't grow this unbounded.
+#[derive(Default)]
+pub struct CryptoKeys {
+    keys: Mutex<HashMap<u32, Arc<CryptoKey>>>,
+    ids: Mutex<HashMap<Vec<u8>, u32>>,
+}
+
+impl CryptoKeys {
+    pub fn new() -> Self {
+        CryptoKeys::default()
+    }
+
+    pub fn get(&self, id: u32) -> FlyResult<Arc<CryptoKey>> {
+        match self.keys.lock().unwrap().get(&id) {
+            Some(key) => Ok(key.clone()),
+            None => Err(errors::new(
+                ErrorKind::InvalidAccessError,
+                format!("unknown crypto key: {}", id),
+            )),
+        }
+    }
+
+    fn insert(&self, fingerprint: Vec<u8>, key: CryptoKey) -> (u32, Arc<CryptoKey>) {
+        let mut ids = self.ids.lock().unwrap();
+        let mut keys = self.keys.lock().unwrap();
+        if let Some(id) = ids.get(&fingerprint) {
+            if let Some(key) = keys.get(id) {
+                return (*id, key.clone());
+            }
+        }
+        let id = crate::get_next_stream_id();
+        let key = Arc::new(key);
+        ids.insert(fingerprint, id);
+        keys.insert(id, key.clone());
+        (id, key)
+    }
+
+    pub fn clear(&self) {
+        self.ids.lock().unwrap().clear();
+        self.keys.lock().unwrap().clear();
+    }
+}
+
+fn unsupported(operation: &str, algo: &str) -> FlyError {
+    errors::new(
+        ErrorKind::NotSupportedError,
+        format!("unsupported {} algorithm: {}", operation, algo),
+    )
+}
+
+fn data_error(e: ErrorStack) -> FlyError {
+    errors::new(ErrorKind::DataError, format!("invalid key data: {}", e))
+}
+
+fn operation_error(e: ErrorStack) -> FlyError {
+    errors::new(ErrorKind::OperationError, format!("{}", e))
+}
+
+fn parse_hash(name: Option<&str>) -> FlyResult<MessageDigest> {
+    let name = name.unwrap_or("").to_uppercase();
+    match name.as_str() {
+        "SHA-1" => Ok(MessageDigest::sha1()),
+        "SHA-256" => Ok(MessageDigest::sha256()),
+        "SHA-384" => Ok(MessageDigest::sha384()),
+        "SHA-512" => Ok(MessageDigest::sha512()),
+        _ => Err(unsupported("hash", &name)),
+    }
+}
+
+fn parse_curve(name: Option<&str>) -> FlyResult<EcGroup> {
+    let nid = match name.unwrap_or("").to_uppercase().as_str() {
+        "P-256" => Nid::X9_62_PRIME256V1,
+        "P-384" => Nid::SECP384R1,
+        "P-521" => Nid::SECP521R1,
+        other => return Err(unsupported("curve", other)),
+    };
+    EcGroup::from_curve_name(nid).map_err(operation_error)
+}
+
+pub fn import_key(
+    format: &str,
+    algo: &str,
+    hash: Option<&str>,
+    named_curve: Option<&str>,
+    data: &[u8],
+) -> FlyResult<CryptoKey> {
+    match (algo.to_uppercase().as_str(), format) {
+        ("HMAC", "raw") => Ok(CryptoKey::Hmac {
+            hash: parse_hash(hash)?,
+            key: data.to_vec(),
+        }),
+        ("AES-GCM", "raw") => match data.len() {
+            16 | 32 => Ok(CryptoKey::AesGcm { key: data.to_vec() }),
+            n => Err(errors::new(
+                ErrorKind::DataError,
+                format!("AES-GCM keys must be 128 or 256 bits, got {}", n * 8),
+            )),
+        },
+        ("ECDSA", "raw") => {
+            let group = parse_curve(named_curve)?;
+            let mut ctx = BigNumContext::new().map_err(operation_error)?;
+            let point = EcPoint::from_bytes(&group, data, &mut ctx).map_err(data_error)?;
+            let ec = EcKey::from_public_key(&group, &point).map_err(data_error)?;
+            Ok(CryptoKey::EcdsaPublic {
+                hash: parse_hash(hash)?,
+                key: PKey::from_ec_key(ec).map_err(data_error)?,
+            })
+        }
+        ("ECDSA", "spki") => Ok(CryptoKey::EcdsaPublic {
+            hash: parse_hash(hash)?,
+            key: PKey::public_key_from_der(data).map_err(data_error)?,
+        }),
+        ("ECDSA", "pkcs8") => Ok(CryptoKey::EcdsaPrivate {
+            hash: parse_hash(hash)?,
+            key: PKey::private_key_from_der(data).map_err(data_error)?,
+        }),
+        (a, f) => Err(unsupported("import", &format!("{} ({})", a, f))),
+    }
+}
+
+// WebCrypto's ECDSA signatures are r and s concatenated, each padded to the
+// curve's size, where openssl deals in DER.
+fn ecdsa_field_size<T: HasPublic>(key: &PKeyRef<T>) -> FlyResult<usize> {
+    let ec = key.ec_key().map_err(operation_error)?;
+    Ok((ec.group().degree() as usize + 7) / 8)
+}
+
+fn pad(bytes: Vec<u8>, len: usize) -> Vec<u8> {
+    let mut out = vec![0u8; len.saturating_sub(bytes.len())];
+    out.extend(bytes);
+    out
+}
+
+fn ecdsa_der_to_raw(der: &[u8], size: usize) -> FlyResult<Vec<u8>> {
+    let sig = EcdsaSig::from_der(der).map_err(operation_error)?;
+    let mut raw = pad(sig.r().to_vec(), size);
+    raw.extend(pad(sig.s().to_vec(), size));
+    Ok(raw)
+}
+
+fn ecdsa_raw_to_der(raw: &[u8], size: usize) -> Option<Vec<u8>> {
+    if raw.len() != size * 2 {
+        return None;
+    }
+    let r = BigNum::from_slice(&raw[..size]).ok()?;
+    let s = BigNum::from_slice(&raw[size..]).ok()?;
+    EcdsaSig::from_private_components(r, s).ok()?.to_der().ok()
+}
+
+fn ecdsa_verify<T: HasPublic>(
+    md: MessageDigest,
+    key: &PKeyRef<T>,
+    signature: &[u8],
+    data: &[u8],
+) -> FlyResult<bool> {
+    let der = match ecdsa_raw_to_der(signature, ecdsa_field_size(key)?) {
+        Some(der) => der,
+        None => return Ok(false),
+    };
+    let mut verifier = Verifier::new(md, key).map_err(operation_error)?;
+    verifier.update(data).map_err(operation_error)?;
+    Ok(verifier.verify(&der).unwrap_or(false))
+}
+
+fn hmac(md: MessageDigest, key: &[u8], data: &[u8]) -> FlyResult<Vec<u8>> {
+    let pkey = PKey::hmac(key).map_err(operation_error)?;
+    let mut signer = Signer::new(md, &pkey).map_err(operation_error)?;
+    signer.update(data).map_err(operation_error)?;
+    signer.sign_to_vec().map_err(operation_error)
+}
+
+// ECDSA takes its hash when signing, HMAC when importing.
+fn sign_hash(algo: &str, hash: Option<&str>, imported: MessageDigest) -> FlyResult<MessageDigest> {
+    match hash {
+        Some(h) if algo.eq_ignore_ascii_case("ECDSA") => parse_hash(Some(h)),
+        _ => Ok(imported),
+    }
+}
+
+fn check_algo(algo: &str, key: &CryptoKey) -> FlyResult<()> {
+    let matches = match key {
+        CryptoKey::Hmac { .. } => algo.eq_ignore_ascii_case("HMAC"),
+        CryptoKey::AesGcm { .. } => algo.eq_ignore_ascii_case("AES-GCM"),
+        CryptoKey::EcdsaPublic { .. } | CryptoKey::EcdsaPrivate { .. } => {
+            algo.eq_ignore_ascii_case("ECDSA")
+        }
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(errors::new(
+            ErrorKind::InvalidAccessError,
+            format!("key can't be used with {}", algo),
+        ))
+    }
+}
+
+pub fn sign(key: &CryptoKey, algo: &str, hash: Option<&str>, data: &[u8]) -> FlyResult<Vec<u8>> {
+    check_algo(algo, key)?;
+    match key {
+        CryptoKey::Hmac { hash, key } => hmac(*hash, key, data),
+        CryptoKey::EcdsaPrivate {
+            hash: imported,
+            key,
+        } => {
+            let md = sign_hash(algo, hash, *imported)?;
+            let mut signer = Signer::new(md, key).map_err(operation_error)?;
+            signer.update(data).map_err(operation_error)?;
+            let der = signer.sign_to_vec().map_err(operation_error)?;
+            ecdsa_der_to_raw(&der, ecdsa_field_size(key)?)
+        }
+        _ => Err(errors::new(
+            ErrorKind::InvalidAccessError,
+            format!("{} key can't be used to sign", key.key_type()),
+        )),
+    }
+}
+
+pub fn verify(
+    key: &CryptoKey,
+    algo: &str,
+    hash: Option<&str>,
+    signature: &[u8],
+    data: &[u8],
+) -> FlyResult<bool> {
+    check_algo(algo, key)?;
+    match key {
+        CryptoKey::Hmac { hash, key } => {
+            let expected = hmac(*hash, key, data)?;
+            Ok(expected.len() == signature.len() && memcmp::eq(&expected, signature))
+        }
+        CryptoKey::EcdsaPublic {
+            hash: imported,
+            key,
+        } => ecdsa_verify(sign_hash(algo, hash, *imported)?, key, signature, data),
+        CryptoKey::EcdsaPrivate {
+            hash: imported,
+            key,
+        } => ecdsa_verify(sign_hash(algo, hash, *imported)?, key, signature, data),
+        CryptoKey::AesGcm { .. } => Err(errors::new(
+            ErrorKind::InvalidAccessError,
+            "AES-GCM keys can't be used to verify".to_string(),
+        )),
+    }
+}
+
+pub fn cipher(
+    key: &CryptoKey,
+    algo: &str,
+    decrypt: bool,
+    iv: &[u8],
+    aad: &[u8],
+    tag_length: usize,
+    data: &[u8],
+) -> FlyResult<Vec<u8>> {
+    check_algo(algo, key)?;
+    let key = match key {
+        CryptoKey::AesGcm { key } => key,
+        _ => {
+            return Err(errors::new(
+                ErrorKind::InvalidAccessError,
+                format!("{} can't be used to encrypt", algo),
+            ));
+        }
+    };
+    let cipher = if key.len() == 16 {
+        Cipher::aes_128_gcm()
+    } else {
+        Cipher::aes_256_gcm()
+    };
+    let tag_len = tag_length / 8;
+    if tag_len < 12 || tag_len > 16 {
+        return Err(errors::new(
+            ErrorKind::OperationError,
+            format!("invalid tag length: {}", tag_length),
+        ));
+    }
+
+    if decrypt {
+        if data.len() < tag_len {
+            return Err(errors::new(
+                ErrorKind::OperationError,
+                "ciphertext is shorter than its tag".to_string(),
+            ));
+        }
+        let (ciphertext, tag) = data.split_at(data.len() - tag_len);
+        decrypt_aead(cipher, key, Some(iv), aad, ciphertext, tag).map_err(operation_error)
+    } else {
+        let mut tag = vec![0u8; tag_len];
+        let mut out =
+            encrypt_aead(cipher, key, Some(iv), aad, data, &mut tag).map_err(operation_error)?;
+        out.extend(tag);
+        Ok(out)
+    }
+}
+
+// Empty and non-buffer arguments come through with a null pointer.
+fn raw_bytes(raw: &fly_buf) -> Vec<u8> {
+    if raw.data_ptr.is_null() || raw.data_len == 0 {
+        return vec![];
+    }
+    unsafe { slice::from_raw_parts(raw.data_ptr, raw.data_len) }.to_vec()
+}
+
+fn fingerprint(parts: &[&str], data: &[u8]) -> Vec<u8> {
+    let mut h = Sha256::default();
+    for part in parts {
+        h.input(part.as_bytes());
+        h.input(&[0]);
+    }
+    h.input(data);
+    h.result().to_vec()
+}
+
+pub fn op_crypto_import_key(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
+    let cmd_id = base.cmd_id();
+    let msg = base.msg_as_crypto_import_key().unwrap();
+
+    let format = msg.format().unwrap_or("raw");
+    let algo = msg.algo().unwrap_or("");
+    let data = raw_bytes(&raw);
+
+    let key = match import_key(format, algo, msg.hash(), msg.named_curve(), &data) {
+        Ok(key) => key,
+        Err(e) => return odd_future(e),
+    };
+    let print = fingerprint(
+        &[
+            format,
+            algo,
+            msg.hash().unwrap_or(""),
+            msg.named_curve().unwrap_or(""),
+        ],
+        &data,
+    );
+    let (id, key) = rt.crypto_keys.insert(print, key);
+
+    let builder = &mut FlatBufferBuilder::new();
+    let key_type = builder.create_string(key.key_type());
+    let ready = msg::CryptoImportKeyReady::create(
+        builder,
+        &msg::CryptoImportKeyReadyArgs {
+            id,
+            key_type: Some(key_type),
+        },
+    );
+    ok_future(serialize_response(
+        cmd_id,
+        builder,
+        msg::BaseArgs {
+            msg: Some(ready.as_union_value()),
+            msg_type: msg::Any::CryptoImportKeyReady,
+            ..Default::default()
+        },
+    ))
+}
+
+pub fn op_crypto_sign(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
+    let cmd_id = base.cmd_id();
+    let msg = base.msg_as_crypto_sign().unwrap();
+
+    let key = match rt.crypto_keys.get(msg.key()) {
+        Ok(key) => key,
+        Err(e) => return odd_future(e),
+    };
+    let algo = msg.algo().unwrap_or("").to_string();
+    let hash = msg.hash().map(String::from);
+    let data = raw_bytes(&raw);
+
+    Box::new(future::lazy(move || {
+        let signature = sign(&key, &algo, hash.as_ref().map(String::as_str), &data)?;
+
+        let builder = &mut FlatBufferBuilder::new();
+        let buffer = builder.create_vector(&signature);
+        let ready = msg::CryptoSignReady::create(
+            builder,
+            &msg::CryptoSignReadyArgs {
+                buffer: Some(buffer),
+            },
+        );
+        Ok(serialize_response(
+            cmd_id,
+            builder,
+            msg::BaseArgs {
+                msg: Some(ready.as_union_value()),
+                msg_type: msg::Any::CryptoSignReady,
+                ..Default::default()
+            },
+        ))
+    }))
+}
+
+pub fn op_crypto_verify(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
+    let cmd_id = base.cmd_id();
+    let msg = base.msg_as_crypto_verify().unwrap();
+
+    let key = match rt.crypto_keys.get(msg.key()) {
+        Ok(key) => key,
+        Err(e) => return odd_future(e),
+    };
+    let algo = msg.algo().unwrap_or("").to_string();
+    let hash = msg.hash().map(String::from);
+    let signature = msg.signature().unwrap_or(&[]).to_vec();
+    let data = raw_bytes(&raw);
+
+    Box::new(future::lazy(move || {
+        let valid = verify(
+            &key,
+            &algo,
+            hash.as_ref().map(String::as_str),
+            &signature,
+            &data,
+        )?;
+
+        let builder = &mut FlatBufferBuilder::new();
+        let ready = msg::CryptoVerifyReady::create(builder, &msg::CryptoVerifyReadyArgs { valid });
+        Ok(serialize_response(
+            cmd_id,
+            builder,
+            msg::BaseArgs {
+                msg: Some(ready.as_union_value()),
+                msg_type: msg::Any::CryptoVerifyReady,
+                ..Default::default()
+            },
+        ))
+    }))
+}
+
+pub fn op_crypto_cipher(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
+    let cmd_id = base.cmd_id();
+    let msg = base.msg_as_crypto_cipher().unwrap();
+
+    let key = match rt.crypto_keys.get(msg.key()) {
+        Ok(key) => key,
+        Err(e) => return odd_future(e),
+    };
+    let algo = msg.algo().unwrap_or("").to_string();
+    let decrypt = msg.decrypt();
+    let iv = msg.iv().unwrap_or(&[]).to_vec();
+    let aad = msg.additional_data().unwrap_or(&[]).to_vec();
+    let tag_length = msg.tag_length() as usize;
+    let data = raw_bytes(&raw);
+
+    Box::new(future::lazy(move || {
+        let out = cipher(&key, &algo, decrypt, &iv, &aad, tag_length, &data)?;
+
+        let builder = &mut FlatBufferBuilder::new();
+        let buffer = builder.create_vector(&out);
+        let ready = msg::CryptoCipherReady::create(
+            builder,
+            &msg::CryptoCipherReadyArgs {
+                buffer: Some(buffer),
+            },
+        );
+        Ok(serialize_response(
+            cmd_id,
+            builder,
+            msg::BaseArgs {
+                msg: Some(ready.as_union_value()),
+                msg_type: msg::Any::CryptoCipherReady,
+                ..Default::default()
+            },
+        ))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_raw_bytes_empty() {
+        let empty = fly_buf {
+            alloc_ptr: ptr::null_mut(),
+            alloc_len: 0,
+            data_ptr: ptr::null_mut(),
+            data_len: 0,
+        };
+        assert!(raw_bytes(&empty).is_empty());
+
+        let mut data = b"abc".to_vec();
+        let buf = fly_buf {
+            alloc_ptr: ptr::null_mut(),
+            alloc_len: 0,
+            data_ptr: data.as_mut_ptr(),
+            data_len: data.len(),
+        };
+        assert_eq!(raw_bytes(&buf), b"abc");
+    }
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231 test case 2
+        let key = import_key("raw", "HMAC", Some("SHA-256"), None, b"Jefe").unwrap();
+        let data = b"what do ya want for nothing?";
+        let sig = sign(&key, "HMAC", None, data).unwrap();
+        assert_eq!(
+            hex(&sig),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert!(verify(&key, "HMAC", None, &sig, data).unwrap());
+        assert!(!verify(&key, "HMAC", None, &sig, b"tampered").unwrap());
+        assert!(!verify(&key, "HMAC", None, &sig[..16], data).unwrap());
+    }
+
+    #[test]
+    fn test_aes_gcm_round_trip() {
+        let key = import_key("raw", "AES-GCM", None, None, &[7u8; 32]).unwrap();
+        let iv = [1u8; 12];
+        let sealed = cipher(&key, "AES-GCM", false, &iv, b"aad", 128, b"secret").unwrap();
+        assert_eq!(sealed.len(), b"secret".len() + 16);
+
+        let opened = cipher(&key, "AES-GCM", true, &iv, b"aad", 128, &sealed).unwrap();
+        assert_eq!(opened, b"secret");
+
+        let err = cipher(&key, "AES-GCM", true, &iv, b"other", 128, &sealed).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::OperationError);
+    }
+
+    #[test]
+    fn test_ecdsa_sign_verify() {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let pkey = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+        let der = pkey.private_key_to_der().unwrap();
+        let mut ctx = BigNumContext::new().unwrap();
+        let point = pkey
+            .ec_key()
+            .unwrap()
+            .public_key()
+            .to_bytes(
+                &group,
+                openssl::ec::PointConversionForm::UNCOMPRESSED,
+                &mut ctx,
+            )
+            .unwrap();
+
+        let private = import_key("pkcs8", "ECDSA", Some("SHA-256"), None, &der).unwrap();
+        let public = import_key("raw", "ECDSA", Some("SHA-256"), Some("P-256"), &point).unwrap();
+        assert_eq!(private.key_type(), "private");
+        assert_eq!(public.key_type(), "public");
+
+        let sig = sign(&private, "ECDSA", Some("SHA-256"), b"payload").unwrap();
+        assert_eq!(sig.len(), 64);
+        assert!(verify(&public, "ECDSA", Some("SHA-256"), &sig, b"payload").unwrap());
+        assert!(!verify(&public, "ECDSA", Some("SHA-256"), &sig, b"other").unwrap());
+
+        let err = sign(&public, "ECDSA", Some("SHA-256"), b"payload").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidAccessError);
+    }
+
+    #[test]
+    fn test_unknown_algorithms() {
+        let err = import_key("raw", "RSA-PSS", None, None, b"key")
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::NotSupportedError);
+
+        let err = import_key("raw", "HMAC", Some("MD5"), None, b"key")
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::NotSupportedError);
+
+        let key = import_key("raw", "HMAC", Some("SHA-256"), None, b"key").unwrap();
+        let err = cipher(&key, "AES-GCM", false, &[0; 12], &[], 128, b"x").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidAccessError);
+    }
+
+    #[test]
+    fn test_import_same_key_twice() {
+        let keys = CryptoKeys::new();
+        let import = || import_key("raw", "HMAC", Some("SHA-256"), None, b"key").unwrap();
+        let print = fingerprint(&["raw", "HMAC", "SHA-256", ""], b"key");
+        let (a, _) = keys.insert(print.clone(), import());
+        let (b, _) = keys.insert(print, import());
+        assert_eq!(a, b);
+        assert!(keys.get(a).is_ok());
+    }
+}
@@ -2,6 +2,7 @@ use crate::msg;
 use flatbuffers::FlatBufferBuilder;
 
 use crate::runtime::Runtime;
+use crate::runtime_permissions::Capability;
 use crate::utils::*;
 use libfly::*;
 
@@ -10,6 +11,9 @@ use futures::Future;
 pub fn op_data_put(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
     let msg = base.msg_as_data_put().unwrap();
     let coll = msg.collection().unwrap().to_string();
+    if let Err(e) = rt.check_permission(Capability::DataCollection(&coll)) {
+        return odd_future(e);
+    }
     let key = msg.key().unwrap().to_string();
     let value = msg.json().unwrap().to_string();
 
@@ -25,6 +29,9 @@ pub fn op_data_get(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op>
     let cmd_id = base.cmd_id();
     let msg = base.msg_as_data_get().unwrap();
     let coll = msg.collection().unwrap().to_string();
+    if let Err(e) = rt.check_permission(Capability::DataCollection(&coll)) {
+        return odd_future(e);
+    }
     let key = msg.key().unwrap().to_string();
 
     Box::new(
@@ -60,6 +67,9 @@ pub fn op_data_get(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op>
 pub fn op_data_del(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
     let msg = base.msg_as_data_del().unwrap();
     let coll = msg.collection().unwrap().to_string();
+    if let Err(e) = rt.check_permission(Capability::DataCollection(&coll)) {
+        return odd_future(e);
+    }
     let key = msg.key().unwrap().to_string();
 
     Box::new(
@@ -73,6 +83,9 @@ pub fn op_data_del(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op>
 pub fn op_data_drop_coll(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
     let msg = base.msg_as_data_drop_collection().unwrap();
     let coll = msg.collection().unwrap().to_string();
+    if let Err(e) = rt.check_permission(Capability::DataCollection(&coll)) {
+        return odd_future(e);
+    }
 
     Box::new(
         rt.data_store
@@ -85,6 +98,9 @@ pub fn op_data_drop_coll(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> B
 pub fn op_data_incr(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
     let msg = base.msg_as_data_incr().unwrap();
     let coll = msg.collection().unwrap().to_string();
+    if let Err(e) = rt.check_permission(Capability::DataCollection(&coll)) {
+        return odd_future(e);
+    }
     let key = msg.key().unwrap().to_string();
     let field = msg.field().unwrap().to_string();
     let amount = msg.amount();
@@ -9,6 +9,7 @@ use std::collections::HashMap;
 use std::sync::Mutex;
 
 use crate::runtime::{Runtime, EVENT_LOOP};
+use crate::runtime_permissions::Capability;
 use crate::utils::*;
 use libfly::*;
 
@@ -272,8 +273,11 @@ fn dns_query(
   )
 }
 
-pub fn op_dns_query(_rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
+pub fn op_dns_query(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
   debug!("handle dns");
+  if let Err(e) = rt.check_permission(Capability::Dns) {
+    return odd_future(e);
+  }
   let cmd_id = base.cmd_id();
   let msg = base.msg_as_dns_query().unwrap();
 
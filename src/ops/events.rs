@@ -87,7 +87,7 @@ pub fn op_add_event_ln(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box
                             .unwrap(),
                         );
 
-                        ptr.send(to_send, None);
+                        ptr.send_for(Some(req.id), to_send, None);
 
                         if let Some(stream) = req.body {
                             send_body_stream(ptr, req.id, stream);
@@ -181,7 +181,7 @@ pub fn op_add_event_ln(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box
                             .unwrap(),
                         );
 
-                        ptr.send(to_send, None);
+                        ptr.send_for(Some(req.id), to_send, None);
                         Ok(())
                     }),
             );
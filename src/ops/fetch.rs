@@ -1,30 +1,37 @@
-use futures::{
-    future,
-    sync::{mpsc, oneshot},
-};
+use bytes::Bytes;
+use futures::future::{self, Either, Loop};
+use futures::sync::oneshot;
+use futures::{Async, Poll};
 
 use crate::msg;
-use flatbuffers::FlatBufferBuilder;
+use flatbuffers::{FlatBufferBuilder, ForwardsUOffset, Vector, WIPOffset};
 
 use crate::js::*;
-use crate::runtime::{Runtime, EVENT_LOOP};
+use crate::runtime::{JsRuntime, Runtime};
+use crate::runtime_permissions::{Capability, RuntimePermissions};
 use crate::utils::*;
 use libfly::*;
 
-use crate::errors::{FlyError, FlyResult};
+use crate::errors::{self, ErrorKind, FlyError, FlyResult};
+use crate::fs_store::{FsEntry, FsError, FsMeta};
+use crate::http_client::FetchClient;
+use crate::http_range;
+use crate::ip_filter::{self, IpFilter};
+use crate::ops::websocket::socket_channel;
 
 use crate::get_next_stream_id;
 
 use hyper::body::Payload;
-use hyper::client::HttpConnector;
-use hyper::header::HeaderName;
+use hyper::header::{self, HeaderName};
 use hyper::rt::{Future, Stream};
 use hyper::HeaderMap;
-use hyper::{Body, Client, Method, Request, StatusCode};
-
-use hyper_tls::HttpsConnector;
+use hyper::{Body, Method, Request, Response, StatusCode};
 
 use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::timer::{Delay, Timeout};
+use url::Url;
 
 use std::slice;
 
@@ -33,21 +40,39 @@ use floating_duration::TimeAsFloat;
 use http::uri::Scheme;
 use std::time;
 
-lazy_static! {
-    static ref HTTP_CLIENT: Client<HttpsConnector<HttpConnector>, Body> = {
-        Client::builder()
-            .executor(EVENT_LOOP.0.clone())
-            .build(HttpsConnector::new(4).unwrap())
-    };
-}
-
 pub fn op_fetch(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
     let cmd_id = base.cmd_id();
     let msg = base.msg_as_http_request().unwrap();
 
+    let method = match msg.method() {
+        msg::HttpMethod::Get => Method::GET,
+        msg::HttpMethod::Head => Method::HEAD,
+        msg::HttpMethod::Post => Method::POST,
+        msg::HttpMethod::Put => Method::PUT,
+        msg::HttpMethod::Patch => Method::PATCH,
+        msg::HttpMethod::Delete => Method::DELETE,
+        msg::HttpMethod::Connect => Method::CONNECT,
+        msg::HttpMethod::Options => Method::OPTIONS,
+        msg::HttpMethod::Trace => Method::TRACE,
+    };
+
+    let msg_headers = msg.headers().unwrap();
+    let mut headers = HeaderMap::new();
+    for i in 0..msg_headers.len() {
+        let h = msg_headers.get(i);
+        trace!("header: {} => {}", h.key().unwrap(), h.value().unwrap());
+        headers.insert(
+            HeaderName::from_bytes(h.key().unwrap().as_bytes()).unwrap(),
+            h.value().unwrap().parse().unwrap(),
+        );
+    }
+
     let url = msg.url().unwrap();
     if url.starts_with("file://") {
-        return file_request(rt, cmd_id, url);
+        if let Err(e) = rt.check_permission(Capability::FetchFile) {
+            return odd_future(e);
+        }
+        return file_request(rt, cmd_id, url, method, headers);
     }
 
     let ptr = rt.ptr;
@@ -61,6 +86,13 @@ pub fn op_fetch(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
 
     // for the metrics
     let host_str = http_uri.host().unwrap_or("unknown");
+    if let Err(e) = rt.check_permission(Capability::FetchHost(host_str)) {
+        return odd_future(e);
+    }
+    let ip_filter = rt.fetch_clients.ip_filter();
+    if let Err(e) = check_ip_literal(&ip_filter, host_str) {
+        return odd_future(e);
+    }
     let host = if let Some(port) = http_uri.port_part() {
         format!("{}:{}", host_str, port.as_str())
     } else {
@@ -80,113 +112,139 @@ pub fn op_fetch(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
         .with_label_values(&[rt.name.as_str(), rt.version.as_str(), host.as_str()])
         .inc();
 
-    let method = match msg.method() {
-        msg::HttpMethod::Get => Method::GET,
-        msg::HttpMethod::Head => Method::HEAD,
-        msg::HttpMethod::Post => Method::POST,
-        msg::HttpMethod::Put => Method::PUT,
-        msg::HttpMethod::Patch => Method::PATCH,
-        msg::HttpMethod::Delete => Method::DELETE,
-        msg::HttpMethod::Connect => Method::CONNECT,
-        msg::HttpMethod::Options => Method::OPTIONS,
-        msg::HttpMethod::Trace => Method::TRACE,
-    };
-
-    let msg_headers = msg.headers().unwrap();
-    let mut headers = HeaderMap::new();
-    for i in 0..msg_headers.len() {
-        let h = msg_headers.get(i);
-        trace!("header: {} => {}", h.key().unwrap(), h.value().unwrap());
-        headers.insert(
-            HeaderName::from_bytes(h.key().unwrap().as_bytes()).unwrap(),
-            h.value().unwrap().parse().unwrap(),
-        );
-    }
-
     let has_body = msg.has_body();
     trace!("HAS BODY? {}", has_body);
     let req_body = if has_body {
         if raw.data_len > 0 {
             trace!("STATIC BODY!");
-            Body::from(unsafe { slice::from_raw_parts(raw.data_ptr, raw.data_len) }.to_vec())
+            FetchBody::Static(Bytes::from(
+                unsafe { slice::from_raw_parts(raw.data_ptr, raw.data_len) }.to_vec(),
+            ))
         } else {
             trace!("STREAMING BODY");
-            let (sender, recver) = mpsc::unbounded::<Vec<u8>>();
+            let (sender, recver) = stream_channel();
             {
                 rt.streams.lock().unwrap().insert(req_id, sender);
             }
-            Body::wrap_stream(recver.map_err(|_| std::sync::mpsc::RecvError {}))
+            FetchBody::Stream(Some(Body::wrap_stream(
+                recver.map_err(|_| std::sync::mpsc::RecvError {}),
+            )))
         }
     } else {
-        Body::empty()
+        FetchBody::Empty
     };
-    // let req_body = Body::empty();
 
-    let mut req = Request::new(req_body);
-    {
-        *req.uri_mut() = http_uri.clone();
-        *req.method_mut() = method;
-        *req.headers_mut() = headers;
-    }
+    let default_client = match rt.fetch_clients.default_client() {
+        Ok(client) => client,
+        Err(e) => return odd_future(e),
+    };
+    let (client, fallback) = match msg.client_identity() {
+        Some(name) => match rt.fetch_clients.get(name) {
+            Ok(client) => (client, Some(default_client)),
+            Err(e) => return odd_future(e),
+        },
+        None => (default_client, None),
+    };
+    let opts = FetchOptions::from_msg(&msg);
+    let state = FetchState {
+        client,
+        fallback,
+        ip_filter,
+        method: method.clone(),
+        uri: http_uri,
+        headers: headers,
+        body: req_body,
+        redirects: 0,
+        retries: 0,
+    };
 
-    let (p, c) = oneshot::channel::<FlyResult<JsHttpResponse>>();
+    let (p, c) = oneshot::channel::<FlyResult<(JsHttpResponse, Option<String>)>>();
 
     let rt_name = rt.name.clone();
     let rt_version = rt.version.clone();
-    let method = req.method().clone();
+    let permissions = Arc::new(rt.permissions.clone());
+
+    let (abort_tx, abort_rx) = oneshot::channel::<()>();
+    rt.fetch_aborts.lock().unwrap().insert(req_id, abort_tx);
+    let aborted = abort_rx.then(|res| match res {
+        Ok(()) => Either::A(future::err(errors::new(
+            ErrorKind::Aborted,
+            "fetch aborted".to_string(),
+        ))),
+        // the fetch is done or the runtime went away
+        Err(_) => Either::B(future::empty()),
+    });
 
     rt.spawn(future::lazy(move || {
         let timer = time::Instant::now();
-        HTTP_CLIENT.request(req).then(move |reserr| {
-            debug!("got http response (or error)");
-            if let Err(err) = reserr {
-                if p.send(Err(err.into())).is_err() {
-                    error!("error sending error for http response :/");
+        let app = Arc::new((rt_name.clone(), rt_version.clone()));
+        send_request(timer, state, opts, permissions, app)
+            .select(aborted)
+            .map(|(res, _)| res)
+            .map_err(|(err, _)| err)
+            .then(move |reserr| {
+                ptr.to_runtime()
+                    .fetch_aborts
+                    .lock()
+                    .unwrap()
+                    .remove(&req_id);
+                debug!("got http response (or error)");
+                if let Err(err) = reserr {
+                    if p.send(Err(err)).is_err() {
+                        error!("error sending error for http response :/");
+                    }
+                    return Ok(());
                 }
-                return Ok(());
-            }
 
-            let res = reserr.unwrap(); // should be safe.
-
-            FETCH_HEADERS_DURATION
-                .with_label_values(&[
-                    rt_name.as_str(),
-                    rt_version.as_str(),
-                    method.as_str(),
-                    host.as_str(),
-                    res.status().as_str(),
-                ])
-                .observe(timer.elapsed().as_fractional_secs());
-
-            let (parts, body) = res.into_parts();
-
-            let mut stream_rx: Option<JsBody> = None;
-            let has_body = !body.is_end_stream();
-            if has_body {
-                stream_rx = Some(JsBody::BoxedStream(Box::new(
-                    body.map_err(|e| format!("{}", e).into()).map(move |chunk| {
+                let (res, redirected_url) = reserr.unwrap(); // should be safe.
+
+                FETCH_HEADERS_DURATION
+                    .with_label_values(&[
+                        rt_name.as_str(),
+                        rt_version.as_str(),
+                        method.as_str(),
+                        host.as_str(),
+                        res.status().as_str(),
+                    ])
+                    .observe(timer.elapsed().as_fractional_secs());
+
+                let (parts, body) = res.into_parts();
+
+                let mut stream_rx: Option<JsBody> = None;
+                let has_body = !body.is_end_stream();
+                if has_body {
+                    let stream = body.map_err(|e| format!("{}", e).into()).map(move |chunk| {
                         let bytes = chunk.into_bytes();
                         DATA_IN_TOTAL
                             .with_label_values(&[rt_name.as_str(), rt_version.as_str(), "fetch"])
                             .inc_by(bytes.len() as i64);
                         bytes.to_vec()
-                    }),
-                )));
-            }
+                    });
+                    stream_rx = Some(JsBody::BoxedStream(match opts.deadline(timer) {
+                        Some(deadline) => {
+                            Box::new(BodyDeadline::new(stream, deadline, opts.timeout_ms))
+                        }
+                        None => Box::new(stream),
+                    }));
+                }
 
-            if p.send(Ok(JsHttpResponse {
-                headers: parts.headers,
-                status: parts.status,
-                body: stream_rx,
-            }))
-            .is_err()
-            {
-                error!("error sending fetch http response");
-                return Ok(());
-            }
-            debug!("done with http request");
-            Ok(())
-        })
+                if p.send(Ok((
+                    JsHttpResponse {
+                        headers: parts.headers,
+                        status: parts.status,
+                        body: stream_rx,
+                        websocket: None,
+                    },
+                    redirected_url,
+                )))
+                .is_err()
+                {
+                    error!("error sending fetch http response");
+                    return Ok(());
+                }
+                debug!("done with http request");
+                Ok(())
+            })
     }));
 
     let fut = c
@@ -196,32 +254,16 @@ pub fn op_fetch(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
                 format!("err getting response from oneshot: {}", e).as_str(),
             ))
         })
-        .and_then(move |reserr: FlyResult<JsHttpResponse>| {
+        .and_then(move |reserr: FlyResult<(JsHttpResponse, Option<String>)>| {
             if let Err(err) = reserr {
                 return Err(err);
             }
 
-            let res = reserr.unwrap();
+            let (res, redirected_url) = reserr.unwrap();
 
             let builder = &mut FlatBufferBuilder::new();
-            let headers: Vec<_> = res
-                .headers
-                .iter()
-                .map(|(key, value)| {
-                    let key = builder.create_string(key.as_str());
-                    let value = builder.create_string(value.to_str().unwrap());
-                    msg::HttpHeader::create(
-                        builder,
-                        &msg::HttpHeaderArgs {
-                            key: Some(key),
-                            value: Some(value),
-                            ..Default::default()
-                        },
-                    )
-                })
-                .collect();
-
-            let res_headers = builder.create_vector(&headers);
+            let url = redirected_url.as_ref().map(|u| builder.create_string(u));
+            let res_headers = serialize_headers(builder, &res.headers);
 
             let msg = msg::FetchHttpResponse::create(
                 builder,
@@ -230,6 +272,8 @@ pub fn op_fetch(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
                     headers: Some(res_headers),
                     status: res.status.as_u16(),
                     has_body: res.body.is_some(),
+                    url: url,
+                    redirected: redirected_url.is_some(),
                     ..Default::default()
                 },
             );
@@ -250,6 +294,335 @@ pub fn op_fetch(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
     Box::new(fut)
 }
 
+const RETRY_BASE_DELAY_MS: u64 = 100;
+
+#[derive(Debug, Clone, Copy)]
+struct FetchOptions {
+    timeout_ms: u32,
+    redirect: msg::RedirectMode,
+    max_redirects: u32,
+    retries: u32,
+}
+
+impl FetchOptions {
+    fn from_msg(msg: &msg::HttpRequest) -> Self {
+        FetchOptions {
+            timeout_ms: msg.timeout_ms(),
+            redirect: msg.redirect(),
+            max_redirects: msg.max_redirects(),
+            retries: msg.retries(),
+        }
+    }
+
+    // When a fetch started at `start` must be done by, body included.
+    fn deadline(&self, start: time::Instant) -> Option<time::Instant> {
+        match self.timeout_ms {
+            0 => None,
+            ms => Some(start + Duration::from_millis(ms as u64)),
+        }
+    }
+}
+
+fn timed_out(timeout_ms: u32) -> FlyError {
+    errors::new(
+        ErrorKind::TimedOut,
+        format!("fetch timed out after {}ms", timeout_ms),
+    )
+}
+
+/// Fails a response body still streaming past the fetch's deadline.
+struct BodyDeadline<S> {
+    inner: S,
+    delay: Delay,
+    timeout_ms: u32,
+}
+
+impl<S> BodyDeadline<S> {
+    fn new(inner: S, deadline: time::Instant, timeout_ms: u32) -> Self {
+        BodyDeadline {
+            inner,
+            delay: Delay::new(deadline),
+            timeout_ms,
+        }
+    }
+}
+
+impl<S> Stream for BodyDeadline<S>
+where
+    S: Stream<Error = FlyError>,
+{
+    type Item = S::Item;
+    type Error = FlyError;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, FlyError> {
+        match self.delay.poll() {
+            Ok(Async::Ready(())) => return Err(timed_out(self.timeout_ms)),
+            Ok(Async::NotReady) => {}
+            Err(e) => return Err(format!("fetch timer error: {}", e).into()),
+        }
+        self.inner.poll()
+    }
+}
+
+enum FetchBody {
+    Empty,
+    Static(Bytes),
+    // taken by the first attempt, it can't be sent twice
+    Stream(Option<Body>),
+}
+
+impl FetchBody {
+    fn replayable(&self) -> bool {
+        match self {
+            FetchBody::Stream(_) => false,
+            _ => true,
+        }
+    }
+
+    fn take(&mut self) -> Body {
+        match self {
+            FetchBody::Empty => Body::empty(),
+            FetchBody::Static(b) => Body::from(b.clone()),
+            FetchBody::Stream(s) => s.take().unwrap_or_else(Body::empty),
+        }
+    }
+}
+
+struct FetchState {
+    client: FetchClient,
+    // replaces a client presenting an identity once redirected off its origin
+    fallback: Option<FetchClient>,
+    ip_filter: Arc<IpFilter>,
+    method: Method,
+    uri: hyper::Uri,
+    headers: HeaderMap,
+    body: FetchBody,
+    redirects: u32,
+    retries: u32,
+}
+
+type Sent = (Response<Body>, Option<String>);
+type Step = Box<Future<Item = Loop<Sent, FetchState>, Error = FlyError> + Send>;
+
+impl FetchState {
+    fn request(&mut self) -> Request<Body> {
+        let mut req = Request::new(self.body.take());
+        *req.uri_mut() = self.uri.clone();
+        *req.method_mut() = self.method.clone();
+        *req.headers_mut() = self.headers.clone();
+        req
+    }
+
+    fn done(self, res: Response<Body>) -> Step {
+        let url = if self.redirects > 0 {
+            Some(self.uri.to_string())
+        } else {
+            None
+        };
+        Box::new(future::ok(Loop::Break((res, url))))
+    }
+
+    fn can_retry(&self, opts: &FetchOptions) -> bool {
+        self.retries < opts.retries && self.method.is_idempotent() && self.body.replayable()
+    }
+
+    fn retry(mut self) -> Step {
+        let delay = Duration::from_millis(RETRY_BASE_DELAY_MS << self.retries.min(5));
+        self.retries += 1;
+        debug!("retrying {} {} in {:?}", self.method, self.uri, delay);
+        Box::new(
+            Delay::new(time::Instant::now() + delay)
+                .map_err(|e| format!("retry timer error: {}", e).into())
+                .map(move |_| Loop::Continue(self)),
+        )
+    }
+
+    fn follow(
+        &mut self,
+        status: StatusCode,
+        location: &str,
+        permissions: &RuntimePermissions,
+        app: &(String, String),
+    ) -> FlyResult<()> {
+        let next = Url::parse(&self.uri.to_string())?.join(location)?;
+        if next.scheme() != "http" && next.scheme() != "https" {
+            return Err(errors::new(
+                ErrorKind::RedirectNotAllowed,
+                format!("cannot redirect to a {} url", next.scheme()),
+            ));
+        }
+        let next: hyper::Uri = next
+            .as_str()
+            .parse()
+            .map_err(|e| FlyError::from(format!("{}", e)))?;
+
+        let host = next.host().unwrap_or("unknown");
+        permissions.check_counted(&app.0, &app.1, Capability::FetchHost(host))?;
+        check_ip_literal(&self.ip_filter, host)?;
+
+        let becomes_get = (status == StatusCode::SEE_OTHER && self.method != Method::HEAD)
+            || ((status == StatusCode::MOVED_PERMANENTLY || status == StatusCode::FOUND)
+                && self.method == Method::POST);
+        if becomes_get {
+            self.method = Method::GET;
+            self.body = FetchBody::Empty;
+            for name in &[
+                header::CONTENT_TYPE,
+                header::CONTENT_LENGTH,
+                header::CONTENT_ENCODING,
+                header::CONTENT_LANGUAGE,
+                header::TRANSFER_ENCODING,
+            ] {
+                self.headers.remove(name);
+            }
+        } else if !self.body.replayable() {
+            return Err(errors::new(
+                ErrorKind::RedirectNotAllowed,
+                "cannot follow a redirect after streaming the request body".to_string(),
+            ));
+        }
+
+        let same_origin = next.scheme_part() == self.uri.scheme_part()
+            && next.authority_part() == self.uri.authority_part();
+        if !same_origin {
+            for name in &[
+                header::AUTHORIZATION,
+                header::PROXY_AUTHORIZATION,
+                header::COOKIE,
+                header::HOST,
+            ] {
+                self.headers.remove(name);
+            }
+            if let Some(client) = self.fallback.take() {
+                self.client = client;
+            }
+        }
+
+        self.uri = next;
+        self.redirects += 1;
+        Ok(())
+    }
+}
+
+fn is_redirect(status: StatusCode) -> bool {
+    match status {
+        StatusCode::MOVED_PERMANENTLY
+        | StatusCode::FOUND
+        | StatusCode::SEE_OTHER
+        | StatusCode::TEMPORARY_REDIRECT
+        | StatusCode::PERMANENT_REDIRECT => true,
+        _ => false,
+    }
+}
+
+fn is_retryable_error(err: &hyper::Error) -> bool {
+    (err.is_connect() || err.is_closed()) && ip_filter::blocked_cause(err).is_none()
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::BAD_GATEWAY
+        || status == StatusCode::SERVICE_UNAVAILABLE
+        || status == StatusCode::GATEWAY_TIMEOUT
+}
+
+// IP literals skip the resolver, so they're checked here.
+fn check_ip_literal(filter: &IpFilter, host: &str) -> FlyResult<()> {
+    if let Ok(ip) = host.trim_matches(|c| c == '[' || c == ']').parse() {
+        if let Err(e) = filter.check(host, &ip) {
+            return Err(errors::new(ErrorKind::AddressBlocked, e.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Sends the request, retrying and following redirects as `opts` allow.
+/// The timeout runs from `start`. This covers the final response's headers,
+/// `BodyDeadline` covers its body.
+fn send_request(
+    start: time::Instant,
+    state: FetchState,
+    opts: FetchOptions,
+    permissions: Arc<RuntimePermissions>,
+    app: Arc<(String, String)>,
+) -> Box<Future<Item = Sent, Error = FlyError> + Send> {
+    let attempts = future::loop_fn(state, move |mut state| {
+        let permissions = permissions.clone();
+        let app = app.clone();
+        let req = state.request();
+        state.client.request(req).then(move |res| -> Step {
+            let res = match res {
+                Ok(res) => res,
+                Err(e) => {
+                    if state.can_retry(&opts) && is_retryable_error(&e) {
+                        return state.retry();
+                    }
+                    return Box::new(future::err(e.into()));
+                }
+            };
+            let status = res.status();
+            if state.can_retry(&opts) && is_retryable_status(status) {
+                return state.retry();
+            }
+            if !is_redirect(status) || opts.redirect == msg::RedirectMode::Manual {
+                return state.done(res);
+            }
+            let location = match res
+                .headers()
+                .get(header::LOCATION)
+                .and_then(|l| l.to_str().ok())
+            {
+                Some(l) => l.to_string(),
+                None => return state.done(res),
+            };
+            if opts.redirect == msg::RedirectMode::Error {
+                return Box::new(future::err(errors::new(
+                    ErrorKind::RedirectNotAllowed,
+                    format!("redirected to {} with redirect mode 'error'", location),
+                )));
+            }
+            if state.redirects >= opts.max_redirects {
+                return Box::new(future::err(errors::new(
+                    ErrorKind::TooManyRedirects,
+                    format!("exceeded {} redirects", opts.max_redirects),
+                )));
+            }
+            match state.follow(status, &location, &permissions, &app) {
+                Ok(()) => Box::new(future::ok(Loop::Continue(state))),
+                Err(e) => Box::new(future::err(e)),
+            }
+        })
+    });
+
+    let deadline = match opts.deadline(start) {
+        Some(deadline) => deadline,
+        None => return Box::new(attempts),
+    };
+    Box::new(Timeout::new_at(attempts, deadline).map_err(move |e| {
+        if e.is_elapsed() {
+            timed_out(opts.timeout_ms)
+        } else if e.is_inner() {
+            e.into_inner().unwrap()
+        } else {
+            format!("fetch timer error: {}", e).into()
+        }
+    }))
+}
+
+pub fn op_fetch_abort(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
+    let msg = base.msg_as_fetch_abort().unwrap();
+    let req_id = msg.id();
+
+    if let Some(abort) = rt.fetch_aborts.lock().unwrap().remove(&req_id) {
+        abort.send(()).ok();
+    }
+    // stops a response body already flowing to JS, and a request body still
+    // flowing from it
+    rt.end_stream_pulls(req_id);
+    rt.streams.lock().unwrap().remove(&req_id);
+
+    ok_future(None)
+}
+
 pub fn op_http_response(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
     debug!("handling http response");
     let msg = base.msg_as_http_response().unwrap();
@@ -277,7 +650,7 @@ pub fn op_http_response(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box
     if has_body {
         if raw.data_len == 0 {
             debug!("http response will have a streaming body");
-            let (sender, recver) = mpsc::unbounded::<Vec<u8>>();
+            let (sender, recver) = stream_channel();
             {
                 let mut streams = rt.streams.lock().unwrap();
                 streams.insert(req_id, sender);
@@ -291,6 +664,15 @@ pub fn op_http_response(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box
         }
     }
 
+    let websocket = if msg.websocket() {
+        // JS may send before the upgrade completes
+        let (handle, queue) = socket_channel();
+        rt.websockets.lock().unwrap().insert(req_id, handle);
+        Some(queue)
+    } else {
+        None
+    };
+
     let mut responses = rt.responses.lock().unwrap();
     match responses.remove(&req_id) {
         Some(sender) => {
@@ -299,6 +681,7 @@ pub fn op_http_response(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box
                     headers: headers,
                     status: status,
                     body: body,
+                    websocket: websocket,
                 })
                 .is_err()
             {
@@ -311,7 +694,13 @@ pub fn op_http_response(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box
     ok_future(None)
 }
 
-fn file_request(rt: &mut Runtime, cmd_id: u32, url: &str) -> Box<Op> {
+fn file_request(
+    rt: &mut Runtime,
+    cmd_id: u32,
+    url: &str,
+    method: Method,
+    req_headers: HeaderMap,
+) -> Box<Op> {
     let req_id = get_next_stream_id();
     let path: String = url.chars().skip(7).collect();
 
@@ -319,17 +708,19 @@ fn file_request(rt: &mut Runtime, cmd_id: u32, url: &str) -> Box<Op> {
 
     Box::new(
         rt.fs_store
-            .read(path)
-            .map_err(|e| format!("fs error: {:?}", e).into())
-            .and_then(move |maybe_entry| {
+            .stat(path.clone())
+            .and_then(move |meta| file_response(ptr, path, &method, &req_headers, meta))
+            .map_err(FlyError::from)
+            .and_then(move |(status, headers, maybe_entry)| {
                 let builder = &mut FlatBufferBuilder::new();
+                let res_headers = serialize_headers(builder, &headers);
 
                 let msg = msg::FetchHttpResponse::create(
                     builder,
                     &msg::FetchHttpResponseArgs {
                         id: req_id,
-                        headers: None,
-                        status: if maybe_entry.is_some() { 404 } else { 200 },
+                        headers: Some(res_headers),
+                        status: status.as_u16(),
                         has_body: maybe_entry.is_some(),
                         ..Default::default()
                     },
@@ -355,3 +746,292 @@ fn file_request(rt: &mut Runtime, cmd_id: u32, url: &str) -> Box<Op> {
             }),
     )
 }
+
+type FileResponse = (StatusCode, HeaderMap, Option<FsEntry>);
+
+// Reads as much of a file as the request's conditional and range headers
+// leave to send, validators come from its size and modification time.
+fn file_response(
+    ptr: JsRuntime,
+    path: String,
+    method: &Method,
+    req_headers: &HeaderMap,
+    meta: Option<FsMeta>,
+) -> Box<Future<Item = FileResponse, Error = FsError> + Send> {
+    let meta = match meta {
+        Some(meta) => meta,
+        None => return Box::new(future::ok((StatusCode::NOT_FOUND, HeaderMap::new(), None))),
+    };
+    let mut status = StatusCode::OK;
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::ACCEPT_RANGES,
+        header::HeaderValue::from_static("bytes"),
+    );
+    headers.insert(header::CONTENT_LENGTH, header::HeaderValue::from(meta.size));
+    if let Some(modified) = meta.modified {
+        let etag = http_range::file_etag(meta.size, modified);
+        headers.insert(header::ETAG, header::HeaderValue::from_str(&etag).unwrap());
+        let date = http_range::http_date(modified);
+        headers.insert(
+            header::LAST_MODIFIED,
+            header::HeaderValue::from_str(&date).unwrap(),
+        );
+    }
+
+    let outcome = http_range::evaluate(method, req_headers, status, &headers, Some(meta.size));
+    outcome.apply(&mut status, &mut headers);
+    if !outcome.has_body() || method == Method::HEAD {
+        return Box::new(future::ok((status, headers, None)));
+    }
+    let fs_store = &ptr.to_runtime().fs_store;
+    let read = match outcome.range() {
+        Some(range) => fs_store.read_range(path, range),
+        None => fs_store.read(path),
+    };
+    Box::new(read.map(move |entry| match entry {
+        Some(entry) => (status, headers, Some(entry)),
+        // removed since it was looked at
+        None => (StatusCode::NOT_FOUND, HeaderMap::new(), None),
+    }))
+}
+
+fn serialize_headers<'a>(
+    builder: &mut FlatBufferBuilder<'a>,
+    headers: &HeaderMap,
+) -> WIPOffset<Vector<'a, ForwardsUOffset<msg::HttpHeader<'a>>>> {
+    let headers: Vec<_> = headers
+        .iter()
+        .map(|(key, value)| {
+            let key = builder.create_string(key.as_str());
+            let value = builder.create_string(value.to_str().unwrap());
+            msg::HttpHeader::create(
+                builder,
+                &msg::HttpHeaderArgs {
+                    key: Some(key),
+                    value: Some(value),
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+    builder.create_vector(&headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::FetchClients;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn state(uri: &str, fallback: bool) -> FetchState {
+        let clients = FetchClients::new(Default::default(), IpFilter::default(), HashMap::new());
+        let client = || clients.default_client().unwrap();
+        FetchState {
+            client: client(),
+            fallback: if fallback { Some(client()) } else { None },
+            ip_filter: Arc::new(IpFilter::default()),
+            method: Method::GET,
+            uri: uri.parse().unwrap(),
+            headers: HeaderMap::new(),
+            body: FetchBody::Empty,
+            redirects: 0,
+            retries: 0,
+        }
+    }
+
+    #[test]
+    fn test_follow_off_origin() {
+        let permissions = RuntimePermissions::default();
+        let app = ("app".to_string(), "1".to_string());
+        let with_credentials = |uri: &str| {
+            let mut state = state(uri, true);
+            for name in &[header::COOKIE, header::PROXY_AUTHORIZATION] {
+                state.headers.insert(name, "secret".parse().unwrap());
+            }
+            state
+        };
+
+        let mut same = with_credentials("https://example.com/a");
+        same.follow(StatusCode::FOUND, "/b", &permissions, &app)
+            .unwrap();
+        assert!(same.fallback.is_some());
+        assert!(same.headers.contains_key(header::COOKIE));
+
+        // the identity and credentials stay with the origin they were for
+        let mut cross = with_credentials("https://example.com/a");
+        cross
+            .follow(
+                StatusCode::FOUND,
+                "https://example.org/",
+                &permissions,
+                &app,
+            )
+            .unwrap();
+        assert!(cross.fallback.is_none());
+        assert!(!cross.headers.contains_key(header::COOKIE));
+        assert!(!cross.headers.contains_key(header::PROXY_AUTHORIZATION));
+        assert_eq!(cross.redirects, 1);
+    }
+
+    // Serves, on a local port:
+    // - /redirect/N, redirecting N times before answering
+    // - /unavailable, a 503 for the first two requests
+    // - /slow, answering after 300ms
+    // - /slow-body, whose body takes 300ms to finish
+    // Also returns how many requests came in.
+    fn serve(el: &mut tokio::runtime::Runtime) -> (String, Arc<AtomicUsize>) {
+        use hyper::service::service_fn;
+        use hyper::{Chunk, Server};
+
+        type Res = Box<Future<Item = Response<Body>, Error = hyper::Error> + Send>;
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let server = Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(move || {
+            let counter = counter.clone();
+            service_fn(move |req: Request<Body>| -> Res {
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                let later = || Delay::new(time::Instant::now() + Duration::from_millis(300));
+                let path = req.uri().path().to_string();
+                let mut res = Response::new(Body::empty());
+                if path.starts_with("/redirect/") {
+                    match path["/redirect/".len()..].parse::<u32>().unwrap() {
+                        0 => *res.body_mut() = Body::from("done"),
+                        left => {
+                            *res.status_mut() = StatusCode::FOUND;
+                            res.headers_mut().insert(
+                                header::LOCATION,
+                                format!("/redirect/{}", left - 1).parse().unwrap(),
+                            );
+                        }
+                    }
+                } else if path == "/unavailable" && n < 2 {
+                    *res.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+                } else if path == "/slow" {
+                    return Box::new(later().then(|_| Ok(res)));
+                } else if path == "/slow-body" {
+                    let rest = later().map(|_| Chunk::from("b"));
+                    *res.body_mut() = Body::wrap_stream(
+                        futures::stream::once(Ok(Chunk::from("a"))).chain(rest.into_stream()),
+                    );
+                }
+                Box::new(future::ok(res))
+            })
+        });
+        let base = format!("http://{}", server.local_addr());
+        el.spawn(server.map_err(|e| panic!("test server error: {}", e)));
+        (base, hits)
+    }
+
+    fn opts(timeout_ms: u32, redirect: msg::RedirectMode, retries: u32) -> FetchOptions {
+        FetchOptions {
+            timeout_ms,
+            redirect,
+            max_redirects: 20,
+            retries,
+        }
+    }
+
+    fn send(
+        el: &mut tokio::runtime::Runtime,
+        state: FetchState,
+        opts: FetchOptions,
+    ) -> FlyResult<Sent> {
+        let app = Arc::new(("app".to_string(), "1".to_string()));
+        let permissions = Arc::new(RuntimePermissions::default());
+        el.block_on(send_request(
+            time::Instant::now(),
+            state,
+            opts,
+            permissions,
+            app,
+        ))
+    }
+
+    #[test]
+    fn test_redirect_modes() {
+        let mut el = tokio::runtime::Runtime::new().unwrap();
+        let (base, _) = serve(&mut el);
+        let url = format!("{}/redirect/2", base);
+
+        let follow = opts(0, msg::RedirectMode::Follow, 0);
+        let (res, redirected) = send(&mut el, state(&url, false), follow).unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(redirected, Some(format!("{}/redirect/0", base)));
+
+        let manual = opts(0, msg::RedirectMode::Manual, 0);
+        let (res, redirected) = send(&mut el, state(&url, false), manual).unwrap();
+        assert_eq!(res.status(), StatusCode::FOUND);
+        assert_eq!(redirected, None);
+
+        let error = opts(0, msg::RedirectMode::Error, 0);
+        let err = send(&mut el, state(&url, false), error).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::RedirectNotAllowed);
+
+        let mut limited = opts(0, msg::RedirectMode::Follow, 0);
+        limited.max_redirects = 1;
+        let err = send(&mut el, state(&url, false), limited).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::TooManyRedirects);
+    }
+
+    #[test]
+    fn test_retries_idempotent_requests() {
+        let mut el = tokio::runtime::Runtime::new().unwrap();
+        let (base, hits) = serve(&mut el);
+        let url = format!("{}/unavailable", base);
+
+        let (res, _) = send(
+            &mut el,
+            state(&url, false),
+            opts(0, msg::RedirectMode::Follow, 2),
+        )
+        .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let (base, hits) = serve(&mut el);
+        let mut post = state(&format!("{}/unavailable", base), false);
+        post.method = Method::POST;
+        let (res, _) = send(&mut el, post, opts(0, msg::RedirectMode::Follow, 2)).unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_timeouts() {
+        let mut el = tokio::runtime::Runtime::new().unwrap();
+        let (base, _) = serve(&mut el);
+
+        let slow = state(&format!("{}/slow", base), false);
+        let err = send(&mut el, slow, opts(100, msg::RedirectMode::Follow, 0))
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+
+        // the headers are in on time, the body isn't
+        let start = time::Instant::now();
+        let slow_body = state(&format!("{}/slow-body", base), false);
+        let (res, _) = send(&mut el, slow_body, opts(100, msg::RedirectMode::Follow, 0)).unwrap();
+        let body = res
+            .into_body()
+            .map_err(|e| FlyError::from(format!("{}", e)));
+        let deadline = start + Duration::from_millis(100);
+        let err = el
+            .block_on(BodyDeadline::new(body, deadline, 100).collect())
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_check_ip_literal() {
+        let mut filter = IpFilter::default();
+        filter.extend(vec!["10.0.0.0/8", "::1/128"]);
+        assert!(check_ip_literal(&filter, "10.1.2.3").is_err());
+        assert!(check_ip_literal(&filter, "[::1]").is_err());
+        assert!(check_ip_literal(&filter, "1.1.1.1").is_ok());
+        // names go through the resolver
+        assert!(check_ip_literal(&filter, "localhost").is_ok());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::msg;
+use flatbuffers::FlatBufferBuilder;
+
+use crate::errors::FlyError;
+use crate::runtime::Runtime;
+use crate::runtime_permissions::Capability;
+use crate::utils::*;
+use libfly::*;
+
+use futures::{stream, Future};
+use std::slice;
+use std::time::UNIX_EPOCH;
+
+pub fn op_fs_write(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
+    let msg = base.msg_as_fs_write().unwrap();
+    if let Err(e) = rt.check_permission(Capability::FsWrite) {
+        return odd_future(e);
+    }
+    let path = msg.path().unwrap().to_string();
+    let data = if raw.data_len > 0 {
+        unsafe { slice::from_raw_parts(raw.data_ptr, raw.data_len) }.to_vec()
+    } else {
+        vec![]
+    };
+
+    Box::new(
+        rt.fs_store
+            .write(path, Box::new(stream::once(Ok(data))))
+            .map_err(FlyError::from)
+            .and_then(|_| Ok(None)),
+    )
+}
+
+pub fn op_fs_delete(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
+    let msg = base.msg_as_fs_delete().unwrap();
+    if let Err(e) = rt.check_permission(Capability::FsWrite) {
+        return odd_future(e);
+    }
+    let path = msg.path().unwrap().to_string();
+
+    Box::new(
+        rt.fs_store
+            .delete(path)
+            .map_err(FlyError::from)
+            .and_then(|_| Ok(None)),
+    )
+}
+
+pub fn op_fs_stat(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
+    let cmd_id = base.cmd_id();
+    let msg = base.msg_as_fs_stat().unwrap();
+    if let Err(e) = rt.check_permission(Capability::FetchFile) {
+        return odd_future(e);
+    }
+    let path = msg.path().unwrap().to_string();
+
+    Box::new(
+        rt.fs_store
+            .stat(path)
+            .map_err(FlyError::from)
+            .and_then(move |meta| match meta {
+                None => Ok(None),
+                Some(meta) => {
+                    let builder = &mut FlatBufferBuilder::new();
+                    let content_type = builder.create_string(&meta.content_type);
+                    let modified = meta
+                        .modified
+                        .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+                        .map_or(0, |d| d.as_secs() * 1000 + u64::from(d.subsec_millis()));
+                    let msg = msg::FsStatReady::create(
+                        builder,
+                        &msg::FsStatReadyArgs {
+                            size: meta.size,
+                            modified,
+                            content_type: Some(content_type),
+                            ..Default::default()
+                        },
+                    );
+                    Ok(serialize_response(
+                        cmd_id,
+                        builder,
+                        msg::BaseArgs {
+                            msg: Some(msg.as_union_value()),
+                            msg_type: msg::Any::FsStatReady,
+                            ..Default::default()
+                        },
+                    ))
+                }
+            }),
+    )
+}
+
+pub fn op_fs_list(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
+    let cmd_id = base.cmd_id();
+    let msg = base.msg_as_fs_list().unwrap();
+    if let Err(e) = rt.check_permission(Capability::FetchFile) {
+        return odd_future(e);
+    }
+    let prefix = msg.prefix().unwrap_or("").to_string();
+
+    Box::new(
+        rt.fs_store
+            .list(prefix)
+            .map_err(FlyError::from)
+            .and_then(move |paths| {
+                let builder = &mut FlatBufferBuilder::new();
+                let paths: Vec<_> = paths.iter().map(|p| builder.create_string(p)).collect();
+                let paths = builder.create_vector(&paths);
+                let msg = msg::FsListReady::create(
+                    builder,
+                    &msg::FsListReadyArgs {
+                        paths: Some(paths),
+                        ..Default::default()
+                    },
+                );
+                Ok(serialize_response(
+                    cmd_id,
+                    builder,
+                    msg::BaseArgs {
+                        msg: Some(msg.as_union_value()),
+                        msg_type: msg::Any::FsListReady,
+                        ..Default::default()
+                    },
+                ))
+            }),
+    )
+}
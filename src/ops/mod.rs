@@ -5,9 +5,11 @@ pub mod data;
 pub mod dns;
 pub mod events;
 pub mod fetch;
+pub mod fs;
 pub mod image;
 pub mod modules;
 pub mod os;
 pub mod source_map;
 pub mod streams;
 pub mod timers;
+pub mod websocket;
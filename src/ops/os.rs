@@ -1,10 +1,11 @@
 use crate::msg;
 use crate::runtime::Runtime;
+use crate::runtime_permissions::Capability;
 use crate::utils::*;
 use libfly::*;
 
 pub fn op_exit(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
-    if let Err(e) = rt.permissions.check_os() {
+    if let Err(e) = rt.check_permission(Capability::Os) {
         return odd_future(e);
     }
 
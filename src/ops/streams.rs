@@ -1,3 +1,4 @@
+use crate::errors::{self, ErrorKind};
 use crate::msg;
 
 use crate::runtime::Runtime;
@@ -5,6 +6,7 @@ use libfly::*;
 
 use crate::utils::*;
 
+use futures::{Future, Sink};
 use std::slice;
 
 pub fn op_stream_chunk(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
@@ -12,22 +14,56 @@ pub fn op_stream_chunk(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<
     let msg = base.msg_as_stream_chunk().unwrap();
     let stream_id = msg.id();
 
-    let mut streams = rt.streams.lock().unwrap();
-    if raw.data_len > 0 {
-        match streams.get_mut(&stream_id) {
-            Some(sender) => {
-                let bytes = unsafe { slice::from_raw_parts(raw.data_ptr, raw.data_len) }.to_vec();
-                match sender.unbounded_send(bytes.to_vec()) {
-                    Err(e) => error!("error sending chunk: {}", e),
-                    _ => debug!("chunk streamed"),
-                }
-            }
-            None => unimplemented!(),
-        };
+    let sender = {
+        let mut streams = rt.streams.lock().unwrap();
+        let sender = streams.get(&stream_id).cloned();
+        if msg.done() {
+            streams.remove(&stream_id);
+        }
+        sender
+    };
+
+    if raw.data_len == 0 {
+        return ok_future(None);
     }
-    if msg.done() {
-        streams.remove(&stream_id);
+
+    let mut sender = match sender {
+        Some(sender) => sender,
+        None => return odd_future(format!("unknown stream: {}", stream_id).into()),
+    };
+    let bytes = unsafe { slice::from_raw_parts(raw.data_ptr, raw.data_len) }.to_vec();
+
+    if base.sync() {
+        // waiting here would block the event loop which may be the one draining this stream
+        return match sender.try_send(bytes) {
+            Ok(_) => ok_future(None),
+            Err(e) => odd_future(errors::new(
+                ErrorKind::WouldBlock,
+                format!("error sending chunk: {}", e),
+            )),
+        };
     }
 
+    // resolves once the consumer has room for the chunk, holding back the JS producer until then
+    Box::new(
+        sender
+            .send(bytes)
+            .map(|_| -> Buf { None })
+            .map_err(move |e| {
+                errors::new(
+                    ErrorKind::BrokenPipe,
+                    format!("stream {} closed: {}", stream_id, e),
+                )
+            }),
+    )
+}
+
+pub fn op_stream_pull(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
+    let msg = base.msg_as_stream_pull().unwrap();
+    if msg.cancel() {
+        rt.end_stream_pulls(msg.id());
+    } else {
+        rt.pull_stream(msg.id());
+    }
     ok_future(None)
 }
@@ -0,0 +1,514 @@
+use crate::msg;
+use flatbuffers::FlatBufferBuilder;
+
+use crate::errors::{self, ErrorKind, FlyError};
+use crate::ip_filter::{self, FilteringResolver};
+use crate::runtime::{JsRuntime, Runtime, RuntimeHandle};
+use crate::runtime_permissions::Capability;
+use crate::utils::*;
+use libfly::*;
+
+use futures::future::{self, Either};
+use futures::sync::{mpsc, oneshot};
+use futures::{Future, Sink, Stream};
+use hyper::client::connect::dns::{Name, Resolve};
+
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::ptr;
+use std::slice;
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio::timer::Delay;
+use tokio_tls::{TlsConnector, TlsStream};
+use tokio_tungstenite::stream::Stream as MaybeTls;
+use tokio_tungstenite::WebSocketStream;
+use tungstenite::handshake::client::Request;
+use tungstenite::protocol::frame::coding::CloseCode;
+use tungstenite::protocol::{CloseFrame, Role};
+use tungstenite::Message;
+use url::{Host, Url};
+
+// Close codes for a close frame without a status, and for a connection that
+// dropped without any close frame.
+const CLOSE_NO_STATUS: u16 = 1005;
+const CLOSE_ABNORMAL: u16 = 1006;
+
+// Messages JS may queue on a socket before sends fail.
+const SEND_QUEUE: usize = 64;
+// How long a closing socket waits on its peer before dropping the connection.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub type WsStream = WebSocketStream<MaybeTls<TcpStream, TlsStream<TcpStream>>>;
+
+pub fn op_websocket_connect(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
+    let cmd_id = base.cmd_id();
+    let msg = base.msg_as_web_socket_connect().unwrap();
+    let id = msg.id();
+
+    let url = match Url::parse(msg.url().unwrap_or("")) {
+        Ok(url) => url,
+        Err(e) => return odd_future(e.into()),
+    };
+    if let Some(host) = url.host_str() {
+        if let Err(e) = rt.check_permission(Capability::FetchHost(host)) {
+            return odd_future(e);
+        }
+    }
+
+    let protocols = match msg.protocols() {
+        Some(protocols) => (0..protocols.len())
+            .map(|i| protocols.get(i).to_string())
+            .collect(),
+        None => vec![],
+    };
+
+    let (resolver, tls) = match rt.fetch_clients.connector() {
+        Ok(connector) => connector,
+        Err(e) => return odd_future(e),
+    };
+    let handle = rt.handle();
+    Box::new(
+        connect(url, protocols, resolver, tls).map(move |(ws, protocol)| {
+            handle.with(|rt| start_socket(rt, id, ws));
+
+            let builder = &mut FlatBufferBuilder::new();
+            let protocol = protocol.map(|p| builder.create_string(&p));
+            let msg = msg::WebSocketConnectReady::create(
+                builder,
+                &msg::WebSocketConnectReadyArgs {
+                    protocol: protocol,
+                    ..Default::default()
+                },
+            );
+            serialize_response(
+                cmd_id,
+                builder,
+                msg::BaseArgs {
+                    msg: Some(msg.as_union_value()),
+                    msg_type: msg::Any::WebSocketConnectReady,
+                    ..Default::default()
+                },
+            )
+        }),
+    )
+}
+
+pub fn op_websocket_send(rt: &mut Runtime, base: &msg::Base, raw: fly_buf) -> Box<Op> {
+    let msg = base.msg_as_web_socket_send().unwrap();
+    let id = msg.id();
+
+    let bytes = if raw.data_len > 0 {
+        unsafe { slice::from_raw_parts(raw.data_ptr, raw.data_len) }.to_vec()
+    } else {
+        vec![]
+    };
+    let message = if msg.text() {
+        match String::from_utf8(bytes) {
+            Ok(text) => Message::Text(text),
+            Err(e) => return odd_future(format!("invalid websocket text: {}", e).into()),
+        }
+    } else {
+        Message::Binary(bytes)
+    };
+
+    let sent = match rt.websockets.lock().unwrap().get_mut(&id) {
+        Some(socket) => socket.outgoing.try_send(message).map_err(|e| {
+            if e.is_full() {
+                errors::new(
+                    ErrorKind::WouldBlock,
+                    format!("websocket {} has too many messages queued", id),
+                )
+            } else {
+                closed(id)
+            }
+        }),
+        None => Err(closed(id)),
+    };
+    match sent {
+        Ok(_) => ok_future(None),
+        Err(e) => odd_future(e),
+    }
+}
+
+pub fn op_websocket_close(rt: &mut Runtime, base: &msg::Base, _raw: fly_buf) -> Box<Op> {
+    let msg = base.msg_as_web_socket_close().unwrap();
+    let id = msg.id();
+
+    let frame = CloseFrame {
+        code: CloseCode::from(msg.code()),
+        reason: Cow::Owned(msg.reason().unwrap_or("").to_string()),
+    };
+    let queued = match rt.websockets.lock().unwrap().get_mut(&id) {
+        Some(socket) => socket
+            .outgoing
+            .try_send(Message::Close(Some(frame)))
+            .is_ok(),
+        None => return ok_future(None),
+    };
+    if !queued {
+        rt.websockets.lock().unwrap().remove(&id);
+        return ok_future(None);
+    }
+
+    // the reader reports the close once the peer answers, a peer which
+    // doesn't is cut off
+    let handle = rt.handle();
+    rt.spawn(
+        Delay::new(Instant::now() + CLOSE_TIMEOUT)
+            .map_err(|e| error!("websocket close timer error: {}", e))
+            .map(move |_| {
+                handle.with(|rt| rt.websockets.lock().unwrap().remove(&id));
+            }),
+    );
+    ok_future(None)
+}
+
+fn closed(id: u32) -> FlyError {
+    errors::new(ErrorKind::BrokenPipe, format!("websocket {} is closed", id))
+}
+
+/// Opens a WebSocket to `url`, resolving its host through the IP filter.
+/// Yields the socket and the subprotocol the server picked, if any.
+pub fn connect(
+    url: Url,
+    protocols: Vec<String>,
+    resolver: FilteringResolver,
+    connector: native_tls::TlsConnector,
+) -> Box<Future<Item = (WsStream, Option<String>), Error = FlyError> + Send> {
+    let tls = match url.scheme() {
+        "ws" => false,
+        "wss" => true,
+        scheme => {
+            return Box::new(future::err(
+                format!("unsupported websocket scheme: {}", scheme).into(),
+            ));
+        }
+    };
+    let port = url
+        .port_or_known_default()
+        .unwrap_or(if tls { 443 } else { 80 });
+    let (domain, addrs) = match url.host() {
+        Some(Host::Domain(domain)) => (domain.to_string(), resolve(&resolver, domain)),
+        Some(Host::Ipv4(ip)) => (ip.to_string(), literal(&resolver, IpAddr::V4(ip))),
+        Some(Host::Ipv6(ip)) => (ip.to_string(), literal(&resolver, IpAddr::V6(ip))),
+        None => return Box::new(future::err("websocket url has no host".to_string().into())),
+    };
+
+    let connector = TlsConnector::from(connector);
+    let mut request = Request::from(url);
+    for protocol in protocols {
+        request.add_protocol(Cow::Owned(protocol));
+    }
+
+    Box::new(
+        addrs
+            .and_then(move |ip| {
+                TcpStream::connect(&SocketAddr::new(ip, port)).map_err(FlyError::from)
+            })
+            .and_then(move |tcp| {
+                if tls {
+                    Either::A(
+                        connector
+                            .connect(&domain, tcp)
+                            .map(MaybeTls::Tls)
+                            .map_err(|e| FlyError::from(format!("websocket tls error: {}", e))),
+                    )
+                } else {
+                    Either::B(future::ok::<_, FlyError>(MaybeTls::Plain(tcp)))
+                }
+            })
+            .and_then(|stream| {
+                tokio_tungstenite::client_async(request, stream)
+                    .map_err(|e| FlyError::from(format!("websocket handshake error: {}", e)))
+            })
+            .map(|(ws, res)| {
+                let protocol = res
+                    .headers
+                    .find_first("Sec-WebSocket-Protocol")
+                    .map(|p| String::from_utf8_lossy(p).into_owned());
+                (ws, protocol)
+            }),
+    )
+}
+
+fn resolve(
+    resolver: &FilteringResolver,
+    domain: &str,
+) -> Box<Future<Item = IpAddr, Error = FlyError> + Send> {
+    let name: Name = match domain.parse() {
+        Ok(name) => name,
+        Err(e) => return Box::new(future::err(format!("invalid host: {}", e).into())),
+    };
+    let domain = domain.to_string();
+    Box::new(
+        resolver
+            .resolve(name)
+            .map_err(|e| match ip_filter::blocked_io(&e) {
+                Some(blocked) => errors::new(ErrorKind::AddressBlocked, blocked.to_string()),
+                None => e.into(),
+            })
+            .and_then(move |mut addrs| {
+                addrs.next().ok_or_else(|| {
+                    FlyError::from(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("no addresses for {}", domain),
+                    ))
+                })
+            }),
+    )
+}
+
+fn literal(
+    resolver: &FilteringResolver,
+    ip: IpAddr,
+) -> Box<Future<Item = IpAddr, Error = FlyError> + Send> {
+    Box::new(future::result(
+        resolver
+            .filter()
+            .check(&ip.to_string(), &ip)
+            .map(|_| ip)
+            .map_err(|e| errors::new(ErrorKind::AddressBlocked, e.to_string())),
+    ))
+}
+
+/// The runtime's end of an open socket, kept in `Runtime::websockets`.
+/// Dropping it, as closing the socket or disposing the runtime does, stops
+/// the socket's tasks.
+pub struct SocketHandle {
+    outgoing: mpsc::Sender<Message>,
+    _stop: oneshot::Sender<()>,
+}
+
+/// The socket's end of a `SocketHandle`.
+pub struct SocketQueue {
+    outgoing: mpsc::Receiver<Message>,
+    stop: oneshot::Receiver<()>,
+}
+
+pub fn socket_channel() -> (SocketHandle, SocketQueue) {
+    let (tx, rx) = mpsc::channel(SEND_QUEUE);
+    let (stop_tx, stop_rx) = oneshot::channel();
+    (
+        SocketHandle {
+            outgoing: tx,
+            _stop: stop_tx,
+        },
+        SocketQueue {
+            outgoing: rx,
+            stop: stop_rx,
+        },
+    )
+}
+
+fn start_socket(rt: &Runtime, id: u32, ws: WsStream) {
+    let (handle, queue) = socket_channel();
+    rt.websockets.lock().unwrap().insert(id, handle);
+    run_socket(rt, id, ws, queue);
+}
+
+/// Bridges the connection an inbound request upgraded to the socket JS
+/// accepted under `id`. `queue` holds what JS sent before the upgrade completed.
+pub fn accept_upgrade<F>(handle: RuntimeHandle, id: u32, upgrade: F, queue: SocketQueue)
+where
+    F: Future + Send + 'static,
+    F::Item: AsyncRead + AsyncWrite + Send + 'static,
+    F::Error: fmt::Display,
+{
+    let task_handle = handle.clone();
+    handle.with(move |rt| {
+        rt.spawn(upgrade.then(move |res| {
+            match res {
+                Ok(io) => {
+                    let ws = WebSocketStream::from_raw_socket(io, Role::Server, None);
+                    task_handle.with(|rt| run_socket(rt, id, ws, queue));
+                }
+                Err(e) => {
+                    debug!("websocket {} upgrade failed: {}", id, e);
+                    task_handle.with(|rt| {
+                        rt.websockets.lock().unwrap().remove(&id);
+                        send_close_event(rt.ptr, id, CLOSE_ABNORMAL, "");
+                    });
+                }
+            }
+            Ok(())
+        }))
+    });
+}
+
+// Pumps messages queued by `op_websocket_send` into the socket, and the
+// socket's messages into JS as stream chunks until it closes. JS grants a
+// credit per message it takes, a socket it doesn't keep up with stops being
+// read.
+fn run_socket<S>(rt: &Runtime, id: u32, ws: WebSocketStream<S>, queue: SocketQueue)
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let handle = rt.handle();
+    let (sink, stream) = ws.split();
+    let stop = queue.stop.shared();
+
+    // the queue ends with the socket's handle, the writer then closes the
+    // socket unless the peer stopped reading
+    let cut_off = stop
+        .clone()
+        .then(|_| Delay::new(Instant::now() + CLOSE_TIMEOUT));
+    rt.spawn(
+        queue
+            .outgoing
+            .forward(sink.sink_map_err(move |e| debug!("websocket {} write error: {}", id, e)))
+            .select2(cut_off)
+            .then(|_| Ok(())),
+    );
+
+    let reader = handle.clone();
+    let messages = pulled(
+        stream.map_err(move |e| debug!("websocket {} read error: {}", id, e)),
+        rt.take_stream_pulls(id),
+    )
+    .fold(None, move |close, message| {
+        let sent = match message {
+            Message::Text(text) => reader.with(|rt| send_stream_text(rt.ptr, id, &text)),
+            Message::Binary(bytes) => reader.with(|rt| {
+                send_stream_chunk(rt.ptr, id, bytes.as_ptr() as *mut u8, bytes.len(), false)
+            }),
+            Message::Close(frame) => {
+                return Ok(Some(frame.map_or((CLOSE_NO_STATUS, String::new()), |f| {
+                    (f.code.into(), f.reason.into_owned())
+                })));
+            }
+            Message::Ping(_) | Message::Pong(_) => Some(()),
+        };
+        sent.map(|_| close).ok_or(())
+    });
+
+    rt.spawn(messages.select2(stop).then(move |res| {
+        let (code, reason) = match res {
+            Ok(Either::A((Some(close), _))) => close,
+            _ => (CLOSE_ABNORMAL, String::new()),
+        };
+        handle.with(|rt| {
+            rt.websockets.lock().unwrap().remove(&id);
+            rt.end_stream_pulls(id);
+            send_close_event(rt.ptr, id, code, &reason);
+        });
+        Ok(())
+    }));
+}
+
+fn send_close_event(ptr: JsRuntime, id: u32, code: u16, reason: &str) {
+    let builder = &mut FlatBufferBuilder::new();
+    let reason = builder.create_string(reason);
+    let msg = msg::WebSocketClose::create(
+        builder,
+        &msg::WebSocketCloseArgs {
+            id: id,
+            code: code,
+            reason: Some(reason),
+            ..Default::default()
+        },
+    );
+    ptr.send(
+        fly_buf_from(
+            serialize_response(
+                0,
+                builder,
+                msg::BaseArgs {
+                    msg: Some(msg.as_union_value()),
+                    msg_type: msg::Any::WebSocketClose,
+                    ..Default::default()
+                },
+            )
+            .unwrap(),
+        ),
+        Some(fly_buf {
+            alloc_ptr: ptr::null_mut() as *mut u8,
+            alloc_len: 0,
+            data_ptr: ptr::null_mut() as *mut u8,
+            data_len: 0,
+        }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::FetchClients;
+    use crate::ip_filter::IpFilter;
+    use std::collections::HashMap;
+    use tokio::net::TcpListener;
+    use tokio::runtime::current_thread;
+
+    fn connector() -> (FilteringResolver, native_tls::TlsConnector) {
+        filtered_connector(IpFilter::default())
+    }
+
+    fn filtered_connector(filter: IpFilter) -> (FilteringResolver, native_tls::TlsConnector) {
+        FetchClients::new(Default::default(), filter, HashMap::new())
+            .connector()
+            .unwrap()
+    }
+
+    fn echo_server(rt: &mut current_thread::Runtime) -> u16 {
+        let listener = TcpListener::bind(&"127.0.0.1:0".parse().unwrap()).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        rt.spawn(listener.incoming().take(1).map_err(|_| ()).for_each(|tcp| {
+            tokio_tungstenite::accept_async(tcp)
+                .map_err(|_| ())
+                .and_then(|ws| {
+                    let (sink, stream) = ws.split();
+                    stream
+                        .take_while(|m| Ok(m.is_text() || m.is_binary()))
+                        .forward(sink)
+                        .map(|_| ())
+                        .map_err(|_| ())
+                })
+        }));
+        port
+    }
+
+    #[test]
+    fn test_connect_echo() {
+        let mut rt = current_thread::Runtime::new().unwrap();
+        let port = echo_server(&mut rt);
+        let url = Url::parse(&format!("ws://127.0.0.1:{}/echo", port)).unwrap();
+        let (resolver, tls) = connector();
+
+        let (ws, protocol) = rt.block_on(connect(url, vec![], resolver, tls)).unwrap();
+        assert_eq!(protocol, None);
+
+        let ws = rt
+            .block_on(ws.send(Message::Text("hello".to_string())))
+            .unwrap();
+        let (echoed, ws) = rt.block_on(ws.into_future()).map_err(|(e, _)| e).unwrap();
+        assert_eq!(echoed, Some(Message::Text("hello".to_string())));
+
+        let ws = rt
+            .block_on(ws.send(Message::Binary(vec![1, 2, 3])))
+            .unwrap();
+        let (echoed, _) = rt.block_on(ws.into_future()).map_err(|(e, _)| e).unwrap();
+        assert_eq!(echoed, Some(Message::Binary(vec![1, 2, 3])));
+    }
+
+    #[test]
+    fn test_connect_rejects_scheme() {
+        let url = Url::parse("https://example.com/").unwrap();
+        let (resolver, tls) = connector();
+        let err = connect(url, vec![], resolver, tls).wait().err().unwrap();
+        assert!(format!("{}", err).contains("unsupported websocket scheme"));
+    }
+
+    #[test]
+    fn test_connect_blocked_literal() {
+        let mut filter = IpFilter::default();
+        filter.extend(vec!["127.0.0.0/8"]);
+        let (resolver, tls) = filtered_connector(filter);
+        let url = Url::parse("ws://127.0.0.1:1/").unwrap();
+        let err = connect(url, vec![], resolver, tls).wait().err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::AddressBlocked);
+    }
+}
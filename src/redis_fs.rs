@@ -1,8 +1,10 @@
+use crate::disk_fs::{blocking, on_event_loop};
 use crate::fs_store::*;
-use futures::{future, stream, Future};
+use futures::{future, stream, Future, Stream};
 
 use crate::settings::RedisStoreConfig;
 use std::fmt::Display;
+use std::ops::Range;
 
 extern crate r2d2_redis;
 use self::r2d2_redis::RedisConnectionManager;
@@ -30,11 +32,141 @@ impl RedisFsStore {
 
 impl FsStore for RedisFsStore {
     fn read(&self, path: String) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send> {
+        self.read_chunks(path, 0, None)
+    }
+
+    fn read_range(
+        &self,
+        path: String,
+        range: Range<u64>,
+    ) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send> {
+        self.read_chunks(path, range.start, Some(range.end))
+    }
+
+    fn stat(&self, path: String) -> Box<Future<Item = Option<FsMeta>, Error = FsError> + Send> {
+        let fullkey = self.file_key(&path);
+        debug!("redis fs stat with key: {}", fullkey);
+
+        let pool = self.pool.clone();
+        on_event_loop(blocking(move || match pool.get() {
+            Err(e) => Err(FsError::Failure(format!("{}", e))),
+            Ok(conn) => match redis::pipe()
+                .cmd("EXISTS")
+                .arg(&fullkey)
+                .cmd("STRLEN")
+                .arg(&fullkey)
+                .query::<(bool, u64)>(&*conn)
+            {
+                Err(e) => Err(FsError::Failure(format!("{}", e))),
+                Ok((false, _)) => Ok(None),
+                // redis doesn't keep track of when values were set
+                Ok((true, size)) => Ok(Some(FsMeta {
+                    size,
+                    modified: None,
+                    content_type: content_type(&path),
+                })),
+            },
+        }))
+    }
+
+    fn write(
+        &self,
+        path: String,
+        data_stream: Box<Stream<Item = Vec<u8>, Error = ()> + Send>,
+    ) -> EmptyFsFuture {
+        let fullkey = self.file_key(path);
+        debug!("redis fs write with key: {}", fullkey);
+
+        let pool = self.pool.clone();
+        // collected first, so readers never see part of a file
+        on_event_loop(
+            data_stream
+                .concat2()
+                .map_err(|_| FsError::Failure("error receiving file data".to_string()))
+                .and_then(move |data| {
+                    blocking(move || {
+                        let conn = pool.get().map_err(|e| FsError::Failure(format!("{}", e)))?;
+                        redis::cmd("SET")
+                            .arg(&fullkey)
+                            .arg(data)
+                            .query::<()>(&*conn)
+                            .map_err(|e| FsError::Failure(format!("{}", e)))
+                    })
+                }),
+        )
+    }
+
+    fn delete(&self, path: String) -> EmptyFsFuture {
+        let fullkey = self.file_key(path);
+        debug!("redis fs delete with key: {}", fullkey);
+
+        let pool = self.pool.clone();
+        on_event_loop(blocking(move || match pool.get() {
+            Err(e) => Err(FsError::Failure(format!("{}", e))),
+            Ok(conn) => redis::cmd("DEL")
+                .arg(&fullkey)
+                .query::<()>(&*conn)
+                .map_err(|e| FsError::Failure(format!("{}", e))),
+        }))
+    }
+
+    fn list(&self, prefix: String) -> Box<Future<Item = Vec<String>, Error = FsError> + Send> {
+        let ns = self.file_key("");
+        let pattern = format!("{}*", self.file_key(escape_pattern(&prefix)));
+        debug!("redis fs list with pattern: {}", pattern);
+
+        let pool = self.pool.clone();
+        on_event_loop(blocking(move || -> FsResult<Vec<String>> {
+            let conn = pool.get().map_err(|e| FsError::Failure(format!("{}", e)))?;
+            let mut paths = vec![];
+            let mut cursor = 0u64;
+            loop {
+                let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                    .arg(cursor)
+                    .arg("MATCH")
+                    .arg(&pattern)
+                    .arg("COUNT")
+                    .arg(1000)
+                    .query(&*conn)
+                    .map_err(|e| FsError::Failure(format!("{}", e)))?;
+                paths.extend(keys.into_iter().map(|k| k[ns.len()..].to_string()));
+                if next == 0 {
+                    break;
+                }
+                cursor = next;
+            }
+            paths.sort();
+            paths.dedup();
+            Ok(paths)
+        }))
+    }
+}
+
+// Matches a prefix literally in a SCAN pattern.
+fn escape_pattern(prefix: &str) -> String {
+    let mut escaped = String::with_capacity(prefix.len());
+    for c in prefix.chars() {
+        if "*?[]\\".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl RedisFsStore {
+    // Streams a value in GETRANGE sized chunks, from `start` up to `end`
+    // (exclusive) or the end of the value.
+    fn read_chunks(
+        &self,
+        path: String,
+        start: u64,
+        end: Option<u64>,
+    ) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send> {
         let fullkey = self.file_key(path);
         debug!("redis fs get with key: {}", fullkey);
 
         let pool = self.pool.clone();
-        // let getpool = self.pool.clone();
         Box::new(future::lazy(move || match pool.get() {
             Err(e) => Err(FsError::Failure(format!("{}", e))),
             Ok(conn) => match redis::cmd("EXISTS").arg(&fullkey).query::<bool>(&*conn) {
@@ -45,24 +177,31 @@ impl FsStore for RedisFsStore {
                     }
                     let size = 256 * 1024;
                     Ok(Some(FsEntry {
-                        stream: Box::new(stream::unfold(0, move |pos| {
-                            // End early given some rules!
-                            // not a multiple of size, means we're done.
-                            if pos > 0 && pos % size > 0 {
-                                return None;
-                            }
+                        stream: Box::new(stream::unfold(Some(start), move |pos| {
+                            // None once a chunk came back short, the value ended.
+                            let pos = pos?;
+                            let last = match end {
+                                Some(end) if pos >= end => return None,
+                                Some(end) => (pos + size).min(end) - 1,
+                                None => pos + size - 1,
+                            };
                             match redis::cmd("GETRANGE")
                                 .arg(&fullkey)
                                 .arg(pos)
-                                .arg(pos + size - 1) // end arg is inclusive
+                                .arg(last) // end arg is inclusive
                                 .query::<Vec<u8>>(&*conn)
                             {
                                 Ok(r) => {
-                                    let len = r.len();
+                                    let len = r.len() as u64;
                                     if len == 0 {
                                         return None;
                                     }
-                                    Some(future::ok::<(Vec<u8>, usize), _>((r, pos + len)))
+                                    let next = if len < last - pos + 1 {
+                                        None
+                                    } else {
+                                        Some(pos + len)
+                                    };
+                                    Some(future::ok::<(Vec<u8>, Option<u64>), _>((r, next)))
                                 }
                                 Err(e) => Some(future::err(FsError::Failure(format!("{}", e)))),
                             }
@@ -115,4 +254,81 @@ mod tests {
 
         assert!(store.read("notfound".to_string()).wait().unwrap().is_none());
     }
+
+    #[test]
+    fn test_redis_fs_read_range() {
+        let store = RedisFsStore::new(&RedisStoreConfig {
+            url: "redis://localhost:6379".to_string(),
+            namespace: Some("fstest".to_string()),
+        });
+        let path = "range.bin";
+
+        let mut v = [0u8; 1000];
+        thread_rng().fill_bytes(&mut v);
+
+        let conn = store.pool.get().unwrap();
+        redis::cmd("SET")
+            .arg(store.file_key(path))
+            .arg(v.to_vec())
+            .query::<()>(&*conn)
+            .unwrap();
+
+        assert_eq!(
+            store
+                .read_range(path.to_string(), 100..200)
+                .wait()
+                .unwrap()
+                .unwrap()
+                .stream
+                .concat2()
+                .wait()
+                .unwrap(),
+            v[100..200].to_vec()
+        );
+
+        let meta = store.stat(path.to_string()).wait().unwrap().unwrap();
+        assert_eq!(meta.size, 1000);
+        assert!(store.stat("notfound".to_string()).wait().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_redis_fs_write_list_delete() {
+        let store = RedisFsStore::new(&RedisStoreConfig {
+            url: "redis://localhost:6379".to_string(),
+            namespace: Some("fslisttest".to_string()),
+        });
+        for path in &["assets/app.js", "assets/a*b.css", "index.html"] {
+            store
+                .write(
+                    path.to_string(),
+                    Box::new(stream::once(Ok(path.as_bytes().to_vec()))),
+                )
+                .wait()
+                .unwrap();
+        }
+
+        let meta = store
+            .stat("assets/app.js".to_string())
+            .wait()
+            .unwrap()
+            .unwrap();
+        assert_eq!(meta.size, 13);
+        assert_eq!(meta.content_type, "application/javascript");
+
+        assert_eq!(
+            store.list("assets/".to_string()).wait().unwrap(),
+            vec!["assets/a*b.css", "assets/app.js"]
+        );
+        assert_eq!(
+            store.list("assets/a*".to_string()).wait().unwrap(),
+            vec!["assets/a*b.css"]
+        );
+
+        store.delete("assets/app.js".to_string()).wait().unwrap();
+        assert!(store
+            .read("assets/app.js".to_string())
+            .wait()
+            .unwrap()
+            .is_none());
+    }
 }
@@ -3,15 +3,17 @@ use tokio;
 use tokio::runtime::current_thread;
 
 use std::ffi::{CStr, CString};
-use std::sync::{Mutex, Once};
+use std::sync::{Arc, Mutex, Once};
 
 use self::fs::File;
 use std::fs;
 use std::io::Read;
+use std::path::PathBuf;
 
 use libfly::*;
 
 use futures::{
+  future::{self, Loop},
   sync::{mpsc, oneshot},
   Future,
 };
@@ -27,11 +29,14 @@ use std::ptr;
 use std::slice;
 
 use crate::js::*;
+use crate::metrics::RUNTIME_TERMINATIONS_TOTAL;
 
 use crate::msg;
 use flatbuffers::FlatBufferBuilder;
 
-use crate::errors::FlyError;
+use crate::error_reporting::{self, ErrorReport, ErrorSink};
+use crate::errors::{FlyError, FlyResult};
+use crate::event_clock::{EventClock, Verdict};
 
 use slog::{slog_debug, slog_error, slog_info, slog_o, slog_trace, slog_warn, Logger};
 
@@ -47,26 +52,33 @@ use crate::redis_cache;
 use crate::sqlite_cache;
 use crate::sqlite_data;
 
-use crate::{disk_fs, redis_fs};
+use crate::{disk_fs, redis_fs, s3_fs};
 
 use crate::v8env::{DEV_TOOLS_SOURCE, FLY_SNAPSHOT};
 
-use crate::runtime_permissions::RuntimePermissions;
+use crate::runtime_permissions::{Capability, RuntimePermissions};
 use crate::settings::{
-  AcmeStoreConfig, CacheStore, CacheStoreNotifier, DataStore, FsStore, Settings,
+  AcmeStoreConfig, CacheStore, CacheStoreNotifier, CompressionMode, DataStore, FsStore,
+  HttpCacheConfig, HttpLimits, Settings,
 };
+use crate::source_maps::SourceMaps;
 
 use crate::module_resolver::{
-  LoadedModule, LocalDiskModuleResolver, ModuleResolver, ModuleResolverManager, RefererInfo,
-  StandardModuleResolverManager,
+  resolve_import, LoadedModule, LocalDiskModuleResolver, ModuleLoadError, ModuleResolver,
+  ModuleResolverManager, StandardModuleResolverManager,
 };
 
 use super::NEXT_FUTURE_ID;
 use std::str;
 
 use std::time;
+use tokio::timer::Delay;
 
+use crate::http_client::{self, ClientIdentity, FetchClients};
+use crate::ip_filter::IpFilter;
 use crate::msg_handler::{DefaultMessageHandler, MessageHandler};
+use crate::ops::crypto::CryptoKeys;
+use crate::ops::websocket::SocketHandle;
 
 #[derive(Debug, Copy, Clone)]
 pub struct JsRuntime(pub *const js_runtime);
@@ -92,11 +104,46 @@ impl JsRuntime {
     self.send(fly_buf_from(buf), None);
   }
 
+  /// Sends into JS on behalf of `event`, the time JS takes counting against it.
+  pub fn send_for(&self, event: Option<u32>, buf: fly_buf, raw: Option<fly_buf>) {
+    let clock = self.to_runtime().event_clock.clone();
+    clock.enter(event);
+    self.send(buf, raw);
+    clock.leave();
+  }
+
   pub fn to_runtime<'a>(&self) -> &'a mut Runtime {
     unsafe { Runtime::from_raw(self.0) }
   }
 }
 
+/// Reaches a runtime from futures and drop guards which may outlive it.
+/// Disposing the runtime revokes every handle, waiting for the ones in use.
+/// The default handle doesn't reach any runtime.
+#[derive(Clone, Default)]
+pub struct RuntimeHandle(Arc<RwLock<Option<JsRuntime>>>);
+
+impl RuntimeHandle {
+  /// Runs `f` against the runtime, unless it was disposed.
+  pub fn with<F, R>(&self, f: F) -> Option<R>
+  where
+    F: FnOnce(&mut Runtime) -> R,
+  {
+    let guard = match self.0.read() {
+      Ok(guard) => guard,
+      Err(poisoned) => poisoned.into_inner(),
+    };
+    guard.map(|ptr| f(ptr.to_runtime()))
+  }
+
+  fn set(&self, ptr: Option<JsRuntime>) {
+    match self.0.write() {
+      Ok(mut guard) => *guard = ptr,
+      Err(poisoned) => *poisoned.into_inner() = ptr,
+    }
+  }
+}
+
 pub struct Runtime {
   pub ptr: JsRuntime,
   pub name: String,
@@ -104,9 +151,15 @@ pub struct Runtime {
   pub app_logger: Logger,
   pub event_loop: Mutex<current_thread::Handle>,
   pub timers: Mutex<HashMap<u32, oneshot::Sender<()>>>,
-  pub responses: Mutex<HashMap<u32, oneshot::Sender<JsHttpResponse>>>,
-  pub dns_responses: Mutex<HashMap<u32, oneshot::Sender<JsDnsResponse>>>,
-  pub streams: Mutex<HashMap<u32, mpsc::UnboundedSender<Vec<u8>>>>,
+  pub responses: Arc<Mutex<HashMap<u32, oneshot::Sender<JsHttpResponse>>>>,
+  pub dns_responses: Arc<Mutex<HashMap<u32, oneshot::Sender<JsDnsResponse>>>>,
+  pub streams: Mutex<HashMap<u32, mpsc::Sender<Vec<u8>>>>,
+  // credits granted by JS for streams flowing into it, one per chunk
+  stream_pulls: Mutex<HashMap<u32, PullChannel>>,
+  // outgoing messages of open websockets, by socket id
+  pub websockets: Mutex<HashMap<u32, SocketHandle>>,
+  // cancels in-flight outbound fetches, by request id
+  pub fetch_aborts: Mutex<HashMap<u32, oneshot::Sender<()>>>,
   pub cache_store: Box<cache_store::CacheStore + 'static + Send + Sync>,
   pub data_store: Box<data_store::DataStore + 'static + Send + Sync>,
   pub fs_store: Box<fs_store::FsStore + 'static + Send + Sync>,
@@ -118,11 +171,35 @@ pub struct Runtime {
   pub msg_handler: Box<MessageHandler>,
   pub permissions: RuntimePermissions,
   pub dev_tools: bool,
+  pub source_maps: SourceMaps,
+  pub crypto_keys: CryptoKeys,
+  pub fetch_clients: FetchClients,
+  pub error_hook: Option<ErrorHook>,
+  pub error_sink: Option<Arc<ErrorSink>>,
+  pub event_time_limit: Option<time::Duration>,
+  pub event_clock: EventClock,
+  pub http_limits: HttpLimits,
+  pub compression: CompressionMode,
+  pub http_cache: HttpCacheConfig,
+  /// How long an event stream may stay idle before a heartbeat is sent.
+  pub sse_heartbeat: time::Duration,
+  // cleared on dispose so a late watchdog never touches a freed isolate
+  terminator: Arc<Mutex<Option<JsRuntime>>>,
+  handle: RuntimeHandle,
+  heap_sample: Arc<Mutex<HeapSample>>,
   metadata_cache: RwLock<HashMap<i32, Box<LoadedModule>>>,
   ready_ch: Option<oneshot::Sender<()>>,
   quit_ch: Option<oneshot::Receiver<()>>,
 }
 
+// Latest heap statistics, taken on the runtime's thread.
+#[derive(Default)]
+struct HeapSample {
+  stats: js_heap_stats,
+  taken_at: Option<time::Instant>,
+  pending: bool,
+}
+
 static JSINIT: Once = Once::new();
 
 fn init_event_loop(
@@ -167,6 +244,9 @@ fn init_event_loop(
   p.wait().unwrap()
 }
 
+/// Called with every exception the app leaves unhandled.
+pub type ErrorHook = Arc<Fn(&Runtime, &JsException) + Send + Sync>;
+
 pub struct RuntimeConfig<'a> {
   pub name: Option<String>,
   pub version: Option<String>,
@@ -176,6 +256,10 @@ pub struct RuntimeConfig<'a> {
   pub msg_handler: Option<Box<MessageHandler>>,
   pub permissions: Option<RuntimePermissions>,
   pub dev_tools: bool,
+  pub error_hook: Option<ErrorHook>,
+  pub error_sink: Option<Arc<ErrorSink>>,
+  /// Client identities for fetch, instead of the ones in `settings`.
+  pub client_identities: Option<HashMap<String, ClientIdentity>>,
 }
 
 impl Runtime {
@@ -202,9 +286,12 @@ impl Runtime {
       ready_ch: Some(txready),
       quit_ch: Some(rxquit),
       timers: Mutex::new(HashMap::new()),
-      responses: Mutex::new(HashMap::new()),
-      dns_responses: Mutex::new(HashMap::new()),
+      responses: Arc::new(Mutex::new(HashMap::new())),
+      dns_responses: Arc::new(Mutex::new(HashMap::new())),
       streams: Mutex::new(HashMap::new()),
+      stream_pulls: Mutex::new(HashMap::new()),
+      websockets: Mutex::new(HashMap::new()),
+      fetch_aborts: Mutex::new(HashMap::new()),
       // stream_recv: Mutex::new(HashMap::new()),
       fetch_events: None,
       resolv_events: None,
@@ -233,11 +320,12 @@ impl Runtime {
         None => Box::new(sqlite_data::SqliteDataStore::new("data.db".to_string())),
       },
       fs_store: match config.settings.fs_store {
-        Some(ref store) => match store {
-          FsStore::Redis(conf) => Box::new(redis_fs::RedisFsStore::new(&conf)),
-          FsStore::Disk => Box::new(disk_fs::DiskFsStore::new()),
-        },
-        None => Box::new(disk_fs::DiskFsStore::new()),
+        Some(FsStore::Redis(ref conf)) => Box::new(redis_fs::RedisFsStore::new(&conf)),
+        Some(FsStore::S3(ref conf)) => Box::new(s3_fs::S3FsStore::new(&conf)),
+        Some(FsStore::Disk) | None => Box::new(match config.settings.fs_root {
+          Some(ref root) => disk_fs::DiskFsStore::with_root(PathBuf::from(root)),
+          None => disk_fs::DiskFsStore::new(),
+        }),
       },
       acme_store: match config.settings.acme_store {
         Some(ref config) => match config {
@@ -256,8 +344,42 @@ impl Runtime {
       msg_handler: config
         .msg_handler
         .unwrap_or(Box::new(DefaultMessageHandler {})),
-      permissions: config.permissions.unwrap_or_default(),
+      permissions: match config.permissions {
+        Some(permissions) => permissions,
+        None => config.settings.permissions.clone().unwrap_or_default(),
+      },
       dev_tools: config.dev_tools,
+      source_maps: SourceMaps::new(),
+      crypto_keys: CryptoKeys::new(),
+      fetch_clients: FetchClients::new(
+        config.settings.fetch.clone().unwrap_or_default(),
+        config
+          .settings
+          .ip_filter
+          .as_ref()
+          .map_or_else(IpFilter::default, IpFilter::from_config),
+        match config.client_identities {
+          Some(identities) => identities,
+          None => http_client::load_identities(config.settings),
+        },
+      ),
+      error_hook: config.error_hook,
+      error_sink: config.error_sink,
+      event_time_limit: config
+        .settings
+        .event_time_limit_ms
+        .map(time::Duration::from_millis),
+      event_clock: EventClock::new(),
+      http_limits: config.settings.http_limits.clone().unwrap_or_default(),
+      compression: config.settings.compression.unwrap_or_default(),
+      http_cache: config.settings.http_cache.clone().unwrap_or_default(),
+      sse_heartbeat: config
+        .settings
+        .sse_heartbeat_secs
+        .map_or(DEFAULT_SSE_HEARTBEAT, time::Duration::from_secs),
+      terminator: Arc::new(Mutex::new(None)),
+      handle: RuntimeHandle(Arc::new(RwLock::new(None))),
+      heap_sample: Arc::new(Mutex::new(HeapSample::default())),
     });
 
     (*rt).ptr.0 = unsafe {
@@ -267,6 +389,7 @@ impl Runtime {
         recv_cb: msg_from_js,
         print_cb: print_from_js,
         resolve_cb: resolve_callback,
+        exception_cb: exception_from_js,
         soft_memory_limit: 128,
         hard_memory_limit: 256,
       });
@@ -275,6 +398,8 @@ impl Runtime {
       js_eval(ptr, cfilename.as_ptr(), cscript.as_ptr());
       ptr
     };
+    *rt.terminator.lock().unwrap() = Some(rt.ptr);
+    rt.handle.set(Some(rt.ptr));
 
     if config.dev_tools {
       debug!("Loading dev tools");
@@ -297,6 +422,29 @@ impl Runtime {
     debug!("finished evaluating '{}'", cfilename.to_string_lossy());
   }
 
+  /// Runs a module, resolved without a referrer. Failing to load one of its
+  /// imports throws in the module, reported like any other exception.
+  pub fn run_module(&self, specifier: &str) -> Result<(), ModuleLoadError> {
+    let compiled_module = self.load_module(specifier)?;
+    debug!("running module '{}'", specifier);
+    unsafe {
+      js_run_module(self.ptr.0, compiled_module);
+    }
+    Ok(())
+  }
+
+  fn load_module(&self, specifier: &str) -> Result<js_compiled_module, ModuleLoadError> {
+    let loaded_module = self
+      .module_resolver_manager
+      .resolve_module(specifier.to_string(), None)
+      .map_err(|e| ModuleLoadError::Resolve {
+        specifier: specifier.to_string(),
+        referrer: String::new(),
+        message: format!("{}", e),
+      })?;
+    unsafe { compile_module(self, specifier, String::new(), loaded_module) }
+  }
+
   pub fn eval_file(&self, filename: &str) {
     let mut file = File::open(filename).unwrap();
     let mut contents = String::new();
@@ -309,8 +457,49 @@ impl Runtime {
     self.eval(filename, &format!("dev.run('{}')", filename));
   }
 
+  /// Heap statistics as of the last sample. Stale samples get refreshed on
+  /// the runtime's own thread, so callers never enter the isolate from theirs
+  /// or while holding a lock of their own; the fresh numbers show up in a
+  /// later call.
   pub fn heap_statistics(&self) -> js_heap_stats {
-    unsafe { js_runtime_heap_statistics(self.ptr.0) }
+    let mut sample = match self.heap_sample.lock() {
+      Ok(guard) => guard,
+      Err(poisoned) => poisoned.into_inner(),
+    };
+    let stale = sample
+      .taken_at
+      .map_or(true, |at| at.elapsed() >= HEAP_SAMPLE_INTERVAL);
+    if stale && !sample.pending {
+      sample.pending = self.sample_heap_statistics();
+    }
+    sample.stats
+  }
+
+  fn sample_heap_statistics(&self) -> bool {
+    let terminator = self.terminator.clone();
+    let heap_sample = self.heap_sample.clone();
+    let handle = match self.event_loop.lock() {
+      Ok(handle) => handle.clone(),
+      Err(_) => return false,
+    };
+    handle
+      .spawn(future::lazy(move || {
+        let stats = match terminator.lock() {
+          Ok(guard) => guard.map(|ptr| unsafe { js_runtime_heap_statistics(ptr.0) }),
+          Err(_) => None,
+        };
+        let mut sample = match heap_sample.lock() {
+          Ok(guard) => guard,
+          Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Some(stats) = stats {
+          sample.stats = stats;
+          sample.taken_at = Some(time::Instant::now());
+        }
+        sample.pending = false;
+        Ok(())
+      }))
+      .is_ok()
   }
 
   pub unsafe fn from_raw<'a>(raw: *const js_runtime) -> &'a mut Self {
@@ -318,7 +507,13 @@ impl Runtime {
     &mut *ptr
   }
 
+  pub fn handle(&self) -> RuntimeHandle {
+    self.handle.clone()
+  }
+
   pub fn dispose(&mut self) {
+    self.handle.set(None);
+
     {
       // stop listening to events
       self.fetch_events.take();
@@ -335,11 +530,99 @@ impl Runtime {
       Err(_) => error!("error acquiring lock to clear streams"),
     };
 
+    match self.stream_pulls.lock() {
+      Ok(mut pulls) => pulls.clear(),
+      Err(_) => error!("error acquiring lock to clear stream pulls"),
+    };
+
+    match self.websockets.lock() {
+      Ok(mut sockets) => sockets.clear(),
+      Err(_) => error!("error acquiring lock to clear websockets"),
+    };
+
+    match self.fetch_aborts.lock() {
+      Ok(mut aborts) => aborts.clear(),
+      Err(_) => error!("error acquiring lock to clear fetch aborts"),
+    };
+
+    self.crypto_keys.clear();
+    self.fetch_clients.clear();
+
+    let mut terminator = match self.terminator.lock() {
+      Ok(guard) => guard,
+      Err(poisoned) => poisoned.into_inner(),
+    };
+    terminator.take();
+
     unsafe {
       js_runtime_dispose(self.ptr.0);
     };
   }
 
+  // Number of events and response bodies this runtime is still working on.
+  pub fn in_flight(&self) -> usize {
+    let responses = self.responses.lock().map(|g| g.len()).unwrap_or(0);
+    let dns_responses = self.dns_responses.lock().map(|g| g.len()).unwrap_or(0);
+    let streams = self.streams.lock().map(|g| g.len()).unwrap_or(0);
+    let websockets = self.websockets.lock().map(|g| g.len()).unwrap_or(0);
+    responses + dns_responses + streams + websockets
+  }
+
+  // Fails every event and stream still in flight, for a runtime which can't
+  // wait on them any longer.
+  pub fn abandon_in_flight(&self) {
+    if let Ok(mut responses) = self.responses.lock() {
+      responses.clear();
+    }
+    if let Ok(mut dns_responses) = self.dns_responses.lock() {
+      dns_responses.clear();
+    }
+    if let Ok(mut streams) = self.streams.lock() {
+      streams.clear();
+    }
+    if let Ok(mut sockets) = self.websockets.lock() {
+      sockets.clear();
+    }
+  }
+
+  // Lets the stream flowing to JS under `id` send one more chunk.
+  pub fn pull_stream(&self, id: u32) {
+    let mut pulls = self.stream_pulls.lock().unwrap();
+    let (tx, _) = pulls.entry(id).or_insert_with(pull_channel);
+    if let Err(e) = tx.unbounded_send(()) {
+      error!("error pulling stream {}: {}", id, e);
+    }
+  }
+
+  // Credits for the stream flowing to JS under `id`. Ends when JS cancels the stream.
+  pub fn take_stream_pulls(&self, id: u32) -> mpsc::UnboundedReceiver<()> {
+    let mut pulls = self.stream_pulls.lock().unwrap();
+    match pulls.entry(id).or_insert_with(pull_channel).1.take() {
+      Some(rx) => rx,
+      None => {
+        error!("stream {} is already being sent", id);
+        mpsc::unbounded().1
+      }
+    }
+  }
+
+  pub fn end_stream_pulls(&self, id: u32) {
+    self.stream_pulls.lock().unwrap().remove(&id);
+  }
+
+  // The client of request `id` went away before getting a response. Drops
+  // what is still pending for it and fires the request's abort signal.
+  pub fn abort_request(&self, id: u32) {
+    if let Ok(mut responses) = self.responses.lock() {
+      responses.remove(&id);
+    }
+    if let Ok(mut streams) = self.streams.lock() {
+      streams.remove(&id);
+    }
+    self.end_stream_pulls(id);
+    send_request_abort(self.ptr, id);
+  }
+
   pub fn run(&mut self) -> oneshot::Receiver<()> {
     self.ready_ch.take().unwrap().send(()).unwrap(); //TODO: no unwrap
     self.quit_ch.take().unwrap()
@@ -359,6 +642,18 @@ impl Runtime {
       .unwrap();
   }
 
+  /// Like `spawn`, for callers which must not panic, such as drop handlers.
+  /// Returns whether the future was spawned.
+  pub fn try_spawn<F>(&self, fut: F) -> bool
+  where
+    F: Future<Item = (), Error = ()> + Send + 'static,
+  {
+    match self.event_loop.lock() {
+      Ok(handle) => handle.spawn(fut).is_ok(),
+      Err(_) => false,
+    }
+  }
+
   pub fn dispatch_event(
     &self,
     id: u32,
@@ -372,7 +667,10 @@ impl Runtime {
             let (tx, rx) = oneshot::channel::<JsHttpResponse>();
             guard.insert(id, tx);
             match ch.unbounded_send(req) {
-              Ok(_) => EventResponseChannel::Http(rx),
+              Ok(_) => {
+                self.watch_event(id, "fetch", self.responses.clone());
+                EventResponseChannel::Http(rx)
+              }
               Err(e) => return Some(Err(EventDispatchError::Http(e))),
             }
           }
@@ -386,7 +684,10 @@ impl Runtime {
             let (tx, rx) = oneshot::channel::<JsDnsResponse>();
             guard.insert(id, tx);
             match ch.unbounded_send(req) {
-              Ok(_) => EventResponseChannel::Dns(rx),
+              Ok(_) => {
+                self.watch_event(id, "resolv", self.dns_responses.clone());
+                EventResponseChannel::Dns(rx)
+              }
               Err(e) => return Some(Err(EventDispatchError::Dns(e))),
             }
           }
@@ -404,6 +705,104 @@ impl Runtime {
     Some(Ok(res))
   }
 
+  // Fails an event still unanswered once the isolate spent `event_time_limit`
+  // running its code, waiting on ops doesn't count. Dropping the pending
+  // sender fails the event for the caller (serve_http answers with a 5xx).
+  // The script is only terminated if it's the event's own code running, the
+  // isolate itself is recovered by libfly and keeps serving other events.
+  fn watch_event<T>(&self, id: u32, kind: &'static str, pending: PendingResponses<T>)
+  where
+    T: Send + 'static,
+  {
+    let limit = match self.event_time_limit {
+      Some(limit) => limit,
+      None => return,
+    };
+    let clock = self.event_clock.clone();
+    clock.watch(id);
+    let terminator = self.terminator.clone();
+    let logger = self.app_logger.clone();
+    let name = self.name.clone();
+    let version = self.version.clone();
+
+    EVENT_LOOP.0.spawn(future::loop_fn(limit, move |wait| {
+      let clock = clock.clone();
+      let pending = pending.clone();
+      let terminator = terminator.clone();
+      let logger = logger.clone();
+      let name = name.clone();
+      let version = version.clone();
+      Delay::new(time::Instant::now() + wait)
+        .map_err(|e| error!("watchdog timer error: {}", e))
+        .map(move |_| {
+          let answered = match pending.lock() {
+            Ok(guard) => !guard.contains_key(&id),
+            Err(_) => true,
+          };
+          if answered {
+            clock.forget(id);
+            return Loop::Break(());
+          }
+          let running = match clock.verdict(id, limit) {
+            Verdict::Within(left) => return Loop::Continue(left.max(MIN_WATCH_INTERVAL)),
+            Verdict::Exceeded { running } => running,
+          };
+          let expired = match pending.lock() {
+            Ok(mut guard) => guard.remove(&id).is_some(),
+            Err(_) => false,
+          };
+          clock.forget(id);
+          if !expired {
+            return Loop::Break(());
+          }
+
+          let terminated = running
+            && match terminator.lock() {
+              Ok(guard) => match *guard {
+                Some(ptr) => clock
+                  .terminate_if_running(id, || unsafe { js_runtime_terminate_execution(ptr.0) }),
+                None => false,
+              },
+              Err(_) => false,
+            };
+
+          RUNTIME_TERMINATIONS_TOTAL
+            .with_label_values(&[name.as_str(), version.as_str(), kind])
+            .inc();
+          slog_warn!(
+            logger,
+            "{} event {} exceeded its time limit of {}ms (script terminated: {})",
+            kind,
+            id,
+            limit.as_millis(),
+            terminated
+          );
+          Loop::Break(())
+        })
+    }));
+  }
+
+  /// Checks the app's permissions, counting any denial.
+  pub fn check_permission(&self, cap: Capability) -> FlyResult<()> {
+    self
+      .permissions
+      .check_counted(&self.name, &self.version, cap)
+  }
+
+  pub fn report_exception(&self, exception: &JsException) {
+    slog_error!(self.app_logger, #"app", "{}", exception;
+      "source" => "app",
+      "error" => exception.message.as_str(),
+      "request_id" => exception.request_id
+    );
+    if let Some(ref hook) = self.error_hook {
+      hook(self, exception);
+    }
+    if let Some(ref sink) = self.error_sink {
+      sink.report(ErrorReport::exception(&self.name, &self.version, exception));
+    }
+  }
+
   pub fn get_module_metadata(&self, hash: &i32) -> Option<Box<LoadedModule>> {
     return match self.metadata_cache.read().unwrap().get(hash) {
       Some(v) => Some((*v).clone()),
@@ -411,8 +810,8 @@ impl Runtime {
     };
   }
 
-  pub fn insert_module_metadata(&mut self, hash: i32, module_metadata: LoadedModule) {
-    let locked_cache = self.metadata_cache.get_mut().unwrap();
+  pub fn insert_module_metadata(&self, hash: i32, module_metadata: LoadedModule) {
+    let mut locked_cache = self.metadata_cache.write().unwrap();
     if locked_cache.contains_key(&hash) {
       error!("Attempted to overwrite entry in module metadata cache.");
     } else {
@@ -421,6 +820,52 @@ impl Runtime {
   }
 }
 
+type PullChannel = (
+  mpsc::UnboundedSender<()>,
+  Option<mpsc::UnboundedReceiver<()>>,
+);
+
+fn pull_channel() -> PullChannel {
+  let (tx, rx) = mpsc::unbounded();
+  (tx, Some(rx))
+}
+
+type PendingResponses<T> = Arc<Mutex<HashMap<u32, oneshot::Sender<T>>>>;
+
+/// A runtime keeping its stores in memory, for tests.
+#[cfg(test)]
+pub fn test_runtime() -> Box<Runtime> {
+  use crate::settings::SqliteStoreConfig;
+  let in_memory = SqliteStoreConfig {
+    filename: ":memory:".to_string(),
+  };
+  let settings = Settings {
+    cache_store: Some(CacheStore::Sqlite(in_memory.clone())),
+    data_store: Some(DataStore::Sqlite(in_memory)),
+    ..Settings::default()
+  };
+  let mut rt = Runtime::new(RuntimeConfig {
+    name: Some("test".to_string()),
+    version: None,
+    settings: &settings,
+    module_resolvers: None,
+    app_logger: &slog::Logger::root(slog::Discard, slog::o!()),
+    msg_handler: None,
+    permissions: None,
+    dev_tools: false,
+    error_hook: None,
+    error_sink: None,
+    client_identities: None,
+  });
+  let _ = rt.run();
+  rt
+}
+
+static HEAP_SAMPLE_INTERVAL: time::Duration = time::Duration::from_secs(10);
+static DEFAULT_SSE_HEARTBEAT: time::Duration = time::Duration::from_secs(15);
+// How often, at most, a watchdog looks at the time an event used.
+static MIN_WATCH_INTERVAL: time::Duration = time::Duration::from_millis(10);
+
 lazy_static! {
   static ref GENERIC_EVENT_LOOP: tokio::runtime::Runtime = {
     let el = tokio::runtime::Runtime::new().unwrap();
@@ -448,12 +893,25 @@ pub extern "C" fn msg_from_js(raw: *const js_runtime, buf: fly_buf, raw_buf: fly
 
   let msg_type = base.msg_type();
   let cmd_id = base.cmd_id();
+  // the op's callback runs on behalf of the event that started it
+  let event = rt.event_clock.current();
+
+  let reporter = rt
+    .error_sink
+    .clone()
+    .map(|sink| (sink, rt.name.clone(), rt.version.clone()));
 
   let fut = rt
     .msg_handler
     .handle_msg(ptr.to_runtime(), &base, raw_buf)
     .or_else(move |err| {
       error!("error in {:?}: {:?}", msg_type, err);
+      match reporter {
+        Some((sink, name, version)) if error_reporting::is_unexpected(&err) => sink.report(
+          ErrorReport::op_failure(&name, &version, event, &format!("{:?}", msg_type), &err),
+        ),
+        _ => {}
+      }
       Ok(build_error(cmd_id, err))
     });
 
@@ -489,7 +947,7 @@ pub extern "C" fn msg_from_js(raw: *const js_runtime, buf: fly_buf, raw_buf: fly
           )
         }
       };
-      ptr.send(buf, None);
+      ptr.send_for(event, buf, None);
       Ok(())
     });
     let rt = ptr.to_runtime();
@@ -523,6 +981,53 @@ pub unsafe extern "C" fn print_from_js(raw: *const js_runtime, lvl: i8, msg: *co
   };
 }
 
+pub unsafe extern "C" fn exception_from_js(
+  raw: *const js_runtime,
+  message: *const libc::c_char,
+  frames: *const js_stack_frame,
+  frame_count: libc::c_int,
+  request_id: i64,
+) {
+  let rt = Runtime::from_raw(raw);
+  let to_string = |s: *const libc::c_char| {
+    if s.is_null() {
+      String::new()
+    } else {
+      CStr::from_ptr(s).to_string_lossy().into_owned()
+    }
+  };
+
+  let frames: &[js_stack_frame] = if frames.is_null() || frame_count <= 0 {
+    &[]
+  } else {
+    slice::from_raw_parts(frames, frame_count as usize)
+  };
+
+  let exception = JsException {
+    message: to_string(message),
+    frames: frames
+      .iter()
+      .map(|f| {
+        let mut frame = JsFrame {
+          function_name: to_string(f.function_name),
+          filename: to_string(f.script_name),
+          line: f.line.max(0) as u32,
+          col: f.column.max(0) as u32,
+        };
+        rt.source_maps.remap(&mut frame);
+        frame
+      })
+      .collect(),
+    request_id: if request_id >= 0 {
+      Some(request_id as u32)
+    } else {
+      None
+    },
+  };
+
+  rt.report_exception(&exception);
+}
+
 pub unsafe extern "C" fn resolve_callback(
   raw: *const js_runtime,
   specifier: *const libc::c_char,
@@ -531,48 +1036,236 @@ pub unsafe extern "C" fn resolve_callback(
   let rt = Runtime::from_raw(raw);
   let specifier_str = CStr::from_ptr(specifier).to_string_lossy().into_owned();
 
-  let referer_loaded_module = match rt.get_module_metadata(&referer_identity_hash) {
-    Some(v) => v,
-    None => {
-      error!("Failed to find module hash in metadata cache! Exiting.");
-      std::process::exit(1);
+  match import_module(rt, &specifier_str, referer_identity_hash) {
+    Ok(compiled_module) => compiled_module,
+    Err(e) => {
+      slog_warn!(rt.app_logger, "{}", e);
+      // V8 rethrows this in the importing script once we hand back no module.
+      let message = c_message(&format!("{}", e));
+      let cspecifier = c_message(e.specifier());
+      let creferrer = c_message(&e.referrer());
+      js_throw_module_error(
+        raw,
+        e.is_syntax_error(),
+        message.as_ptr(),
+        cspecifier.as_ptr(),
+        creferrer.as_ptr(),
+      );
+      js_compiled_module {
+        hash: 0,
+        data: js_module_data {
+          origin_url: ptr::null(),
+          source_map_url: ptr::null(),
+          is_wasm: false,
+          source_code: fly_simple_buf {
+            ptr: ptr::null(),
+            len: 0,
+          },
+        },
+        ptr: ptr::null_mut(),
+      }
     }
-  };
+  }
+}
 
-  let loaded_module = match rt.module_resolver_manager.resolve_module(
-    specifier_str,
-    Some(RefererInfo {
-      origin_url: referer_loaded_module.origin_url,
-      is_wasm: Some(referer_loaded_module.loaded_source.is_wasm),
-      source_code: Some(referer_loaded_module.loaded_source.source),
-      indentifier_hash: Some(referer_identity_hash),
-    }),
-  ) {
-    Ok(v) => v,
-    Err(e) => {
-      error!("Failed to resolve and load module! Exiting. {}", e);
-      std::process::exit(1);
+unsafe fn import_module(
+  rt: &Runtime,
+  specifier: &str,
+  referer_identity_hash: i32,
+) -> Result<js_compiled_module, ModuleLoadError> {
+  let referer_loaded_module = rt.get_module_metadata(&referer_identity_hash);
+  let referrer = referer_loaded_module
+    .as_ref()
+    .map(|m| m.origin_url.clone())
+    .unwrap_or_default();
+
+  let loaded_module = resolve_import(
+    &*rt.module_resolver_manager,
+    specifier,
+    referer_identity_hash,
+    referer_loaded_module,
+  )?;
+
+  compile_module(rt, specifier, referrer, loaded_module)
+}
+
+// Compiles a loaded module, remembering it so its own imports can be resolved.
+unsafe fn compile_module(
+  rt: &Runtime,
+  specifier: &str,
+  referrer: String,
+  loaded_module: LoadedModule,
+) -> Result<js_compiled_module, ModuleLoadError> {
+  let origin_url = match CString::new(loaded_module.origin_url.as_str()) {
+    Ok(url) => url,
+    Err(_) => {
+      return Err(ModuleLoadError::Resolve {
+        specifier: specifier.to_string(),
+        referrer,
+        message: "resolved module url contains a NUL byte".to_string(),
+      });
     }
   };
-
+  let source_map_url = CString::default();
+  // passed with its length, NUL bytes in the source are fine
+  let source_code = loaded_module.loaded_source.source.as_bytes();
   let module_data = js_module_data {
-    origin_url: CString::new(loaded_module.origin_url).unwrap().as_ptr(),
-    source_map_url: CString::new("").unwrap().as_ptr(),
+    origin_url: origin_url.as_ptr(),
+    source_map_url: source_map_url.as_ptr(),
     is_wasm: loaded_module.loaded_source.is_wasm,
     source_code: fly_simple_buf {
-      ptr: CString::new(loaded_module.loaded_source.source.as_str())
-        .unwrap()
-        .as_ptr(),
-      len: loaded_module.loaded_source.source.len() as i32,
+      ptr: source_code.as_ptr() as *const libc::c_char,
+      len: source_code.len() as i32,
     },
   };
 
-  let compile_result = js_compile_module(raw, module_data);
+  let compile_result = js_compile_module(rt.ptr.0, module_data);
 
   if compile_result.success {
-    return compile_result.compiled_module;
+    if let Some(ref source_map) = loaded_module.loaded_source.source_map {
+      if let Err(e) = rt.source_maps.insert(&loaded_module.origin_url, source_map) {
+        slog_warn!(
+          rt.app_logger,
+          "invalid source map for {}: {}",
+          loaded_module.origin_url,
+          e
+        );
+      }
+    }
+    let compiled_module = compile_result.compiled_module;
+    rt.insert_module_metadata(compiled_module.hash, loaded_module);
+    Ok(compiled_module)
   } else {
-    error!("Module compile failed! Exiting.");
-    std::process::exit(1);
+    let message = if compile_result.error.is_null() {
+      "module failed to compile".to_string()
+    } else {
+      CStr::from_ptr(compile_result.error)
+        .to_string_lossy()
+        .into_owned()
+    };
+    Err(ModuleLoadError::Compile {
+      specifier: specifier.to_string(),
+      referrer,
+      message,
+    })
+  }
+}
+
+// Error text handed to V8, which can't take NUL bytes.
+fn c_message(s: &str) -> CString {
+  CString::new(s.replace('\0', "")).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Collects what a runtime reports as thrown.
+  fn thrown(rt: &mut Runtime) -> Arc<Mutex<Vec<String>>> {
+    let messages = Arc::new(Mutex::new(vec![]));
+    let sink = messages.clone();
+    rt.error_hook = Some(Arc::new(move |_: &Runtime, e: &JsException| {
+      sink.lock().unwrap().push(e.message.clone());
+    }));
+    messages
+  }
+
+  fn module_url(dir: &tempfile::TempDir, name: &str) -> String {
+    url::Url::from_file_path(dir.path().join(name))
+      .unwrap()
+      .to_string()
+  }
+
+  // Failed imports are thrown at the importer, the runtime carries on.
+  fn assert_still_running(rt: &Runtime, thrown: &Arc<Mutex<Vec<String>>>) {
+    thrown.lock().unwrap().clear();
+    rt.eval("after.js", "throw new Error('still running')");
+    let messages = thrown.lock().unwrap();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("still running"), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_import_missing_module() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("index.js"), "import './missing.js';").unwrap();
+    let mut rt = test_runtime();
+    let errors = thrown(&mut rt);
+
+    rt.run_module(&module_url(&dir, "index.js")).unwrap();
+    {
+      let messages = errors.lock().unwrap();
+      assert_eq!(messages.len(), 1, "{:?}", messages);
+      assert!(messages[0].contains("'./missing.js'"), "{}", messages[0]);
+      assert!(
+        messages[0].contains(&module_url(&dir, "index.js")),
+        "{}",
+        messages[0]
+      );
+      assert!(!messages[0].contains("SyntaxError"), "{}", messages[0]);
+    }
+    assert_still_running(&rt, &errors);
+    rt.dispose();
+  }
+
+  #[test]
+  fn test_import_broken_module() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("index.js"), "import './broken.js';").unwrap();
+    fs::write(dir.path().join("broken.js"), "export let = ;").unwrap();
+    let mut rt = test_runtime();
+    let errors = thrown(&mut rt);
+
+    rt.run_module(&module_url(&dir, "index.js")).unwrap();
+    {
+      let messages = errors.lock().unwrap();
+      assert_eq!(messages.len(), 1, "{:?}", messages);
+      assert!(messages[0].contains("SyntaxError"), "{}", messages[0]);
+      assert!(messages[0].contains("'./broken.js'"), "{}", messages[0]);
+      assert!(
+        messages[0].contains(&module_url(&dir, "index.js")),
+        "{}",
+        messages[0]
+      );
+    }
+    assert_still_running(&rt, &errors);
+    rt.dispose();
+  }
+
+  #[test]
+  fn test_import_from_unknown_referrer() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("index.js"), "import './dep.js';").unwrap();
+    fs::write(dir.path().join("dep.js"), "export default 1;").unwrap();
+    let mut rt = test_runtime();
+    let errors = thrown(&mut rt);
+
+    // the importer is compiled, then forgotten before its imports are resolved
+    let compiled_module = rt.load_module(&module_url(&dir, "index.js")).unwrap();
+    let referrer = format!("#{}", compiled_module.hash);
+    rt.metadata_cache.write().unwrap().clear();
+    unsafe {
+      assert!(!js_run_module(rt.ptr.0, compiled_module));
+    }
+    {
+      let messages = errors.lock().unwrap();
+      assert_eq!(messages.len(), 1, "{:?}", messages);
+      assert!(messages[0].contains("'./dep.js'"), "{}", messages[0]);
+      assert!(messages[0].contains(&referrer), "{}", messages[0]);
+    }
+    assert_still_running(&rt, &errors);
+    rt.dispose();
+  }
+
+  #[test]
+  fn test_missing_entry_module() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut rt = test_runtime();
+    let url = module_url(&dir, "index.js");
+    match rt.run_module(&url) {
+      Err(ModuleLoadError::Resolve { specifier, .. }) => assert_eq!(specifier, url),
+      other => panic!("expected a resolve error, got {:?}", other.err()),
+    }
+    rt.dispose();
   }
 }
@@ -0,0 +1,149 @@
+use futures::{
+    future::{self, Either, Loop},
+    Future,
+};
+use libfly::js_heap_stats;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::timer::Delay;
+
+use crate::metrics::RUNTIME_RECYCLES_TOTAL;
+use crate::runtime::Runtime;
+use crate::runtime_pool::PooledRuntime;
+use crate::SelectorError;
+
+/// Fraction of the isolate's heap limit past which a runtime gets recycled.
+pub static DEFAULT_HEAP_RECYCLE_THRESHOLD: f64 = 0.9;
+
+/// How long a replaced runtime gets to finish its in-flight events before
+/// being disposed regardless.
+static DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+static DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Implemented by selectors able to swap a runtime for a freshly built one.
+pub trait RecyclableSelector {
+    /// Builds and starts a new runtime for `key` in place of its isolate using
+    /// the most heap. Returns the runtime it replaced, if any.
+    fn replace_runtime(&self, key: &str) -> Result<Option<PooledRuntime>, SelectorError>;
+}
+
+pub fn heap_exceeded(stats: &js_heap_stats, threshold: f64) -> bool {
+    stats.heap_size_limit > 0
+        && stats.used_heap_size as f64 >= stats.heap_size_limit as f64 * threshold
+}
+
+/// Swaps the runtime behind `key` and disposes of the old one once drained.
+/// Must be called from within a tokio runtime.
+pub fn recycle<S: RecyclableSelector + ?Sized>(selector: &S, key: &str) {
+    match selector.replace_runtime(key) {
+        Ok(Some(old)) => {
+            info!("recycling runtime {}:v{}", old.name, old.version);
+            RUNTIME_RECYCLES_TOTAL
+                .with_label_values(&[old.name.as_str(), old.version.as_str()])
+                .inc();
+            tokio::spawn(retire(old));
+        }
+        Ok(None) => {}
+        Err(e) => error!("error replacing runtime {}: {:?}", key, e),
+    }
+}
+
+/// Waits for in-flight responses and streams of a runtime which no longer
+/// receives events, and for its last lease to be dropped, then disposes of it
+/// on its own thread. What's still in flight after the drain timeout is
+/// abandoned, the runtime is never disposed of while anything uses it.
+pub fn retire(rt: PooledRuntime) -> impl Future<Item = (), Error = ()> + Send {
+    let deadline = Instant::now() + DRAIN_TIMEOUT;
+    future::loop_fn(Draining(rt), move |mut draining| {
+        let in_flight = draining.0.in_flight();
+        if in_flight == 0 {
+            match Arc::try_unwrap(draining.0) {
+                Ok(rt) => return Either::A(future::ok(Loop::Break(Retiring(rt)))),
+                // still leased, an event is about to be dispatched to it
+                Err(rt) => draining = Draining(rt),
+            }
+        } else if Instant::now() >= deadline {
+            warn!(
+                "runtime {}:v{} still had {} events in flight after draining, abandoning them",
+                draining.0.name, draining.0.version, in_flight
+            );
+            draining.0.abandon_in_flight();
+        }
+        Either::B(
+            Delay::new(Instant::now() + DRAIN_POLL_INTERVAL)
+                .map_err(|e| error!("drain timer error: {}", e))
+                .map(move |_| Loop::Continue(draining)),
+        )
+    })
+    .map(dispose_on_own_thread)
+}
+
+// The isolate may still be running timers on the runtime's thread, it's only
+// safe to dispose of it from there, between two of its tasks.
+fn dispose_on_own_thread(retiring: Retiring) {
+    debug!(
+        "disposing of retired runtime {}:v{}",
+        retiring.0.name, retiring.0.version
+    );
+    let handle = match retiring.0.event_loop.lock() {
+        Ok(handle) => handle.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+    let slot = Arc::new(Mutex::new(Some(retiring)));
+    let task_slot = slot.clone();
+    let spawned = handle.spawn(future::lazy(move || {
+        if let Some(mut retiring) = task_slot.lock().unwrap().take() {
+            retiring.0.dispose();
+        }
+        Ok(())
+    }));
+    if spawned.is_err() {
+        // its event loop is gone, nothing runs the isolate anymore
+        if let Some(mut retiring) = slot.lock().unwrap().take() {
+            retiring.0.dispose();
+        }
+    }
+}
+
+// `Runtime` isn't `Send` because of its boxed module resolvers and message
+// handler, which are only ever called from the isolate's callbacks, on the
+// runtime's own thread. A retiring runtime doesn't receive events anymore and
+// its isolate is only disposed of from its own thread (or once that thread is
+// gone), so moving the box between threads never races with those callbacks.
+struct Retiring(Box<Runtime>);
+unsafe impl Send for Retiring {}
+
+// Same as `Retiring`, for a runtime whose leases may still be around. Those
+// only ever read from it to dispatch events.
+struct Draining(PooledRuntime);
+unsafe impl Send for Draining {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(used: usize, limit: usize) -> js_heap_stats {
+        js_heap_stats {
+            total_heap_size: used,
+            total_heap_size_executable: 0,
+            total_physical_size: used,
+            total_available_size: limit - used,
+            used_heap_size: used,
+            heap_size_limit: limit,
+            malloced_memory: 0,
+            peak_malloced_memory: 0,
+            number_of_native_contexts: 1,
+            number_of_detached_contexts: 0,
+            does_zap_garbage: false,
+            externally_allocated: 0,
+        }
+    }
+
+    #[test]
+    fn test_heap_exceeded() {
+        assert!(!heap_exceeded(&stats(50, 100), 0.9));
+        assert!(heap_exceeded(&stats(90, 100), 0.9));
+        assert!(heap_exceeded(&stats(100, 100), 0.9));
+        assert!(!heap_exceeded(&stats(0, 0), 0.9));
+    }
+}
@@ -1,26 +1,229 @@
-use crate::errors::{permission_denied, FlyResult};
+use crate::errors::{self, permission_denied, ErrorKind, FlyError, FlyResult};
+use crate::metrics::PERMISSION_DENIALS_TOTAL;
 
-#[derive(Debug)]
+/// Something an app has to be granted before it can use it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Capability<'a> {
+  Os,
+  FetchHost(&'a str),
+  FetchFile,
+  FsWrite,
+  Dns,
+  CacheNamespace(&'a str),
+  DataCollection(&'a str),
+}
+
+impl<'a> Capability<'a> {
+  /// Label used for the permission denial metric.
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Capability::Os => "os",
+      Capability::FetchHost(_) => "fetch",
+      Capability::FetchFile => "fetch_file",
+      Capability::FsWrite => "fs_write",
+      Capability::Dns => "dns",
+      Capability::CacheNamespace(_) => "cache",
+      Capability::DataCollection(_) => "data",
+    }
+  }
+
+  fn denied(&self) -> FlyError {
+    let msg = match self {
+      Capability::Os => return permission_denied(),
+      Capability::FetchHost(host) => format!("permission denied: fetch to host '{}'", host),
+      Capability::FetchFile => "permission denied: fetch of file:// urls".to_owned(),
+      Capability::FsWrite => "permission denied: writing files".to_owned(),
+      Capability::Dns => "permission denied: dns queries".to_owned(),
+      Capability::CacheNamespace(key) => format!("permission denied: cache key '{}'", key),
+      Capability::DataCollection(coll) => {
+        format!("permission denied: data collection '{}'", coll)
+      }
+    };
+    errors::new(ErrorKind::PermissionDenied, msg)
+  }
+}
+
+/// Which hosts `fetch` may reach. Entries are hostnames, or `*.example.com`
+/// to match every subdomain. Deny entries win over allow entries.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct FetchPermissions {
+  /// When set, only these hosts can be fetched.
+  pub allow_hosts: Option<Vec<String>>,
+  pub deny_hosts: Vec<String>,
+}
+
+impl FetchPermissions {
+  pub fn permits(&self, host: &str) -> bool {
+    let host = host.trim_end_matches('.').to_lowercase();
+    if self.deny_hosts.iter().any(|p| host_matches(p, &host)) {
+      return false;
+    }
+    match self.allow_hosts {
+      Some(ref allowed) => allowed.iter().any(|p| host_matches(p, &host)),
+      None => true,
+    }
+  }
+}
+
+fn host_matches(pattern: &str, host: &str) -> bool {
+  let pattern = pattern.trim_end_matches('.').to_lowercase();
+  if pattern == "*" {
+    return true;
+  }
+  if pattern.starts_with("*.") {
+    let suffix = &pattern[1..];
+    host.ends_with(suffix) && host.len() > suffix.len()
+  } else {
+    host == pattern
+  }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
 pub struct RuntimePermissions {
   pub allow_os: bool,
+  pub allow_file_fetch: bool,
+  /// Lets `fly.fs` write and delete files. Off by default.
+  pub allow_fs_write: bool,
+  pub allow_dns: bool,
+  pub fetch: FetchPermissions,
+  /// When set, cache keys, and the tags purged, must start with one of these
+  /// prefixes.
+  pub cache_namespaces: Option<Vec<String>>,
+  /// When set, only these data collections can be used.
+  pub data_collections: Option<Vec<String>>,
 }
 
 impl RuntimePermissions {
   pub fn new(allow_os: bool) -> Self {
-    Self { allow_os }
+    Self {
+      allow_os,
+      ..Default::default()
+    }
   }
 
-  pub fn check_os(&self) -> FlyResult<()> {
-    if self.allow_os {
+  pub fn check(&self, cap: Capability) -> FlyResult<()> {
+    let permitted = match cap {
+      Capability::Os => self.allow_os,
+      Capability::FetchHost(host) => self.fetch.permits(host),
+      Capability::FetchFile => self.allow_file_fetch,
+      Capability::FsWrite => self.allow_fs_write,
+      Capability::Dns => self.allow_dns,
+      Capability::CacheNamespace(key) => match self.cache_namespaces {
+        Some(ref prefixes) => prefixes.iter().any(|p| key.starts_with(p.as_str())),
+        None => true,
+      },
+      Capability::DataCollection(coll) => match self.data_collections {
+        Some(ref colls) => colls.iter().any(|c| c == coll),
+        None => true,
+      },
+    };
+    if permitted {
       Ok(())
     } else {
-      Err(permission_denied())
+      Err(cap.denied())
     }
   }
+
+  /// Like `check`, counting any denial against the app's version.
+  pub fn check_counted(&self, name: &str, version: &str, cap: Capability) -> FlyResult<()> {
+    self.check(cap).map_err(|e| {
+      PERMISSION_DENIALS_TOTAL
+        .with_label_values(&[name, version, cap.as_str()])
+        .inc();
+      e
+    })
+  }
+
+  pub fn check_os(&self) -> FlyResult<()> {
+    self.check(Capability::Os)
+  }
 }
 
 impl Default for RuntimePermissions {
   fn default() -> Self {
-    RuntimePermissions { allow_os: false }
+    RuntimePermissions {
+      allow_os: false,
+      allow_file_fetch: true,
+      allow_fs_write: false,
+      allow_dns: true,
+      fetch: FetchPermissions::default(),
+      cache_namespaces: None,
+      data_collections: None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_default_permissions() {
+    let perms = RuntimePermissions::default();
+    assert!(perms.check(Capability::Os).is_err());
+    assert!(perms.check(Capability::FetchHost("example.com")).is_ok());
+    assert!(perms.check(Capability::FetchFile).is_ok());
+    assert!(perms.check(Capability::FsWrite).is_err());
+    assert!(perms.check(Capability::Dns).is_ok());
+    assert!(perms.check(Capability::CacheNamespace("anything")).is_ok());
+    assert!(perms.check(Capability::DataCollection("anything")).is_ok());
+  }
+
+  #[test]
+  fn test_fetch_hosts() {
+    let perms = RuntimePermissions {
+      fetch: FetchPermissions {
+        allow_hosts: Some(vec!["*.example.com".to_string(), "fly.io".to_string()]),
+        deny_hosts: vec!["internal.example.com".to_string()],
+      },
+      ..Default::default()
+    };
+    assert!(perms
+      .check(Capability::FetchHost("api.example.com"))
+      .is_ok());
+    assert!(perms
+      .check(Capability::FetchHost("API.Example.com."))
+      .is_ok());
+    assert!(perms.check(Capability::FetchHost("fly.io")).is_ok());
+    assert!(perms.check(Capability::FetchHost("example.com")).is_err());
+    assert!(perms
+      .check(Capability::FetchHost("notexample.com"))
+      .is_err());
+    assert!(perms
+      .check(Capability::FetchHost("internal.example.com"))
+      .is_err());
+  }
+
+  #[test]
+  fn test_namespaces() {
+    let perms = RuntimePermissions {
+      cache_namespaces: Some(vec!["public:".to_string()]),
+      data_collections: Some(vec!["users".to_string()]),
+      ..Default::default()
+    };
+    assert!(perms
+      .check(Capability::CacheNamespace("public:foo"))
+      .is_ok());
+    assert!(perms
+      .check(Capability::CacheNamespace("private:foo"))
+      .is_err());
+    assert!(perms.check(Capability::DataCollection("users")).is_ok());
+    let err = perms
+      .check(Capability::DataCollection("sessions"))
+      .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+  }
+
+  #[test]
+  fn test_deserialize_partial() {
+    let perms: RuntimePermissions =
+      serde_json::from_str(r#"{"allow_dns": false, "fetch": {"deny_hosts": ["localhost"]}}"#)
+        .unwrap();
+    assert!(!perms.allow_os);
+    assert!(perms.allow_file_fetch);
+    assert!(perms.check(Capability::Dns).is_err());
+    assert!(perms.check(Capability::FetchHost("localhost")).is_err());
   }
 }
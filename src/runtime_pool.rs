@@ -0,0 +1,288 @@
+use std::mem;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::metrics::RUNTIME_POOL_SIZE_GAUGE;
+use crate::runtime::Runtime;
+use crate::runtime_lifecycle;
+use crate::runtime_selector::RuntimeLease;
+use crate::settings::RuntimePoolConfig;
+
+/// Builds a runtime with the app loaded and its event loop running.
+pub type RuntimeFactory = Box<Fn() -> Box<Runtime> + Send + Sync>;
+
+/// A runtime in a pool, shared with the leases handed out for it. It stays
+/// boxed so it keeps the address its isolate knows it by.
+#[allow(clippy::redundant_allocation)]
+pub type PooledRuntime = Arc<Box<Runtime>>;
+
+/// Average number of outstanding events per isolate past which the pool grows.
+static SCALE_UP_QUEUE_DEPTH: usize = 4;
+
+/// A set of isolates running the same app, each on its own event loop.
+pub struct RuntimePool {
+    runtimes: RwLock<Vec<PooledRuntime>>,
+    factory: Option<RuntimeFactory>,
+    min_size: usize,
+    max_size: usize,
+}
+
+impl RuntimePool {
+    pub fn new(factory: RuntimeFactory, config: &RuntimePoolConfig) -> Self {
+        let min_size = config.min_size.max(1);
+        let max_size = config.max_size.max(min_size);
+        let runtimes = (0..min_size).map(|_| Arc::new(factory())).collect();
+        let pool = RuntimePool {
+            runtimes: RwLock::new(runtimes),
+            factory: Some(factory),
+            min_size,
+            max_size,
+        };
+        pool.record_size();
+        pool
+    }
+
+    /// A pool of exactly one runtime, which can't be scaled nor recycled.
+    pub fn from_runtime(runtime: Box<Runtime>) -> Self {
+        RuntimePool {
+            runtimes: RwLock::new(vec![Arc::new(runtime)]),
+            factory: None,
+            min_size: 1,
+            max_size: 1,
+        }
+    }
+
+    /// Leases the runtime with the fewest outstanding events and leases.
+    pub fn lease(&self) -> Option<RuntimeLease> {
+        let runtimes = self.read();
+        runtimes
+            .iter()
+            .min_by_key(|rt| load(rt))
+            .map(|rt| RuntimeLease::new(rt.clone()))
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<Vec<PooledRuntime>> {
+        match self.runtimes.read() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn write(&self) -> RwLockWriteGuard<Vec<PooledRuntime>> {
+        match self.runtimes.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                error!("runtime pool is poisoned! {}", poisoned);
+                poisoned.into_inner()
+            }
+        }
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.read().iter().map(load).sum()
+    }
+
+    /// Most recent event time, in seconds since the epoch, across the pool.
+    pub fn last_event_at(&self) -> usize {
+        self.read()
+            .iter()
+            .map(|rt| rt.last_event_at.load(Ordering::SeqCst))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Grows the pool by one isolate when they're all queuing events, or
+    /// retires one when the rest could absorb its load. Must be called from
+    /// within a tokio runtime.
+    pub fn autoscale(&self) {
+        let factory = match self.factory {
+            Some(ref f) => f,
+            None => return,
+        };
+        let (len, in_flight) = {
+            let runtimes = self.read();
+            (runtimes.len(), runtimes.iter().map(load).sum::<usize>())
+        };
+
+        if len < self.max_size && in_flight > len * SCALE_UP_QUEUE_DEPTH {
+            let rt = factory();
+            debug!(
+                "growing runtime pool {}:v{} to {}",
+                rt.name,
+                rt.version,
+                len + 1
+            );
+            self.write().push(Arc::new(rt));
+        } else if len > self.min_size && in_flight < (len - 1) * SCALE_UP_QUEUE_DEPTH / 2 {
+            let mut runtimes = self.write();
+            let idlest = runtimes
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, rt)| load(rt))
+                .map(|(i, _)| i);
+            if let Some(i) = idlest {
+                let rt = runtimes.remove(i);
+                debug!(
+                    "shrinking runtime pool {}:v{} to {}",
+                    rt.name,
+                    rt.version,
+                    len - 1
+                );
+                tokio::spawn(runtime_lifecycle::retire(rt));
+            }
+        } else {
+            return;
+        }
+        self.record_size();
+    }
+
+    /// Swaps the isolate using the most heap for a fresh one, returning it.
+    pub fn replace_heaviest(&self) -> Option<PooledRuntime> {
+        let fresh = match self.factory {
+            Some(ref f) => Arc::new(f()),
+            None => return None,
+        };
+        let mut runtimes = self.write();
+        let heaviest = runtimes
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, rt)| rt.heap_statistics().used_heap_size)
+            .map(|(i, _)| i);
+        match heaviest {
+            Some(i) => Some(mem::replace(&mut runtimes[i], fresh)),
+            None => {
+                runtimes.push(fresh);
+                None
+            }
+        }
+    }
+
+    /// Disposes of every runtime right away, for shutdown. Runtimes still
+    /// leased are left alone, `runtime_lifecycle::retire` waits for them.
+    pub fn dispose(&self) {
+        self.write()
+            .iter_mut()
+            .for_each(|rt| match Arc::get_mut(rt) {
+                Some(rt) => rt.dispose(),
+                None => warn!(
+                    "runtime {}:v{} is still leased, not disposing of it",
+                    rt.name, rt.version
+                ),
+            });
+    }
+
+    pub fn into_runtimes(self) -> Vec<PooledRuntime> {
+        match self.runtimes.into_inner() {
+            Ok(runtimes) => runtimes,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn record_size(&self) {
+        let runtimes = self.read();
+        if let Some(rt) = runtimes.first() {
+            RUNTIME_POOL_SIZE_GAUGE
+                .with_label_values(&[rt.name.as_str(), rt.version.as_str()])
+                .set(runtimes.len() as i64);
+        }
+    }
+}
+
+// Events and bodies in flight on a pooled runtime, plus the leases about to
+// dispatch more.
+fn load(rt: &PooledRuntime) -> usize {
+    rt.in_flight() + Arc::strong_count(rt) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_runtime;
+    use crate::runtime_lifecycle::retire;
+    use futures::{future, Future};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn pool(min_size: usize, max_size: usize) -> RuntimePool {
+        RuntimePool::new(
+            Box::new(test_runtime),
+            &RuntimePoolConfig { min_size, max_size },
+        )
+    }
+
+    fn same(a: &Runtime, b: &Runtime) -> bool {
+        a as *const Runtime == b as *const Runtime
+    }
+
+    #[test]
+    fn test_lease_least_loaded() {
+        let pool = pool(2, 2);
+        let first = pool.lease().unwrap();
+        let second = pool.lease().unwrap();
+        assert!(!same(&first, &second));
+        assert_eq!(pool.in_flight(), 2);
+
+        drop(first);
+        let third = pool.lease().unwrap();
+        assert!(!same(&second, &third));
+        drop(second);
+        drop(third);
+        assert_eq!(pool.in_flight(), 0);
+        pool.dispose();
+    }
+
+    #[test]
+    fn test_retire_waits_for_leases() {
+        let pool = RuntimePool::from_runtime(test_runtime());
+        let lease = pool.lease().unwrap();
+        let rt = pool.into_runtimes().pop().unwrap();
+
+        let el = tokio::runtime::Runtime::new().unwrap();
+        let (tx, rx) = mpsc::channel();
+        el.executor()
+            .spawn(retire(rt).map(move |_| tx.send(()).unwrap()));
+
+        // the leased runtime is still usable, not disposed of
+        assert!(rx.recv_timeout(Duration::from_millis(300)).is_err());
+        assert_eq!(lease.in_flight(), 0);
+
+        drop(lease);
+        assert!(rx.recv_timeout(Duration::from_secs(2)).is_ok());
+        el.shutdown_now().wait().unwrap();
+    }
+
+    #[test]
+    fn test_autoscale() {
+        let pool = pool(1, 2);
+        let mut el = tokio::runtime::current_thread::Runtime::new().unwrap();
+
+        // every lease counts as an event about to be dispatched
+        let leases: Vec<RuntimeLease> = (0..=SCALE_UP_QUEUE_DEPTH)
+            .map(|_| pool.lease().unwrap())
+            .collect();
+        el.block_on(future::lazy(|| {
+            pool.autoscale();
+            future::ok::<(), ()>(())
+        }))
+        .unwrap();
+        assert_eq!(pool.read().len(), 2);
+
+        // at most max_size
+        el.block_on(future::lazy(|| {
+            pool.autoscale();
+            future::ok::<(), ()>(())
+        }))
+        .unwrap();
+        assert_eq!(pool.read().len(), 2);
+
+        drop(leases);
+        el.block_on(future::lazy(|| {
+            pool.autoscale();
+            future::ok::<(), ()>(())
+        }))
+        .unwrap();
+        assert_eq!(pool.read().len(), 1);
+        el.run().unwrap();
+        pool.dispose();
+    }
+}
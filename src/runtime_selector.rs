@@ -1,11 +1,35 @@
 use crate::runtime::Runtime;
+use crate::runtime_pool::PooledRuntime;
+use std::ops::Deref;
 
 pub trait RuntimeSelector {
-    fn get_by_hostname(&self, hostname: &str) -> Result<Option<&mut Runtime>, SelectorError>;
+    fn get_by_hostname(&self, hostname: &str) -> Result<Option<RuntimeLease>, SelectorError>;
 }
 
 #[derive(Debug)]
 pub enum SelectorError {
     Unknown,
     Failure(String),
+    // No room left on this node for the requested runtime.
+    AtCapacity,
+}
+
+/// A runtime picked by a selector to handle a single event. The runtime
+/// isn't disposed of while leased: retiring it waits for its leases to go.
+pub struct RuntimeLease {
+    runtime: PooledRuntime,
+}
+
+impl RuntimeLease {
+    pub fn new(runtime: PooledRuntime) -> Self {
+        RuntimeLease { runtime }
+    }
+}
+
+impl Deref for RuntimeLease {
+    type Target = Runtime;
+
+    fn deref(&self) -> &Runtime {
+        &self.runtime
+    }
 }
@@ -0,0 +1,1219 @@
+use crate::fs_store::*;
+use futures::future::{self, Either, Loop};
+use futures::{stream, Async, Future, Poll, Stream};
+
+use crate::disk_fs::DiskFsStore;
+use crate::http_range::parse_http_date;
+use crate::settings::S3StoreConfig;
+
+use rusoto_core::{HttpClient, Region};
+use rusoto_credential::{DefaultCredentialsProvider, StaticProvider};
+use rusoto_s3::{
+    DeleteObjectRequest, GetObjectError, GetObjectRequest, HeadObjectError, HeadObjectRequest,
+    ListObjectsV2Request, PutObjectRequest, S3Client, StreamingBody, S3,
+};
+use sha2::{Digest, Sha256};
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant, SystemTime};
+
+use tempfile::NamedTempFile;
+
+static DEFAULT_CACHE_SIZE_MB: u64 = 512;
+static DEFAULT_CACHE_TTL_SECS: u64 = 60;
+
+// Temporary files left alone this long belong to a process that's gone.
+const STALE_PARTIAL_SECS: u64 = 3600;
+
+// What S3 said about a cached object goes in a file named after it, plus this.
+const META_SUFFIX: &str = ".meta";
+
+lazy_static! {
+    // stores caching into the same directory share its index
+    static ref DISK_CACHES: Mutex<HashMap<PathBuf, Weak<DiskCache>>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Clone)]
+pub struct S3FsStore {
+    // why there's no client, every operation fails with it
+    client: Result<Arc<S3Client>, String>,
+    bucket: String,
+    prefix: String,
+    cache: Option<Arc<DiskCache>>,
+}
+
+impl S3FsStore {
+    pub fn new(conf: &S3StoreConfig) -> Self {
+        let region = match conf.endpoint {
+            Some(ref endpoint) => Region::Custom {
+                name: conf
+                    .region
+                    .clone()
+                    .unwrap_or_else(|| "us-east-1".to_string()),
+                endpoint: endpoint.clone(),
+            },
+            None => match conf.region {
+                Some(ref name) => name.parse().unwrap_or_else(|_| {
+                    warn!("unknown s3 region {}, using us-east-1", name);
+                    Region::UsEast1
+                }),
+                None => Region::UsEast1,
+            },
+        };
+        let client = HttpClient::new()
+            .map_err(|e| format!("error creating s3 request dispatcher: {}", e))
+            .and_then(|dispatcher| {
+                Ok(match (&conf.access_key_id, &conf.secret_access_key) {
+                    (Some(id), Some(secret)) => S3Client::new_with(
+                        dispatcher,
+                        StaticProvider::new_minimal(id.clone(), secret.clone()),
+                        region,
+                    ),
+                    _ => S3Client::new_with(
+                        dispatcher,
+                        DefaultCredentialsProvider::new()
+                            .map_err(|e| format!("error loading s3 credentials: {}", e))?,
+                        region,
+                    ),
+                })
+            });
+        if let Err(ref e) = client {
+            error!("{}", e);
+        }
+        S3FsStore {
+            client: client.map(Arc::new),
+            bucket: conf.bucket.clone(),
+            prefix: conf.prefix.clone().unwrap_or_default(),
+            cache: conf.cache_dir.as_ref().map(|dir| {
+                DiskCache::shared(
+                    PathBuf::from(dir),
+                    conf.cache_size_mb.unwrap_or(DEFAULT_CACHE_SIZE_MB) * 1024 * 1024,
+                    Duration::from_secs(conf.cache_ttl_secs.unwrap_or(DEFAULT_CACHE_TTL_SECS)),
+                )
+            }),
+        }
+    }
+
+    fn client(&self) -> FsResult<Arc<S3Client>> {
+        match self.client {
+            Ok(ref client) => Ok(client.clone()),
+            Err(ref e) => Err(FsError::Failure(e.clone())),
+        }
+    }
+
+    // Keys are opaque to S3, but some compatible services collapse "..",
+    // which would reach past the prefix.
+    fn object_key(&self, path: &str) -> FsResult<String> {
+        if path.split('/').any(|part| part == "..") {
+            return Err(FsError::PermissionDenied(format!(
+                "path outside of the app root: {}",
+                path
+            )));
+        }
+        Ok(format!("{}{}", self.prefix, path.trim_start_matches('/')))
+    }
+
+    // Whole objects are cached on their way to the reader, ranges go straight
+    // to S3 unless the object is already cached.
+    fn get_object(
+        &self,
+        key: String,
+        range: Option<Range<u64>>,
+    ) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send> {
+        let client = match self.client() {
+            Ok(client) => client,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let cache = match range {
+            None => self.cache.clone(),
+            Some(_) => None,
+        };
+        let req = GetObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key.clone(),
+            range: range.map(|r| format!("bytes={}-{}", r.start, r.end - 1)),
+            ..Default::default()
+        };
+        Box::new(client.get_object(req).then(move |res| match res {
+            Ok(output) => {
+                let body = match output.body {
+                    Some(body) => body,
+                    None => return Ok(Some(empty_entry())),
+                };
+                let stream: FsStream = match cache {
+                    Some(cache) => {
+                        let meta = ObjectMeta {
+                            size: output.content_length.unwrap_or(0) as u64,
+                            etag: output.e_tag,
+                            last_modified: output.last_modified,
+                            content_type: output.content_type,
+                        };
+                        cache.fill(
+                            &key,
+                            output.content_length.map(|len| len as u64),
+                            meta,
+                            body,
+                        )
+                    }
+                    None => Box::new(body.map_err(FsError::from)),
+                };
+                Ok(Some(FsEntry { stream }))
+            }
+            Err(GetObjectError::NoSuchKey(_)) => Ok(None),
+            Err(GetObjectError::Unknown(ref res)) if res.status.as_u16() == 404 => Ok(None),
+            // the range starts past the end of the object
+            Err(GetObjectError::Unknown(ref res)) if res.status.as_u16() == 416 => {
+                Ok(Some(empty_entry()))
+            }
+            Err(e) => Err(FsError::Failure(format!("{}", e))),
+        }))
+    }
+
+    // Cached copies older than the TTL are checked with S3 before they're
+    // used, and dropped if the object changed.
+    fn read_object(
+        &self,
+        path: String,
+        range: Option<Range<u64>>,
+    ) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send> {
+        let key = match self.object_key(&path) {
+            Ok(key) => key,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let (cache, cached) = match self.cache {
+            Some(ref cache) => match cache.get(&key) {
+                Some(cached) => (cache.clone(), cached),
+                None => return self.get_object(key, range),
+            },
+            None => return self.get_object(key, range),
+        };
+        if cached.fresh {
+            return self.read_cached(cached.path, key, range);
+        }
+        let store = self.clone();
+        Box::new(
+            self.revalidate(key.clone(), &cached.meta)
+                .and_then(move |current| {
+                    if current {
+                        cache.confirm(&key);
+                        store.read_cached(cached.path, key, range)
+                    } else {
+                        cache.remove(&key);
+                        store.get_object(key, range)
+                    }
+                }),
+        )
+    }
+
+    // Falls back to S3 when the cached file went away, evicted in between.
+    fn read_cached(
+        &self,
+        file: PathBuf,
+        key: String,
+        range: Option<Range<u64>>,
+    ) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send> {
+        let store = self.clone();
+        Box::new(DiskFsStore::read_file(file, range.clone(), None).and_then(
+            move |entry| match entry {
+                Some(entry) => Either::A(future::ok(Some(entry))),
+                None => Either::B(store.get_object(key, range)),
+            },
+        ))
+    }
+
+    // Whether a cached copy is still what's in the bucket.
+    fn revalidate(
+        &self,
+        key: String,
+        meta: &ObjectMeta,
+    ) -> Box<Future<Item = bool, Error = FsError> + Send> {
+        let client = match self.client() {
+            Ok(client) => client,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let (if_none_match, if_modified_since) = match meta.etag {
+            Some(ref etag) => (Some(etag.clone()), None),
+            None => (None, meta.last_modified.clone()),
+        };
+        if if_none_match.is_none() && if_modified_since.is_none() {
+            return Box::new(future::ok(false));
+        }
+        let req = HeadObjectRequest {
+            bucket: self.bucket.clone(),
+            key,
+            if_none_match,
+            if_modified_since,
+            ..Default::default()
+        };
+        Box::new(client.head_object(req).then(|res| match res {
+            Err(HeadObjectError::Unknown(ref res)) if res.status.as_u16() == 304 => Ok(true),
+            Ok(_) | Err(HeadObjectError::NoSuchKey(_)) => Ok(false),
+            Err(HeadObjectError::Unknown(ref res)) if res.status.as_u16() == 404 => Ok(false),
+            Err(e) => Err(FsError::Failure(format!("{}", e))),
+        }))
+    }
+}
+
+impl FsStore for S3FsStore {
+    fn read(&self, path: String) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send> {
+        debug!("s3 fs read: {}", path);
+        self.read_object(path, None)
+    }
+
+    fn read_range(
+        &self,
+        path: String,
+        range: Range<u64>,
+    ) -> Box<Future<Item = Option<FsEntry>, Error = FsError> + Send> {
+        debug!("s3 fs read range {:?}: {}", range, path);
+        // S3 can't be asked for nothing
+        if range.start >= range.end {
+            return Box::new(self.stat(path).map(|meta| meta.map(|_| empty_entry())));
+        }
+        self.read_object(path, Some(range))
+    }
+
+    fn stat(&self, path: String) -> Box<Future<Item = Option<FsMeta>, Error = FsError> + Send> {
+        let key = match self.object_key(&path) {
+            Ok(key) => key,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        debug!("s3 fs stat with key: {}", key);
+
+        if let Some(ref cache) = self.cache {
+            match cache.get(&key) {
+                Some(ref cached) if cached.fresh => {
+                    return Box::new(future::ok(Some(cached.meta.fs_meta(&path))));
+                }
+                _ => {}
+            }
+        }
+        let client = match self.client() {
+            Ok(client) => client,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let req = HeadObjectRequest {
+            bucket: self.bucket.clone(),
+            key,
+            ..Default::default()
+        };
+        Box::new(client.head_object(req).then(move |res| {
+            match res {
+                Ok(output) => Ok(Some(FsMeta {
+                    size: output.content_length.unwrap_or(0) as u64,
+                    modified: output
+                        .last_modified
+                        .as_ref()
+                        .and_then(|date| parse_http_date(date)),
+                    content_type: output.content_type.unwrap_or_else(|| content_type(&path)),
+                })),
+                Err(HeadObjectError::NoSuchKey(_)) => Ok(None),
+                // HEAD responses have no body to tell what went wrong
+                Err(HeadObjectError::Unknown(ref res)) if res.status.as_u16() == 404 => Ok(None),
+                Err(e) => Err(FsError::Failure(format!("{}", e))),
+            }
+        }))
+    }
+
+    fn write(
+        &self,
+        path: String,
+        data_stream: Box<Stream<Item = Vec<u8>, Error = ()> + Send>,
+    ) -> EmptyFsFuture {
+        let key = match self.object_key(&path) {
+            Ok(key) => key,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        debug!("s3 fs write with key: {}", key);
+
+        let client = match self.client() {
+            Ok(client) => client,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let bucket = self.bucket.clone();
+        let cache = self.cache.clone();
+        // S3 needs the length upfront
+        Box::new(
+            data_stream
+                .concat2()
+                .map_err(|_| FsError::Failure("error receiving file data".to_string()))
+                .and_then(move |data| {
+                    let req = PutObjectRequest {
+                        bucket,
+                        key: key.clone(),
+                        content_length: Some(data.len() as i64),
+                        content_type: Some(content_type(&path)),
+                        body: Some(StreamingBody::from(data)),
+                        ..Default::default()
+                    };
+                    client
+                        .put_object(req)
+                        .map_err(|e| FsError::Failure(format!("{}", e)))
+                        .map(move |_| {
+                            if let Some(cache) = cache {
+                                cache.remove(&key);
+                            }
+                        })
+                }),
+        )
+    }
+
+    fn delete(&self, path: String) -> EmptyFsFuture {
+        let key = match self.object_key(&path) {
+            Ok(key) => key,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        debug!("s3 fs delete with key: {}", key);
+
+        let client = match self.client() {
+            Ok(client) => client,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let cache = self.cache.clone();
+        let req = DeleteObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key.clone(),
+            ..Default::default()
+        };
+        Box::new(
+            client
+                .delete_object(req)
+                .map_err(|e| FsError::Failure(format!("{}", e)))
+                .map(move |_| {
+                    if let Some(cache) = cache {
+                        cache.remove(&key);
+                    }
+                }),
+        )
+    }
+
+    fn list(&self, prefix: String) -> Box<Future<Item = Vec<String>, Error = FsError> + Send> {
+        let full_prefix = match self.object_key(&prefix) {
+            Ok(key) => key,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        debug!("s3 fs list with prefix: {}", full_prefix);
+
+        let client = match self.client() {
+            Ok(client) => client,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let bucket = self.bucket.clone();
+        let strip = self.prefix.len();
+        Box::new(future::loop_fn(
+            (vec![], None),
+            move |(mut paths, token): (Vec<String>, Option<String>)| {
+                let req = ListObjectsV2Request {
+                    bucket: bucket.clone(),
+                    prefix: Some(full_prefix.clone()),
+                    continuation_token: token,
+                    ..Default::default()
+                };
+                client
+                    .list_objects_v2(req)
+                    .map_err(|e| FsError::Failure(format!("{}", e)))
+                    .map(move |output| {
+                        for object in output.contents.unwrap_or_default() {
+                            if let Some(key) = object.key {
+                                paths.push(key[strip..].to_string());
+                            }
+                        }
+                        match output.next_continuation_token {
+                            Some(token) if output.is_truncated == Some(true) => {
+                                Loop::Continue((paths, Some(token)))
+                            }
+                            _ => {
+                                paths.sort();
+                                Loop::Break(paths)
+                            }
+                        }
+                    })
+            },
+        ))
+    }
+}
+
+fn empty_entry() -> FsEntry {
+    FsEntry {
+        stream: Box::new(stream::empty()),
+    }
+}
+
+/// Whole objects kept on local disk, the least recently read going first once
+/// they take more than `capacity` bytes. Files are named after a hash of their
+/// key, with what S3 said about the object next to them. Copies older than
+/// `ttl` are checked with S3 before they're used again. Stores caching into the
+/// same directory share one cache, sized by the first of them.
+struct DiskCache {
+    dir: PathBuf,
+    capacity: u64,
+    ttl: Duration,
+    index: Mutex<CacheIndex>,
+}
+
+#[derive(Default)]
+struct CacheIndex {
+    entries: HashMap<String, CacheSlot>,
+    size: u64,
+    clock: u64,
+}
+
+struct CacheSlot {
+    meta: ObjectMeta,
+    used_at: u64,
+    // when S3 last said the copy was current, never for files found on open
+    checked_at: Option<Instant>,
+}
+
+/// What S3 said about a cached object.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ObjectMeta {
+    size: u64,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content_type: Option<String>,
+}
+
+impl ObjectMeta {
+    fn fs_meta(&self, path: &str) -> FsMeta {
+        FsMeta {
+            size: self.size,
+            modified: self
+                .last_modified
+                .as_ref()
+                .and_then(|date| parse_http_date(date)),
+            content_type: self
+                .content_type
+                .clone()
+                .unwrap_or_else(|| content_type(path)),
+        }
+    }
+}
+
+struct Cached {
+    path: PathBuf,
+    meta: ObjectMeta,
+    // young enough to use without asking S3
+    fresh: bool,
+}
+
+impl DiskCache {
+    fn shared(dir: PathBuf, capacity: u64, ttl: Duration) -> Arc<DiskCache> {
+        if let Err(e) = fs::create_dir_all(&dir) {
+            warn!("error creating s3 cache dir {}: {}", dir.display(), e);
+        }
+        let dir = dir.canonicalize().unwrap_or(dir);
+        let mut caches = DISK_CACHES.lock().unwrap();
+        caches.retain(|_, cache| cache.upgrade().is_some());
+        if let Some(cache) = caches.get(&dir).and_then(Weak::upgrade) {
+            return cache;
+        }
+        let cache = Arc::new(DiskCache::open(dir.clone(), capacity, ttl));
+        caches.insert(dir, Arc::downgrade(&cache));
+        cache
+    }
+
+    // Files left by an earlier process are kept, to be checked with S3 before
+    // they're read. Another process could be using the directory too, so only
+    // what it clearly abandoned is removed.
+    fn open(dir: PathBuf, capacity: u64, ttl: Duration) -> Self {
+        if let Err(e) = fs::create_dir_all(&dir) {
+            warn!("error creating s3 cache dir {}: {}", dir.display(), e);
+        }
+        let mut index = CacheIndex::default();
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let file_meta = match entry.metadata() {
+                    Ok(meta) => meta,
+                    Err(_) => continue,
+                };
+                if name.ends_with(META_SUFFIX) {
+                    let object = entry
+                        .path()
+                        .with_file_name(&name[..name.len() - META_SUFFIX.len()]);
+                    if !object.exists() && abandoned(&file_meta) {
+                        let _ = fs::remove_file(entry.path());
+                    }
+                    continue;
+                }
+                let meta = fs::read(meta_path(&entry.path()))
+                    .ok()
+                    .and_then(|data| serde_json::from_slice::<ObjectMeta>(&data).ok())
+                    .filter(|meta| meta.size == file_meta.len());
+                match meta {
+                    // a finished object
+                    Some(meta) if !name.starts_with('.') => {
+                        index.size += meta.size;
+                        index.entries.insert(
+                            name,
+                            CacheSlot {
+                                meta,
+                                used_at: 0,
+                                checked_at: None,
+                            },
+                        );
+                    }
+                    // a download, or an object still being stored
+                    _ if !abandoned(&file_meta) => {}
+                    _ => {
+                        let _ = fs::remove_file(entry.path());
+                        let _ = fs::remove_file(meta_path(&entry.path()));
+                    }
+                }
+            }
+        }
+        let cache = DiskCache {
+            dir,
+            capacity,
+            ttl,
+            index: Mutex::new(index),
+        };
+        cache.evict(&mut cache.index.lock().unwrap());
+        cache
+    }
+
+    fn file_name(key: &str) -> String {
+        format!("{:x}", Sha256::digest(key.as_bytes()))
+    }
+
+    fn get(&self, key: &str) -> Option<Cached> {
+        let name = DiskCache::file_name(key);
+        let mut index = self.index.lock().unwrap();
+        index.clock += 1;
+        let now = index.clock;
+        let slot = index.entries.get_mut(&name)?;
+        slot.used_at = now;
+        Some(Cached {
+            path: self.dir.join(name),
+            meta: slot.meta.clone(),
+            fresh: slot
+                .checked_at
+                .map_or(false, |checked| checked.elapsed() < self.ttl),
+        })
+    }
+
+    // S3 said the cached copy is current.
+    fn confirm(&self, key: &str) {
+        let name = DiskCache::file_name(key);
+        if let Some(slot) = self.index.lock().unwrap().entries.get_mut(&name) {
+            slot.checked_at = Some(Instant::now());
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let name = DiskCache::file_name(key);
+        let mut index = self.index.lock().unwrap();
+        if let Some(slot) = index.entries.remove(&name) {
+            index.size -= slot.meta.size;
+        }
+        let path = self.dir.join(&name);
+        let _ = fs::remove_file(meta_path(&path));
+        if let Err(e) = fs::remove_file(path) {
+            if e.kind() != io::ErrorKind::NotFound {
+                warn!("error removing cached s3 object {}: {}", key, e);
+            }
+        }
+    }
+
+    // Caches an object as it's being read, unless it couldn't fit.
+    fn fill<S>(self: Arc<Self>, key: &str, len: Option<u64>, meta: ObjectMeta, inner: S) -> FsStream
+    where
+        S: Stream<Item = Vec<u8>, Error = io::Error> + Send + 'static,
+    {
+        if len.map_or(false, |len| len > self.capacity) {
+            return Box::new(inner.map_err(FsError::from));
+        }
+        match NamedTempFile::new_in(&self.dir) {
+            Ok(file) => Box::new(CacheFill {
+                inner,
+                name: DiskCache::file_name(key),
+                meta,
+                file: Some(file),
+                pending: None,
+                done: false,
+                written: 0,
+                cache: self,
+            }),
+            Err(e) => {
+                warn!("error creating s3 cache file: {}", e);
+                Box::new(inner.map_err(FsError::from))
+            }
+        }
+    }
+
+    // The metadata goes first, so a cached object never lacks it.
+    fn insert(&self, name: String, file: NamedTempFile, meta: ObjectMeta) {
+        if meta.size > self.capacity {
+            return;
+        }
+        let mut index = self.index.lock().unwrap();
+        let path = self.dir.join(&name);
+        let stored = NamedTempFile::new_in(&self.dir)
+            .and_then(|mut meta_file| {
+                serde_json::to_writer(&mut meta_file, &meta)?;
+                meta_file.persist(meta_path(&path)).map_err(|e| e.error)
+            })
+            .and_then(|_| file.persist(&path).map_err(|e| e.error));
+        if let Err(e) = stored {
+            warn!("error storing s3 cache file: {}", e);
+            return;
+        }
+        index.clock += 1;
+        let slot = CacheSlot {
+            meta,
+            used_at: index.clock,
+            checked_at: Some(Instant::now()),
+        };
+        index.size += slot.meta.size;
+        if let Some(old) = index.entries.insert(name, slot) {
+            index.size -= old.meta.size;
+        }
+        self.evict(&mut index);
+    }
+
+    fn evict(&self, index: &mut CacheIndex) {
+        while index.size > self.capacity {
+            let lru = index
+                .entries
+                .iter()
+                .min_by_key(|(_, slot)| slot.used_at)
+                .map(|(name, _)| name.clone());
+            let name = match lru {
+                Some(name) => name,
+                None => break,
+            };
+            if let Some(slot) = index.entries.remove(&name) {
+                index.size -= slot.meta.size;
+            }
+            let path = self.dir.join(&name);
+            let _ = fs::remove_file(meta_path(&path));
+            if let Err(e) = fs::remove_file(path) {
+                warn!("error evicting s3 cache file {}: {}", name, e);
+            }
+        }
+    }
+}
+
+fn meta_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(META_SUFFIX);
+    path.with_file_name(name)
+}
+
+fn abandoned(meta: &fs::Metadata) -> bool {
+    meta.modified()
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .map_or(false, |age| age.as_secs() > STALE_PARTIAL_SECS)
+}
+
+// Runs file IO on the thread pool's blocking capacity, so it doesn't hold up
+// the event loop, or inline when not on a thread pool.
+fn poll_blocking<T, F: FnOnce() -> T>(f: F) -> Async<T> {
+    let mut f = Some(f);
+    match tokio_threadpool::blocking(|| f.take().unwrap()()) {
+        Ok(res) => res,
+        Err(_) => Async::Ready(f.take().unwrap()()),
+    }
+}
+
+// Passes an object on while writing it to the cache. Nothing is kept if the
+// reader stops early or the download fails, the temporary file goes away.
+struct CacheFill<S> {
+    inner: S,
+    name: String,
+    meta: ObjectMeta,
+    file: Option<NamedTempFile>,
+    // a chunk passed on once it's written
+    pending: Option<Vec<u8>>,
+    done: bool,
+    written: u64,
+    cache: Arc<DiskCache>,
+}
+
+impl<S> CacheFill<S> {
+    fn finish(&mut self) -> Async<Option<Vec<u8>>> {
+        let (file, cache, name, meta) = (&mut self.file, &self.cache, &self.name, &self.meta);
+        let written = self.written;
+        let stored = poll_blocking(|| {
+            if let Some(file) = file.take() {
+                let meta = ObjectMeta {
+                    size: written,
+                    ..meta.clone()
+                };
+                cache.insert(name.clone(), file, meta);
+            }
+        });
+        stored.map(|_| None)
+    }
+}
+
+impl<S> Stream for CacheFill<S>
+where
+    S: Stream<Item = Vec<u8>, Error = io::Error>,
+{
+    type Item = Vec<u8>;
+    type Error = FsError;
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, FsError> {
+        if self.done {
+            return Ok(self.finish());
+        }
+        if self.pending.is_none() {
+            match self.inner.poll() {
+                Ok(Async::Ready(Some(chunk))) => self.pending = Some(chunk),
+                Ok(Async::Ready(None)) => {
+                    self.done = true;
+                    return Ok(self.finish());
+                }
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(e) => {
+                    self.file = None;
+                    return Err(e.into());
+                }
+            }
+        }
+        let written = match (&mut self.file, &self.pending) {
+            (Some(file), Some(chunk)) => match poll_blocking(|| file.write_all(chunk)) {
+                Async::Ready(res) => res.is_ok(),
+                Async::NotReady => return Ok(Async::NotReady),
+            },
+            _ => true,
+        };
+        if !written {
+            warn!("error writing s3 cache file, not caching");
+            self.file = None;
+        }
+        let chunk = self.pending.take().unwrap();
+        self.written += chunk.len() as u64;
+        Ok(Async::Ready(Some(chunk)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_range::{http_date, ByteRange};
+    use hyper::service::service_fn;
+    use hyper::{Body, HeaderMap, Method, Request, Response, Server, StatusCode};
+    use std::collections::BTreeMap;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::SystemTime;
+    use url::form_urlencoded;
+    use url::percent_encoding::percent_decode;
+
+    // Just enough of S3 for the store, with path-style requests, objects in
+    // memory and no authentication. Listings come two keys at a time.
+    #[derive(Clone, Default)]
+    struct FakeS3 {
+        objects: Arc<Mutex<BTreeMap<String, (Vec<u8>, SystemTime)>>>,
+        gets: Arc<AtomicUsize>,
+    }
+
+    impl FakeS3 {
+        fn start() -> (FakeS3, String) {
+            let s3 = FakeS3::default();
+            let handler = s3.clone();
+            let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(move || {
+                let handler = handler.clone();
+                service_fn(move |req: Request<Body>| {
+                    let handler = handler.clone();
+                    let (parts, body) = req.into_parts();
+                    body.concat2().map(move |body| {
+                        handler.respond(parts.method, parts.uri, parts.headers, body.to_vec())
+                    })
+                })
+            });
+            let endpoint = format!("http://{}", server.local_addr());
+            thread::spawn(move || hyper::rt::run(server.map_err(|e| panic!("{}", e))));
+            (s3, endpoint)
+        }
+
+        fn gets(&self) -> usize {
+            self.gets.load(Ordering::SeqCst)
+        }
+
+        fn respond(
+            &self,
+            method: Method,
+            uri: hyper::Uri,
+            headers: HeaderMap,
+            body: Vec<u8>,
+        ) -> Response<Body> {
+            let path = percent_decode(uri.path().as_bytes())
+                .decode_utf8_lossy()
+                .into_owned();
+            let key = path
+                .trim_start_matches('/')
+                .splitn(2, '/')
+                .nth(1)
+                .unwrap_or("");
+            let mut objects = self.objects.lock().unwrap();
+
+            if key.is_empty() && method == Method::GET {
+                let query: HashMap<String, String> =
+                    form_urlencoded::parse(uri.query().unwrap_or("").as_bytes())
+                        .into_owned()
+                        .collect();
+                let prefix = query.get("prefix").cloned().unwrap_or_default();
+                let after = query.get("continuation-token").cloned();
+                let mut keys = objects
+                    .keys()
+                    .filter(|key| key.starts_with(&prefix))
+                    .filter(|key| after.as_ref().map_or(true, |after| *key > after));
+                let page: Vec<&String> = keys.by_ref().take(2).collect();
+                let truncated = keys.next().is_some();
+                let mut xml = format!(
+                    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+                     <ListBucketResult xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+                     <Name>test</Name><Prefix>{}</Prefix><KeyCount>{}</KeyCount>\
+                     <MaxKeys>2</MaxKeys><IsTruncated>{}</IsTruncated>",
+                    prefix,
+                    page.len(),
+                    truncated
+                );
+                for key in page.iter() {
+                    let size = objects[*key].0.len();
+                    xml.push_str(&format!(
+                        "<Contents><Key>{}</Key><Size>{}</Size></Contents>",
+                        key, size
+                    ));
+                }
+                if truncated {
+                    xml.push_str(&format!(
+                        "<NextContinuationToken>{}</NextContinuationToken>",
+                        page[page.len() - 1]
+                    ));
+                }
+                xml.push_str("</ListBucketResult>");
+                return Response::new(Body::from(xml));
+            }
+
+            match method {
+                Method::GET | Method::HEAD => {
+                    let (data, modified) = match objects.get(key) {
+                        Some(object) => object.clone(),
+                        None => return error(StatusCode::NOT_FOUND, "NoSuchKey"),
+                    };
+                    let etag = format!("\"{:x}\"", Sha256::digest(&data));
+                    if headers
+                        .get("if-none-match")
+                        .map_or(false, |tag| tag == etag.as_str())
+                    {
+                        return Response::builder()
+                            .status(StatusCode::NOT_MODIFIED)
+                            .body(Body::empty())
+                            .unwrap();
+                    }
+                    if method == Method::GET {
+                        self.gets.fetch_add(1, Ordering::SeqCst);
+                    }
+                    let len = data.len() as u64;
+                    let range = headers
+                        .get("range")
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(ByteRange::parse);
+                    let (status, range) = match range.map(|r| r.resolve(len)) {
+                        None => (StatusCode::OK, 0..len),
+                        Some(Some(range)) => (StatusCode::PARTIAL_CONTENT, range),
+                        Some(None) => {
+                            return error(StatusCode::RANGE_NOT_SATISFIABLE, "InvalidRange")
+                        }
+                    };
+                    let slice = data[range.start as usize..range.end as usize].to_vec();
+                    Response::builder()
+                        .status(status)
+                        .header("content-length", slice.len().to_string().as_str())
+                        .header("content-type", content_type(key).as_str())
+                        .header("last-modified", http_date(modified).as_str())
+                        .header("etag", etag.as_str())
+                        .body(Body::from(slice))
+                        .unwrap()
+                }
+                Method::PUT => {
+                    objects.insert(key.to_string(), (body, SystemTime::now()));
+                    Response::new(Body::empty())
+                }
+                Method::DELETE => {
+                    objects.remove(key);
+                    Response::builder()
+                        .status(StatusCode::NO_CONTENT)
+                        .body(Body::empty())
+                        .unwrap()
+                }
+                _ => error(StatusCode::BAD_REQUEST, "InvalidRequest"),
+            }
+        }
+    }
+
+    fn error(status: StatusCode, code: &str) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .body(Body::from(format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+                 <Error><Code>{}</Code><Message>{}</Message></Error>",
+                code, code
+            )))
+            .unwrap()
+    }
+
+    fn config(endpoint: &str, cache_dir: Option<&Path>) -> S3StoreConfig {
+        S3StoreConfig {
+            bucket: "test".to_string(),
+            region: None,
+            endpoint: Some(endpoint.to_string()),
+            prefix: Some("app/".to_string()),
+            access_key_id: Some("key".to_string()),
+            secret_access_key: Some("secret".to_string()),
+            cache_dir: cache_dir.map(|dir| dir.to_string_lossy().into_owned()),
+            cache_size_mb: None,
+            cache_ttl_secs: None,
+        }
+    }
+
+    fn store(endpoint: &str, cache_dir: Option<&Path>) -> S3FsStore {
+        S3FsStore::new(&config(endpoint, cache_dir))
+    }
+
+    fn write(store: &S3FsStore, path: &str, data: &[u8]) {
+        store
+            .write(path.to_string(), Box::new(stream::once(Ok(data.to_vec()))))
+            .wait()
+            .unwrap();
+    }
+
+    fn read(res: FsResult<Option<FsEntry>>) -> Option<Vec<u8>> {
+        res.unwrap()
+            .map(|entry| entry.stream.concat2().wait().unwrap())
+    }
+
+    #[test]
+    fn test_s3_fs_read_write() {
+        let (s3, endpoint) = FakeS3::start();
+        let store = store(&endpoint, None);
+
+        write(&store, "/dir/hello.txt", b"hello world");
+        write(&store, "dir/a.txt", b"a");
+        write(&store, "dir/b.txt", b"b");
+        write(&store, "other.txt", b"other");
+        assert!(s3.objects.lock().unwrap().contains_key("app/dir/hello.txt"));
+
+        assert_eq!(
+            read(store.read("dir/hello.txt".to_string()).wait()),
+            Some(b"hello world".to_vec())
+        );
+        assert_eq!(
+            read(store.read_range("dir/hello.txt".to_string(), 6..100).wait()),
+            Some(b"world".to_vec())
+        );
+        assert_eq!(
+            read(store.read_range("dir/hello.txt".to_string(), 20..30).wait()),
+            Some(vec![])
+        );
+        assert!(read(store.read("missing.txt".to_string()).wait()).is_none());
+
+        let meta = store
+            .stat("dir/hello.txt".to_string())
+            .wait()
+            .unwrap()
+            .unwrap();
+        assert_eq!(meta.size, 11);
+        assert_eq!(meta.content_type, "text/plain");
+        assert!(meta.modified.is_some());
+        assert!(store
+            .stat("missing.txt".to_string())
+            .wait()
+            .unwrap()
+            .is_none());
+
+        assert_eq!(
+            store.list("dir/".to_string()).wait().unwrap(),
+            vec!["dir/a.txt", "dir/b.txt", "dir/hello.txt"]
+        );
+
+        store.delete("dir/a.txt".to_string()).wait().unwrap();
+        store.delete("dir/a.txt".to_string()).wait().unwrap();
+        assert!(read(store.read("dir/a.txt".to_string()).wait()).is_none());
+
+        match store.read("dir/../../secret".to_string()).wait() {
+            Err(FsError::PermissionDenied(_)) => {}
+            _ => panic!("expected permission denied"),
+        }
+    }
+
+    #[test]
+    fn test_s3_fs_cache() {
+        let (s3, endpoint) = FakeS3::start();
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(&endpoint, Some(dir.path()));
+
+        write(&store, "hello.txt", b"hello world");
+        assert_eq!(
+            read(store.read("hello.txt".to_string()).wait()),
+            Some(b"hello world".to_vec())
+        );
+        assert_eq!(
+            read(store.read("hello.txt".to_string()).wait()),
+            Some(b"hello world".to_vec())
+        );
+        assert_eq!(
+            read(store.read_range("hello.txt".to_string(), 0..5).wait()),
+            Some(b"hello".to_vec())
+        );
+        assert_eq!(s3.gets(), 1);
+
+        // writing through the store drops the cached copy
+        write(&store, "hello.txt", b"bye");
+        assert_eq!(
+            read(store.read("hello.txt".to_string()).wait()),
+            Some(b"bye".to_vec())
+        );
+        assert_eq!(s3.gets(), 2);
+
+        store.delete("hello.txt".to_string()).wait().unwrap();
+        assert!(read(store.read("hello.txt".to_string()).wait()).is_none());
+
+        // changed behind the store's back, the cached copy is used until the
+        // TTL runs out
+        write(&store, "other.txt", b"other");
+        read(store.read("other.txt".to_string()).wait());
+        s3.objects.lock().unwrap().insert(
+            "app/other.txt".to_string(),
+            (b"changed".to_vec(), SystemTime::now()),
+        );
+        let meta = store.stat("other.txt".to_string()).wait().unwrap().unwrap();
+        assert_eq!(meta.size, 5);
+        assert_eq!(meta.content_type, "text/plain");
+        assert_eq!(
+            read(store.read("other.txt".to_string()).wait()),
+            Some(b"other".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_s3_fs_cache_revalidates() {
+        let (s3, endpoint) = FakeS3::start();
+        let dir = tempfile::tempdir().unwrap();
+        let store = S3FsStore::new(&S3StoreConfig {
+            cache_ttl_secs: Some(0),
+            ..config(&endpoint, Some(dir.path()))
+        });
+
+        write(&store, "hello.txt", b"hello world");
+        read(store.read("hello.txt".to_string()).wait());
+        assert_eq!(
+            read(store.read("hello.txt".to_string()).wait()),
+            Some(b"hello world".to_vec())
+        );
+        assert_eq!(
+            read(store.read_range("hello.txt".to_string(), 0..5).wait()),
+            Some(b"hello".to_vec())
+        );
+        // not modified
+        assert_eq!(s3.gets(), 1);
+
+        s3.objects.lock().unwrap().insert(
+            "app/hello.txt".to_string(),
+            (b"changed".to_vec(), SystemTime::now()),
+        );
+        assert_eq!(
+            read(store.read("hello.txt".to_string()).wait()),
+            Some(b"changed".to_vec())
+        );
+        assert_eq!(s3.gets(), 2);
+        assert_eq!(
+            store
+                .stat("hello.txt".to_string())
+                .wait()
+                .unwrap()
+                .unwrap()
+                .size,
+            7
+        );
+    }
+
+    #[test]
+    fn test_s3_fs_no_client() {
+        let store = S3FsStore {
+            client: Err("no client".to_string()),
+            bucket: "test".to_string(),
+            prefix: String::new(),
+            cache: None,
+        };
+        match store.read("hello.txt".to_string()).wait() {
+            Err(FsError::Failure(ref e)) if e == "no client" => {}
+            _ => panic!("expected the client error"),
+        }
+    }
+
+    #[test]
+    fn test_disk_cache_shared() {
+        let dir = tempfile::tempdir().unwrap();
+        let ttl = Duration::from_secs(60);
+        let a = DiskCache::shared(dir.path().to_path_buf(), 10, ttl);
+        let b = DiskCache::shared(dir.path().join("."), 20, ttl);
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(b.capacity, 10);
+
+        let other = tempfile::tempdir().unwrap();
+        let c = DiskCache::shared(other.path().to_path_buf(), 10, ttl);
+        assert!(!Arc::ptr_eq(&a, &c));
+    }
+
+    #[test]
+    fn test_disk_cache_evicts_least_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let ttl = Duration::from_secs(60);
+        let cache = Arc::new(DiskCache::open(dir.path().to_path_buf(), 10, ttl));
+        let fill = |key: &str, len: usize| {
+            let data = stream::once::<_, io::Error>(Ok(vec![1u8; len]));
+            cache
+                .clone()
+                .fill(key, Some(len as u64), ObjectMeta::default(), data)
+                .collect()
+                .wait()
+                .unwrap();
+        };
+
+        fill("a", 4);
+        fill("b", 4);
+        assert!(cache.get("a").is_some());
+        fill("c", 4);
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_some());
+
+        // too big to ever fit
+        fill("d", 11);
+        assert!(cache.get("d").is_none());
+
+        // an unfinished read keeps nothing
+        let data = stream::iter_ok::<_, io::Error>(vec![vec![1u8; 2], vec![1u8; 2]]);
+        cache
+            .clone()
+            .fill("e", None, ObjectMeta::default(), data)
+            .into_future()
+            .wait()
+            .ok();
+        assert!(cache.get("e").is_none());
+        // a and c, each with its metadata
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 4);
+
+        // someone else's download is left alone
+        fs::write(dir.path().join(".tmp-download"), b"part").unwrap();
+        let reopened = DiskCache::open(dir.path().to_path_buf(), 10, ttl);
+        let a = reopened.get("a").unwrap();
+        assert_eq!(a.meta.size, 4);
+        assert!(!a.fresh);
+        assert_eq!(reopened.index.lock().unwrap().size, 8);
+        assert!(dir.path().join(".tmp-download").exists());
+    }
+}
@@ -1,5 +1,7 @@
 extern crate config;
 use self::config::{Config, ConfigError, Environment, File};
+use crate::runtime_permissions::RuntimePermissions;
+use std::collections::HashMap;
 use std::sync::RwLock;
 
 lazy_static! {
@@ -26,6 +28,26 @@ pub struct RedisStoreConfig {
   pub namespace: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct S3StoreConfig {
+  pub bucket: String,
+  /// Defaults to us-east-1.
+  pub region: Option<String>,
+  /// For S3-compatible services, requests then go to this URL instead of AWS.
+  pub endpoint: Option<String>,
+  /// Prepended to every file path, e.g. "apps/123/".
+  pub prefix: Option<String>,
+  /// Credentials are looked up in the usual AWS places when unset.
+  pub access_key_id: Option<String>,
+  pub secret_access_key: Option<String>,
+  /// Directory files are cached in, nothing is cached when unset.
+  pub cache_dir: Option<String>,
+  pub cache_size_mb: Option<u64>,
+  /// Seconds a cached file is used before S3 is asked whether it changed, 60
+  /// by default.
+  pub cache_ttl_secs: Option<u64>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct RedisCacheNotifierConfig {
   pub reader_url: String,
@@ -36,6 +58,7 @@ pub struct RedisCacheNotifierConfig {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum FsStore {
   Redis(RedisStoreConfig),
+  S3(S3StoreConfig),
   Disk,
 }
 
@@ -65,13 +88,144 @@ pub enum CacheStoreNotifier {
   Redis(RedisCacheNotifierConfig),
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct ErrorReportingConfig {
+  pub collector_url: String,
+  pub reports_per_minute: Option<u32>,
+}
+
+/// Address ranges outbound fetches may not connect to.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct IpFilterConfig {
+  /// Deny loopback, link-local and private ranges.
+  pub deny_private: bool,
+  pub deny: Vec<String>,
+  /// File with one CIDR range per line.
+  pub blacklist_ip_path: Option<String>,
+}
+
+/// Which encodings `serve_http` may compress responses with, none unless
+/// configured.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionMode {
+  Off,
+  Gzip,
+  /// Brotli for clients accepting it, gzip for the rest.
+  Auto,
+}
+
+impl Default for CompressionMode {
+  fn default() -> Self {
+    CompressionMode::Off
+  }
+}
+
+/// Outbound fetch client settings.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct FetchConfig {
+  pub dns_threads: usize,
+  /// Idle connections kept open per host.
+  pub max_idle_per_host: usize,
+  pub idle_timeout_secs: u64,
+  /// Covers the TLS handshake too.
+  pub connect_timeout_secs: u64,
+  /// Hosts spoken to over HTTP/2 without negotiating it first, as hyper-tls
+  /// doesn't offer ALPN. Every other host gets HTTP/1.1.
+  pub http2_hosts: Vec<String>,
+  /// PEM file with extra root certificates to trust.
+  pub ca_bundle_path: Option<String>,
+}
+
+impl Default for FetchConfig {
+  fn default() -> Self {
+    FetchConfig {
+      dns_threads: 4,
+      max_idle_per_host: 32,
+      idle_timeout_secs: 90,
+      connect_timeout_secs: 10,
+      http2_hosts: vec![],
+      ca_bundle_path: None,
+    }
+  }
+}
+
+/// Per-app caps on what `serve_http` accepts and sends, in bytes. Unset
+/// limits aren't enforced.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct HttpLimits {
+  pub max_request_body_bytes: Option<u64>,
+  pub max_response_body_bytes: Option<u64>,
+  /// Applies to request and response headers alike.
+  pub max_header_bytes: Option<u64>,
+}
+
+/// The edge cache answering requests before they reach the app.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct HttpCacheConfig {
+  pub enabled: bool,
+  /// Larger responses aren't cached.
+  pub max_body_bytes: usize,
+}
+
+impl Default for HttpCacheConfig {
+  fn default() -> Self {
+    HttpCacheConfig {
+      enabled: false,
+      max_body_bytes: 1024 * 1024,
+    }
+  }
+}
+
+/// A client certificate fetch can present, referenced by name from JS.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ClientIdentityConfig {
+  pub cert_path: String,
+  pub key_path: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct RuntimePoolConfig {
+  pub min_size: usize,
+  pub max_size: usize,
+}
+
+impl Default for RuntimePoolConfig {
+  fn default() -> Self {
+    RuntimePoolConfig {
+      min_size: 1,
+      max_size: 1,
+    }
+  }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Settings {
   pub data_store: Option<DataStore>,
   pub cache_store: Option<CacheStore>,
   pub cache_store_notifier: Option<CacheStoreNotifier>,
   pub fs_store: Option<FsStore>,
+  /// Directory disk files are confined to, `fly_fs` under the current one by
+  /// default.
+  pub fs_root: Option<String>,
   pub acme_store: Option<AcmeStoreConfig>,
+  pub event_time_limit_ms: Option<u64>,
+  pub heap_recycle_threshold: Option<f64>,
+  pub runtime_pool: Option<RuntimePoolConfig>,
+  pub error_reporting: Option<ErrorReportingConfig>,
+  pub permissions: Option<RuntimePermissions>,
+  pub ip_filter: Option<IpFilterConfig>,
+  pub fetch: Option<FetchConfig>,
+  pub client_identities: Option<HashMap<String, ClientIdentityConfig>>,
+  /// Seconds between heartbeats on idle `text/event-stream` responses.
+  pub sse_heartbeat_secs: Option<u64>,
+  pub http_limits: Option<HttpLimits>,
+  pub compression: Option<CompressionMode>,
+  pub http_cache: Option<HttpCacheConfig>,
 }
 
 impl Settings {
@@ -91,7 +245,20 @@ impl Default for Settings {
       cache_store: None,
       cache_store_notifier: None,
       fs_store: None,
+      fs_root: None,
       acme_store: None,
+      event_time_limit_ms: None,
+      heap_recycle_threshold: None,
+      runtime_pool: None,
+      error_reporting: None,
+      permissions: None,
+      ip_filter: None,
+      fetch: None,
+      client_identities: None,
+      sse_heartbeat_secs: None,
+      http_limits: None,
+      compression: None,
+      http_cache: None,
     }
   }
 }
@@ -0,0 +1,129 @@
+use sourcemap::SourceMap;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::js::JsFrame;
+use crate::v8env::V8ENV_SOURCEMAP;
+
+static V8ENV_FILENAME: &str = "v8env/dist/v8env.js";
+
+// Parsed once per thread: source maps aren't `Sync`, and exceptions are only
+// remapped on runtime threads.
+thread_local! {
+    static V8ENV_SOURCE_MAP: Option<SourceMap> = match SourceMap::from_reader(*V8ENV_SOURCEMAP) {
+        Ok(sm) => Some(sm),
+        Err(e) => {
+            error!("error parsing v8env source map: {}", e);
+            None
+        }
+    };
+}
+
+/// Source maps of the scripts loaded in a runtime, by filename. Kept raw and
+/// parsed when an exception needs them, parsed maps can't be shared between
+/// threads.
+#[derive(Default)]
+pub struct SourceMaps {
+    maps: RwLock<HashMap<String, String>>,
+}
+
+impl SourceMaps {
+    pub fn new() -> Self {
+        SourceMaps::default()
+    }
+
+    pub fn insert(&self, filename: &str, raw: &str) -> Result<(), sourcemap::Error> {
+        SourceMap::from_slice(raw.as_bytes())?;
+        self.maps
+            .write()
+            .unwrap()
+            .insert(filename.to_string(), raw.to_string());
+        Ok(())
+    }
+
+    /// Points `frame` at its original source, if a map covers it. Lines and
+    /// columns are 1-based, as V8 reports them.
+    pub fn remap(&self, frame: &mut JsFrame) {
+        if frame.filename == V8ENV_FILENAME {
+            V8ENV_SOURCE_MAP.with(|sm| {
+                if let Some(ref sm) = *sm {
+                    remap_with(sm, frame);
+                }
+            });
+            return;
+        }
+        if let Some(raw) = self.maps.read().unwrap().get(&frame.filename) {
+            if let Ok(sm) = SourceMap::from_slice(raw.as_bytes()) {
+                remap_with(&sm, frame);
+            }
+        }
+    }
+}
+
+fn remap_with(sm: &SourceMap, frame: &mut JsFrame) {
+    if frame.line == 0 {
+        return;
+    }
+    let token = match sm.lookup_token(frame.line - 1, frame.col.saturating_sub(1)) {
+        Some(t) => t,
+        None => return,
+    };
+    frame.line = token.get_src_line() + 1;
+    frame.col = token.get_src_col() + 1;
+    if let Some(source) = token.get_source() {
+        frame.filename = source.to_string();
+    }
+    if let Some(name) = token.get_name() {
+        if frame.function_name.is_empty() {
+            frame.function_name = name.to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // app.js line 1 comes from app.ts line 1, app.js line 2 from app.ts line 3
+    static APP_MAP: &str =
+        r#"{"version":3,"file":"app.js","sources":["app.ts"],"names":[],"mappings":"AAAA;AAEA"}"#;
+
+    fn frame(filename: &str, line: u32, col: u32) -> JsFrame {
+        JsFrame {
+            function_name: "handler".to_string(),
+            filename: filename.to_string(),
+            line,
+            col,
+        }
+    }
+
+    #[test]
+    fn test_remap_user_module() {
+        let maps = SourceMaps::new();
+        maps.insert("file:///app.js", APP_MAP).unwrap();
+
+        let mut f = frame("file:///app.js", 2, 1);
+        maps.remap(&mut f);
+        assert_eq!(f.filename, "app.ts");
+        assert_eq!(f.line, 3);
+        assert_eq!(f.col, 1);
+        assert_eq!(f.function_name, "handler");
+    }
+
+    #[test]
+    fn test_remap_unknown_file() {
+        let maps = SourceMaps::new();
+        maps.insert("file:///app.js", APP_MAP).unwrap();
+
+        let mut f = frame("file:///other.js", 2, 1);
+        maps.remap(&mut f);
+        assert_eq!(f, frame("file:///other.js", 2, 1));
+    }
+
+    #[test]
+    fn test_insert_invalid_map() {
+        assert!(SourceMaps::new()
+            .insert("file:///app.js", "not a map")
+            .is_err());
+    }
+}
@@ -86,20 +86,44 @@ pub fn fly_buf_from(x: Box<[u8]>) -> fly_buf {
   }
 }
 
+// Chunks a stream buffers before its producer has to wait for the consumer.
+pub static STREAM_BUFFER_CHUNKS: usize = 8;
+
+pub fn stream_channel() -> (mpsc::Sender<Vec<u8>>, mpsc::Receiver<Vec<u8>>) {
+  mpsc::channel(STREAM_BUFFER_CHUNKS)
+}
+
+// Yields one item of `s` per pull, ending when either runs out.
+pub fn pulled<S>(
+  s: S,
+  pulls: mpsc::UnboundedReceiver<()>,
+) -> impl Stream<Item = S::Item, Error = ()>
+where
+  S: Stream<Error = ()>,
+{
+  s.zip(pulls).map(|(item, _)| item)
+}
+
 pub fn send_body_stream(ptr: JsRuntime, req_id: u32, stream: JsBody) {
   let rt = ptr.to_runtime();
 
   match stream {
     JsBody::BoxedStream(s) => rt.spawn(
-      s.map_err(|e| error!("error sending boxed stream: {}", e))
-        .for_each(move |v| {
-          send_stream_chunk(ptr, req_id, v.as_ptr() as *mut u8, v.len(), false);
-          Ok(())
-        })
-        .and_then(move |_| {
+      pulled(
+        s.map_err(|e| error!("error sending boxed stream: {}", e)),
+        rt.take_stream_pulls(req_id),
+      )
+      .for_each(move |v| {
+        send_stream_chunk(ptr, req_id, v.as_ptr() as *mut u8, v.len(), false);
+        Ok(())
+      })
+      .then(move |res| {
+        ptr.to_runtime().end_stream_pulls(req_id);
+        if res.is_ok() {
           send_done_stream(ptr, req_id);
-          Ok(())
-        }),
+        }
+        Ok(())
+      }),
     ),
     JsBody::Static(v) => {
       rt.spawn(future::lazy(move || {
@@ -109,27 +133,50 @@ pub fn send_body_stream(ptr: JsRuntime, req_id: u32, stream: JsBody) {
     }
     JsBody::Stream(rx) => {
       rt.spawn(
-        rx.map_err(move |e| error!("error reading from stream channel: {:?}", e))
-          .for_each(move |v| {
-            send_stream_chunk(ptr, req_id, v.as_ptr() as *mut u8, v.len(), false);
-            Ok(())
-          })
-          .and_then(move |_| {
+        pulled(
+          rx.map_err(move |e| error!("error reading from stream channel: {:?}", e)),
+          rt.take_stream_pulls(req_id),
+        )
+        .for_each(move |v| {
+          send_stream_chunk(ptr, req_id, v.as_ptr() as *mut u8, v.len(), false);
+          Ok(())
+        })
+        .then(move |res| {
+          ptr.to_runtime().end_stream_pulls(req_id);
+          if res.is_ok() {
             send_done_stream(ptr, req_id);
-            Ok(())
-          }),
+          }
+          Ok(())
+        }),
       );
     }
   };
 }
 
 pub fn send_stream_chunk(ptr: JsRuntime, req_id: u32, chunk: *mut u8, len: usize, done: bool) {
+  send_chunk(ptr, req_id, chunk, len, done, false)
+}
+
+/// Sends a chunk JS should read as a string rather than bytes.
+pub fn send_stream_text(ptr: JsRuntime, req_id: u32, text: &str) {
+  send_chunk(
+    ptr,
+    req_id,
+    text.as_ptr() as *mut u8,
+    text.len(),
+    false,
+    true,
+  )
+}
+
+fn send_chunk(ptr: JsRuntime, req_id: u32, chunk: *mut u8, len: usize, done: bool, text: bool) {
   let builder = &mut FlatBufferBuilder::new();
   let chunk_msg = msg::StreamChunk::create(
     builder,
     &msg::StreamChunkArgs {
       id: req_id,
       done: done,
+      text: text,
     },
   );
   ptr.send(
@@ -154,6 +201,46 @@ pub fn send_stream_chunk(ptr: JsRuntime, req_id: u32, chunk: *mut u8, len: usize
   );
 }
 
+pub fn send_stream_cancel(ptr: JsRuntime, req_id: u32) {
+  let builder = &mut FlatBufferBuilder::new();
+  let cancel_msg = msg::StreamCancel::create(builder, &msg::StreamCancelArgs { id: req_id });
+  ptr.send(
+    fly_buf_from(
+      serialize_response(
+        0,
+        builder,
+        msg::BaseArgs {
+          msg: Some(cancel_msg.as_union_value()),
+          msg_type: msg::Any::StreamCancel,
+          ..Default::default()
+        },
+      )
+      .unwrap(),
+    ),
+    None,
+  );
+}
+
+pub fn send_request_abort(ptr: JsRuntime, req_id: u32) {
+  let builder = &mut FlatBufferBuilder::new();
+  let abort_msg = msg::HttpRequestAbort::create(builder, &msg::HttpRequestAbortArgs { id: req_id });
+  ptr.send(
+    fly_buf_from(
+      serialize_response(
+        0,
+        builder,
+        msg::BaseArgs {
+          msg: Some(abort_msg.as_union_value()),
+          msg_type: msg::Any::HttpRequestAbort,
+          ..Default::default()
+        },
+      )
+      .unwrap(),
+    ),
+    None,
+  );
+}
+
 pub fn send_done_stream(ptr: JsRuntime, req_id: u32) {
   let builder = &mut FlatBufferBuilder::new();
   let chunk_msg = msg::StreamChunk::create(
@@ -161,6 +248,7 @@ pub fn send_done_stream(ptr: JsRuntime, req_id: u32) {
     &msg::StreamChunkArgs {
       id: req_id,
       done: true,
+      ..Default::default()
     },
   );
   ptr.send(
@@ -229,3 +317,75 @@ pub fn signal_monitor() -> (
     sigrx,
   )
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use futures::{stream, Sink};
+  use std::sync::{Arc, Mutex};
+  use tokio::runtime::current_thread;
+
+  const CHUNK_SIZE: usize = 64 * 1024;
+
+  // (chunks alive, most chunks ever alive at once)
+  type Live = Arc<Mutex<(usize, usize)>>;
+
+  fn produce(live: Live, total_bytes: usize) -> impl Stream<Item = Vec<u8>, Error = ()> {
+    stream::iter_ok(0..total_bytes / CHUNK_SIZE).map(move |_| {
+      let mut live = live.lock().unwrap();
+      live.0 += 1;
+      live.1 = live.1.max(live.0);
+      vec![0u8; CHUNK_SIZE]
+    })
+  }
+
+  #[test]
+  fn test_streamed_body_memory_stays_flat() {
+    let live: Live = Arc::new(Mutex::new((0, 0)));
+    let total_bytes = 3 * 1024 * 1024 * 1024;
+
+    // producer -> bounded channel -> pulled one chunk at a time by a consumer
+    let (sender, recver) = stream_channel();
+    let (pull_tx, pull_rx) = mpsc::unbounded();
+    pull_tx.unbounded_send(()).unwrap();
+
+    let producer = sender
+      .sink_map_err(|_| ())
+      .send_all(produce(live.clone(), total_bytes))
+      .map(|_| ());
+
+    let consumer_live = live.clone();
+    let consumer = pulled(recver, pull_rx).fold(0usize, move |received, chunk| {
+      consumer_live.lock().unwrap().0 -= 1;
+      pull_tx.unbounded_send(()).unwrap();
+      Ok::<_, ()>(received + chunk.len())
+    });
+
+    let (_, received) = current_thread::Runtime::new()
+      .unwrap()
+      .block_on(producer.join(consumer))
+      .unwrap();
+
+    assert_eq!(received, total_bytes);
+    let peak = live.lock().unwrap().1;
+    assert!(
+      peak <= STREAM_BUFFER_CHUNKS + 3,
+      "{} chunks were buffered at once",
+      peak
+    );
+  }
+
+  #[test]
+  fn test_pulled_waits_for_pulls() {
+    let (pull_tx, pull_rx) = mpsc::unbounded();
+    pull_tx.unbounded_send(()).unwrap();
+    pull_tx.unbounded_send(()).unwrap();
+    drop(pull_tx);
+
+    let items = pulled(stream::iter_ok::<_, ()>(vec![1, 2, 3, 4]), pull_rx)
+      .collect()
+      .wait()
+      .unwrap();
+    assert_eq!(items, vec![1, 2]);
+  }
+}
@@ -0,0 +1,58 @@
+/**
+ * @module fetch
+ */
+
+type AbortListener = (this: FlyAbortSignal, ev: { type: string, target: FlyAbortSignal }) => any;
+
+/**
+ * Tells an operation it should stop. Requests handed to fetch event handlers
+ * carry one that fires when the client goes away.
+ */
+export class FlyAbortSignal {
+	aborted: boolean = false
+	onabort: AbortListener | null = null
+	private listeners: AbortListener[] = []
+
+	addEventListener(type: string, listener: AbortListener) {
+		if (type === "abort")
+			this.listeners.push(listener)
+	}
+
+	removeEventListener(type: string, listener: AbortListener) {
+		if (type === "abort")
+			this.listeners = this.listeners.filter(l => l !== listener)
+	}
+
+	dispatchEvent(ev: { type: string }): boolean {
+		if (ev.type !== "abort")
+			return true
+		const event = { type: ev.type, target: this }
+		const fns = this.onabort ? [this.onabort, ...this.listeners] : this.listeners
+		for (const fn of fns) {
+			try {
+				fn.call(this, event)
+			} catch (e) {
+				console.error("error in abort listener:", e.message)
+			}
+		}
+		return true
+	}
+}
+
+export class FlyAbortController {
+	readonly signal = new FlyAbortSignal()
+
+	abort() {
+		if (this.signal.aborted)
+			return
+		this.signal.aborted = true
+		this.signal.dispatchEvent({ type: "abort" })
+	}
+}
+
+/** The error operations reject with once their signal fired. */
+export function abortError(): Error {
+	const err = new Error("The operation was aborted.")
+	err.name = "AbortError"
+	return err
+}
@@ -52,6 +52,11 @@ export class FlyBlob implements Blob {
   }
 }
 
+/** The bytes backing a blob, without copying them. */
+export function blobBytes(blob: FlyBlob): Uint8Array {
+  return blob[bytesSymbol];
+}
+
 function processBlobParts(
   blobParts: BlobPart[],
   options: BlobPropertyBag
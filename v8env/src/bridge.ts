@@ -14,11 +14,16 @@ import { FlyResponse } from "./response";
 import { ReadableStream, ReadableStreamSource, StreamStrategy } from "@stardazed/streams";
 import { DNSRequest, DNSQuery, DNSResponse, DNSDataA, DNSDataAAAA, DNSDataCNAME, DNSDataMX, DNSDataNS, DNSDataPTR, DNSDataSOA, DNSDataSRV, DNSDataTXT } from './dns';
 import { isAcmeChallengeRequest, handleAcmeChallenge } from "./acme";
+import { FlyAbortController } from "./abort";
 
 let nextCmdId = 1; // 0 is for events
 const promiseTable = new Map<number, util.Resolvable<fbs.Base>>();
 const listenerTable = new Map<fbs.Any, Function>();
 export const streams = new Map<number, (msg: fbs.StreamChunk, raw: Uint8Array) => void>();
+// fires the signal of incoming requests whose client went away
+const requestAborts = new Map<number, FlyAbortController>();
+// streams JS is sending to Rust, stopped when Rust cancels them
+const outgoing = new Map<number, () => void>();
 
 export function handleAsyncMsgFromRust(ui8: Uint8Array, raw: Uint8Array) {
   const bb = new flatbuffers.ByteBuffer(ui8);
@@ -50,6 +55,12 @@ function handleEvent(base: fbs.Base, raw: Uint8Array) {
     case fbs.Any.StreamChunk:
       handleBody(base, raw);
       break;
+    case fbs.Any.StreamCancel:
+      handleStreamCancel(base);
+      break;
+    case fbs.Any.HttpRequestAbort:
+      handleRequestAbort(base);
+      break;
     default:
       const ln = listenerTable.get(type);
       if (!ln) {
@@ -60,6 +71,57 @@ function handleEvent(base: fbs.Base, raw: Uint8Array) {
   }
 }
 
+/**
+ * Body streamed from Rust under `id`. Each chunk is only sent once the stream
+ * is pulled, so a slow reader holds back the source instead of buffering it.
+ */
+export function streamFromRust(id: number, onEnd?: () => void): ReadableStream {
+  let pending: (() => void) | undefined;
+  return new ReadableStream({
+    start(controller) {
+      streams.set(id, (chunkMsg: fbs.StreamChunk, raw: Uint8Array) => {
+        if (raw && raw.byteLength > 0)
+          controller.enqueue(raw);
+        if (chunkMsg.done()) {
+          controller.close()
+          streams.delete(id)
+          if (onEnd) onEnd()
+        }
+        if (pending) {
+          pending()
+          pending = undefined
+        }
+      })
+    },
+    pull() {
+      return new Promise<void>(resolve => {
+        pending = resolve
+        sendStreamPull(id, false)
+      })
+    },
+    cancel() {
+      streams.delete(id)
+      sendStreamPull(id, true)
+      if (onEnd) onEnd()
+    }
+  })
+}
+
+export function sendStreamPull(id: number, cancel: boolean) {
+  const fbb = flatbuffers.createBuilder()
+  fbs.StreamPull.startStreamPull(fbb)
+  fbs.StreamPull.addId(fbb, id)
+  fbs.StreamPull.addCancel(fbb, cancel)
+  sendSync(fbb, fbs.Any.StreamPull, fbs.StreamPull.endStreamPull(fbb))
+}
+
+/**
+ * Handles events Rust sends on its own, outside of any request.
+ */
+export function setEventListener(type: fbs.Any, fn: (base: fbs.Base) => void) {
+  listenerTable.set(type, fn);
+}
+
 function handleBody(base: fbs.Base, raw: Uint8Array) {
   let msg = new fbs.StreamChunk();
   base.msg(msg);
@@ -69,6 +131,26 @@ function handleBody(base: fbs.Base, raw: Uint8Array) {
     enqueuer(msg, raw)
 }
 
+function handleStreamCancel(base: fbs.Base) {
+  let msg = new fbs.StreamCancel();
+  base.msg(msg);
+
+  let cancel = outgoing.get(msg.id())
+  if (cancel)
+    cancel()
+}
+
+function handleRequestAbort(base: fbs.Base) {
+  let msg = new fbs.HttpRequestAbort();
+  base.msg(msg);
+
+  let controller = requestAborts.get(msg.id())
+  if (controller) {
+    requestAborts.delete(msg.id())
+    controller.abort()
+  }
+}
+
 export type DNSResponseFn = () => DNSResponse | Promise<DNSResponse>
 
 export type EventResponse<REST> = () => REST | Promise<REST> | REST;
@@ -110,21 +192,13 @@ export function addEventListener(name: string, fn: EventListenerFunction<Request
           method: fbs.HttpMethod[msg.method()].toUpperCase(),
           headers: headersInit,
           body: msg.hasBody() ?
-            new ReadableStream({
-              start(controller) {
-                streams.set(id, (chunkMsg: fbs.StreamChunk, raw: Uint8Array) => {
-                  // console.log("got a chunk:", chunkMsg.bytesArray());
-                  controller.enqueue(raw);
-                  if (chunkMsg.done()) {
-                    controller.close()
-                    streams.delete(chunkMsg.id())
-                  }
-                })
-              }
-            }) : null
+            streamFromRust(id) : null
         })
 
         req.remoteAddr = msg.remoteAddr();
+        const controller = new FlyAbortController();
+        req.signal = <any>controller.signal;
+        requestAborts.set(id, controller);
 
         if (isAcmeChallengeRequest(req)) {
           handleAcmeChallenge(req)
@@ -348,6 +422,9 @@ function handleDNSRes(id: number, res: DNSResponse) {
 }
 
 function handleError(id: number, err: Error) {
+  requestAborts.delete(id)
+  libfly.reportError(err, id);
+
   const fbb = flatbuffers.createBuilder();
 
   fbs.HttpResponse.startHttpResponse(fbb);
@@ -357,14 +434,21 @@ function handleError(id: number, err: Error) {
 
   const resMsg = fbs.HttpResponse.endHttpResponse(fbb);
   sendSync(fbb, fbs.Any.HttpResponse, resMsg);
-  sendStreamChunk(id, true, new TextEncoder().encode(err.stack));
+  sendStreamChunk(id, true, new TextEncoder().encode(err.stack))
+    .catch(e => console.error("error sending error body:", e.message));
 }
 
 export async function sendStreamChunks(id: number, stream: ReadableStream) {
   let reader = stream.getReader();
+  let cancelled = false
+  outgoing.set(id, () => {
+    cancelled = true
+    outgoing.delete(id)
+    reader.cancel(new Error("stream cancelled by the receiver"))
+  })
   let cur = await reader.read()
   let done = false
-  while (!done) {
+  while (!done && !cancelled) {
     let value: BufferSource;
     if (typeof cur.value === 'string')
       value = new TextEncoder().encode(cur.value)
@@ -374,23 +458,32 @@ export async function sendStreamChunks(id: number, stream: ReadableStream) {
       value = undefined
     else
       throw new TypeError(`wrong body type: ${typeof cur.value} -> ${cur.value}`)
-    sendStreamChunk(id, cur.done, value);
+    try {
+      // resolves once rust has room for the chunk
+      await sendStreamChunk(id, cur.done, value);
+    } catch (e) {
+      outgoing.delete(id)
+      reader.cancel(e)
+      return
+    }
     if (cur.done)
       done = true
     else
       cur = await reader.read()
   }
+  outgoing.delete(id)
 }
 
-export function sendStreamChunk(id: number, done: boolean, value?: BufferSource) {
+export function sendStreamChunk(id: number, done: boolean, value?: BufferSource): Promise<fbs.Base> {
   const fbb = flatbuffers.createBuilder()
   fbs.StreamChunk.startStreamChunk(fbb)
   fbs.StreamChunk.addId(fbb, id);
   fbs.StreamChunk.addDone(fbb, done);
-  sendSync(fbb, fbs.Any.StreamChunk, fbs.StreamChunk.endStreamChunk(fbb), value)
+  return sendAsync(fbb, fbs.Any.StreamChunk, fbs.StreamChunk.endStreamChunk(fbb), value)
 }
 
 async function handleRes(id: number, res: FlyResponse) {
+  requestAborts.delete(id)
   if (res.bodyUsed)
     throw new Error("BODY HAS BEEN USED, NO PUEDO!")
   // console.log("respond with!", res);
@@ -420,6 +513,8 @@ async function handleRes(id: number, res: FlyResponse) {
     let resBody = res.body;
     let hasBody = resBody != null && (!res.isStatic || res.isStatic && res.staticBody.byteLength > 0)
     fbs.HttpResponse.addHasBody(fbb, hasBody)
+    if (res.webSocket)
+      fbs.HttpResponse.addWebsocket(fbb, true)
 
     const resMsg = fbs.HttpResponse.endHttpResponse(fbb);
 
@@ -427,6 +522,8 @@ async function handleRes(id: number, res: FlyResponse) {
     if (hasBody && res.isStatic)
       staticBody = res.staticBody
     sendSync(fbb, fbs.Any.HttpResponse, resMsg, staticBody); // sync so we can send body chunks when it's ready!
+    if (res.webSocket)
+      res.webSocket._accept(id)
 
     if (staticBody || !hasBody)
       return
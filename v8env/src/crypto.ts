@@ -12,6 +12,80 @@ import { sendSync, sendAsync } from "./bridge";
 
 /** @hidden */
 
+export type KeyFormat = "raw" | "spki" | "pkcs8";
+export type KeyType = "secret" | "public" | "private";
+export type KeyUsage = "encrypt" | "decrypt" | "sign" | "verify";
+
+export interface Algorithm {
+  name: string;
+  hash?: string | { name: string };
+  namedCurve?: string;
+  iv?: BufferSource;
+  additionalData?: BufferSource;
+  tagLength?: number;
+}
+
+/** Handle to key material held by the runtime. */
+export class CryptoKey {
+  /** @internal */
+  constructor(
+    readonly _id: number,
+    readonly type: KeyType,
+    readonly algorithm: Algorithm,
+    readonly extractable: boolean,
+    readonly usages: KeyUsage[]
+  ) { }
+}
+
+function normalizeAlgorithm(algo: string | Algorithm): Algorithm {
+  return typeof algo === "string" ? { name: algo } : algo
+}
+
+function hashName(algo: Algorithm): string | undefined {
+  if (!algo.hash)
+    return undefined
+  return typeof algo.hash === "string" ? algo.hash : algo.hash.name
+}
+
+function toBytes(data: BufferSource | undefined): Uint8Array {
+  if (!data)
+    return new Uint8Array(0)
+  if (data instanceof ArrayBuffer)
+    return new Uint8Array(data)
+  return new Uint8Array(data.buffer, data.byteOffset, data.byteLength)
+}
+
+function checkUsage(key: CryptoKey, usage: KeyUsage) {
+  if (key.usages.indexOf(usage) === -1)
+    throw new Error(`InvalidAccessError: key doesn't allow "${usage}"`)
+}
+
+function bufferOf(u8: Uint8Array): ArrayBuffer {
+  return u8.buffer.slice(u8.byteOffset, u8.byteOffset + u8.byteLength) as ArrayBuffer
+}
+
+function cipher(decrypt: boolean, algorithm: string | Algorithm, key: CryptoKey, data: BufferSource): Promise<ArrayBuffer> {
+  const algo = normalizeAlgorithm(algorithm);
+  checkUsage(key, decrypt ? "decrypt" : "encrypt");
+  const fbb = flatbuffers.createBuilder();
+  const algoidx = fbb.createString(algo.name);
+  const ividx = fbs.CryptoCipher.createIvVector(fbb, toBytes(algo.iv));
+  const aadidx = fbs.CryptoCipher.createAdditionalDataVector(fbb, toBytes(algo.additionalData));
+  fbs.CryptoCipher.startCryptoCipher(fbb);
+  fbs.CryptoCipher.addKey(fbb, key._id);
+  fbs.CryptoCipher.addAlgo(fbb, algoidx);
+  fbs.CryptoCipher.addDecrypt(fbb, decrypt);
+  fbs.CryptoCipher.addIv(fbb, ividx);
+  fbs.CryptoCipher.addAdditionalData(fbb, aadidx);
+  if (algo.tagLength)
+    fbs.CryptoCipher.addTagLength(fbb, algo.tagLength);
+  return sendAsync(fbb, fbs.Any.CryptoCipher, fbs.CryptoCipher.endCryptoCipher(fbb), toBytes(data)).then(baseRes => {
+    const msg = new fbs.CryptoCipherReady();
+    baseRes.msg(msg);
+    return bufferOf(msg.bufferArray());
+  })
+}
+
 export const crypto = {
   subtle: {
     digest(algo: string, data: ArrayBufferView | ArrayBuffer): Promise<ArrayBufferLike> {
@@ -28,6 +102,76 @@ export const crypto = {
         return u8.buffer.slice(u8.byteOffset);
       })
     },
+
+    importKey(format: KeyFormat, keyData: BufferSource, algorithm: string | Algorithm, extractable: boolean, usages: KeyUsage[]): Promise<CryptoKey> {
+      const algo = normalizeAlgorithm(algorithm);
+      const hash = hashName(algo);
+      const fbb = flatbuffers.createBuilder();
+      const formatidx = fbb.createString(format);
+      const algoidx = fbb.createString(algo.name);
+      const hashidx = hash ? fbb.createString(hash) : 0;
+      const curveidx = algo.namedCurve ? fbb.createString(algo.namedCurve) : 0;
+      fbs.CryptoImportKey.startCryptoImportKey(fbb);
+      fbs.CryptoImportKey.addFormat(fbb, formatidx);
+      fbs.CryptoImportKey.addAlgo(fbb, algoidx);
+      if (hashidx)
+        fbs.CryptoImportKey.addHash(fbb, hashidx);
+      if (curveidx)
+        fbs.CryptoImportKey.addNamedCurve(fbb, curveidx);
+      return sendAsync(fbb, fbs.Any.CryptoImportKey, fbs.CryptoImportKey.endCryptoImportKey(fbb), toBytes(keyData)).then(baseRes => {
+        const msg = new fbs.CryptoImportKeyReady();
+        baseRes.msg(msg);
+        return new CryptoKey(msg.id(), msg.keyType() as KeyType, algo, extractable, usages);
+      })
+    },
+
+    sign(algorithm: string | Algorithm, key: CryptoKey, data: BufferSource): Promise<ArrayBuffer> {
+      const algo = normalizeAlgorithm(algorithm);
+      checkUsage(key, "sign");
+      const hash = hashName(algo);
+      const fbb = flatbuffers.createBuilder();
+      const algoidx = fbb.createString(algo.name);
+      const hashidx = hash ? fbb.createString(hash) : 0;
+      fbs.CryptoSign.startCryptoSign(fbb);
+      fbs.CryptoSign.addKey(fbb, key._id);
+      fbs.CryptoSign.addAlgo(fbb, algoidx);
+      if (hashidx)
+        fbs.CryptoSign.addHash(fbb, hashidx);
+      return sendAsync(fbb, fbs.Any.CryptoSign, fbs.CryptoSign.endCryptoSign(fbb), toBytes(data)).then(baseRes => {
+        const msg = new fbs.CryptoSignReady();
+        baseRes.msg(msg);
+        return bufferOf(msg.bufferArray());
+      })
+    },
+
+    verify(algorithm: string | Algorithm, key: CryptoKey, signature: BufferSource, data: BufferSource): Promise<boolean> {
+      const algo = normalizeAlgorithm(algorithm);
+      checkUsage(key, "verify");
+      const hash = hashName(algo);
+      const fbb = flatbuffers.createBuilder();
+      const algoidx = fbb.createString(algo.name);
+      const hashidx = hash ? fbb.createString(hash) : 0;
+      const sigidx = fbs.CryptoVerify.createSignatureVector(fbb, toBytes(signature));
+      fbs.CryptoVerify.startCryptoVerify(fbb);
+      fbs.CryptoVerify.addKey(fbb, key._id);
+      fbs.CryptoVerify.addAlgo(fbb, algoidx);
+      if (hashidx)
+        fbs.CryptoVerify.addHash(fbb, hashidx);
+      fbs.CryptoVerify.addSignature(fbb, sigidx);
+      return sendAsync(fbb, fbs.Any.CryptoVerify, fbs.CryptoVerify.endCryptoVerify(fbb), toBytes(data)).then(baseRes => {
+        const msg = new fbs.CryptoVerifyReady();
+        baseRes.msg(msg);
+        return msg.valid();
+      })
+    },
+
+    encrypt(algorithm: string | Algorithm, key: CryptoKey, data: BufferSource): Promise<ArrayBuffer> {
+      return cipher(false, algorithm, key, data)
+    },
+
+    decrypt(algorithm: string | Algorithm, key: CryptoKey, data: BufferSource): Promise<ArrayBuffer> {
+      return cipher(true, algorithm, key, data)
+    },
   },
   getRandomValues(typedArray: Uint8Array): void {
     if (!(typedArray instanceof Uint8Array)) {
@@ -4,19 +4,32 @@
 import { RequestInit, RequestInfo } from './dom_types';
 import { FlyResponse } from './response';
 import { FlyRequest } from './request';
-import { sendAsync, streams, sendStreamChunks } from './bridge';
+import { sendAsync, sendSync, streamFromRust, sendStreamChunks } from './bridge';
+import { FlyAbortSignal, abortError } from './abort';
 
 import * as fbs from "./msg_generated";
 import * as flatbuffers from "./flatbuffers"
-import { ReadableStream } from '@stardazed/streams';
 
 import { libfly } from './libfly';
 
 export interface FlyRequestInit extends RequestInit {
+	/** Milliseconds the whole fetch may take, response body included, across redirects and retries */
 	timeout?: number,
-	readTimeout?: number
+	readTimeout?: number,
+	/** Redirects to follow before failing, 20 by default */
+	maxRedirects?: number,
+	/** Times to retry idempotent requests after a network error or a 502, 503 or 504 */
+	retries?: number,
+	/** Name of a configured client certificate to present to the server, not to other origins it redirects to */
+	clientIdentity?: string
 }
 
+const fbsRedirectMap: Map<String, fbs.RedirectMode> = new Map([
+	["follow", fbs.RedirectMode.Follow],
+	["manual", fbs.RedirectMode.Manual],
+	["error", fbs.RedirectMode.Error],
+]);
+
 const fbsMethodMap: Map<String, fbs.HttpMethod> = new Map([
 	["GET", fbs.HttpMethod.Get],
 	["HEAD", fbs.HttpMethod.Head],
@@ -46,6 +59,10 @@ export function fetch(info: RequestInfo, init?: FlyRequestInit): Promise<FlyResp
 	if (!url)
 		throw new Error("fetch url required")
 
+	const signal = <FlyAbortSignal><any>req.signal
+	if (signal && signal.aborted)
+		return Promise.reject(abortError())
+
 	let fbbMethod = fbsMethodMap.get(req.method.toUpperCase());
 	if (typeof fbbMethod === "undefined")
 		throw new Error(`unknown http method: ${req.method}`);
@@ -63,6 +80,13 @@ export function fetch(info: RequestInfo, init?: FlyRequestInit): Promise<FlyResp
 		fbs.HttpHeader.addValue(fbb, value);
 		fbbHeaders[i++] = fbs.HttpHeader.endHttpHeader(fbb);
 	}
+	const fbbRedirect = fbsRedirectMap.get(req.redirect);
+	if (typeof fbbRedirect === "undefined")
+		throw new TypeError(`unknown redirect mode: ${req.redirect}`);
+	init = init || {};
+
+	const clientIdentity = init.clientIdentity ? fbb.createString(init.clientIdentity) : null;
+
 	let reqHeaders = fbs.HttpRequest.createHeadersVector(fbb, fbbHeaders);
 	fbs.HttpRequest.startHttpRequest(fbb);
 	const reqId = libfly.getNextStreamId();
@@ -70,6 +94,15 @@ export function fetch(info: RequestInfo, init?: FlyRequestInit): Promise<FlyResp
 	fbs.HttpRequest.addMethod(fbb, fbbMethod);
 	fbs.HttpRequest.addUrl(fbb, urlStr);
 	fbs.HttpRequest.addHeaders(fbb, reqHeaders);
+	fbs.HttpRequest.addRedirect(fbb, fbbRedirect);
+	if (init.timeout)
+		fbs.HttpRequest.addTimeoutMs(fbb, init.timeout);
+	if (typeof init.maxRedirects === "number")
+		fbs.HttpRequest.addMaxRedirects(fbb, init.maxRedirects);
+	if (init.retries)
+		fbs.HttpRequest.addRetries(fbb, init.retries);
+	if (clientIdentity)
+		fbs.HttpRequest.addClientIdentity(fbb, clientIdentity);
 
 	let reqBody = req.body;
 	let hasBody = reqBody != null && (!req.isStatic || req.isStatic && req.staticBody.byteLength > 0);
@@ -79,34 +112,48 @@ export function fetch(info: RequestInfo, init?: FlyRequestInit): Promise<FlyResp
 	if (hasBody && req.isStatic)
 		staticBody = req.staticBody
 
+	// the abort listener lives until the response body is done with
+	const onAbort = () => sendFetchAbort(reqId)
+	const settle = () => signal && signal.removeEventListener("abort", onAbort)
+
 	const prom = sendAsync(fbb, fbs.Any.HttpRequest, fbs.HttpRequest.endHttpRequest(fbb), staticBody).then((base) => {
 		let msg = new fbs.FetchHttpResponse();
 		base.msg(msg);
 		const body = msg.hasBody() ?
-			new ReadableStream({
-				start(controller) {
-					streams.set(msg.id(), (chunkMsg: fbs.StreamChunk, raw: Uint8Array) => {
-						controller.enqueue(raw);
-						if (chunkMsg.done()) {
-							controller.close()
-							streams.delete(chunkMsg.id())
-						}
-					})
-				}
-			}) : null
+			streamFromRust(msg.id(), settle) : null
+		if (!body)
+			settle()
 		const headersInit: Array<[string, string]> = [];
 		for (let i = 0; i < msg.headersLength(); i++) {
 			const h = msg.headers(i);
 			headersInit.push([h.key(), h.value()]);
 		}
 
-		return new FlyResponse(body, { headers: headersInit, status: msg.status() })
+		const res = new FlyResponse(body, { headers: headersInit, status: msg.status() })
+		res.url = msg.redirected() ? msg.url() : url
+		res.redirected = msg.redirected()
+		return res
 	});
 
 	if (!staticBody && hasBody) // must be a stream
 		sendStreamChunks(reqId, reqBody) // don't wait for it, just start sending.
-
-	return prom
+			.catch(e => console.error("error sending request body:", e.message))
+
+	if (!signal)
+		return prom
+	// also cuts off the response body if it arrives before the abort
+	signal.addEventListener("abort", onAbort)
+	return prom.catch(err => {
+		settle()
+		throw signal.aborted ? abortError() : err
+	})
 };
 
+function sendFetchAbort(id: number) {
+	const fbb = flatbuffers.createBuilder();
+	fbs.FetchAbort.startFetchAbort(fbb);
+	fbs.FetchAbort.addId(fbb, id);
+	sendSync(fbb, fbs.Any.FetchAbort, fbs.FetchAbort.endFetchAbort(fbb));
+}
+
 export class TimeoutError extends Error { }
\ No newline at end of file
@@ -0,0 +1,95 @@
+/**
+ * Manages the app's static files, the ones `fetch("file://...")` serves.
+ *
+ * ```javascript
+ * await fly.fs.writeFile("assets/hello.txt", "hello world")
+ *
+ * const info = await fly.fs.stat("assets/hello.txt")
+ * const paths = await fly.fs.list("assets/")
+ * ```
+ *
+ * Writing and deleting needs the `allow_fs_write` permission.
+ * @module fly/fs
+ */
+
+import * as fbs from "../msg_generated";
+import * as flatbuffers from "../flatbuffers";
+import { sendAsync } from "../bridge";
+import { stringToArrayBuffer } from "../util";
+
+export interface FileInfo {
+  /** Size in bytes */
+  size: number
+  /** Last modification, null when the store doesn't keep track of it */
+  modified: Date | null
+  /** Guessed from the file's extension */
+  contentType: string
+}
+
+/**
+ * Replaces a file, or creates it.
+ * @param path path of the file
+ * @param data its contents
+ */
+export async function writeFile(path: string, data: string | ArrayBuffer | ArrayBufferView): Promise<void> {
+  const fbb = flatbuffers.createBuilder();
+  const fbbPath = fbb.createString(path);
+  fbs.FsWrite.startFsWrite(fbb);
+  fbs.FsWrite.addPath(fbb, fbbPath);
+  const raw = typeof data === "string" ? stringToArrayBuffer(data) : data;
+  await sendAsync(fbb, fbs.Any.FsWrite, fbs.FsWrite.endFsWrite(fbb), raw);
+}
+
+/**
+ * Deletes a file. Deleting a file that doesn't exist succeeds.
+ * @param path path of the file
+ */
+export async function deleteFile(path: string): Promise<void> {
+  const fbb = flatbuffers.createBuilder();
+  const fbbPath = fbb.createString(path);
+  fbs.FsDelete.startFsDelete(fbb);
+  fbs.FsDelete.addPath(fbb, fbbPath);
+  await sendAsync(fbb, fbs.Any.FsDelete, fbs.FsDelete.endFsDelete(fbb));
+}
+
+/**
+ * Describes a file.
+ * @param path path of the file
+ * @returns null if there's no such file
+ */
+export async function stat(path: string): Promise<FileInfo | null> {
+  const fbb = flatbuffers.createBuilder();
+  const fbbPath = fbb.createString(path);
+  fbs.FsStat.startFsStat(fbb);
+  fbs.FsStat.addPath(fbb, fbbPath);
+  const baseRes = await sendAsync(fbb, fbs.Any.FsStat, fbs.FsStat.endFsStat(fbb));
+  if (baseRes.msgType() == fbs.Any.NONE)
+    return null
+  const msg = new fbs.FsStatReady();
+  baseRes.msg(msg);
+  const modified = msg.modified().toFloat64();
+  return {
+    size: msg.size().toFloat64(),
+    modified: modified > 0 ? new Date(modified) : null,
+    contentType: msg.contentType() || "application/octet-stream",
+  }
+}
+
+/**
+ * Lists files.
+ * @param prefix only lists paths starting with it
+ * @returns the paths, sorted
+ */
+export async function list(prefix: string = ""): Promise<string[]> {
+  const fbb = flatbuffers.createBuilder();
+  const fbbPrefix = fbb.createString(prefix);
+  fbs.FsList.startFsList(fbb);
+  fbs.FsList.addPrefix(fbb, fbbPrefix);
+  const baseRes = await sendAsync(fbb, fbs.Any.FsList, fbs.FsList.endFsList(fbb));
+  const msg = new fbs.FsListReady();
+  baseRes.msg(msg);
+  const paths: string[] = [];
+  for (let i = 0; i < msg.pathsLength(); i++)
+    paths.push(msg.paths(i));
+  return paths
+}
@@ -1,4 +1,4 @@
-import { sendAsync, streams, sendStreamChunks } from '../../bridge'
+import { sendAsync, streamFromRust, sendStreamChunks } from '../../bridge'
 import * as fbs from "../../msg_generated";
 import * as flatbuffers from "../../flatbuffers";
 import { ReadableStream } from '@stardazed/streams';
@@ -64,17 +64,7 @@ export class Image {
 
             await sendStreamChunks(msg.inId(), this.src);
 
-            return new ReadableStream({
-                start(controller) {
-                    streams.set(msg.outId(), (chunkMsg: fbs.StreamChunk, raw: Uint8Array) => {
-                        controller.enqueue(raw);
-                        if (chunkMsg.done()) {
-                            controller.close()
-                            streams.delete(chunkMsg.id())
-                        }
-                    })
-                }
-            })
+            return streamFromRust(msg.outId())
         })
 
     }
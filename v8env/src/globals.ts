@@ -10,6 +10,8 @@ import { FlyResponse } from "./response";
 import * as fetch_ from './fetch';
 import * as resolv_ from './resolv';
 import * as dns from './dns';
+import { FlyWebSocket, WebSocketPair as FlyWebSocketPair } from "./websocket";
+import { FlyAbortController, FlyAbortSignal } from "./abort";
 import * as crypto_ from "./crypto";
 import cache_ from "./cache";
 import { Image } from "./fly/image";
@@ -17,6 +19,7 @@ import { Image } from "./fly/image";
 import * as url from './url';
 import { FlyRequest } from "./request";
 import * as flyData from './fly/data';
+import * as flyFs from './fly/fs';
 import * as flyCache from './fly/cache';
 import * as flyResponseCache from './fly/response';
 import flyHttp from './fly/http'
@@ -53,6 +56,10 @@ declare global {
   const Request: typeof FlyRequest;
 
   const fetch: typeof fetch_.fetch;
+  const WebSocket: typeof FlyWebSocket;
+  const WebSocketPair: typeof FlyWebSocketPair;
+  const AbortController: typeof FlyAbortController;
+  const AbortSignal: typeof FlyAbortSignal;
 
   // tslint:disable:variable-name
   let TextEncoder: typeof textEncoding.TextEncoder;
@@ -69,6 +76,7 @@ declare global {
     cache: typeof flyCache
     responseCache: typeof flyResponseCache
     data: typeof flyData
+    fs: typeof flyFs
     http: typeof flyHttp
     Image: typeof Image
     app: AppRelease;
@@ -120,6 +128,10 @@ window.FormData = formData.FlyFormData as domTypes.FormDataConstructor;
 export type FormData = domTypes.FormData;
 
 window.fetch = fetch_.fetch;
+window.WebSocket = FlyWebSocket;
+window.WebSocketPair = FlyWebSocketPair;
+window.AbortController = FlyAbortController;
+window.AbortSignal = FlyAbortSignal;
 window.resolv = resolv_.resolv;
 window.crypto = crypto_.crypto;
 window.cache = cache_;
@@ -129,6 +141,7 @@ window.fly = {
   cache: flyCache,
   responseCache: flyResponseCache,
   data: flyData,
+  fs: flyFs,
   http: flyHttp,
   Image: Image,
   runtime,
@@ -15,6 +15,8 @@ interface Libfly {
     ) => void
   ) => void;
   getNextStreamId(): number;
+  // hands an error over to the runtime, tied to the event being handled if any
+  reportError(error: any, requestId?: number): void;
 }
 
 const window = globalEval("this");
@@ -167,7 +167,29 @@ export enum Any{
   ImageReady= 39,
   AcmeGetChallenge= 40,
   AcmeGetChallengeReady= 41,
-  OsExit= 42
+  OsExit= 42,
+  StreamPull= 43,
+  CryptoImportKey= 44,
+  CryptoImportKeyReady= 45,
+  CryptoSign= 46,
+  CryptoSignReady= 47,
+  CryptoVerify= 48,
+  CryptoVerifyReady= 49,
+  CryptoCipher= 50,
+  CryptoCipherReady= 51,
+  WebSocketConnect= 52,
+  WebSocketConnectReady= 53,
+  WebSocketSend= 54,
+  WebSocketClose= 55,
+  StreamCancel= 56,
+  HttpRequestAbort= 57,
+  FetchAbort= 58,
+  FsWrite= 59,
+  FsDelete= 60,
+  FsStat= 61,
+  FsStatReady= 62,
+  FsList= 63,
+  FsListReady= 64
 };
 
 /**
@@ -208,7 +230,15 @@ export enum ErrorKind{
   HttpClosed= 31,
   HttpCanceled= 32,
   HttpParse= 33,
-  HttpOther= 34
+  HttpOther= 34,
+  NotSupportedError= 35,
+  DataError= 36,
+  OperationError= 37,
+  InvalidAccessError= 38,
+  AddressBlocked= 39,
+  TooManyRedirects= 40,
+  RedirectNotAllowed= 41,
+  Aborted= 42
 };
 
 /**
@@ -226,6 +256,15 @@ export enum HttpMethod{
   Trace= 8
 };
 
+/**
+ * @enum
+ */
+export enum RedirectMode{
+  Follow= 0,
+  Manual= 1,
+  Error= 2
+};
+
 /**
  * @enum
  */
@@ -4529,16 +4568,16 @@ static endOsExit(builder:flatbuffers.Builder):flatbuffers.Offset {
 /**
  * @constructor
  */
-export class Base {
+export class CryptoImportKey {
   bb: flatbuffers.ByteBuffer|null = null;
 
   bb_pos:number = 0;
 /**
  * @param number i
  * @param flatbuffers.ByteBuffer bb
- * @returns Base
+ * @returns CryptoImportKey
  */
-__init(i:number, bb:flatbuffers.ByteBuffer):Base {
+__init(i:number, bb:flatbuffers.ByteBuffer):CryptoImportKey {
   this.bb_pos = i;
   this.bb = bb;
   return this;
@@ -4546,211 +4585,119 @@ __init(i:number, bb:flatbuffers.ByteBuffer):Base {
 
 /**
  * @param flatbuffers.ByteBuffer bb
- * @param Base= obj
- * @returns Base
- */
-static getRootAsBase(bb:flatbuffers.ByteBuffer, obj?:Base):Base {
-  return (obj || new Base).__init(bb.readInt32(bb.position()) + bb.position(), bb);
-};
-
-/**
- * @returns number
+ * @param CryptoImportKey= obj
+ * @returns CryptoImportKey
  */
-cmdId():number {
-  var offset = this.bb!.__offset(this.bb_pos, 4);
-  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+static getRootAsCryptoImportKey(bb:flatbuffers.ByteBuffer, obj?:CryptoImportKey):CryptoImportKey {
+  return (obj || new CryptoImportKey).__init(bb.readInt32(bb.position()) + bb.position(), bb);
 };
 
 /**
- * @param number value
- * @returns boolean
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
  */
-mutate_cmd_id(value:number):boolean {
+format():string|null
+format(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+format(optionalEncoding?:any):string|Uint8Array|null {
   var offset = this.bb!.__offset(this.bb_pos, 4);
-
-  if (offset === 0) {
-    return false;
-  }
-
-  this.bb!.writeUint32(this.bb_pos + offset, value);
-  return true;
-};
-
-/**
- * @returns boolean
- */
-sync():boolean {
-  var offset = this.bb!.__offset(this.bb_pos, 6);
-  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
 };
 
 /**
- * @param boolean value
- * @returns boolean
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
  */
-mutate_sync(value:boolean):boolean {
+algo():string|null
+algo(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+algo(optionalEncoding?:any):string|Uint8Array|null {
   var offset = this.bb!.__offset(this.bb_pos, 6);
-
-  if (offset === 0) {
-    return false;
-  }
-
-  this.bb!.writeInt8(this.bb_pos + offset, +value);
-  return true;
-};
-
-/**
- * @returns ErrorKind
- */
-errorKind():ErrorKind {
-  var offset = this.bb!.__offset(this.bb_pos, 8);
-  return offset ? /**  */ (this.bb!.readInt8(this.bb_pos + offset)) : ErrorKind.NoError;
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
 };
 
 /**
- * @param ErrorKind value
- * @returns boolean
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
  */
-mutate_error_kind(value:ErrorKind):boolean {
+hash():string|null
+hash(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+hash(optionalEncoding?:any):string|Uint8Array|null {
   var offset = this.bb!.__offset(this.bb_pos, 8);
-
-  if (offset === 0) {
-    return false;
-  }
-
-  this.bb!.writeInt8(this.bb_pos + offset, value);
-  return true;
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
 };
 
 /**
  * @param flatbuffers.Encoding= optionalEncoding
  * @returns string|Uint8Array|null
  */
-error():string|null
-error(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
-error(optionalEncoding?:any):string|Uint8Array|null {
+namedCurve():string|null
+namedCurve(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+namedCurve(optionalEncoding?:any):string|Uint8Array|null {
   var offset = this.bb!.__offset(this.bb_pos, 10);
   return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
 };
 
-/**
- * @returns Any
- */
-msgType():Any {
-  var offset = this.bb!.__offset(this.bb_pos, 12);
-  return offset ? /**  */ (this.bb!.readUint8(this.bb_pos + offset)) : Any.NONE;
-};
-
-/**
- * @param Any value
- * @returns boolean
- */
-mutate_msg_type(value:Any):boolean {
-  var offset = this.bb!.__offset(this.bb_pos, 12);
-
-  if (offset === 0) {
-    return false;
-  }
-
-  this.bb!.writeUint8(this.bb_pos + offset, value);
-  return true;
-};
-
-/**
- * @param flatbuffers.Table obj
- * @returns ?flatbuffers.Table
- */
-msg<T extends flatbuffers.Table>(obj:T):T|null {
-  var offset = this.bb!.__offset(this.bb_pos, 14);
-  return offset ? this.bb!.__union(obj, this.bb_pos + offset) : null;
-};
-
-/**
- * @param flatbuffers.Builder builder
- */
-static startBase(builder:flatbuffers.Builder) {
-  builder.startObject(6);
-};
-
-/**
- * @param flatbuffers.Builder builder
- * @param number cmdId
- */
-static addCmdId(builder:flatbuffers.Builder, cmdId:number) {
-  builder.addFieldInt32(0, cmdId, 0);
-};
-
 /**
  * @param flatbuffers.Builder builder
- * @param boolean sync
  */
-static addSync(builder:flatbuffers.Builder, sync:boolean) {
-  builder.addFieldInt8(1, +sync, +false);
+static startCryptoImportKey(builder:flatbuffers.Builder) {
+  builder.startObject(4);
 };
 
 /**
  * @param flatbuffers.Builder builder
- * @param ErrorKind errorKind
+ * @param flatbuffers.Offset formatOffset
  */
-static addErrorKind(builder:flatbuffers.Builder, errorKind:ErrorKind) {
-  builder.addFieldInt8(2, errorKind, ErrorKind.NoError);
+static addFormat(builder:flatbuffers.Builder, formatOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, formatOffset, 0);
 };
 
 /**
  * @param flatbuffers.Builder builder
- * @param flatbuffers.Offset errorOffset
+ * @param flatbuffers.Offset algoOffset
  */
-static addError(builder:flatbuffers.Builder, errorOffset:flatbuffers.Offset) {
-  builder.addFieldOffset(3, errorOffset, 0);
+static addAlgo(builder:flatbuffers.Builder, algoOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, algoOffset, 0);
 };
 
 /**
  * @param flatbuffers.Builder builder
- * @param Any msgType
+ * @param flatbuffers.Offset hashOffset
  */
-static addMsgType(builder:flatbuffers.Builder, msgType:Any) {
-  builder.addFieldInt8(4, msgType, Any.NONE);
+static addHash(builder:flatbuffers.Builder, hashOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, hashOffset, 0);
 };
 
 /**
  * @param flatbuffers.Builder builder
- * @param flatbuffers.Offset msgOffset
+ * @param flatbuffers.Offset namedCurveOffset
  */
-static addMsg(builder:flatbuffers.Builder, msgOffset:flatbuffers.Offset) {
-  builder.addFieldOffset(5, msgOffset, 0);
+static addNamedCurve(builder:flatbuffers.Builder, namedCurveOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(3, namedCurveOffset, 0);
 };
 
 /**
  * @param flatbuffers.Builder builder
  * @returns flatbuffers.Offset
  */
-static endBase(builder:flatbuffers.Builder):flatbuffers.Offset {
+static endCryptoImportKey(builder:flatbuffers.Builder):flatbuffers.Offset {
   var offset = builder.endObject();
   return offset;
 };
 
-/**
- * @param flatbuffers.Builder builder
- * @param flatbuffers.Offset offset
- */
-static finishBaseBuffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
-  builder.finish(offset);
-};
-
 }
 /**
  * @constructor
  */
-export class TimerStart {
+export class CryptoImportKeyReady {
   bb: flatbuffers.ByteBuffer|null = null;
 
   bb_pos:number = 0;
 /**
  * @param number i
  * @param flatbuffers.ByteBuffer bb
- * @returns TimerStart
+ * @returns CryptoImportKeyReady
  */
-__init(i:number, bb:flatbuffers.ByteBuffer):TimerStart {
+__init(i:number, bb:flatbuffers.ByteBuffer):CryptoImportKeyReady {
   this.bb_pos = i;
   this.bb = bb;
   return this;
@@ -4758,11 +4705,11 @@ __init(i:number, bb:flatbuffers.ByteBuffer):TimerStart {
 
 /**
  * @param flatbuffers.ByteBuffer bb
- * @param TimerStart= obj
- * @returns TimerStart
+ * @param CryptoImportKeyReady= obj
+ * @returns CryptoImportKeyReady
  */
-static getRootAsTimerStart(bb:flatbuffers.ByteBuffer, obj?:TimerStart):TimerStart {
-  return (obj || new TimerStart).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+static getRootAsCryptoImportKeyReady(bb:flatbuffers.ByteBuffer, obj?:CryptoImportKeyReady):CryptoImportKeyReady {
+  return (obj || new CryptoImportKeyReady).__init(bb.readInt32(bb.position()) + bb.position(), bb);
 };
 
 /**
@@ -4789,32 +4736,20 @@ mutate_id(value:number):boolean {
 };
 
 /**
- * @returns number
- */
-delay():number {
-  var offset = this.bb!.__offset(this.bb_pos, 6);
-  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
-};
-
-/**
- * @param number value
- * @returns boolean
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
  */
-mutate_delay(value:number):boolean {
+keyType():string|null
+keyType(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+keyType(optionalEncoding?:any):string|Uint8Array|null {
   var offset = this.bb!.__offset(this.bb_pos, 6);
-
-  if (offset === 0) {
-    return false;
-  }
-
-  this.bb!.writeUint32(this.bb_pos + offset, value);
-  return true;
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
 };
 
 /**
  * @param flatbuffers.Builder builder
  */
-static startTimerStart(builder:flatbuffers.Builder) {
+static startCryptoImportKeyReady(builder:flatbuffers.Builder) {
   builder.startObject(2);
 };
 
@@ -4828,17 +4763,17 @@ static addId(builder:flatbuffers.Builder, id:number) {
 
 /**
  * @param flatbuffers.Builder builder
- * @param number delay
+ * @param flatbuffers.Offset keyTypeOffset
  */
-static addDelay(builder:flatbuffers.Builder, delay:number) {
-  builder.addFieldInt32(1, delay, 0);
+static addKeyType(builder:flatbuffers.Builder, keyTypeOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, keyTypeOffset, 0);
 };
 
 /**
  * @param flatbuffers.Builder builder
  * @returns flatbuffers.Offset
  */
-static endTimerStart(builder:flatbuffers.Builder):flatbuffers.Offset {
+static endCryptoImportKeyReady(builder:flatbuffers.Builder):flatbuffers.Offset {
   var offset = builder.endObject();
   return offset;
 };
@@ -4847,16 +4782,16 @@ static endTimerStart(builder:flatbuffers.Builder):flatbuffers.Offset {
 /**
  * @constructor
  */
-export class TimerReady {
+export class CryptoSign {
   bb: flatbuffers.ByteBuffer|null = null;
 
   bb_pos:number = 0;
 /**
  * @param number i
  * @param flatbuffers.ByteBuffer bb
- * @returns TimerReady
+ * @returns CryptoSign
  */
-__init(i:number, bb:flatbuffers.ByteBuffer):TimerReady {
+__init(i:number, bb:flatbuffers.ByteBuffer):CryptoSign {
   this.bb_pos = i;
   this.bb = bb;
   return this;
@@ -4864,17 +4799,17 @@ __init(i:number, bb:flatbuffers.ByteBuffer):TimerReady {
 
 /**
  * @param flatbuffers.ByteBuffer bb
- * @param TimerReady= obj
- * @returns TimerReady
+ * @param CryptoSign= obj
+ * @returns CryptoSign
  */
-static getRootAsTimerReady(bb:flatbuffers.ByteBuffer, obj?:TimerReady):TimerReady {
-  return (obj || new TimerReady).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+static getRootAsCryptoSign(bb:flatbuffers.ByteBuffer, obj?:CryptoSign):CryptoSign {
+  return (obj || new CryptoSign).__init(bb.readInt32(bb.position()) + bb.position(), bb);
 };
 
 /**
  * @returns number
  */
-id():number {
+key():number {
   var offset = this.bb!.__offset(this.bb_pos, 4);
   return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
 };
@@ -4883,7 +4818,7 @@ id():number {
  * @param number value
  * @returns boolean
  */
-mutate_id(value:number):boolean {
+mutate_key(value:number):boolean {
   var offset = this.bb!.__offset(this.bb_pos, 4);
 
   if (offset === 0) {
@@ -4895,56 +4830,63 @@ mutate_id(value:number):boolean {
 };
 
 /**
- * @returns boolean
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
  */
-canceled():boolean {
+algo():string|null
+algo(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+algo(optionalEncoding?:any):string|Uint8Array|null {
   var offset = this.bb!.__offset(this.bb_pos, 6);
-  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
 };
 
 /**
- * @param boolean value
- * @returns boolean
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
  */
-mutate_canceled(value:boolean):boolean {
-  var offset = this.bb!.__offset(this.bb_pos, 6);
-
-  if (offset === 0) {
-    return false;
-  }
+hash():string|null
+hash(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+hash(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
 
-  this.bb!.writeInt8(this.bb_pos + offset, +value);
-  return true;
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startCryptoSign(builder:flatbuffers.Builder) {
+  builder.startObject(3);
 };
 
 /**
  * @param flatbuffers.Builder builder
+ * @param number key
  */
-static startTimerReady(builder:flatbuffers.Builder) {
-  builder.startObject(2);
+static addKey(builder:flatbuffers.Builder, key:number) {
+  builder.addFieldInt32(0, key, 0);
 };
 
 /**
  * @param flatbuffers.Builder builder
- * @param number id
+ * @param flatbuffers.Offset algoOffset
  */
-static addId(builder:flatbuffers.Builder, id:number) {
-  builder.addFieldInt32(0, id, 0);
+static addAlgo(builder:flatbuffers.Builder, algoOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, algoOffset, 0);
 };
 
 /**
  * @param flatbuffers.Builder builder
- * @param boolean canceled
+ * @param flatbuffers.Offset hashOffset
  */
-static addCanceled(builder:flatbuffers.Builder, canceled:boolean) {
-  builder.addFieldInt8(1, +canceled, +false);
+static addHash(builder:flatbuffers.Builder, hashOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, hashOffset, 0);
 };
 
 /**
  * @param flatbuffers.Builder builder
  * @returns flatbuffers.Offset
  */
-static endTimerReady(builder:flatbuffers.Builder):flatbuffers.Offset {
+static endCryptoSign(builder:flatbuffers.Builder):flatbuffers.Offset {
   var offset = builder.endObject();
   return offset;
 };
@@ -4953,16 +4895,16 @@ static endTimerReady(builder:flatbuffers.Builder):flatbuffers.Offset {
 /**
  * @constructor
  */
-export class TimerClear {
+export class CryptoSignReady {
   bb: flatbuffers.ByteBuffer|null = null;
 
   bb_pos:number = 0;
 /**
  * @param number i
  * @param flatbuffers.ByteBuffer bb
- * @returns TimerClear
+ * @returns CryptoSignReady
  */
-__init(i:number, bb:flatbuffers.ByteBuffer):TimerClear {
+__init(i:number, bb:flatbuffers.ByteBuffer):CryptoSignReady {
   this.bb_pos = i;
   this.bb = bb;
   return this;
@@ -4970,29 +4912,2219 @@ __init(i:number, bb:flatbuffers.ByteBuffer):TimerClear {
 
 /**
  * @param flatbuffers.ByteBuffer bb
- * @param TimerClear= obj
- * @returns TimerClear
+ * @param CryptoSignReady= obj
+ * @returns CryptoSignReady
  */
-static getRootAsTimerClear(bb:flatbuffers.ByteBuffer, obj?:TimerClear):TimerClear {
-  return (obj || new TimerClear).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+static getRootAsCryptoSignReady(bb:flatbuffers.ByteBuffer, obj?:CryptoSignReady):CryptoSignReady {
+  return (obj || new CryptoSignReady).__init(bb.readInt32(bb.position()) + bb.position(), bb);
 };
 
 /**
+ * @param number index
  * @returns number
  */
-id():number {
+buffer(index: number):number|null {
   var offset = this.bb!.__offset(this.bb_pos, 4);
-  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
 };
 
 /**
- * @param number value
- * @returns boolean
+ * @returns number
  */
-mutate_id(value:number):boolean {
+bufferLength():number {
   var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+};
 
-  if (offset === 0) {
+/**
+ * @returns Uint8Array
+ */
+bufferArray():Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startCryptoSignReady(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset bufferOffset
+ */
+static addBuffer(builder:flatbuffers.Builder, bufferOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, bufferOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param Array.<number> data
+ * @returns flatbuffers.Offset
+ */
+static createBufferVector(builder:flatbuffers.Builder, data:number[] | Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (var i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]);
+  }
+  return builder.endVector();
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number numElems
+ */
+static startBufferVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endCryptoSignReady(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class CryptoVerify {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns CryptoVerify
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):CryptoVerify {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param CryptoVerify= obj
+ * @returns CryptoVerify
+ */
+static getRootAsCryptoVerify(bb:flatbuffers.ByteBuffer, obj?:CryptoVerify):CryptoVerify {
+  return (obj || new CryptoVerify).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+key():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_key(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+algo():string|null
+algo(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+algo(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+hash():string|null
+hash(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+hash(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param number index
+ * @returns number
+ */
+signature(index: number):number|null {
+  var offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+};
+
+/**
+ * @returns number
+ */
+signatureLength():number {
+  var offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @returns Uint8Array
+ */
+signatureArray():Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startCryptoVerify(builder:flatbuffers.Builder) {
+  builder.startObject(4);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number key
+ */
+static addKey(builder:flatbuffers.Builder, key:number) {
+  builder.addFieldInt32(0, key, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset algoOffset
+ */
+static addAlgo(builder:flatbuffers.Builder, algoOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, algoOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset hashOffset
+ */
+static addHash(builder:flatbuffers.Builder, hashOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, hashOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset signatureOffset
+ */
+static addSignature(builder:flatbuffers.Builder, signatureOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(3, signatureOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param Array.<number> data
+ * @returns flatbuffers.Offset
+ */
+static createSignatureVector(builder:flatbuffers.Builder, data:number[] | Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (var i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]);
+  }
+  return builder.endVector();
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number numElems
+ */
+static startSignatureVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endCryptoVerify(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class CryptoVerifyReady {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns CryptoVerifyReady
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):CryptoVerifyReady {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param CryptoVerifyReady= obj
+ * @returns CryptoVerifyReady
+ */
+static getRootAsCryptoVerifyReady(bb:flatbuffers.ByteBuffer, obj?:CryptoVerifyReady):CryptoVerifyReady {
+  return (obj || new CryptoVerifyReady).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns boolean
+ */
+valid():boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+};
+
+/**
+ * @param boolean value
+ * @returns boolean
+ */
+mutate_valid(value:boolean):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startCryptoVerifyReady(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param boolean valid
+ */
+static addValid(builder:flatbuffers.Builder, valid:boolean) {
+  builder.addFieldInt8(0, +valid, +false);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endCryptoVerifyReady(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class CryptoCipher {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns CryptoCipher
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):CryptoCipher {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param CryptoCipher= obj
+ * @returns CryptoCipher
+ */
+static getRootAsCryptoCipher(bb:flatbuffers.ByteBuffer, obj?:CryptoCipher):CryptoCipher {
+  return (obj || new CryptoCipher).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+key():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_key(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+algo():string|null
+algo(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+algo(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @returns boolean
+ */
+decrypt():boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+};
+
+/**
+ * @param boolean value
+ * @returns boolean
+ */
+mutate_decrypt(value:boolean):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+};
+
+/**
+ * @param number index
+ * @returns number
+ */
+iv(index: number):number|null {
+  var offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+};
+
+/**
+ * @returns number
+ */
+ivLength():number {
+  var offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @returns Uint8Array
+ */
+ivArray():Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+};
+
+/**
+ * @param number index
+ * @returns number
+ */
+additionalData(index: number):number|null {
+  var offset = this.bb!.__offset(this.bb_pos, 12);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+};
+
+/**
+ * @returns number
+ */
+additionalDataLength():number {
+  var offset = this.bb!.__offset(this.bb_pos, 12);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @returns Uint8Array
+ */
+additionalDataArray():Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 12);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+};
+
+/**
+ * @returns number
+ */
+tagLength():number {
+  var offset = this.bb!.__offset(this.bb_pos, 14);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 128;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_tag_length(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 14);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startCryptoCipher(builder:flatbuffers.Builder) {
+  builder.startObject(6);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number key
+ */
+static addKey(builder:flatbuffers.Builder, key:number) {
+  builder.addFieldInt32(0, key, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset algoOffset
+ */
+static addAlgo(builder:flatbuffers.Builder, algoOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, algoOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param boolean decrypt
+ */
+static addDecrypt(builder:flatbuffers.Builder, decrypt:boolean) {
+  builder.addFieldInt8(2, +decrypt, +false);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset ivOffset
+ */
+static addIv(builder:flatbuffers.Builder, ivOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(3, ivOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param Array.<number> data
+ * @returns flatbuffers.Offset
+ */
+static createIvVector(builder:flatbuffers.Builder, data:number[] | Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (var i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]);
+  }
+  return builder.endVector();
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number numElems
+ */
+static startIvVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset additionalDataOffset
+ */
+static addAdditionalData(builder:flatbuffers.Builder, additionalDataOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(4, additionalDataOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param Array.<number> data
+ * @returns flatbuffers.Offset
+ */
+static createAdditionalDataVector(builder:flatbuffers.Builder, data:number[] | Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (var i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]);
+  }
+  return builder.endVector();
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number numElems
+ */
+static startAdditionalDataVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number tagLength
+ */
+static addTagLength(builder:flatbuffers.Builder, tagLength:number) {
+  builder.addFieldInt32(5, tagLength, 128);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endCryptoCipher(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class CryptoCipherReady {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns CryptoCipherReady
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):CryptoCipherReady {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param CryptoCipherReady= obj
+ * @returns CryptoCipherReady
+ */
+static getRootAsCryptoCipherReady(bb:flatbuffers.ByteBuffer, obj?:CryptoCipherReady):CryptoCipherReady {
+  return (obj || new CryptoCipherReady).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @param number index
+ * @returns number
+ */
+buffer(index: number):number|null {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+};
+
+/**
+ * @returns number
+ */
+bufferLength():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @returns Uint8Array
+ */
+bufferArray():Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startCryptoCipherReady(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset bufferOffset
+ */
+static addBuffer(builder:flatbuffers.Builder, bufferOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, bufferOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param Array.<number> data
+ * @returns flatbuffers.Offset
+ */
+static createBufferVector(builder:flatbuffers.Builder, data:number[] | Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (var i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]);
+  }
+  return builder.endVector();
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number numElems
+ */
+static startBufferVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endCryptoCipherReady(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class WebSocketConnect {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns WebSocketConnect
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):WebSocketConnect {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param WebSocketConnect= obj
+ * @returns WebSocketConnect
+ */
+static getRootAsWebSocketConnect(bb:flatbuffers.ByteBuffer, obj?:WebSocketConnect):WebSocketConnect {
+  return (obj || new WebSocketConnect).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+id():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_id(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+url():string|null
+url(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+url(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param number index
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array
+ */
+protocols(index: number):string
+protocols(index: number,optionalEncoding:flatbuffers.Encoding):string|Uint8Array
+protocols(index: number,optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__string(this.bb!.__vector(this.bb_pos + offset) + index * 4, optionalEncoding) : null;
+};
+
+/**
+ * @returns number
+ */
+protocolsLength():number {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startWebSocketConnect(builder:flatbuffers.Builder) {
+  builder.startObject(3);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number id
+ */
+static addId(builder:flatbuffers.Builder, id:number) {
+  builder.addFieldInt32(0, id, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset urlOffset
+ */
+static addUrl(builder:flatbuffers.Builder, urlOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, urlOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset protocolsOffset
+ */
+static addProtocols(builder:flatbuffers.Builder, protocolsOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, protocolsOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param Array.<flatbuffers.Offset> data
+ * @returns flatbuffers.Offset
+ */
+static createProtocolsVector(builder:flatbuffers.Builder, data:flatbuffers.Offset[]):flatbuffers.Offset {
+  builder.startVector(4, data.length, 4);
+  for (var i = data.length - 1; i >= 0; i--) {
+    builder.addOffset(data[i]);
+  }
+  return builder.endVector();
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number numElems
+ */
+static startProtocolsVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(4, numElems, 4);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endWebSocketConnect(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class WebSocketConnectReady {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns WebSocketConnectReady
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):WebSocketConnectReady {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param WebSocketConnectReady= obj
+ * @returns WebSocketConnectReady
+ */
+static getRootAsWebSocketConnectReady(bb:flatbuffers.ByteBuffer, obj?:WebSocketConnectReady):WebSocketConnectReady {
+  return (obj || new WebSocketConnectReady).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+protocol():string|null
+protocol(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+protocol(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startWebSocketConnectReady(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset protocolOffset
+ */
+static addProtocol(builder:flatbuffers.Builder, protocolOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, protocolOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endWebSocketConnectReady(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class WebSocketSend {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns WebSocketSend
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):WebSocketSend {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param WebSocketSend= obj
+ * @returns WebSocketSend
+ */
+static getRootAsWebSocketSend(bb:flatbuffers.ByteBuffer, obj?:WebSocketSend):WebSocketSend {
+  return (obj || new WebSocketSend).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+id():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_id(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @returns boolean
+ */
+text():boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+};
+
+/**
+ * @param boolean value
+ * @returns boolean
+ */
+mutate_text(value:boolean):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startWebSocketSend(builder:flatbuffers.Builder) {
+  builder.startObject(2);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number id
+ */
+static addId(builder:flatbuffers.Builder, id:number) {
+  builder.addFieldInt32(0, id, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param boolean text
+ */
+static addText(builder:flatbuffers.Builder, text:boolean) {
+  builder.addFieldInt8(1, +text, +false);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endWebSocketSend(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class WebSocketClose {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns WebSocketClose
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):WebSocketClose {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param WebSocketClose= obj
+ * @returns WebSocketClose
+ */
+static getRootAsWebSocketClose(bb:flatbuffers.ByteBuffer, obj?:WebSocketClose):WebSocketClose {
+  return (obj || new WebSocketClose).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+id():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_id(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @returns number
+ */
+code():number {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint16(this.bb_pos + offset) : 1000;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_code(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint16(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+reason():string|null
+reason(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+reason(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startWebSocketClose(builder:flatbuffers.Builder) {
+  builder.startObject(3);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number id
+ */
+static addId(builder:flatbuffers.Builder, id:number) {
+  builder.addFieldInt32(0, id, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number code
+ */
+static addCode(builder:flatbuffers.Builder, code:number) {
+  builder.addFieldInt16(1, code, 1000);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset reasonOffset
+ */
+static addReason(builder:flatbuffers.Builder, reasonOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, reasonOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endWebSocketClose(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class FsWrite {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns FsWrite
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):FsWrite {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param FsWrite= obj
+ * @returns FsWrite
+ */
+static getRootAsFsWrite(bb:flatbuffers.ByteBuffer, obj?:FsWrite):FsWrite {
+  return (obj || new FsWrite).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+path():string|null
+path(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+path(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startFsWrite(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset pathOffset
+ */
+static addPath(builder:flatbuffers.Builder, pathOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, pathOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endFsWrite(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class FsDelete {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns FsDelete
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):FsDelete {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param FsDelete= obj
+ * @returns FsDelete
+ */
+static getRootAsFsDelete(bb:flatbuffers.ByteBuffer, obj?:FsDelete):FsDelete {
+  return (obj || new FsDelete).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+path():string|null
+path(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+path(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startFsDelete(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset pathOffset
+ */
+static addPath(builder:flatbuffers.Builder, pathOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, pathOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endFsDelete(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class FsStat {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns FsStat
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):FsStat {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param FsStat= obj
+ * @returns FsStat
+ */
+static getRootAsFsStat(bb:flatbuffers.ByteBuffer, obj?:FsStat):FsStat {
+  return (obj || new FsStat).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+path():string|null
+path(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+path(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startFsStat(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset pathOffset
+ */
+static addPath(builder:flatbuffers.Builder, pathOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, pathOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endFsStat(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class FsStatReady {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns FsStatReady
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):FsStatReady {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param FsStatReady= obj
+ * @returns FsStatReady
+ */
+static getRootAsFsStatReady(bb:flatbuffers.ByteBuffer, obj?:FsStatReady):FsStatReady {
+  return (obj || new FsStatReady).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns flatbuffers.Long
+ */
+size():flatbuffers.Long {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint64(this.bb_pos + offset) : this.bb!.createLong(0, 0);
+};
+
+/**
+ * @param flatbuffers.Long value
+ * @returns boolean
+ */
+mutate_size(value:flatbuffers.Long):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint64(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @returns flatbuffers.Long
+ */
+modified():flatbuffers.Long {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint64(this.bb_pos + offset) : this.bb!.createLong(0, 0);
+};
+
+/**
+ * @param flatbuffers.Long value
+ * @returns boolean
+ */
+mutate_modified(value:flatbuffers.Long):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint64(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+contentType():string|null
+contentType(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+contentType(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startFsStatReady(builder:flatbuffers.Builder) {
+  builder.startObject(3);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Long size
+ */
+static addSize(builder:flatbuffers.Builder, size:flatbuffers.Long) {
+  builder.addFieldInt64(0, size, builder.createLong(0, 0));
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Long modified
+ */
+static addModified(builder:flatbuffers.Builder, modified:flatbuffers.Long) {
+  builder.addFieldInt64(1, modified, builder.createLong(0, 0));
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset contentTypeOffset
+ */
+static addContentType(builder:flatbuffers.Builder, contentTypeOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, contentTypeOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endFsStatReady(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class FsList {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns FsList
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):FsList {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param FsList= obj
+ * @returns FsList
+ */
+static getRootAsFsList(bb:flatbuffers.ByteBuffer, obj?:FsList):FsList {
+  return (obj || new FsList).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+prefix():string|null
+prefix(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+prefix(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startFsList(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset prefixOffset
+ */
+static addPrefix(builder:flatbuffers.Builder, prefixOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, prefixOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endFsList(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class FsListReady {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns FsListReady
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):FsListReady {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param FsListReady= obj
+ * @returns FsListReady
+ */
+static getRootAsFsListReady(bb:flatbuffers.ByteBuffer, obj?:FsListReady):FsListReady {
+  return (obj || new FsListReady).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @param number index
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array
+ */
+paths(index: number):string
+paths(index: number,optionalEncoding:flatbuffers.Encoding):string|Uint8Array
+paths(index: number,optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb!.__vector(this.bb_pos + offset) + index * 4, optionalEncoding) : null;
+};
+
+/**
+ * @returns number
+ */
+pathsLength():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startFsListReady(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset pathsOffset
+ */
+static addPaths(builder:flatbuffers.Builder, pathsOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, pathsOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param Array.<flatbuffers.Offset> data
+ * @returns flatbuffers.Offset
+ */
+static createPathsVector(builder:flatbuffers.Builder, data:flatbuffers.Offset[]):flatbuffers.Offset {
+  builder.startVector(4, data.length, 4);
+  for (var i = data.length - 1; i >= 0; i--) {
+    builder.addOffset(data[i]);
+  }
+  return builder.endVector();
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number numElems
+ */
+static startPathsVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(4, numElems, 4);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endFsListReady(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class Base {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns Base
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):Base {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param Base= obj
+ * @returns Base
+ */
+static getRootAsBase(bb:flatbuffers.ByteBuffer, obj?:Base):Base {
+  return (obj || new Base).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+cmdId():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_cmd_id(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @returns boolean
+ */
+sync():boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+};
+
+/**
+ * @param boolean value
+ * @returns boolean
+ */
+mutate_sync(value:boolean):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+};
+
+/**
+ * @returns ErrorKind
+ */
+errorKind():ErrorKind {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? /**  */ (this.bb!.readInt8(this.bb_pos + offset)) : ErrorKind.NoError;
+};
+
+/**
+ * @param ErrorKind value
+ * @returns boolean
+ */
+mutate_error_kind(value:ErrorKind):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+error():string|null
+error(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+error(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @returns Any
+ */
+msgType():Any {
+  var offset = this.bb!.__offset(this.bb_pos, 12);
+  return offset ? /**  */ (this.bb!.readUint8(this.bb_pos + offset)) : Any.NONE;
+};
+
+/**
+ * @param Any value
+ * @returns boolean
+ */
+mutate_msg_type(value:Any):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 12);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint8(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Table obj
+ * @returns ?flatbuffers.Table
+ */
+msg<T extends flatbuffers.Table>(obj:T):T|null {
+  var offset = this.bb!.__offset(this.bb_pos, 14);
+  return offset ? this.bb!.__union(obj, this.bb_pos + offset) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startBase(builder:flatbuffers.Builder) {
+  builder.startObject(6);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number cmdId
+ */
+static addCmdId(builder:flatbuffers.Builder, cmdId:number) {
+  builder.addFieldInt32(0, cmdId, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param boolean sync
+ */
+static addSync(builder:flatbuffers.Builder, sync:boolean) {
+  builder.addFieldInt8(1, +sync, +false);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param ErrorKind errorKind
+ */
+static addErrorKind(builder:flatbuffers.Builder, errorKind:ErrorKind) {
+  builder.addFieldInt8(2, errorKind, ErrorKind.NoError);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset errorOffset
+ */
+static addError(builder:flatbuffers.Builder, errorOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(3, errorOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param Any msgType
+ */
+static addMsgType(builder:flatbuffers.Builder, msgType:Any) {
+  builder.addFieldInt8(4, msgType, Any.NONE);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset msgOffset
+ */
+static addMsg(builder:flatbuffers.Builder, msgOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(5, msgOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endBase(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset offset
+ */
+static finishBaseBuffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset);
+};
+
+}
+/**
+ * @constructor
+ */
+export class TimerStart {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns TimerStart
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):TimerStart {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param TimerStart= obj
+ * @returns TimerStart
+ */
+static getRootAsTimerStart(bb:flatbuffers.ByteBuffer, obj?:TimerStart):TimerStart {
+  return (obj || new TimerStart).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+id():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_id(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @returns number
+ */
+delay():number {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_delay(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startTimerStart(builder:flatbuffers.Builder) {
+  builder.startObject(2);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number id
+ */
+static addId(builder:flatbuffers.Builder, id:number) {
+  builder.addFieldInt32(0, id, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number delay
+ */
+static addDelay(builder:flatbuffers.Builder, delay:number) {
+  builder.addFieldInt32(1, delay, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endTimerStart(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class TimerReady {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns TimerReady
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):TimerReady {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param TimerReady= obj
+ * @returns TimerReady
+ */
+static getRootAsTimerReady(bb:flatbuffers.ByteBuffer, obj?:TimerReady):TimerReady {
+  return (obj || new TimerReady).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+id():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_id(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @returns boolean
+ */
+canceled():boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+};
+
+/**
+ * @param boolean value
+ * @returns boolean
+ */
+mutate_canceled(value:boolean):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startTimerReady(builder:flatbuffers.Builder) {
+  builder.startObject(2);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number id
+ */
+static addId(builder:flatbuffers.Builder, id:number) {
+  builder.addFieldInt32(0, id, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param boolean canceled
+ */
+static addCanceled(builder:flatbuffers.Builder, canceled:boolean) {
+  builder.addFieldInt8(1, +canceled, +false);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endTimerReady(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class TimerClear {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns TimerClear
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):TimerClear {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param TimerClear= obj
+ * @returns TimerClear
+ */
+static getRootAsTimerClear(bb:flatbuffers.ByteBuffer, obj?:TimerClear):TimerClear {
+  return (obj || new TimerClear).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+id():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_id(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startTimerClear(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number id
+ */
+static addId(builder:flatbuffers.Builder, id:number) {
+  builder.addFieldInt32(0, id, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endTimerClear(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class HttpHeader {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns HttpHeader
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):HttpHeader {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param HttpHeader= obj
+ * @returns HttpHeader
+ */
+static getRootAsHttpHeader(bb:flatbuffers.ByteBuffer, obj?:HttpHeader):HttpHeader {
+  return (obj || new HttpHeader).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+key():string|null
+key(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+key(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+value():string|null
+value(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+value(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startHttpHeader(builder:flatbuffers.Builder) {
+  builder.startObject(2);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset keyOffset
+ */
+static addKey(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, keyOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset valueOffset
+ */
+static addValue(builder:flatbuffers.Builder, valueOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, valueOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endHttpHeader(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class StreamChunk {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns StreamChunk
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):StreamChunk {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param StreamChunk= obj
+ * @returns StreamChunk
+ */
+static getRootAsStreamChunk(bb:flatbuffers.ByteBuffer, obj?:StreamChunk):StreamChunk {
+  return (obj || new StreamChunk).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+id():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_id(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
     return false;
   }
 
@@ -5000,11 +7132,57 @@ mutate_id(value:number):boolean {
   return true;
 };
 
+/**
+ * @returns boolean
+ */
+done():boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+};
+
+/**
+ * @param boolean value
+ * @returns boolean
+ */
+mutate_done(value:boolean):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+};
+
+/**
+ * @returns boolean
+ */
+text():boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+};
+
+/**
+ * @param boolean value
+ * @returns boolean
+ */
+mutate_text(value:boolean):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 8);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+};
+
 /**
  * @param flatbuffers.Builder builder
  */
-static startTimerClear(builder:flatbuffers.Builder) {
-  builder.startObject(1);
+static startStreamChunk(builder:flatbuffers.Builder) {
+  builder.startObject(3);
 };
 
 /**
@@ -5015,11 +7193,27 @@ static addId(builder:flatbuffers.Builder, id:number) {
   builder.addFieldInt32(0, id, 0);
 };
 
+/**
+ * @param flatbuffers.Builder builder
+ * @param boolean done
+ */
+static addDone(builder:flatbuffers.Builder, done:boolean) {
+  builder.addFieldInt8(1, +done, +false);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param boolean text
+ */
+static addText(builder:flatbuffers.Builder, text:boolean) {
+  builder.addFieldInt8(2, +text, +false);
+};
+
 /**
  * @param flatbuffers.Builder builder
  * @returns flatbuffers.Offset
  */
-static endTimerClear(builder:flatbuffers.Builder):flatbuffers.Offset {
+static endStreamChunk(builder:flatbuffers.Builder):flatbuffers.Offset {
   var offset = builder.endObject();
   return offset;
 };
@@ -5028,16 +7222,16 @@ static endTimerClear(builder:flatbuffers.Builder):flatbuffers.Offset {
 /**
  * @constructor
  */
-export class HttpHeader {
+export class StreamPull {
   bb: flatbuffers.ByteBuffer|null = null;
 
   bb_pos:number = 0;
 /**
  * @param number i
  * @param flatbuffers.ByteBuffer bb
- * @returns HttpHeader
+ * @returns StreamPull
  */
-__init(i:number, bb:flatbuffers.ByteBuffer):HttpHeader {
+__init(i:number, bb:flatbuffers.ByteBuffer):StreamPull {
   this.bb_pos = i;
   this.bb = bb;
   return this;
@@ -5045,63 +7239,237 @@ __init(i:number, bb:flatbuffers.ByteBuffer):HttpHeader {
 
 /**
  * @param flatbuffers.ByteBuffer bb
- * @param HttpHeader= obj
- * @returns HttpHeader
+ * @param StreamPull= obj
+ * @returns StreamPull
  */
-static getRootAsHttpHeader(bb:flatbuffers.ByteBuffer, obj?:HttpHeader):HttpHeader {
-  return (obj || new HttpHeader).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+static getRootAsStreamPull(bb:flatbuffers.ByteBuffer, obj?:StreamPull):StreamPull {
+  return (obj || new StreamPull).__init(bb.readInt32(bb.position()) + bb.position(), bb);
 };
 
 /**
- * @param flatbuffers.Encoding= optionalEncoding
- * @returns string|Uint8Array|null
+ * @returns number
  */
-key():string|null
-key(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
-key(optionalEncoding?:any):string|Uint8Array|null {
+id():number {
   var offset = this.bb!.__offset(this.bb_pos, 4);
-  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
 };
 
 /**
- * @param flatbuffers.Encoding= optionalEncoding
- * @returns string|Uint8Array|null
+ * @param number value
+ * @returns boolean
  */
-value():string|null
-value(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
-value(optionalEncoding?:any):string|Uint8Array|null {
+mutate_id(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @returns boolean
+ */
+cancel():boolean {
   var offset = this.bb!.__offset(this.bb_pos, 6);
-  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+};
+
+/**
+ * @param boolean value
+ * @returns boolean
+ */
+mutate_cancel(value:boolean):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 6);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startStreamPull(builder:flatbuffers.Builder) {
+  builder.startObject(2);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number id
+ */
+static addId(builder:flatbuffers.Builder, id:number) {
+  builder.addFieldInt32(0, id, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param boolean cancel
+ */
+static addCancel(builder:flatbuffers.Builder, cancel:boolean) {
+  builder.addFieldInt8(1, +cancel, +false);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endStreamPull(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class StreamCancel {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns StreamCancel
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):StreamCancel {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param StreamCancel= obj
+ * @returns StreamCancel
+ */
+static getRootAsStreamCancel(bb:flatbuffers.ByteBuffer, obj?:StreamCancel):StreamCancel {
+  return (obj || new StreamCancel).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+id():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_id(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ */
+static startStreamCancel(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number id
+ */
+static addId(builder:flatbuffers.Builder, id:number) {
+  builder.addFieldInt32(0, id, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @returns flatbuffers.Offset
+ */
+static endStreamCancel(builder:flatbuffers.Builder):flatbuffers.Offset {
+  var offset = builder.endObject();
+  return offset;
+};
+
+}
+/**
+ * @constructor
+ */
+export class HttpRequestAbort {
+  bb: flatbuffers.ByteBuffer|null = null;
+
+  bb_pos:number = 0;
+/**
+ * @param number i
+ * @param flatbuffers.ByteBuffer bb
+ * @returns HttpRequestAbort
+ */
+__init(i:number, bb:flatbuffers.ByteBuffer):HttpRequestAbort {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+};
+
+/**
+ * @param flatbuffers.ByteBuffer bb
+ * @param HttpRequestAbort= obj
+ * @returns HttpRequestAbort
+ */
+static getRootAsHttpRequestAbort(bb:flatbuffers.ByteBuffer, obj?:HttpRequestAbort):HttpRequestAbort {
+  return (obj || new HttpRequestAbort).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+};
+
+/**
+ * @returns number
+ */
+id():number {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
 };
 
 /**
- * @param flatbuffers.Builder builder
+ * @param number value
+ * @returns boolean
  */
-static startHttpHeader(builder:flatbuffers.Builder) {
-  builder.startObject(2);
+mutate_id(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
 };
 
 /**
  * @param flatbuffers.Builder builder
- * @param flatbuffers.Offset keyOffset
  */
-static addKey(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset) {
-  builder.addFieldOffset(0, keyOffset, 0);
+static startHttpRequestAbort(builder:flatbuffers.Builder) {
+  builder.startObject(1);
 };
 
 /**
  * @param flatbuffers.Builder builder
- * @param flatbuffers.Offset valueOffset
+ * @param number id
  */
-static addValue(builder:flatbuffers.Builder, valueOffset:flatbuffers.Offset) {
-  builder.addFieldOffset(1, valueOffset, 0);
+static addId(builder:flatbuffers.Builder, id:number) {
+  builder.addFieldInt32(0, id, 0);
 };
 
 /**
  * @param flatbuffers.Builder builder
  * @returns flatbuffers.Offset
  */
-static endHttpHeader(builder:flatbuffers.Builder):flatbuffers.Offset {
+static endHttpRequestAbort(builder:flatbuffers.Builder):flatbuffers.Offset {
   var offset = builder.endObject();
   return offset;
 };
@@ -5110,16 +7478,16 @@ static endHttpHeader(builder:flatbuffers.Builder):flatbuffers.Offset {
 /**
  * @constructor
  */
-export class StreamChunk {
+export class FetchAbort {
   bb: flatbuffers.ByteBuffer|null = null;
 
   bb_pos:number = 0;
 /**
  * @param number i
  * @param flatbuffers.ByteBuffer bb
- * @returns StreamChunk
+ * @returns FetchAbort
  */
-__init(i:number, bb:flatbuffers.ByteBuffer):StreamChunk {
+__init(i:number, bb:flatbuffers.ByteBuffer):FetchAbort {
   this.bb_pos = i;
   this.bb = bb;
   return this;
@@ -5127,11 +7495,11 @@ __init(i:number, bb:flatbuffers.ByteBuffer):StreamChunk {
 
 /**
  * @param flatbuffers.ByteBuffer bb
- * @param StreamChunk= obj
- * @returns StreamChunk
+ * @param FetchAbort= obj
+ * @returns FetchAbort
  */
-static getRootAsStreamChunk(bb:flatbuffers.ByteBuffer, obj?:StreamChunk):StreamChunk {
-  return (obj || new StreamChunk).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+static getRootAsFetchAbort(bb:flatbuffers.ByteBuffer, obj?:FetchAbort):FetchAbort {
+  return (obj || new FetchAbort).__init(bb.readInt32(bb.position()) + bb.position(), bb);
 };
 
 /**
@@ -5157,34 +7525,11 @@ mutate_id(value:number):boolean {
   return true;
 };
 
-/**
- * @returns boolean
- */
-done():boolean {
-  var offset = this.bb!.__offset(this.bb_pos, 6);
-  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
-};
-
-/**
- * @param boolean value
- * @returns boolean
- */
-mutate_done(value:boolean):boolean {
-  var offset = this.bb!.__offset(this.bb_pos, 6);
-
-  if (offset === 0) {
-    return false;
-  }
-
-  this.bb!.writeInt8(this.bb_pos + offset, +value);
-  return true;
-};
-
 /**
  * @param flatbuffers.Builder builder
  */
-static startStreamChunk(builder:flatbuffers.Builder) {
-  builder.startObject(2);
+static startFetchAbort(builder:flatbuffers.Builder) {
+  builder.startObject(1);
 };
 
 /**
@@ -5195,19 +7540,11 @@ static addId(builder:flatbuffers.Builder, id:number) {
   builder.addFieldInt32(0, id, 0);
 };
 
-/**
- * @param flatbuffers.Builder builder
- * @param boolean done
- */
-static addDone(builder:flatbuffers.Builder, done:boolean) {
-  builder.addFieldInt8(1, +done, +false);
-};
-
 /**
  * @param flatbuffers.Builder builder
  * @returns flatbuffers.Offset
  */
-static endStreamChunk(builder:flatbuffers.Builder):flatbuffers.Offset {
+static endFetchAbort(builder:flatbuffers.Builder):flatbuffers.Offset {
   var offset = builder.endObject();
   return offset;
 };
@@ -5349,11 +7686,114 @@ mutate_has_body(value:boolean):boolean {
   return true;
 };
 
+/**
+ * @returns number
+ */
+timeoutMs():number {
+  var offset = this.bb!.__offset(this.bb_pos, 16);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_timeout_ms(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 16);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @returns RedirectMode
+ */
+redirect():RedirectMode {
+  var offset = this.bb!.__offset(this.bb_pos, 18);
+  return offset ? /**  */ (this.bb!.readInt8(this.bb_pos + offset)) : RedirectMode.Follow;
+};
+
+/**
+ * @param RedirectMode value
+ * @returns boolean
+ */
+mutate_redirect(value:RedirectMode):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 18);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @returns number
+ */
+maxRedirects():number {
+  var offset = this.bb!.__offset(this.bb_pos, 20);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 20;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_max_redirects(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 20);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @returns number
+ */
+retries():number {
+  var offset = this.bb!.__offset(this.bb_pos, 22);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+};
+
+/**
+ * @param number value
+ * @returns boolean
+ */
+mutate_retries(value:number):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 22);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint32(this.bb_pos + offset, value);
+  return true;
+};
+
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+clientIdentity():string|null
+clientIdentity(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+clientIdentity(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 24);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
 /**
  * @param flatbuffers.Builder builder
  */
 static startHttpRequest(builder:flatbuffers.Builder) {
-  builder.startObject(6);
+  builder.startObject(11);
 };
 
 /**
@@ -5425,6 +7865,46 @@ static addHasBody(builder:flatbuffers.Builder, hasBody:boolean) {
   builder.addFieldInt8(5, +hasBody, +false);
 };
 
+/**
+ * @param flatbuffers.Builder builder
+ * @param number timeoutMs
+ */
+static addTimeoutMs(builder:flatbuffers.Builder, timeoutMs:number) {
+  builder.addFieldInt32(6, timeoutMs, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param RedirectMode redirect
+ */
+static addRedirect(builder:flatbuffers.Builder, redirect:RedirectMode) {
+  builder.addFieldInt8(7, redirect, RedirectMode.Follow);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number maxRedirects
+ */
+static addMaxRedirects(builder:flatbuffers.Builder, maxRedirects:number) {
+  builder.addFieldInt32(8, maxRedirects, 20);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param number retries
+ */
+static addRetries(builder:flatbuffers.Builder, retries:number) {
+  builder.addFieldInt32(9, retries, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset clientIdentityOffset
+ */
+static addClientIdentity(builder:flatbuffers.Builder, clientIdentityOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(10, clientIdentityOffset, 0);
+};
+
 /**
  * @param flatbuffers.Builder builder
  * @returns flatbuffers.Offset
@@ -5549,11 +8029,34 @@ mutate_has_body(value:boolean):boolean {
   return true;
 };
 
+/**
+ * @returns boolean
+ */
+websocket():boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 12);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+};
+
+/**
+ * @param boolean value
+ * @returns boolean
+ */
+mutate_websocket(value:boolean):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 12);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+};
+
 /**
  * @param flatbuffers.Builder builder
  */
 static startHttpResponse(builder:flatbuffers.Builder) {
-  builder.startObject(4);
+  builder.startObject(5);
 };
 
 /**
@@ -5609,6 +8112,14 @@ static addHasBody(builder:flatbuffers.Builder, hasBody:boolean) {
   builder.addFieldInt8(3, +hasBody, +false);
 };
 
+/**
+ * @param flatbuffers.Builder builder
+ * @param boolean websocket
+ */
+static addWebsocket(builder:flatbuffers.Builder, websocket:boolean) {
+  builder.addFieldInt8(4, +websocket, +false);
+};
+
 /**
  * @param flatbuffers.Builder builder
  * @returns flatbuffers.Offset
@@ -5808,11 +8319,45 @@ mutate_has_body(value:boolean):boolean {
   return true;
 };
 
+/**
+ * @param flatbuffers.Encoding= optionalEncoding
+ * @returns string|Uint8Array|null
+ */
+url():string|null
+url(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+url(optionalEncoding?:any):string|Uint8Array|null {
+  var offset = this.bb!.__offset(this.bb_pos, 12);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+};
+
+/**
+ * @returns boolean
+ */
+redirected():boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 14);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+};
+
+/**
+ * @param boolean value
+ * @returns boolean
+ */
+mutate_redirected(value:boolean):boolean {
+  var offset = this.bb!.__offset(this.bb_pos, 14);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+};
+
 /**
  * @param flatbuffers.Builder builder
  */
 static startFetchHttpResponse(builder:flatbuffers.Builder) {
-  builder.startObject(4);
+  builder.startObject(6);
 };
 
 /**
@@ -5868,6 +8413,22 @@ static addHasBody(builder:flatbuffers.Builder, hasBody:boolean) {
   builder.addFieldInt8(3, +hasBody, +false);
 };
 
+/**
+ * @param flatbuffers.Builder builder
+ * @param flatbuffers.Offset urlOffset
+ */
+static addUrl(builder:flatbuffers.Builder, urlOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(4, urlOffset, 0);
+};
+
+/**
+ * @param flatbuffers.Builder builder
+ * @param boolean redirected
+ */
+static addRedirected(builder:flatbuffers.Builder, redirected:boolean) {
+  builder.addFieldInt8(5, +redirected, +false);
+};
+
 /**
  * @param flatbuffers.Builder builder
  * @returns flatbuffers.Offset
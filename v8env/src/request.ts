@@ -5,6 +5,7 @@ import CookieJar from './cookie_jar'
 import FlyBody from './body_mixin'
 import { Request, Headers, RequestMode, RequestCredentials, RequestCache, RequestDestination, RequestRedirect, ReferrerPolicy, AbortSignal, RequestInit, HeadersInit } from "./dom_types"
 import { FlyHeaders } from './headers';
+import { FlyAbortController } from './abort';
 
 function byteUpperCase(s) {
 	return String(s)
@@ -91,6 +92,9 @@ export class FlyRequest extends FlyBody implements Request {
 		// readonly attribute RequestCredentials credentials;
 		this.credentials = 'omit';
 
+		// readonly attribute RequestRedirect redirect;
+		this.redirect = 'follow';
+
 		if (input instanceof FlyRequest) {
 			if (input.bodyUsed) throw TypeError();
 			this.method = input.method;
@@ -101,6 +105,8 @@ export class FlyRequest extends FlyBody implements Request {
 			this.remoteAddr = input.remoteAddr;
 			this.referrer = input.referrer;
 			this.mode = input.mode;
+			this.redirect = input.redirect;
+			this.signal = input.signal;
 		} else {
 			this.headers = new FlyHeaders({})
 			this.url = <string>input
@@ -127,6 +133,17 @@ export class FlyRequest extends FlyBody implements Request {
 		if ('credentials' in init &&
 			(['omit', 'same-origin', 'include'].indexOf(init.credentials) !== -1))
 			this.credentials = init.credentials;
+
+		if ('redirect' in init) {
+			if (['follow', 'error', 'manual'].indexOf(init.redirect) === -1)
+				throw TypeError(`invalid redirect mode: ${init.redirect}`)
+			this.redirect = init.redirect;
+		}
+
+		if (init.signal)
+			this.signal = init.signal;
+		if (!this.signal)
+			this.signal = <AbortSignal><any>new FlyAbortController().signal;
 	}
 
 	get cookies() {
@@ -159,7 +176,8 @@ export class FlyRequest extends FlyBody implements Request {
 			remoteAddr: this.remoteAddr,
 			method: this.method,
 			headers: headersList,
-			credentials: this.credentials
+			credentials: this.credentials,
+			redirect: this.redirect
 		})
 		return cloned
 	}
@@ -6,6 +6,12 @@ import FlyBody from './body_mixin'
 import { FlyHeaders } from './headers';
 import { Response, Headers, ResponseType, ResponseInit, BodyInit } from './dom_types';
 import { ReadableStream } from '@stardazed/streams';
+import { FlyWebSocket } from './websocket';
+
+export interface FlyResponseInit extends ResponseInit {
+	/** The client end of a WebSocketPair, accepting a websocket upgrade. */
+	webSocket?: FlyWebSocket
+}
 
 function ushort(x) { return x & 0xFFFF; }
 
@@ -21,6 +27,7 @@ export class FlyResponse extends FlyBody implements Response {
 	type: ResponseType
 	redirected: boolean
 	trailer: Promise<Headers>
+	webSocket: FlyWebSocket | null
 	private cookieJar: CookieJar
 
 	static redirect(url, status = 302) {
@@ -32,7 +39,7 @@ export class FlyResponse extends FlyBody implements Response {
 		})
 	}
 
-	constructor(body: BodyInit, init: ResponseInit | FlyResponse) {
+	constructor(body: BodyInit, init: FlyResponseInit | FlyResponse) {
 		if (arguments.length < 1)
 			body = '';
 
@@ -59,7 +66,10 @@ export class FlyResponse extends FlyBody implements Response {
 
 		// readonly attribute unsigned short status;
 		var status = 'status' in init ? ushort(init.status) : 200;
-		if (status < 200 || status > 599) throw RangeError();
+		this.webSocket = init.webSocket || null;
+		// 101 only makes sense when accepting a websocket
+		if (status === 101 && !this.webSocket) throw RangeError();
+		if ((status < 200 && status !== 101) || status > 599) throw RangeError();
 
 		/**
 		 * @public
@@ -0,0 +1,257 @@
+/**
+ * @module fly
+ * @private
+ */
+import * as flatbuffers from "./flatbuffers";
+import * as fbs from "./msg_generated";
+import { libfly } from "./libfly";
+import { sendAsync, sendStreamPull, setEventListener, streams } from "./bridge";
+import { FlyBlob, blobBytes } from "./blob";
+
+export type BinaryType = "blob" | "arraybuffer";
+
+export interface WebSocketEvent {
+  type: string;
+  target: FlyWebSocket;
+}
+
+export interface MessageEvent extends WebSocketEvent {
+  data: string | ArrayBuffer | FlyBlob;
+}
+
+export interface CloseEvent extends WebSocketEvent {
+  code: number;
+  reason: string;
+  wasClean: boolean;
+}
+
+type Listener = (event: WebSocketEvent) => void;
+
+const sockets = new Map<number, FlyWebSocket>();
+// lets WebSocketPair build sockets that don't connect anywhere
+const pairToken = Symbol("pair");
+
+setEventListener(fbs.Any.WebSocketClose, (base: fbs.Base) => {
+  const msg = new fbs.WebSocketClose();
+  base.msg(msg);
+  const socket = sockets.get(msg.id());
+  if (socket)
+    socket._closed(msg.code(), msg.reason() || "");
+});
+
+/**
+ * An outbound WebSocket, following the browser API. Messages arrive from
+ * Rust as stream chunks under the socket's id.
+ */
+export class FlyWebSocket {
+  static readonly CONNECTING = 0;
+  static readonly OPEN = 1;
+  static readonly CLOSING = 2;
+  static readonly CLOSED = 3;
+
+  readonly CONNECTING = 0;
+  readonly OPEN = 1;
+  readonly CLOSING = 2;
+  readonly CLOSED = 3;
+
+  readonly url: string;
+  readyState: number = FlyWebSocket.CONNECTING;
+  protocol: string = "";
+  binaryType: BinaryType = "blob";
+
+  onopen: Listener | null = null;
+  onmessage: ((event: MessageEvent) => void) | null = null;
+  onerror: Listener | null = null;
+  onclose: ((event: CloseEvent) => void) | null = null;
+
+  private id: number;
+  // the other end of a WebSocketPair
+  private peer: FlyWebSocket | null = null;
+  private readonly listeners = new Map<string, Listener[]>();
+  // close() called before the connection opened
+  private pendingClose: [number, string] | null = null;
+
+  constructor(url: string, protocols?: string | string[], token?: typeof pairToken) {
+    if (token === pairToken) {
+      this.url = url;
+      return;
+    }
+    const parsed = new URL(url);
+    if (parsed.protocol !== "ws:" && parsed.protocol !== "wss:")
+      throw new SyntaxError(`invalid websocket url: ${url}`);
+    this.url = parsed.href;
+    if (typeof protocols === "string")
+      protocols = [protocols];
+
+    this.register(libfly.getNextStreamId());
+
+    const fbb = flatbuffers.createBuilder();
+    const urlStr = fbb.createString(this.url);
+    const fbbProtocols = fbs.WebSocketConnect.createProtocolsVector(
+      fbb, (protocols || []).map(p => fbb.createString(p)));
+    fbs.WebSocketConnect.startWebSocketConnect(fbb);
+    fbs.WebSocketConnect.addId(fbb, this.id);
+    fbs.WebSocketConnect.addUrl(fbb, urlStr);
+    fbs.WebSocketConnect.addProtocols(fbb, fbbProtocols);
+    sendAsync(fbb, fbs.Any.WebSocketConnect, fbs.WebSocketConnect.endWebSocketConnect(fbb)).then(base => {
+      const msg = new fbs.WebSocketConnectReady();
+      base.msg(msg);
+      this.protocol = msg.protocol() || "";
+      if (this.pendingClose) {
+        this.sendClose(this.pendingClose[0], this.pendingClose[1]);
+        return;
+      }
+      this.readyState = FlyWebSocket.OPEN;
+      this.dispatch("open", {});
+    }).catch(err => {
+      console.error("websocket connection failed:", err.message);
+      this.dispatch("error", {});
+      this._closed(1006, "");
+    });
+  }
+
+  send(data: string | ArrayBuffer | ArrayBufferView | FlyBlob) {
+    if (this.readyState === FlyWebSocket.CONNECTING)
+      throw new Error("InvalidStateError: websocket is still connecting");
+    if (this.readyState !== FlyWebSocket.OPEN)
+      return;
+
+    let raw: BufferSource;
+    const text = typeof data === "string";
+    if (typeof data === "string")
+      raw = new TextEncoder().encode(data);
+    else if (data instanceof FlyBlob)
+      raw = blobBytes(data);
+    else
+      raw = data;
+
+    const fbb = flatbuffers.createBuilder();
+    fbs.WebSocketSend.startWebSocketSend(fbb);
+    fbs.WebSocketSend.addId(fbb, this.id);
+    fbs.WebSocketSend.addText(fbb, text);
+    sendAsync(fbb, fbs.Any.WebSocketSend, fbs.WebSocketSend.endWebSocketSend(fbb), raw)
+      .catch(err => this.dispatch("error", { message: err.message }));
+  }
+
+  close(code: number = 1000, reason: string = "") {
+    if (code !== 1000 && (code < 3000 || code > 4999))
+      throw new RangeError(`invalid close code: ${code}`);
+    if (this.readyState === FlyWebSocket.CLOSING || this.readyState === FlyWebSocket.CLOSED)
+      return;
+    if (this.readyState === FlyWebSocket.CONNECTING)
+      this.pendingClose = [code, reason];
+    else
+      this.sendClose(code, reason);
+    this.readyState = FlyWebSocket.CLOSING;
+  }
+
+  private sendClose(code: number, reason: string) {
+    const fbb = flatbuffers.createBuilder();
+    const reasonStr = fbb.createString(reason);
+    fbs.WebSocketClose.startWebSocketClose(fbb);
+    fbs.WebSocketClose.addId(fbb, this.id);
+    fbs.WebSocketClose.addCode(fbb, code);
+    fbs.WebSocketClose.addReason(fbb, reasonStr);
+    sendAsync(fbb, fbs.Any.WebSocketClose, fbs.WebSocketClose.endWebSocketClose(fbb))
+      .catch(err => console.error("error closing websocket:", err.message));
+  }
+
+  addEventListener(type: string, fn: Listener) {
+    const fns = this.listeners.get(type) || [];
+    fns.push(fn);
+    this.listeners.set(type, fns);
+  }
+
+  removeEventListener(type: string, fn: Listener) {
+    const fns = this.listeners.get(type);
+    if (fns)
+      this.listeners.set(type, fns.filter(f => f !== fn));
+  }
+
+  /** @internal */
+  static _pair(): [FlyWebSocket, FlyWebSocket] {
+    const client = new FlyWebSocket("", undefined, pairToken);
+    const server = new FlyWebSocket("", undefined, pairToken);
+    client.peer = server;
+    return [client, server];
+  }
+
+  /**
+   * @internal Called once the response carrying the client end of a pair
+   * went out, the server end then talks over the request's connection.
+   */
+  _accept(id: number) {
+    const server = this.peer;
+    if (!server || server.id !== undefined)
+      throw new Error("websocket was already used in a response");
+    server.register(id);
+    if (server.pendingClose) {
+      server.sendClose(server.pendingClose[0], server.pendingClose[1]);
+      return;
+    }
+    server.readyState = FlyWebSocket.OPEN;
+    Promise.resolve().then(() => server.dispatch("open", {}));
+  }
+
+  /** @internal Called once Rust reports the connection is gone. */
+  _closed(code: number, reason: string) {
+    if (this.readyState === FlyWebSocket.CLOSED)
+      return;
+    this.readyState = FlyWebSocket.CLOSED;
+    sockets.delete(this.id);
+    streams.delete(this.id);
+    sendStreamPull(this.id, true);
+    this.dispatch("close", { code, reason, wasClean: code !== 1006 });
+  }
+
+  // Rust reads one message per credit, so a socket the app can't keep up
+  // with stops being read instead of piling up messages.
+  private register(id: number) {
+    this.id = id;
+    sockets.set(id, this);
+    streams.set(id, (chunk: fbs.StreamChunk, raw: Uint8Array) => {
+      if (this.readyState === FlyWebSocket.OPEN)
+        this.dispatch("message", { data: this.messageData(chunk.text(), raw) });
+      sendStreamPull(id, false);
+    });
+    sendStreamPull(id, false);
+  }
+
+  private messageData(text: boolean, raw: Uint8Array): string | ArrayBuffer | FlyBlob {
+    const bytes = raw || new Uint8Array(0);
+    if (text)
+      return new TextDecoder("utf-8").decode(bytes);
+    // raw points at memory Rust reclaims once the chunk is handled
+    const copy = bytes.slice().buffer;
+    return this.binaryType === "arraybuffer" ? copy : new FlyBlob([copy]);
+  }
+
+  private dispatch(type: string, init: object) {
+    const event = Object.assign({ type, target: this }, init);
+    const handler = this["on" + type];
+    const fns = [...(handler ? [handler] : []), ...(this.listeners.get(type) || [])];
+    for (const fn of fns) {
+      try {
+        fn.call(this, event);
+      } catch (e) {
+        console.error(`error in websocket ${type} listener:`, e.message);
+      }
+    }
+  }
+}
+
+/**
+ * Two connected sockets for answering a websocket upgrade: respond with
+ * `new Response(null, { status: 101, webSocket: pair[0] })` and talk to the
+ * client through `pair[1]`.
+ */
+export class WebSocketPair {
+  readonly 0: FlyWebSocket;
+  readonly 1: FlyWebSocket;
+
+  constructor() {
+    const [client, server] = FlyWebSocket._pair();
+    this[0] = client;
+    this[1] = server;
+  }
+}
@@ -0,0 +1,33 @@
+describe("AbortController", () => {
+  it("fires its signal once", () => {
+    const controller = new AbortController()
+    let calls = 0
+    controller.signal.onabort = () => calls++
+    controller.signal.addEventListener("abort", () => calls++)
+    expect(controller.signal.aborted).to.eq(false)
+    controller.abort()
+    controller.abort()
+    expect(controller.signal.aborted).to.eq(true)
+    expect(calls).to.eq(2)
+  })
+
+  it("gives requests a signal", () => {
+    const controller = new AbortController()
+    expect(new Request("http://example.com").signal.aborted).to.eq(false)
+    const req = new Request("http://example.com", { signal: controller.signal })
+    expect(req.signal).to.eq(controller.signal)
+    expect(new Request(req).signal).to.eq(controller.signal)
+  })
+
+  it("rejects fetches with an aborted signal", async () => {
+    const controller = new AbortController()
+    controller.abort()
+    let err
+    try {
+      await fetch("http://example.com", { signal: controller.signal })
+    } catch (e) {
+      err = e
+    }
+    expect(err.name).to.eq("AbortError")
+  })
+})
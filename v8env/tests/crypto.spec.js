@@ -10,7 +10,13 @@ describe("crypto", () => {
       expect(hash).to.be.instanceof(ArrayBuffer)
     })
 
-    it.skip("errors on bad algo", (done) => {
+    it("creates SHA-384 and SHA-512 hashes", async () => {
+      let data = (new TextEncoder('utf-8')).encode("hello world")
+      expect((await crypto.subtle.digest("SHA-384", data)).byteLength).to.equal(48)
+      expect((await crypto.subtle.digest("SHA-512", data)).byteLength).to.equal(64)
+    })
+
+    it("errors on bad algo", (done) => {
       let ret = crypto.subtle.digest("SHA-123", '')
         .then(() => { done(new Error("should've thrown!")) })
         .catch((e) => {
@@ -19,6 +25,38 @@ describe("crypto", () => {
         })
     })
   })
+  describe("subtle", () => {
+    const encoder = new TextEncoder('utf-8')
+
+    it("signs and verifies with HMAC", async () => {
+      const key = await crypto.subtle.importKey("raw", encoder.encode("secret"), { name: "HMAC", hash: "SHA-256" }, false, ["sign", "verify"])
+      expect(key.type).to.equal("secret")
+      const data = encoder.encode("signed cookie")
+      const sig = await crypto.subtle.sign("HMAC", key, data)
+      expect(sig.byteLength).to.equal(32)
+      expect(await crypto.subtle.verify("HMAC", key, sig, data)).to.equal(true)
+      expect(await crypto.subtle.verify("HMAC", key, sig, encoder.encode("tampered"))).to.equal(false)
+    })
+
+    it("encrypts and decrypts with AES-GCM", async () => {
+      const key = await crypto.subtle.importKey("raw", new Uint8Array(32), "AES-GCM", false, ["encrypt", "decrypt"])
+      const iv = new Uint8Array(12)
+      crypto.getRandomValues(iv)
+      const sealed = await crypto.subtle.encrypt({ name: "AES-GCM", iv }, key, encoder.encode("hello"))
+      const opened = await crypto.subtle.decrypt({ name: "AES-GCM", iv }, key, sealed)
+      expect(new TextDecoder().decode(opened)).to.equal("hello")
+    })
+
+    it("rejects unknown algorithms", async () => {
+      try {
+        await crypto.subtle.importKey("raw", encoder.encode("key"), "RSA-PSS", false, ["sign"])
+        throw new Error("should've thrown!")
+      } catch (e) {
+        expect(e.name).to.equal("NotSupportedError")
+      }
+    })
+  })
+
   describe("getRandomValues", () => {
     it("fills the Uint8Array", () => {
       let array = new Uint8Array(24);
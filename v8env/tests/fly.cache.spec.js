@@ -54,6 +54,16 @@ describe("fly.cache", () => {
       const result = await fly.cache.getString(k)
       expect(result).to.eq('')
     })
+
+    test("Edge cache keys", async () => {
+      let err = null
+      try {
+        await fly.cache.get("fly-edge:https://example.com/")
+      } catch (e) {
+        err = e
+      }
+      expect(err).to.not.be.null
+    })
   })
 
   describe("getMulti()", () => {
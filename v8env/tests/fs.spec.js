@@ -0,0 +1,43 @@
+const fs = fly.fs;
+
+describe('@fly/fs', () => {
+  const dir = "fs-spec/"
+
+  afterEach(async () => {
+    for (const path of await fs.list(dir))
+      await fs.deleteFile(path)
+  })
+
+  it("writes files fetch can read", async () => {
+    await fs.writeFile(dir + "hello.txt", "hello world")
+    const res = await fetch("file://" + dir + "hello.txt")
+    expect(res.status).to.eq(200)
+    expect(await res.text()).to.eq("hello world")
+  })
+
+  it("stats files", async () => {
+    await fs.writeFile(dir + "data.json", new Uint8Array([123, 125]))
+    const info = await fs.stat(dir + "data.json")
+    expect(info.size).to.eq(2)
+    expect(info.contentType).to.eq("application/json")
+    expect(info.modified).to.be.instanceOf(Date)
+    expect(await fs.stat(dir + "missing.json")).to.eq(null)
+  })
+
+  it("lists files by prefix", async () => {
+    await fs.writeFile(dir + "a/one.txt", "1")
+    await fs.writeFile(dir + "a/two.txt", "2")
+    await fs.writeFile(dir + "b.txt", "3")
+    expect(await fs.list(dir + "a/")).to.deep.eq([dir + "a/one.txt", dir + "a/two.txt"])
+    expect(await fs.list(dir)).to.have.length(3)
+  })
+
+  it("deletes files", async () => {
+    await fs.writeFile(dir + "gone.txt", "bye")
+    await fs.deleteFile(dir + "gone.txt")
+    await fs.deleteFile(dir + "gone.txt")
+    expect(await fs.stat(dir + "gone.txt")).to.eq(null)
+    const res = await fetch("file://" + dir + "gone.txt")
+    expect(res.status).to.eq(404)
+  })
+})
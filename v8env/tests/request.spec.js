@@ -13,6 +13,21 @@ describe("Request", () => {
     expect(req.bodySource).to.eq(r.bodySource)
   })
 
+  it("follows redirects by default", () => {
+    expect(new Request("http://example.com").redirect).to.eq("follow")
+  })
+
+  it("keeps the redirect mode from init and initializing request", () => {
+    const r = new Request("http://example.com", { redirect: "manual" })
+    expect(r.redirect).to.eq("manual")
+    expect(new Request(r).redirect).to.eq("manual")
+    expect(r.clone().redirect).to.eq("manual")
+  })
+
+  it("rejects unknown redirect modes", () => {
+    expect(() => new Request("http://example.com", { redirect: "sideways" })).to.throw(TypeError)
+  })
+
   it('returns an ArrayBuffer given a Int8Array', async () => {
     const r = new Response(new Int8Array(buffer))
 
@@ -0,0 +1,67 @@
+// served by `fly test`
+const echoUrl = window.WEBSOCKET_ECHO_URL
+
+describe("WebSocket", () => {
+  it("rejects non-websocket urls", () => {
+    expect(() => new WebSocket("http://example.com")).to.throw(SyntaxError)
+  })
+
+  it("exposes ready state constants", () => {
+    expect(WebSocket.CONNECTING).to.eq(0)
+    expect(WebSocket.OPEN).to.eq(1)
+    expect(WebSocket.CLOSING).to.eq(2)
+    expect(WebSocket.CLOSED).to.eq(3)
+  })
+
+  it("starts out connecting and refuses to send", () => {
+    const ws = new WebSocket(echoUrl)
+    expect(ws.readyState).to.eq(WebSocket.CONNECTING)
+    expect(() => ws.send("hello")).to.throw()
+    ws.close()
+    expect(ws.readyState).to.eq(WebSocket.CLOSING)
+  })
+
+  it("rejects reserved close codes", () => {
+    const ws = new WebSocket(echoUrl)
+    expect(() => ws.close(1006)).to.throw(RangeError)
+    ws.close()
+  })
+
+  it("echoes messages", (done) => {
+    const ws = new WebSocket(echoUrl)
+    ws.binaryType = "arraybuffer"
+    const received = []
+    ws.onopen = () => {
+      ws.send("hello")
+      ws.send(new Uint8Array([1, 2, 3]))
+    }
+    ws.onmessage = (event) => {
+      received.push(event.data)
+      if (received.length === 2)
+        ws.close(1000, "bye")
+    }
+    ws.onclose = (event) => {
+      try {
+        expect(received[0]).to.eq("hello")
+        expect(Array.from(new Uint8Array(received[1]))).to.deep.eq([1, 2, 3])
+        expect(event.code).to.eq(1000)
+        expect(event.wasClean).to.eq(true)
+        done()
+      } catch (e) {
+        done(e)
+      }
+    }
+  })
+
+  it("accepts upgrades with a WebSocketPair", () => {
+    const pair = new WebSocketPair()
+    const res = new Response(null, { status: 101, webSocket: pair[0] })
+    expect(res.status).to.eq(101)
+    expect(res.webSocket).to.eq(pair[0])
+    expect(pair[1].readyState).to.eq(WebSocket.CONNECTING)
+  })
+
+  it("only allows 101 responses carrying a websocket", () => {
+    expect(() => new Response(null, { status: 101 })).to.throw(RangeError)
+  })
+})